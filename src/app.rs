@@ -12,8 +12,16 @@ use arboard::Clipboard;
 use ratatui::style::Color;
 use std::path::PathBuf;
 use crate::utils::file_matcher::{find_matches_for_items, FileIndex, FileEntry};
-use crate::bible::{BibleService, BibleVersion, ScriptureHeader, parse_scripture_ref};
-use crate::hymnal::HymnalService;
+use crate::bible::books::BookNameRegistry;
+use crate::bible::{BibleService, BibleVersion, ScriptureHeader, ScriptureRef, parse_scripture_ref};
+use crate::find::{self, Match, Substitution};
+use crate::services::editor::{Editor, EditorAction};
+use crate::services::vim::Vim;
+use crate::history::{EditHistory, EditKind};
+use crate::hymnal::{HymnMetadata, HymnalService};
+use crate::lyrics::{LyricsLibrary, SongMetadata};
+use crate::lyrics::lint::{self, Diagnostic};
+use crate::lyrics::structure::StructureProposal;
 use tokio::sync::mpsc;
 
 use crate::config::Config;
@@ -24,8 +32,17 @@ use crate::constants::template::MIN_SLIDE_WRAP;
 use crate::error::Result;
 use crate::item_state::ItemStateStore;
 use crate::planning_center::PlanningCenterClient;
-use crate::planning_center::types::{Service, Plan, Item, Category};
+use crate::planning_center::types::{Service, Plan, Item, Category, Song};
 use crate::types::ItemId;
+use crate::propresenter::copyright;
+use crate::propresenter::data_model as dm;
+use crate::propresenter::generated::rv_data;
+use crate::propresenter::playlist::{PlaylistChangeset, PlaylistFile};
+use crate::propresenter::fonts::FontLibrary;
+use crate::propresenter::template::{SlidePreview, TemplateCache, TemplateType, TextMeasure};
+
+/// Signature shared by the template presentation builders.
+type PresentationBuilderFn = fn(&str, &rv_data::Presentation, &[String], &TextMeasure, usize) -> Option<rv_data::Presentation>;
 
 /// Messages sent from async tasks back to the main thread.
 #[derive(Debug)]
@@ -34,6 +51,8 @@ pub enum AppUpdate {
     DataLoaded(Result<(Vec<Service>, Vec<Plan>)>),
     /// Items for a specific plan have been fetched.
     ItemsLoaded(Result<Vec<Item>>),
+    /// The configured font directories have been indexed.
+    FontsLoaded(FontLibrary),
 }
 
 /// Represents which screen the application is currently displaying.
@@ -54,6 +73,7 @@ pub use crate::types::SlideType;
 
 /// Persistent and transient state for the slide content editor.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct EditorState {
     /// Lines of text content being edited.
     pub content: Vec<String>,
@@ -81,6 +101,15 @@ pub struct EditorState {
     /// Whether the editor is in `:command` entry mode.
     #[serde(skip)]
     pub is_command_mode: bool,
+    /// Buffer for the `/pattern` being typed.
+    #[serde(skip)]
+    pub search_buffer: String,
+    /// Whether the editor is in `/pattern` entry mode.
+    #[serde(skip)]
+    pub is_search_mode: bool,
+    /// Last search pattern, highlighted and used by next/previous match.
+    #[serde(skip)]
+    pub search_pattern: Option<String>,
     /// Visible line count for scroll calculations.
     #[serde(skip, default = "default_viewport_height")]
     pub viewport_height: usize,
@@ -93,12 +122,28 @@ pub struct EditorState {
     /// Line where the current selection began.
     #[serde(skip)]
     pub selection_start_y: usize,
+    /// Whether the content was loaded from a curated hymnal file.
+    #[serde(default)]
+    pub from_hymnal: bool,
 }
 
 const fn default_wrap_column() -> usize { DEFAULT_WRAP_COLUMN }
 const fn default_wrap_auto() -> bool { true }
 const fn default_viewport_height() -> usize { DEFAULT_VIEWPORT_HEIGHT }
 
+/// An existing playlist synced with the plan, waiting for the user to confirm the write.
+#[derive(Debug, Clone)]
+pub struct PendingPlaylistSync {
+    /// Where the playlist was read from and will be written back to.
+    pub path: PathBuf,
+    /// The playlist with the plan's changes applied.
+    pub playlist: PlaylistFile,
+    /// What the sync changed.
+    pub changes: PlaylistChangeset,
+    /// Edited matched files to write back to the library once confirmed.
+    pub library_updates: Vec<(PathBuf, rv_data::Presentation)>,
+}
+
 /// A labeled song-section marker (e.g., Verse, Chorus) with its shorthand command.
 #[derive(Debug, Clone)]
 pub struct VerseGroup {
@@ -122,10 +167,75 @@ impl Default for EditorState {
             last_viewport_width: None,
             command_buffer: String::new(),
             is_command_mode: false,
+            search_buffer: String::new(),
+            is_search_mode: false,
+            search_pattern: None,
             viewport_height: DEFAULT_VIEWPORT_HEIGHT,
             selection_active: false,
             selection_start_x: 0,
             selection_start_y: 0,
+            from_hymnal: false,
+        }
+    }
+}
+
+impl Editor for EditorState {
+    fn content(&self) -> &[String] {
+        &self.content
+    }
+
+    fn content_mut(&mut self) -> &mut Vec<String> {
+        &mut self.content
+    }
+
+    fn cursor_position(&self) -> (usize, usize) {
+        (self.cursor_y, self.cursor_x)
+    }
+
+    fn set_cursor(&mut self, line: usize, column: usize) {
+        self.cursor_y = line;
+        self.cursor_x = column;
+    }
+
+    fn insert_char(&mut self, c: char) {
+        self.insert_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    fn insert_str(&mut self, s: &str) {
+        if self.cursor_y >= self.content.len() {
+            self.content.resize(self.cursor_y + 1, String::new());
+        }
+        let line = &mut self.content[self.cursor_y];
+        if self.cursor_x > line.len() {
+            line.push_str(&" ".repeat(self.cursor_x - line.len()));
+        }
+        line.insert_str(self.cursor_x, s);
+        self.cursor_x += s.len();
+    }
+
+    fn delete_backward(&mut self) {
+        if self.cursor_x > 0 {
+            let Some(line) = self.content.get_mut(self.cursor_y) else { return };
+            let Some(c) = line.get(..self.cursor_x).and_then(|before| before.chars().next_back()) else { return };
+            self.cursor_x -= c.len_utf8();
+            line.remove(self.cursor_x);
+        } else if self.cursor_y > 0 && self.cursor_y < self.content.len() {
+            let line = self.content.remove(self.cursor_y);
+            self.cursor_y -= 1;
+            self.cursor_x = self.content[self.cursor_y].len();
+            self.content[self.cursor_y].push_str(&line);
+        }
+    }
+
+    fn delete_forward(&mut self) {
+        let Some(line) = self.content.get_mut(self.cursor_y) else { return };
+        if self.cursor_x < line.len() {
+            if line.is_char_boundary(self.cursor_x) {
+                line.remove(self.cursor_x);
+            }
+        } else if self.cursor_y + 1 < self.content.len() {
+            let next = self.content.remove(self.cursor_y + 1);
+            self.content[self.cursor_y].push_str(&next);
         }
     }
 }
@@ -161,6 +271,8 @@ pub struct App {
     pub file_search_query: String,
     /// State for the slide content editor.
     pub editor: EditorState,
+    /// Undo/redo history for the item open in the editor.
+    pub editor_history: EditHistory,
     /// Available verse/section markers for lyrics.
     pub verse_groups: Vec<VerseGroup>,
     /// Buffer for the global `:command` being typed.
@@ -195,6 +307,8 @@ pub struct App {
     pub bible_service: Option<BibleService>,
     /// Hymnal lookup service for curated `.txt` files.
     pub hymnal_service: Option<HymnalService>,
+    /// Imported lyric files (`OpenLyrics`, `ChordPro`, `SongSelect`).
+    pub lyrics_library: Option<LyricsLibrary>,
     /// Whether the Bible version picker overlay is shown.
     pub version_picker_active: bool,
     /// Currently selected index in the Bible version list.
@@ -205,12 +319,22 @@ pub struct App {
     pub editor_side_pane_idx: usize,
     /// Whether the editor side pane has keyboard focus.
     pub editor_side_pane_focused: bool,
+    /// Whether the editor shows the slide-break preview pane.
+    pub show_slide_preview: bool,
+    /// Vim-style modal editing state, when turned on.
+    pub vim: Option<Vim>,
     /// Parsed scripture header for display above editor content.
     pub current_scripture_header: Option<ScriptureHeader>,
+    /// Hymnal front-matter metadata for the item open in the editor.
+    pub current_hymn_metadata: Option<HymnMetadata>,
     /// Number of uncompleted items pending playlist confirmation, if any.
     pub pending_playlist_confirmation: Option<usize>,
+    /// Synced playlist awaiting confirmation before it overwrites the existing file.
+    pub pending_playlist_sync: Option<PendingPlaylistSync>,
+    /// Relabeled lyrics awaiting confirmation from the `:structure` preview.
+    pub pending_structure: Option<StructureProposal>,
     /// Cache of `ProPresenter` templates for slide generation.
-    pub template_cache: Option<crate::propresenter::template::TemplateCache>,
+    pub template_cache: Option<TemplateCache>,
 }
 
 /// Locate a subdirectory under the app's bundled data folder.
@@ -285,7 +409,11 @@ impl App {
         let (async_task_tx, async_task_rx) = mpsc::channel(CHANNEL_BUFFER_SIZE);
 
         // Extract hymnal service before config is moved into struct
-        let hymnal_service = config.hymnal_path.as_ref().map(|p| HymnalService::new(p.clone()));
+        let hymnal_service = (!config.hymnals.is_empty()).then(|| {
+            HymnalService::new(config.hymnals.clone()).with_repeat_refrain(config.hymn_repeat_refrain)
+        });
+        let lyrics_library = (!config.lyrics_dirs.is_empty()).then(|| LyricsLibrary::new(config.lyrics_dirs.clone()));
+        let vim = config.vim_mode.then(Vim::new);
 
         Self {
             mode: AppMode::Splash,
@@ -300,6 +428,7 @@ impl App {
             matching_files: Vec::new(),
             file_list_state: ListState::default(),
             editor: EditorState::default(),
+            editor_history: EditHistory::new(),
             verse_groups: vec![
                 VerseGroup {
                     name: "Verse".to_string(),
@@ -311,6 +440,11 @@ impl App {
                     command: "c".to_string(),
                     color: Color::Green,
                 },
+                VerseGroup {
+                    name: "Pre-Chorus".to_string(),
+                    command: "pc".to_string(),
+                    color: Color::LightMagenta,
+                },
                 VerseGroup {
                     name: "Bridge".to_string(),
                     command: "br".to_string(),
@@ -365,23 +499,31 @@ impl App {
             file_index: None,
             bible_service: {
                 let bible_path = find_data_subdir("bibles");
-                Some(BibleService::new(bible_path))
+                let mut book_names = BookNameRegistry::bundled();
+                book_names.load_dir(&find_data_subdir("books"));
+                Some(BibleService::new(bible_path).with_book_names(book_names))
             },
             hymnal_service,
+            lyrics_library,
             version_picker_active: false,
             version_picker_selection: 0, // Default to NRSVue
             current_slide_type: SlideType::Text,
             editor_side_pane_idx: 0,
             editor_side_pane_focused: false,
+            show_slide_preview: true,
+            vim,
             current_scripture_header: None,
+            current_hymn_metadata: None,
             pending_playlist_confirmation: None,
+            pending_playlist_sync: None,
+            pending_structure: None,
             template_cache: {
                 let mut paths = Vec::new();
                 if let Some(ref lib) = library_path {
                     paths.push(lib.clone());
                 }
                 paths.push(find_data_subdir("templates"));
-                Some(crate::propresenter::template::TemplateCache::new(paths))
+                Some(TemplateCache::new(paths))
             },
         }
     }
@@ -435,6 +577,28 @@ impl App {
             }
             return;
         }
+        if self.pending_playlist_sync.is_some() {
+            match key.code {
+                KeyCode::Char('y' | 'Y') => {
+                    self.status_message = None;
+                    self.write_playlist_sync();
+                }
+                KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+                    self.pending_playlist_sync = None;
+                    self.status_message = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+        if self.pending_structure.is_some() {
+            match key.code {
+                KeyCode::Char('y' | 'Y') | KeyCode::Enter => self.apply_structure(),
+                KeyCode::Char('n' | 'N') | KeyCode::Esc => self.pending_structure = None,
+                _ => {}
+            }
+            return;
+        }
         if self.status_message.is_some() {
             if key.code == KeyCode::Esc {
                 self.status_message = None;
@@ -448,13 +612,26 @@ impl App {
             return;
         }
 
+        // Vim insert mode types ':' and '/' like any other character
+        let vim_inserting = self.vim.as_ref().is_some_and(Vim::is_inserting);
+
         // Editor-local command mode with ':'
-        if self.mode == AppMode::Editor && key.code == KeyCode::Char(':') {
+        if self.mode == AppMode::Editor && key.code == KeyCode::Char(':') && !vim_inserting {
             self.editor.is_command_mode = true;
             self.editor.command_buffer.clear();
             return;
         }
 
+        // Editor-local search with '/'
+        if self.mode == AppMode::Editor && key.code == KeyCode::Char('/')
+            && !self.editor.is_command_mode && !self.editor.is_search_mode && !self.editor_side_pane_focused
+            && !vim_inserting
+        {
+            self.editor.is_search_mode = true;
+            self.editor.search_buffer.clear();
+            return;
+        }
+
         // Then, handle global commands
         if self.is_global_command_mode {
             self.handle_global_command_input(key);
@@ -487,7 +664,10 @@ impl App {
             if let Some(lib_path) = &self.library_path {
                 self.is_loading = true;
                 match FileIndex::build(lib_path) {
-                    Ok(index) => {
+                    Ok(mut index) => {
+                        if let Some(hymnal) = &self.hymnal_service {
+                            index.set_hymnal_codes(hymnal.codes());
+                        }
                         self.file_index = Some(index);
                         self.is_loading = false;
                     },
@@ -497,6 +677,13 @@ impl App {
                     }
                 }
             }
+
+            // Index fonts in the background; text is measured in columns until then
+            let font_dirs = self.config.font_dirs.clone();
+            let tx = self.async_task_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let _ = tx.blocking_send(AppUpdate::FontsLoaded(FontLibrary::scan(&font_dirs)));
+            });
             
             self.initialized = true;
         }
@@ -545,13 +732,20 @@ impl App {
                 // Reload data from the API
                 self.retry_data_loading();
             }
+            command if command.starts_with("import ") && self.mode == AppMode::ItemList => {
+                let path = command["import ".len()..].trim().to_string();
+                self.import_item_bundle(&path);
+            }
             // Add other global commands here
             _ => {
                 // If we don't recognize it as global, maybe it's a verse marker
                 // Try to find a matching verse group
                 if let Some(marker) = self.parse_verse_marker(&self.global_command_buffer) {
                     if self.mode == AppMode::Editor {
+                        let before = self.editor.content.clone();
+                        let cursor_before = (self.editor.cursor_x, self.editor.cursor_y);
                         self.insert_verse_marker(&marker);
+                        self.record_edit(&before, cursor_before, EditKind::Other);
                     }
                 }
             }
@@ -793,6 +987,9 @@ impl App {
             KeyCode::Char('g') if !files_focused => {
                 self.try_generate_playlist();
             }
+            KeyCode::Char('b') if !files_focused => {
+                self.export_item_bundle();
+            }
             KeyCode::Char('t') if !files_focused => {
                 // Cycle slide type for current item
                 if let Some(idx) = self.item_list_state.selected() {
@@ -867,15 +1064,36 @@ impl App {
     }
 
     fn handle_editor_input(&mut self, key: KeyEvent) {
-        if self.editor.is_command_mode {
-            self.handle_editor_command_input(key);
-        } else {
-            self.handle_editor_normal_input(key);
+        let before = self.editor.content.clone();
+        let cursor_before = (self.editor.cursor_x, self.editor.cursor_y);
+        let typing = self.vim.as_ref().is_none_or(Vim::is_inserting);
+
+        let history_step = self.history_step_for_key(key);
+        match history_step {
+            Some(redo) => {
+                self.editor.is_search_mode = false;
+                self.editor.is_command_mode = false;
+                self.editor.command_buffer.clear();
+                self.step_history(redo);
+            }
+            None if self.editor.is_command_mode => self.handle_editor_command_input(key),
+            None if self.editor.is_search_mode => self.handle_editor_search_input(key),
+            None => self.handle_editor_normal_input(key),
         }
 
         // Ensure there's always an empty line at the end
         self.ensure_empty_line_at_end();
 
+        // Record whatever the key changed as one undoable step
+        if history_step.is_none() {
+            let kind = match key.code {
+                KeyCode::Char(c) if typing && !self.editor.is_command_mode
+                    && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::META) => EditKind::Insert(c),
+                _ => EditKind::Other,
+            };
+            self.record_edit(&before, cursor_before, kind);
+        }
+
         // Update the stored editor state in the store
         if let Some(item_idx) = self.item_list_state.selected() {
             if let Some(item) = self.items.get(item_idx) {
@@ -896,6 +1114,57 @@ impl App {
         }
     }
 
+    /// Whether `key` is undo (`Some(false)`) or redo (`Some(true)`):
+    /// Ctrl+Z / Ctrl+Shift+Z / Ctrl+Y, or `:undo` / `:redo`.
+    fn history_step_for_key(&self, key: KeyEvent) -> Option<bool> {
+        if self.editor.is_command_mode {
+            if key.code != KeyCode::Enter {
+                return None;
+            }
+            return match self.editor.command_buffer.trim() {
+                "u" | "undo" => Some(false),
+                "redo" => Some(true),
+                _ => None,
+            };
+        }
+        if self.editor_side_pane_focused {
+            return None;
+        }
+        if let Some(step) = self.vim.as_ref().filter(|_| !self.editor.is_search_mode).and_then(|vim| vim.history_step(key)) {
+            return Some(step);
+        }
+        if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::META) {
+            return None;
+        }
+        match key.code {
+            KeyCode::Char('z') => Some(key.modifiers.contains(KeyModifiers::SHIFT)),
+            KeyCode::Char('Z' | 'y') => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Undo (or redo) one step, restoring the cursor to where the edit happened.
+    fn step_history(&mut self, redo: bool) {
+        let cursor = if redo {
+            self.editor_history.redo(&mut self.editor.content)
+        } else {
+            self.editor_history.undo(&mut self.editor.content)
+        };
+        let Some((x, y)) = cursor else { return };
+        if self.editor.content.is_empty() {
+            self.editor.content.push(String::new());
+        }
+        self.editor.cursor_y = y.min(self.editor.content.len() - 1);
+        self.editor.cursor_x = x.min(self.editor.content[self.editor.cursor_y].len());
+        self.editor.selection_active = false;
+    }
+
+    /// Record the change from `before` to the current content in the undo history.
+    fn record_edit(&mut self, before: &[String], cursor_before: (usize, usize), kind: EditKind) {
+        let cursor_after = (self.editor.cursor_x, self.editor.cursor_y);
+        self.editor_history.record(before, &self.editor.content, cursor_before, cursor_after, kind);
+    }
+
     fn handle_editor_command_input(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => {
@@ -917,6 +1186,83 @@ impl App {
         }
     }
 
+    fn handle_editor_search_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.editor.is_search_mode = false;
+                self.editor.search_buffer.clear();
+            }
+            KeyCode::Enter => {
+                self.editor.is_search_mode = false;
+                let pattern = std::mem::take(&mut self.editor.search_buffer);
+                // An empty search repeats the last one
+                if !pattern.is_empty() {
+                    self.editor.search_pattern = Some(pattern);
+                }
+                self.jump_to_match(true);
+            }
+            KeyCode::Backspace => {
+                self.editor.search_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.editor.search_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    /// Matches to highlight: the pattern being typed, else the last search.
+    /// Invalid patterns match nothing.
+    pub fn editor_search_matches(&self) -> Vec<Match> {
+        let pattern = if self.editor.is_search_mode {
+            Some(self.editor.search_buffer.as_str()).filter(|p| !p.is_empty())
+        } else {
+            self.editor.search_pattern.as_deref()
+        };
+        pattern
+            .and_then(|p| find::compile(p).ok())
+            .map_or_else(Vec::new, |regex| find::find_all(&self.editor.content, &regex))
+    }
+
+    /// Move the cursor to the next (or previous) match of the last search.
+    fn jump_to_match(&mut self, forward: bool) {
+        let Some(pattern) = self.editor.search_pattern.clone() else {
+            self.status_message = Some("No previous search".to_string());
+            return;
+        };
+        if let Err(e) = find::compile(&pattern) {
+            self.status_message = Some(format!("Invalid pattern: {e}"));
+            return;
+        }
+        let matches = self.editor_search_matches();
+        let cursor = (self.editor.cursor_x, self.editor.cursor_y);
+        match find::next_match(&matches, cursor, forward) {
+            Some(m) => {
+                self.editor.cursor_y = m.line;
+                self.editor.cursor_x = m.start;
+                self.editor.selection_active = false;
+            }
+            None => self.status_message = Some(format!("Pattern not found: {pattern}")),
+        }
+    }
+
+    /// Run a `:s/pattern/replacement/flags` over the selection, or the whole buffer.
+    fn substitute(&mut self, substitution: &Substitution) {
+        let selection = self.editor.selection_active.then(|| {
+            let anchor = (self.editor.selection_start_x, self.editor.selection_start_y);
+            let cursor = (self.editor.cursor_x, self.editor.cursor_y);
+            if (anchor.1, anchor.0) <= (cursor.1, cursor.0) { (anchor, cursor) } else { (cursor, anchor) }
+        });
+        let count = substitution.apply(&mut self.editor.content, selection);
+        self.editor.selection_active = false;
+        self.clamp_cursor();
+        self.status_message = Some(if count == 0 {
+            "Pattern not found".to_string()
+        } else {
+            format!("Replaced {count} match(es)")
+        });
+    }
+
     #[allow(clippy::too_many_lines)]
     fn handle_editor_normal_input(&mut self, key: KeyEvent) {
         // Tab to switch pane focus
@@ -930,15 +1276,36 @@ impl App {
             self.handle_side_pane_input(key);
             return;
         }
-        
+
         // Handle side pane shortcuts based on slide type (number keys work even when not focused)
-        match (key.code, self.current_slide_type) {
-            // Scripture mode: 1-4 to switch Bible versions
-            (KeyCode::Char('1'), SlideType::Scripture) => { self.switch_bible_version(0); return; }
-            (KeyCode::Char('2'), SlideType::Scripture) => { self.switch_bible_version(1); return; }
-            (KeyCode::Char('3'), SlideType::Scripture) => { self.switch_bible_version(2); return; }
-            (KeyCode::Char('4'), SlideType::Scripture) => { self.switch_bible_version(3); return; }
-            _ => {}
+        // Scripture mode: number keys switch Bible versions
+        if let (KeyCode::Char(c @ '1'..='9'), SlideType::Scripture) = (key.code, self.current_slide_type) {
+            let index = c as usize - '1' as usize;
+            if index < BibleVersion::all().len() {
+                self.switch_bible_version(index);
+                return;
+            }
+        }
+        
+        // Vim normal/visual keys; anything it passes on is edited as usual
+        if let Some(vim) = self.vim.as_mut() {
+            let action = vim.handle_key(&mut self.editor, key);
+            let anchor = vim.visual_anchor();
+            match action {
+                EditorAction::Unhandled => {}
+                EditorAction::SearchNext(forward) => {
+                    self.jump_to_match(forward);
+                    return;
+                }
+                _ => {
+                    self.editor.selection_active = anchor.is_some();
+                    if let Some((line, column)) = anchor {
+                        self.editor.selection_start_y = line;
+                        self.editor.selection_start_x = column;
+                    }
+                    return;
+                }
+            }
         }
         
         match key.code {
@@ -954,27 +1321,48 @@ impl App {
                     if let Some(item) = self.items.get(item_idx) {
                         let item_id = ItemId::new(&item.id);
 
-                        if has_content {
-                            // Save editor state - this is a custom creation
+                        // Content of a matched .pro file, if that's what was opened
+                        let matched_lines = self.item_states.get_matched_file(&item_id)
+                            .filter(|path| std::path::Path::new(path).extension().is_some_and(|e| e == "pro"))
+                            .and_then(|path| crate::propresenter::deserialize::read_presentation_file(path).ok())
+                            .map(|presentation| crate::propresenter::update::editor_lines(&presentation));
+
+                        if has_content && matched_lines.as_ref().is_some_and(|lines| *lines == self.editor.content) {
+                            // Matched file left as it was - nothing to update
+                            self.item_states.set_editor(&item_id, None);
+                            self.item_states.set_history(&item_id, EditHistory::new());
+                            if let Some(index) = &mut self.file_index {
+                                index.remove_editor_state(&item.id);
+                                index.persist();
+                            }
+                        } else if has_content {
+                            // Save editor state - a custom creation, or edits to the matched file
                             self.item_states.set_editor(&item_id, Some(self.editor.clone()));
+                            self.item_states.set_history(&item_id, self.editor_history.clone());
 
-                            // Clear any matched file - custom creation and file match are mutually exclusive
-                            self.item_states.set_matched_file(&item_id, None);
+                            // A matched .pro file is updated in place on export; any
+                            // other match gives way to the custom creation
+                            if matched_lines.is_none() {
+                                self.item_states.set_matched_file(&item_id, None);
+                            }
 
                             // Mark as complete since we have content
                             self.item_states.set_completed(&item_id, true);
 
                             // Persist to cache
                             if let Some(index) = &mut self.file_index {
-                                index.save_editor_state(&item.id, &self.editor);
-                                index.item_file_selections.remove(&item.id);
+                                index.save_editor_state(&item.id, &self.editor, &self.editor_history);
+                                if matched_lines.is_none() {
+                                    index.item_file_selections.remove(&item.id);
+                                }
                                 index.save_item_completion(&item.id, true);
                             }
                         } else {
                             // No content - clear editor state
                             self.item_states.set_editor(&item_id, None);
+                            self.item_states.set_history(&item_id, EditHistory::new());
                             if let Some(index) = &mut self.file_index {
-                                index.editor_states.remove(&item.id);
+                                index.remove_editor_state(&item.id);
                                 index.persist();
                             }
                         }
@@ -1030,6 +1418,11 @@ impl App {
                     self.insert_char('v');
                 }
             }
+            // Next/previous search match (F3 / Shift+F3, Ctrl+N / Ctrl+P)
+            KeyCode::F(3) => self.jump_to_match(!key.modifiers.contains(KeyModifiers::SHIFT)),
+            KeyCode::Char(c @ ('n' | 'p')) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.jump_to_match(c == 'n');
+            }
             // Terminal-friendly keybindings for wrap guide
             KeyCode::Left if key.modifiers.contains(KeyModifiers::ALT) => {
                 if self.editor.wrap_column > MIN_WRAP_COLUMN {
@@ -1206,18 +1599,115 @@ impl App {
             "export" | "save" => {
                 self.export_editor_to_pro();
             }
+            "structure" => {
+                self.preview_structure();
+            }
+            "lint" => {
+                let diagnostics = self.editor_diagnostics();
+                self.status_message = Some(if diagnostics.is_empty() {
+                    "No style issues".to_string()
+                } else {
+                    diagnostics.iter()
+                        .map(|d| format!("Ln {}: {}", d.line + 1, d.message))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                });
+            }
+            "fix" => {
+                let changed = lint::fix(&mut self.editor.content, &self.config.lint);
+                self.status_message = Some(format!("Fixed {changed} line(s)"));
+            }
+            "preview" => {
+                self.show_slide_preview = !self.show_slide_preview;
+            }
+            "vim" => {
+                self.vim = if self.vim.is_some() { None } else { Some(Vim::new()) };
+                self.editor.selection_active = false;
+            }
+            "noh" | "nohlsearch" => {
+                self.editor.search_pattern = None;
+            }
             _ if cmd.starts_with("wrap ") => {
                 if let Ok(col) = cmd[5..].parse::<usize>() {
                     self.editor.wrap_auto = false; // explicit manual wrap
                     self.editor.wrap_column = col.max(MIN_WRAP_COLUMN);
                 }
             }
+            _ if cmd.starts_with("transpose ") => {
+                // Transpose inline chords: ":transpose A" (target key) or ":transpose -2" (semitones)
+                if let Some(value) = cmd.split_whitespace().nth(1) {
+                    crate::lyrics::chordpro::set_directive(&mut self.editor.content, "transpose", value);
+                }
+            }
             _ if cmd.starts_with("export ") || cmd.starts_with("save ") => {
                 // Export with custom filename
                 let filename = cmd.split_whitespace().nth(1).unwrap_or("presentation").to_string();
                 self.export_editor_to_pro_with_name(&filename);
             }
-            _ => {}
+            _ => match Substitution::parse(&cmd) {
+                Some(Ok(substitution)) => self.substitute(&substitution),
+                Some(Err(e)) => self.status_message = Some(format!("Invalid pattern: {e}")),
+                None => {}
+            },
+        }
+    }
+
+    /// Detect verse/chorus structure in the editor content and show the proposed labels.
+    fn preview_structure(&mut self) {
+        let groups: Vec<&str> = self.verse_groups.iter().map(|g| g.name.as_str()).collect();
+        let proposal = crate::lyrics::structure::normalize(&self.editor.content, &groups);
+        if proposal.content == self.editor.content {
+            self.status_message = Some("Lyrics are already labeled".to_string());
+        } else {
+            self.pending_structure = Some(proposal);
+        }
+    }
+
+    /// Replace the editor content with the previewed structure.
+    fn apply_structure(&mut self) {
+        let Some(proposal) = self.pending_structure.take() else { return };
+        let before = std::mem::replace(
+            &mut self.editor.content,
+            if proposal.content.is_empty() { vec![String::new()] } else { proposal.content },
+        );
+        let cursor_before = (self.editor.cursor_x, self.editor.cursor_y);
+        self.editor.cursor_y = 0;
+        self.editor.cursor_x = 0;
+        self.editor.scroll_offset = 0;
+        self.editor.selection_active = false;
+        self.record_edit(&before, cursor_before, EditKind::Other);
+    }
+
+    /// The slides an export would cut from the editor content right now.
+    pub fn slide_preview(&mut self) -> Vec<SlidePreview> {
+        use crate::propresenter::litany::preview_litany_slides;
+        use crate::propresenter::template::{preview_slides, DEFAULT_MAX_LINES_PER_SLIDE};
+
+        let slide_type = Self::content_slide_type(self.current_slide_type, &self.editor.content);
+        let measure = Self::text_measure(self.template_cache.as_mut(), slide_type, self.editor.wrap_column);
+        let preview = if slide_type == SlideType::Litany {
+            preview_litany_slides
+        } else {
+            preview_slides
+        };
+        preview(&self.editor.content, &measure, DEFAULT_MAX_LINES_PER_SLIDE)
+    }
+
+    /// House-style diagnostics for the editor content (lyrics only).
+    pub fn editor_diagnostics(&self) -> Vec<Diagnostic> {
+        if self.current_slide_type == SlideType::Lyrics {
+            lint::lint(&self.editor.content, &self.config.lint)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Number of lint errors that should block exporting `content`.
+    fn blocking_lint_errors(&self, slide_type: SlideType, content: &[String]) -> usize {
+        if slide_type == SlideType::Lyrics && self.config.lint.block_on_error {
+            lint::error_count(&lint::lint(content, &self.config.lint))
+        } else {
+            0
         }
     }
 
@@ -1234,22 +1724,35 @@ impl App {
         let slide_type = self.get_slide_type_for_item(item);
         self.current_slide_type = slide_type;
         self.editor_side_pane_idx = 0;
+        self.editor_history = EditHistory::new();
+
+        // Curated hymn file for lyrics items (content source and metadata)
+        let hymn = if slide_type == SlideType::Lyrics {
+            self.hymnal_service.as_mut().and_then(|h| h.lookup_from_title(&title))
+        } else {
+            None
+        };
+        self.current_hymn_metadata = None;
 
         // Priority 1: Existing editor state (user's custom creation)
         if let Some(state) = self.item_states.get_editor(&item_id_typed) {
             self.editor = state.clone();
+            if state.from_hymnal {
+                self.current_hymn_metadata = hymn.map(|h| h.metadata).filter(|m| !m.is_empty());
+            }
+            self.editor_history = self.item_states.get_history(&item_id_typed).cloned().unwrap_or_default();
             self.mode = AppMode::Editor;
             return;
         }
 
-        // Priority 2: Matched .pro file - extract its content
+        // Priority 2: Matched .pro file - list its groups and slides
         if let Some(matched_path) = self.item_states.get_matched_file(&item_id_typed) {
-            use crate::propresenter::extract::extract_text_from_pro;
+            use crate::propresenter::{deserialize::read_presentation_file, update::editor_lines};
             use std::path::Path;
             
             let path = Path::new(matched_path);
             if path.exists() && path.extension().is_some_and(|e| e == "pro") {
-                match extract_text_from_pro(path) {
+                match read_presentation_file(path).map(|presentation| editor_lines(&presentation)) {
                     Ok(lines) => {
                         self.editor = EditorState {
                             content: if lines.is_empty() {
@@ -1285,11 +1788,25 @@ impl App {
         }
 
         // Priority 3.5: Hymnal lookup for lyrics items (curated .txt files)
+        if let Some(hymn) = hymn {
+            // Labeled stanzas, honoring a verse selection in the title or notes
+            let selection_sources: Vec<&str> = [Some(title.as_str()), item.note.as_deref(), item.description.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect();
+            let content = self.hymnal_service.as_ref()
+                .map_or_else(|| hymn.content.clone(), |h| h.editor_lines(&hymn, &selection_sources));
+            self.current_hymn_metadata = Some(hymn.metadata).filter(|m| !m.is_empty());
+            self.editor = EditorState { content, from_hymnal: true, ..EditorState::default() };
+            self.mode = AppMode::Editor;
+            return;
+        }
+
+        // Priority 3.75: Imported lyric files (OpenLyrics, ChordPro, SongSelect)
         if slide_type == SlideType::Lyrics {
-            let hymnal_result = self.hymnal_service.as_mut()
-                .and_then(|h| h.lookup_from_title(&title));
-            if let Some((_title, lines)) = hymnal_result {
-                self.editor = EditorState { content: lines, ..EditorState::default() };
+            let ccli = item.song.as_ref().and_then(|s| s.ccli.as_deref()).and_then(|n| n.trim().parse().ok());
+            if let Some(song) = self.lyrics_library.as_mut().and_then(|l| l.lookup(&title, ccli)) {
+                self.editor = EditorState { content: song.editor_lines(), ..EditorState::default() };
                 self.mode = AppMode::Editor;
                 return;
             }
@@ -1300,7 +1817,7 @@ impl App {
         let mut new_state = EditorState::default();
         if let Some(lyrics) = lyrics {
             new_state.content = lyrics.lines().map(String::from).collect();
-            if new_state.content.last().is_some_and(|l| !l.is_empty()) {
+            if new_state.content.last().is_none_or(|l| !l.is_empty()) {
                 new_state.content.push(String::new());
             }
         }
@@ -1309,14 +1826,23 @@ impl App {
         self.mode = AppMode::Editor;
     }
     
+    /// Parse a scripture reference from an item title.
+    ///
+    /// Uses the Bible service's book name tables (including any loaded from
+    /// the data directory) when available.
+    fn parse_scripture_title(&self, title: &str) -> Option<ScriptureRef> {
+        self.bible_service.as_ref().map_or_else(
+            || parse_scripture_ref(title),
+            |bible| bible.parse_reference(title),
+        )
+    }
+    
     /// Detect the slide type for an item based on category and title.
-    fn detect_slide_type(category: Category, title: &str) -> SlideType {
+    fn detect_slide_type(&self, category: Category, title: &str) -> SlideType {
         let title_lower = title.to_lowercase();
         
         // Check for explicit scripture indicators
-        if title_lower.starts_with("scripture") || 
-           (title_lower.contains("scripture") && parse_scripture_ref(title).is_some()) ||
-           parse_scripture_ref(title).is_some() {
+        if title_lower.starts_with("scripture") || self.parse_scripture_title(title).is_some() {
             return SlideType::Scripture;
         }
         
@@ -1325,6 +1851,13 @@ impl App {
             return SlideType::Lyrics;
         }
         
+        // Responsive readings
+        if title_lower.contains("call to worship") ||
+           title_lower.contains("litany") ||
+           title_lower.contains("responsive") {
+            return SlideType::Litany;
+        }
+        
         // Title/nametag patterns
         if matches!(category, Category::Title) ||
            title_lower.contains("sermon") ||
@@ -1350,7 +1883,7 @@ impl App {
     pub fn get_slide_type_for_item(&self, item: &Item) -> SlideType {
         let item_id = ItemId::new(&item.id);
         self.item_states.get_slide_type(&item_id)
-            .unwrap_or_else(|| Self::detect_slide_type(item.category, &item.title))
+            .unwrap_or_else(|| self.detect_slide_type(item.category, &item.title))
     }
     
     /// Handle input when side pane is focused
@@ -1431,7 +1964,7 @@ impl App {
         let version = BibleVersion::all()[self.version_picker_selection];
         
         // Parse scripture reference from title
-        let Some(reference) = parse_scripture_ref(title) else {
+        let Some(reference) = self.parse_scripture_title(title) else {
             self.error_message = Some(format!("Could not parse: {title}"));
             return;
         };
@@ -1492,7 +2025,7 @@ impl App {
         let version = BibleVersion::all()[self.version_picker_selection];
         
         // Parse scripture reference from title
-        let Some(reference) = parse_scripture_ref(title) else {
+        let Some(reference) = self.parse_scripture_title(title) else {
             self.error_message = Some(format!("Could not parse scripture reference: {title}"));
             self.current_scripture_header = None;
             self.mode = AppMode::Editor;
@@ -1525,6 +2058,103 @@ impl App {
         }
     }
 
+    /// Load the template for a slide type.
+    ///
+    /// Types with a dedicated template (e.g. litany) fall back to their
+    /// parent template when the dedicated one is not in the library.
+    fn template_for_slide_type(
+        cache: Option<&mut TemplateCache>,
+        slide_type: SlideType,
+    ) -> std::result::Result<rv_data::Presentation, String> {
+        let template_type = Self::template_type(slide_type);
+        
+        let template = cache.and_then(|c| {
+            if c.has_template(template_type) {
+                c.get(template_type).cloned()
+            } else {
+                template_type.fallback().and_then(|fallback| c.get(fallback).cloned())
+            }
+        });
+        
+        template.ok_or_else(|| format!(
+            "No template found! Create '{}' in your ProPresenter library with your desired styling.",
+            template_type.fallback().unwrap_or(template_type).filename()
+        ))
+    }
+    
+    /// Template used for a slide type (before any fallback).
+    const fn template_type(slide_type: SlideType) -> TemplateType {
+        match slide_type {
+            SlideType::Scripture => TemplateType::Scripture,
+            SlideType::Lyrics => TemplateType::Song,
+            SlideType::Litany => TemplateType::Litany,
+            SlideType::Title | SlideType::Text | SlideType::Graphic => TemplateType::Info,
+        }
+    }
+
+    /// How a slide type's text is measured: its template's font and text box
+    /// when available, else columns at `wrap_column`.
+    fn text_measure(cache: Option<&mut TemplateCache>, slide_type: SlideType, wrap_column: usize) -> TextMeasure {
+        cache.map_or(TextMeasure::Columns(wrap_column), |c| c.text_measure(Self::template_type(slide_type), wrap_column))
+    }
+
+    /// CCLI credits for a song titled `name`.
+    ///
+    /// Hymnal metadata comes first unless Planning Center has a CCLI number
+    /// for the song, since the hymn is only matched by title. Then come the
+    /// credits of a matching imported lyric file, then Planning Center's song
+    /// details. A missing song number is taken from Planning Center.
+    fn song_credits(
+        lyrics_library: Option<&mut LyricsLibrary>,
+        item: Option<&Item>,
+        name: &str,
+        hymn: Option<(&HymnMetadata, &str)>,
+    ) -> dm::CCLIInfo {
+        let song = item.and_then(|item| item.song.as_ref());
+        let song_number = song.and_then(|s| s.ccli.as_deref()).and_then(|n| n.trim().parse().ok());
+        let imported = || lyrics_library
+            .and_then(|library| library.lookup(name, song_number))
+            .filter(|imported| imported.metadata != SongMetadata::default());
+
+        let mut ccli = if let Some((metadata, title)) = hymn.filter(|_| song_number.is_none()) {
+            metadata.to_ccli_info(title)
+        } else if let Some(imported) = imported() {
+            imported.metadata.to_ccli_info(&imported.title)
+        } else {
+            song.map_or_else(|| SongMetadata::default().to_ccli_info(name), Song::to_ccli_info)
+        };
+        if ccli.song_number == 0 {
+            ccli.song_number = song_number.unwrap_or(0);
+        }
+        ccli
+    }
+
+    /// Slide type to build `content` with.
+    ///
+    /// Plain text that carries "L:" / "P:" speaker prefixes is a responsive
+    /// reading even when its title doesn't say so.
+    fn content_slide_type(slide_type: SlideType, content: &[String]) -> SlideType {
+        use crate::propresenter::litany::has_speaker_prefixes;
+
+        if slide_type == SlideType::Text && has_speaker_prefixes(content) {
+            SlideType::Litany
+        } else {
+            slide_type
+        }
+    }
+
+    /// Pick the template builder for a slide type.
+    fn presentation_builder(slide_type: SlideType) -> PresentationBuilderFn {
+        use crate::propresenter::litany::build_litany_presentation_from_template;
+        use crate::propresenter::template::build_presentation_from_template_with_options;
+        
+        if slide_type == SlideType::Litany {
+            build_litany_presentation_from_template
+        } else {
+            build_presentation_from_template_with_options
+        }
+    }
+
     fn export_editor_to_pro(&mut self) {
         // Get the item title as the presentation name
         let name = self.get_current_item_title()
@@ -1533,38 +2163,48 @@ impl App {
     }
 
     fn export_editor_to_pro_with_name(&mut self, name: &str) {
-        use crate::propresenter::template::{TemplateType, build_presentation_from_template_with_options, DEFAULT_MAX_LINES_PER_SLIDE};
+        use crate::propresenter::template::DEFAULT_MAX_LINES_PER_SLIDE;
         use crate::propresenter::serialize::write_presentation_file;
-        
-        // Map slide type to template type
-        let template_type = match self.current_slide_type {
-            SlideType::Scripture => TemplateType::Scripture,
-            SlideType::Lyrics => TemplateType::Song,
-            SlideType::Title | SlideType::Text | SlideType::Graphic => TemplateType::Info,
-        };
+
+        let errors = self.blocking_lint_errors(self.current_slide_type, &self.editor.content);
+        if errors > 0 {
+            self.error_message = Some(format!("Export blocked: {errors} style error(s). Use :lint to list or :fix to correct."));
+            return;
+        }
         
         // Get template - require it to exist
-        let Some(template) = self.template_cache.as_mut().and_then(|c| c.get(template_type).cloned()) else {
-            self.error_message = Some(format!(
-                "No template found! Create '{}' in your ProPresenter library with your desired styling.",
-                template_type.filename()
-            ));
-            return;
+        let slide_type = Self::content_slide_type(self.current_slide_type, &self.editor.content);
+        let template = match Self::template_for_slide_type(self.template_cache.as_mut(), slide_type) {
+            Ok(template) => template,
+            Err(e) => {
+                self.error_message = Some(e);
+                return;
+            }
         };
         
         // Build presentation from template with auto-splitting
-        let wrap_col = self.editor.wrap_column;
-        let Some(presentation) = build_presentation_from_template_with_options(
+        let measure = Self::text_measure(self.template_cache.as_mut(), slide_type, self.editor.wrap_column);
+        let build = Self::presentation_builder(slide_type);
+        let Some(mut presentation) = build(
             name,
             &template,
             &self.editor.content,
-            wrap_col,
+            &measure,
             DEFAULT_MAX_LINES_PER_SLIDE,
         ) else {
             self.error_message = Some("Failed to build presentation from template".to_string());
             return;
         };
         
+        // Credit songs in the CCLI block and, per the settings, on the slides
+        if self.current_slide_type == SlideType::Lyrics {
+            let item = self.item_list_state.selected().and_then(|idx| self.items.get(idx));
+            let hymn = self.current_hymn_metadata.as_ref().map(|metadata| (metadata, name));
+            let ccli = Self::song_credits(self.lyrics_library.as_mut(), item, name, hymn);
+            copyright::add_copyright(&mut presentation, &template, &ccli, self.config.ccli_license.as_deref(), self.config.ccli_footer);
+        }
+        let license_note = self.license_note(self.current_slide_type == SlideType::Lyrics);
+        
         // Write to file
         let output_path = self.get_pro_output_path(name);
         match write_presentation_file(&presentation, &output_path) {
@@ -1587,7 +2227,7 @@ impl App {
                 }
 
                 self.update_matching_files();
-                self.status_message = Some(format!("Exported: {}", output_path.display()));
+                self.status_message = Some(format!("Exported: {}{license_note}", output_path.display()));
             }
             Err(e) => {
                 self.error_message = Some(format!("Export failed: {e}"));
@@ -1680,6 +2320,7 @@ impl App {
                 self.item_states.set_completed(&item_id, index.get_item_completion(&item.id).unwrap_or(false));
                 self.item_states.set_ignored(&item_id, index.get_item_ignored(&item.id).unwrap_or(false));
                 self.item_states.set_editor(&item_id, index.get_editor_state(&item.id).cloned());
+                self.item_states.set_history(&item_id, index.get_edit_history(&item.id).cloned().unwrap_or_default());
                 self.item_states.set_matched_file(&item_id, index.get_selection_for_item(&item.id).cloned());
             }
         }
@@ -2021,11 +2662,14 @@ impl App {
     fn generate_playlist(&mut self, allow_incomplete: bool) {
         use std::path::Path;
         use crate::propresenter::playlist::{build_playlist, write_playlist_file, PlaylistEntry};
-        use crate::propresenter::template::{TemplateType, build_presentation_from_template_with_options, DEFAULT_MAX_LINES_PER_SLIDE};
+        use crate::propresenter::template::DEFAULT_MAX_LINES_PER_SLIDE;
         use prost::Message;
         
         // Collect entries for non-ignored items with matched files
         let mut entries: Vec<PlaylistEntry> = Vec::new();
+        let mut credited = false;
+        // Edited matched files, written back only once the playlist is
+        let mut library_updates: Vec<(PathBuf, rv_data::Presentation)> = Vec::new();
 
         for item in &self.items {
             let item_id = ItemId::new(&item.id);
@@ -2044,6 +2688,37 @@ impl App {
                 let entry_name = file_stem.to_string();
                 let slide_type = self.item_states.get_slide_type(&item_id).unwrap_or(SlideType::Text);
 
+                // Edited in the editor: embed the updated file, and update
+                // the library copy after the playlist is written
+                if let Some(state) = self.item_states.get_editor(&item_id) {
+                    let errors = self.blocking_lint_errors(Self::content_slide_type(slide_type, &state.content), &state.content);
+                    if errors > 0 {
+                        self.error_message = Some(format!("Item '{}' has {errors} style error(s). Fix them in the editor first.", item.title));
+                        return;
+                    }
+                    match crate::propresenter::update::update_pro_file(Path::new(matched_path), &state.content) {
+                        Ok(Some(presentation)) => {
+                            entries.push(PlaylistEntry {
+                                name: entry_name,
+                                slide_type,
+                                from_matched_file: true,
+                                presentation_path: matched_path.to_string(),
+                                arrangement_uuid: None,
+                                embedded_data: Some(presentation.encode_to_vec()),
+                                pco_item_id: Some(item.id.clone()),
+                            });
+                            library_updates.push((PathBuf::from(matched_path), presentation));
+                            continue;
+                        }
+                        // Unchanged: embed the file as it is
+                        Ok(None) => {}
+                        Err(e) => {
+                            self.error_message = Some(format!("Failed to update {matched_path}: {e}"));
+                            return;
+                        }
+                    }
+                }
+
                 // Read the .pro file and embed it
                 match std::fs::read(matched_path) {
                     Ok(data) => {
@@ -2054,6 +2729,7 @@ impl App {
                             presentation_path: matched_path.to_string(),
                             arrangement_uuid: None,
                             embedded_data: Some(data),
+                            pco_item_id: Some(item.id.clone()),
                         });
                     }
                     Err(e) => {
@@ -2066,6 +2742,7 @@ impl App {
                             presentation_path: matched_path.to_string(),
                             arrangement_uuid: None,
                             embedded_data: None,
+                            pco_item_id: Some(item.id.clone()),
                         });
                     }
                 }
@@ -2083,36 +2760,49 @@ impl App {
                     return;
                 }
 
-                // Determine template type based on slide type
+                // Determine template based on slide type (required)
                 let slide_type = self.item_states.get_slide_type(&item_id).unwrap_or(SlideType::Text);
-                let template_type = match slide_type {
-                    SlideType::Scripture => TemplateType::Scripture,
-                    SlideType::Lyrics => TemplateType::Song,
-                    SlideType::Title | SlideType::Text | SlideType::Graphic => TemplateType::Info,
-                };
-
-                // Require template - no fallback
-                let Some(template) = self.template_cache.as_mut().and_then(|c| c.get(template_type).cloned()) else {
-                    self.error_message = Some(format!(
-                        "No template found! Create '{}' in your ProPresenter library with your desired styling.",
-                        template_type.filename()
-                    ));
+                let slide_type = Self::content_slide_type(slide_type, &state.content);
+                let errors = self.blocking_lint_errors(slide_type, &state.content);
+                if errors > 0 {
+                    self.error_message = Some(format!("Item '{}' has {errors} style error(s). Fix them in the editor first.", item.title));
                     return;
+                }
+                let template = match Self::template_for_slide_type(self.template_cache.as_mut(), slide_type) {
+                    Ok(template) => template,
+                    Err(e) => {
+                        self.error_message = Some(e);
+                        return;
+                    }
                 };
                 
                 // Use the item's wrap column for splitting, clamped to minimum
                 let wrap_col = state.wrap_column.max(MIN_SLIDE_WRAP);
-                let Some(presentation) = build_presentation_from_template_with_options(
+                let measure = Self::text_measure(self.template_cache.as_mut(), slide_type, wrap_col);
+                let build = Self::presentation_builder(slide_type);
+                let Some(mut presentation) = build(
                     &item.title,
                     &template,
                     &state.content,
-                    wrap_col,
+                    &measure,
                     DEFAULT_MAX_LINES_PER_SLIDE,
                 ) else {
                     self.error_message = Some(format!("Failed to build presentation for '{}'", item.title));
                     return;
                 };
                 
+                // Credit songs in the CCLI block and, per the settings, on the slides
+                if slide_type == SlideType::Lyrics {
+                    let hymn = self.hymnal_service.as_mut()
+                        .filter(|_| state.from_hymnal)
+                        .and_then(|h| h.lookup_from_title(&item.title))
+                        .filter(|h| !h.metadata.is_empty());
+                    let hymn = hymn.as_ref().map(|hymn| (&hymn.metadata, hymn.title.as_str()));
+                    let ccli = Self::song_credits(self.lyrics_library.as_mut(), Some(item), &item.title, hymn);
+                    copyright::add_copyright(&mut presentation, &template, &ccli, self.config.ccli_license.as_deref(), self.config.ccli_footer);
+                    credited = true;
+                }
+                
                 let mut data = Vec::new();
                 if presentation.encode(&mut data).is_err() {
                     self.error_message = Some(format!("Failed to encode presentation for '{}'", item.title));
@@ -2133,6 +2823,7 @@ impl App {
                     presentation_path: String::new(),
                     arrangement_uuid: None,
                     embedded_data: Some(data),
+                    pco_item_id: Some(item.id.clone()),
                 });
                 continue;
             }
@@ -2154,18 +2845,34 @@ impl App {
         let playlist_name = self.get_current_plan_title()
             .unwrap_or_else(|| "Service Playlist".to_string());
 
-        // Determine output path
-        let output_path = self.get_playlist_output_path(&playlist_name);
+        // The plan already has a playlist: sync it instead of writing a second one
+        let plan_path = self.plan_playlist_path(&playlist_name);
+        if plan_path.exists() {
+            match crate::propresenter::playlist::read_playlist_file(&plan_path) {
+                Ok(playlist) => {
+                    self.sync_playlist(plan_path, playlist, &entries, library_updates, self.license_note(credited));
+                    return;
+                }
+                // Not a playlist we can read: leave it alone and write a new one beside it
+                Err(e) => tracing::warn!("Not syncing {}: {e}", plan_path.display()),
+            }
+        }
 
         // Build and write the playlist
+        let output_path = self.get_playlist_output_path(&playlist_name);
         let playlist = build_playlist(&playlist_name, &entries);
         
         match write_playlist_file(&playlist, &entries, &output_path) {
             Ok(()) => {
+                if let Err(e) = Self::save_library_updates(&library_updates) {
+                    self.error_message = Some(e);
+                    return;
+                }
                 self.status_message = Some(format!(
-                    "Playlist saved: {} ({} items)",
+                    "Playlist saved: {} ({} items){}",
                     output_path.display(),
-                    entries.len()
+                    entries.len(),
+                    self.license_note(credited)
                 ));
             }
             Err(e) => {
@@ -2192,7 +2899,85 @@ impl App {
         Some(format!("{date} - {svc}"))
     }
 
-    fn get_playlist_output_path(&self, name: &str) -> std::path::PathBuf {
+    /// Apply the plan's entries to the playlist read from `path` and ask before writing.
+    ///
+    /// `library_updates` are written with the playlist; `note` is added to
+    /// the status line, e.g. from [`Self::license_note`].
+    fn sync_playlist(
+        &mut self,
+        path: PathBuf,
+        mut playlist: PlaylistFile,
+        entries: &[crate::propresenter::playlist::PlaylistEntry],
+        library_updates: Vec<(PathBuf, rv_data::Presentation)>,
+        note: &str,
+    ) {
+        let changes = playlist.sync(entries);
+        if changes.is_empty() {
+            match Self::save_library_updates(&library_updates) {
+                Ok(()) => self.status_message = Some(format!("Playlist up to date: {}{note}", path.display())),
+                Err(e) => self.error_message = Some(e),
+            }
+            return;
+        }
+        self.status_message = Some(format!("Sync {}: {changes}{note}. Write changes? (y/n)", path.display()));
+        self.pending_playlist_sync = Some(PendingPlaylistSync { path, playlist, changes, library_updates });
+    }
+
+    /// Write a confirmed playlist sync back over the existing file.
+    fn write_playlist_sync(&mut self) {
+        let Some(sync) = self.pending_playlist_sync.take() else { return };
+        match sync.playlist.write(&sync.path) {
+            Ok(()) => {
+                if let Err(e) = Self::save_library_updates(&sync.library_updates) {
+                    self.error_message = Some(e);
+                    return;
+                }
+                self.status_message = Some(format!(
+                    "Playlist synced: {} ({}; {} items)",
+                    sync.path.display(),
+                    sync.changes,
+                    sync.playlist.items().len()
+                ));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to write playlist: {e}"));
+            }
+        }
+    }
+
+    /// Write edited matched files back to the library, each after a `.bak` copy.
+    fn save_library_updates(updates: &[(PathBuf, rv_data::Presentation)]) -> std::result::Result<(), String> {
+        for (path, presentation) in updates {
+            crate::propresenter::update::save_pro_file(path, presentation)
+                .map_err(|e| format!("Failed to update {}: {e}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Status line note for exports that credited songs without a CCLI license number
+    const fn license_note(&self, credited: bool) -> &'static str {
+        if credited && self.config.ccli_license.is_none() {
+            " (CCLI_LICENSE not set: song credits have no license number)"
+        } else {
+            ""
+        }
+    }
+
+    /// The playlist path for plan `name`, whether or not it exists yet.
+    fn plan_playlist_path(&self, name: &str) -> PathBuf {
+        self.export_path(name, "proplaylist")
+    }
+
+    /// A path for a new playlist for plan `name` that doesn't overwrite a file.
+    fn get_playlist_output_path(&self, name: &str) -> PathBuf {
+        self.unique_export_path(name, "proplaylist")
+    }
+
+    /// Where exported file `name`.`extension` goes, with `name` made filesystem-safe.
+    ///
+    /// Playlists and bundles go in the library folder, or the working
+    /// directory without one.
+    fn export_path(&self, name: &str, extension: &str) -> PathBuf {
         let base_path = self.library_path.clone()
             .unwrap_or_else(|| std::path::PathBuf::from("."));
 
@@ -2206,14 +2991,20 @@ impl App {
             })
             .collect();
 
-        let candidate = base_path.join(format!("{safe_name}.proplaylist"));
+        base_path.join(format!("{safe_name}.{extension}"))
+    }
+
+    /// [`Self::export_path`], numbered if that's taken so nothing is overwritten.
+    fn unique_export_path(&self, name: &str, extension: &str) -> PathBuf {
+        let candidate = self.export_path(name, extension);
         if !candidate.exists() {
             return candidate;
         }
 
         // Collision avoidance: append (2), (3), … (99)
+        let stem = candidate.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
         for n in 2..=99 {
-            let numbered = base_path.join(format!("{safe_name} ({n}).proplaylist"));
+            let numbered = candidate.with_file_name(format!("{stem} ({n}).{extension}"));
             if !numbered.exists() {
                 return numbered;
             }
@@ -2712,6 +3503,7 @@ impl App {
                                         self.item_states.set_completed(&item_id, index.get_item_completion(&item.id).unwrap_or(false));
                                         self.item_states.set_ignored(&item_id, index.get_item_ignored(&item.id).unwrap_or(false));
                                         self.item_states.set_editor(&item_id, index.get_editor_state(&item.id).cloned());
+                                        self.item_states.set_history(&item_id, index.get_edit_history(&item.id).cloned().unwrap_or_default());
                                         self.item_states.set_matched_file(&item_id, index.get_selection_for_item(&item.id).cloned());
                                     }
                                 }
@@ -2726,6 +3518,11 @@ impl App {
                             }
                         }
                     },
+                    AppUpdate::FontsLoaded(fonts) => {
+                        if let Some(cache) = &mut self.template_cache {
+                            cache.set_fonts(fonts);
+                        }
+                    },
                 }
             },
             Err(mpsc::error::TryRecvError::Empty | mpsc::error::TryRecvError::Disconnected) => {},
@@ -2771,6 +3568,66 @@ impl App {
         None
     }
 
+    /// Bundle the selected item's matched .pro file with its media as a .probundle.
+    fn export_item_bundle(&mut self) {
+        let Some(item) = self.item_list_state.selected().and_then(|idx| self.items.get(idx)) else { return };
+        let Some(matched_path) = self.item_states.get_matched_file(&ItemId::new(&item.id)).map(PathBuf::from) else {
+            self.error_message = Some(format!("Item '{}' has no matched .pro file to bundle.", item.title));
+            return;
+        };
+
+        let stem = matched_path.file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled");
+        let bundle_path = self.unique_export_path(stem, "probundle");
+        match crate::propresenter::bundle::export_bundle(&matched_path, &bundle_path) {
+            Ok(bundle) => {
+                self.status_message = Some(format!(
+                    "Bundle saved: {} ({} media files)",
+                    bundle_path.display(),
+                    bundle.files.len()
+                ));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to bundle {}: {e}", matched_path.display()));
+            }
+        }
+    }
+
+    /// Import a .probundle into the library and match it to the selected item.
+    fn import_item_bundle(&mut self, bundle_path: &str) {
+        let Some(library_path) = self.library_path.clone() else {
+            self.error_message = Some("No library folder set; can't import bundles.".to_string());
+            return;
+        };
+        let bundle_path = PathBuf::from(shellexpand::tilde(bundle_path).to_string());
+        let pro_path = match crate::propresenter::bundle::import_bundle(&bundle_path, &library_path) {
+            Ok(pro_path) => pro_path,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to import {}: {e}", bundle_path.display()));
+                return;
+            }
+        };
+
+        if let Some(index) = &mut self.file_index {
+            index.add_entry(&pro_path);
+        }
+        if let Some(item) = self.item_list_state.selected().and_then(|idx| self.items.get(idx)) {
+            let item_id = ItemId::new(&item.id);
+            self.item_states.set_editor(&item_id, None);
+            self.item_states.set_history(&item_id, EditHistory::new());
+            self.item_states.set_matched_file(&item_id, Some(pro_path.to_string_lossy().to_string()));
+            self.item_states.set_completed(&item_id, true);
+            if let Some(index) = &mut self.file_index {
+                index.remove_editor_state(&item.id);
+                index.record_selection(&item.id, &pro_path);
+                index.save_item_completion(&item.id, true);
+                index.persist();
+            }
+        }
+
+        self.update_matching_files();
+        self.status_message = Some(format!("Imported: {}", pro_path.display()));
+    }
+
     fn select_file_for_item(&mut self) {
         let Some(selected_file_idx) = self.file_list_state.selected() else { return };
         let Some(selected_item_idx) = self.item_list_state.selected() else { return };
@@ -2784,8 +3641,9 @@ impl App {
         // IMPORTANT: Clear editor state when selecting a file match
         // (file match and custom creation are mutually exclusive)
         self.item_states.set_editor(&item_id, None);
+        self.item_states.set_history(&item_id, EditHistory::new());
         if let Some(index) = &mut self.file_index {
-            index.editor_states.remove(&item_id_str);
+            index.remove_editor_state(&item_id_str);
         }
 
         // Record the selection in our item state store
@@ -0,0 +1,295 @@
+//! Localized book names and alias tables for scripture reference parsing.
+//!
+//! Each language is described by a JSON file keyed by canonical (English)
+//! book name:
+//!
+//! ```json
+//! {
+//!   "language": "es",
+//!   "books": {
+//!     "John": { "name": "Juan", "aliases": ["Jn"] }
+//!   }
+//! }
+//! ```
+//!
+//! The canonical name and the localized name always resolve; `aliases` adds
+//! abbreviations. Matching ignores case, periods, whitespace, common Latin
+//! accents, and Roman-numeral prefixes ("I Cor", "II Sam.").
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+/// Language code of the table used when no localized name exists.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Deuterocanonical / apocryphal books, by canonical name.
+///
+/// These appear in Catholic, Orthodox, and ecumenical editions but not in the
+/// Protestant canon.
+pub const DEUTEROCANONICAL_BOOKS: &[&str] = &[
+    "Tobit",
+    "Judith",
+    "Wisdom of Solomon",
+    "Sirach",
+    "Baruch",
+    "Letter of Jeremiah",
+    "Prayer of Azariah",
+    "Susanna",
+    "Bel and the Dragon",
+    "1 Maccabees",
+    "2 Maccabees",
+    "1 Esdras",
+    "2 Esdras",
+    "Prayer of Manasseh",
+    "3 Maccabees",
+    "4 Maccabees",
+];
+
+/// Returns true if `canonical` names a deuterocanonical book.
+pub fn is_deuterocanonical(canonical: &str) -> bool {
+    DEUTEROCANONICAL_BOOKS.contains(&canonical)
+}
+
+/// Tables compiled into the binary so parsing works without a data directory.
+const BUNDLED_TABLES: &[(&str, &str)] = &[
+    ("en.json", include_str!("../../data/books/en.json")),
+    ("es.json", include_str!("../../data/books/es.json")),
+    ("ko.json", include_str!("../../data/books/ko.json")),
+];
+
+/// Registry built from the bundled tables only.
+pub static BUNDLED: LazyLock<BookNameRegistry> = LazyLock::new(BookNameRegistry::bundled);
+
+/// On-disk format of a book name table.
+#[derive(Debug, Deserialize)]
+struct BookNamesFile {
+    language: String,
+    books: HashMap<String, BookEntry>,
+}
+
+/// One book in a [`BookNamesFile`].
+#[derive(Debug, Deserialize)]
+struct BookEntry {
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+/// Book names for a single language.
+#[derive(Debug, Clone)]
+pub struct BookNames {
+    /// Language code (e.g., "en", "es", "ko")
+    pub language: String,
+    /// Canonical book name -> localized display name
+    names: HashMap<String, String>,
+    /// Normalized alias -> canonical book name
+    aliases: HashMap<String, String>,
+}
+
+impl BookNames {
+    /// Parse a table from its JSON representation.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let file: BookNamesFile = serde_json::from_str(json)
+            .map_err(|e| format!("Invalid book name table: {e}"))?;
+
+        let mut names = HashMap::new();
+        let mut aliases = HashMap::new();
+        for (canonical, entry) in file.books {
+            let spellings = [canonical.as_str(), entry.name.as_str()]
+                .into_iter()
+                .chain(entry.aliases.iter().map(String::as_str));
+            for spelling in spellings {
+                aliases.insert(alias_key(spelling), canonical.clone());
+            }
+            names.insert(canonical, entry.name);
+        }
+
+        Ok(Self { language: file.language, names, aliases })
+    }
+
+    /// Resolve a book name or abbreviation to its canonical name.
+    pub fn canonical(&self, name: &str) -> Option<&str> {
+        self.aliases.get(&alias_key(name)).map(String::as_str)
+    }
+
+    /// Localized display name for a canonical book name.
+    pub fn display_name(&self, canonical: &str) -> Option<&str> {
+        self.names.get(canonical).map(String::as_str)
+    }
+}
+
+/// Ordered collection of per-language book name tables.
+///
+/// Lookups try tables in insertion order, so the default language wins
+/// when two languages share an abbreviation.
+#[derive(Debug, Clone, Default)]
+pub struct BookNameRegistry {
+    tables: Vec<BookNames>,
+}
+
+impl BookNameRegistry {
+    /// Registry containing the tables shipped with the application.
+    pub fn bundled() -> Self {
+        let mut registry = Self::default();
+        for (file_name, json) in BUNDLED_TABLES {
+            match BookNames::from_json(json) {
+                Ok(table) => registry.add(table),
+                Err(e) => tracing::warn!("Skipping bundled {file_name}: {e}"),
+            }
+        }
+        registry
+    }
+
+    /// Add a table, replacing any existing table for the same language.
+    pub fn add(&mut self, table: BookNames) {
+        if let Some(existing) = self.tables.iter_mut().find(|t| t.language == table.language) {
+            *existing = table;
+        } else {
+            self.tables.push(table);
+        }
+    }
+
+    /// Load every `*.json` table in a directory.
+    ///
+    /// Files that fail to parse are logged and skipped. Returns the number of
+    /// tables loaded.
+    pub fn load_dir(&mut self, dir: &Path) -> usize {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return 0;
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        let mut loaded = 0;
+        for path in paths {
+            let table = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| BookNames::from_json(&json));
+            match table {
+                Ok(table) => {
+                    self.add(table);
+                    loaded += 1;
+                }
+                Err(e) => tracing::warn!("Skipping {}: {e}", path.display()),
+            }
+        }
+        loaded
+    }
+
+    /// Resolve a book name in any known language to its canonical name.
+    pub fn canonical(&self, name: &str) -> Option<&str> {
+        self.tables.iter().find_map(|t| t.canonical(name))
+    }
+
+    /// Display name for a book in the given language.
+    ///
+    /// Falls back to the canonical name when the language or book is unknown.
+    pub fn display_name<'a>(&'a self, canonical: &'a str, language: &str) -> &'a str {
+        self.tables
+            .iter()
+            .find(|t| t.language == language)
+            .and_then(|t| t.display_name(canonical))
+            .unwrap_or(canonical)
+    }
+}
+
+/// Normalize a book name for alias lookup.
+///
+/// Lowercases, drops periods and whitespace, folds accented Latin vowels, and
+/// rewrites a leading Roman numeral ("I" through "IV") to a digit.
+fn alias_key(name: &str) -> String {
+    let lower = name.to_lowercase().replace('.', " ");
+    let mut words = lower.split_whitespace().peekable();
+
+    let mut key = String::new();
+    if let Some(&first) = words.peek() {
+        let numeral = match first {
+            "i" => Some('1'),
+            "ii" => Some('2'),
+            "iii" => Some('3'),
+            "iv" => Some('4'),
+            _ => None,
+        };
+        if let Some(digit) = numeral {
+            key.push(digit);
+            words.next();
+        }
+    }
+
+    for word in words {
+        key.extend(word.chars().map(fold_accent));
+    }
+    key
+}
+
+/// Strip the accent from a lowercase Latin vowel.
+const fn fold_accent(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ä' => 'a',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ó' | 'ò' | 'ô' | 'ö' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn test_bundled_tables_load() {
+        let registry = BookNameRegistry::bundled();
+        assert_eq!(registry.tables.len(), BUNDLED_TABLES.len());
+        for table in &registry.tables {
+            assert_eq!(table.names.len(), 66 + DEUTEROCANONICAL_BOOKS.len(), "{} table is incomplete", table.language);
+            for book in DEUTEROCANONICAL_BOOKS {
+                assert!(table.display_name(book).is_some(), "{} table is missing {book}", table.language);
+            }
+        }
+    }
+
+    #[test]
+    fn test_alias_key_normalization() {
+        assert_eq!(alias_key("I Cor."), "1cor");
+        assert_eq!(alias_key("II  Sam"), "2sam");
+        assert_eq!(alias_key("Gen."), "gen");
+        assert_eq!(alias_key("Isaiah"), "isaiah");
+        assert_eq!(alias_key("Génesis"), "genesis");
+    }
+
+    #[test]
+    fn test_registry_resolves_localized_names() {
+        let registry = BookNameRegistry::bundled();
+        assert_eq!(registry.canonical("Juan"), Some("John"));
+        assert_eq!(registry.canonical("1 Corintios"), Some("1 Corinthians"));
+        assert_eq!(registry.canonical("요한복음"), Some("John"));
+        assert_eq!(registry.canonical("III John"), Some("3 John"));
+        assert_eq!(registry.canonical("Ecclus."), Some("Sirach"));
+        assert_eq!(registry.canonical("II Macc"), Some("2 Maccabees"));
+        assert_eq!(registry.canonical("Sabiduría"), Some("Wisdom of Solomon"));
+        assert_eq!(registry.display_name("John", "ko"), "요한복음");
+        assert_eq!(registry.display_name("John", "fr"), "John");
+    }
+
+    #[test]
+    fn test_add_replaces_language() {
+        let mut registry = BookNameRegistry::default();
+        registry.add(BookNames::from_json(r#"{"language":"fr","books":{"John":{"name":"Jean"}}}"#).unwrap());
+        registry.add(BookNames::from_json(r#"{"language":"fr","books":{"John":{"name":"Jean","aliases":["Jn"]}}}"#).unwrap());
+        assert_eq!(registry.tables.len(), 1);
+        assert_eq!(registry.canonical("jn"), Some("John"));
+        assert_eq!(registry.display_name("John", "fr"), "Jean");
+    }
+}
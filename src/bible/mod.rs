@@ -1,13 +1,16 @@
 //! Bible verse lookup and scripture reference parsing.
 
+pub mod books;
+
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::path::PathBuf;
-use std::sync::LazyLock;
+
+use books::BookNameRegistry;
 
 /// Supported Bible versions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
-#[allow(clippy::upper_case_acronyms)] // NRSV, NIV, KJV are standard Bible version abbreviations
+#[allow(clippy::upper_case_acronyms)] // NRSV, NIV, KJV, KRV are standard Bible version abbreviations
 pub enum BibleVersion {
     /// New Revised Standard Version Updated Edition
     #[default]
@@ -18,12 +21,16 @@ pub enum BibleVersion {
     NIV,
     /// King James Version
     KJV,
+    /// Reina-Valera 1960 (Spanish)
+    RVR1960,
+    /// Korean Revised Version
+    KRV,
 }
 
 impl BibleVersion {
     /// Returns all available Bible versions.
     pub const fn all() -> &'static [Self] {
-        &[Self::NRSVue, Self::NRSV, Self::NIV, Self::KJV]
+        &[Self::NRSVue, Self::NRSV, Self::NIV, Self::KJV, Self::RVR1960, Self::KRV]
     }
 
     /// Returns the human-readable name of this version.
@@ -33,6 +40,25 @@ impl BibleVersion {
             Self::NRSV => "NRSV",
             Self::NIV => "NIV",
             Self::KJV => "KJV",
+            Self::RVR1960 => "RVR1960",
+            Self::KRV => "KRV",
+        }
+    }
+
+    /// Returns the language code used for book names in this version.
+    pub const fn language(self) -> &'static str {
+        match self {
+            Self::NRSVue | Self::NRSV | Self::NIV | Self::KJV => books::DEFAULT_LANGUAGE,
+            Self::RVR1960 => "es",
+            Self::KRV => "ko",
+        }
+    }
+
+    /// Returns the canon this version's data files cover.
+    pub const fn canon(self) -> Canon {
+        match self {
+            Self::NRSVue | Self::NRSV => Canon::WithDeuterocanon,
+            Self::NIV | Self::KJV | Self::RVR1960 | Self::KRV => Canon::Protestant,
         }
     }
 
@@ -43,6 +69,8 @@ impl BibleVersion {
             Self::NRSV => "NRSV.json",
             Self::NIV => "NIV.json",
             Self::KJV => "KJV.json",
+            Self::RVR1960 => "RVR1960.json",
+            Self::KRV => "KRV.json",
         }
     }
 
@@ -53,10 +81,31 @@ impl BibleVersion {
         if upper.contains("NRSV") { return Some(Self::NRSV); }
         if upper.contains("NIV") { return Some(Self::NIV); }
         if upper.contains("KJV") { return Some(Self::KJV); }
+        if upper.contains("RVR") { return Some(Self::RVR1960); }
+        if upper.contains("KRV") { return Some(Self::KRV); }
         None
     }
 }
 
+/// The set of books a Bible version includes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Canon {
+    /// The 66-book Protestant canon
+    Protestant,
+    /// Protestant canon plus the deuterocanonical books
+    WithDeuterocanon,
+}
+
+impl Canon {
+    /// Returns true if `book` (canonical name) belongs to this canon.
+    pub fn includes(self, book: &str) -> bool {
+        match self {
+            Self::Protestant => !books::is_deuterocanonical(book),
+            Self::WithDeuterocanon => true,
+        }
+    }
+}
+
 /// A parsed scripture reference
 #[derive(Debug, Clone)]
 pub struct ScriptureRef {
@@ -73,152 +122,6 @@ pub struct ScriptureRef {
 /// Bible data structure: Book -> Chapter -> Verse -> Text
 type BibleData = HashMap<String, HashMap<String, HashMap<String, String>>>;
 
-/// Book name normalization map
-static BOOK_ALIASES: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
-    let mut m = HashMap::new();
-    // Common abbreviations and variations
-    m.insert("gen", "Genesis");
-    m.insert("genesis", "Genesis");
-    m.insert("ex", "Exodus");
-    m.insert("exod", "Exodus");
-    m.insert("exodus", "Exodus");
-    m.insert("lev", "Leviticus");
-    m.insert("leviticus", "Leviticus");
-    m.insert("num", "Numbers");
-    m.insert("numbers", "Numbers");
-    m.insert("deut", "Deuteronomy");
-    m.insert("deuteronomy", "Deuteronomy");
-    m.insert("josh", "Joshua");
-    m.insert("joshua", "Joshua");
-    m.insert("judg", "Judges");
-    m.insert("judges", "Judges");
-    m.insert("ruth", "Ruth");
-    m.insert("1 sam", "1 Samuel");
-    m.insert("1 samuel", "1 Samuel");
-    m.insert("1sam", "1 Samuel");
-    m.insert("2 sam", "2 Samuel");
-    m.insert("2 samuel", "2 Samuel");
-    m.insert("2sam", "2 Samuel");
-    m.insert("1 kings", "1 Kings");
-    m.insert("1 kgs", "1 Kings");
-    m.insert("1kings", "1 Kings");
-    m.insert("2 kings", "2 Kings");
-    m.insert("2 kgs", "2 Kings");
-    m.insert("2kings", "2 Kings");
-    m.insert("1 chr", "1 Chronicles");
-    m.insert("1 chronicles", "1 Chronicles");
-    m.insert("1chronicles", "1 Chronicles");
-    m.insert("2 chr", "2 Chronicles");
-    m.insert("2 chronicles", "2 Chronicles");
-    m.insert("2chronicles", "2 Chronicles");
-    m.insert("ezra", "Ezra");
-    m.insert("neh", "Nehemiah");
-    m.insert("nehemiah", "Nehemiah");
-    m.insert("esth", "Esther");
-    m.insert("esther", "Esther");
-    m.insert("job", "Job");
-    m.insert("ps", "Psalms");
-    m.insert("psalm", "Psalms");
-    m.insert("psalms", "Psalms");
-    m.insert("prov", "Proverbs");
-    m.insert("proverbs", "Proverbs");
-    m.insert("eccl", "Ecclesiastes");
-    m.insert("ecclesiastes", "Ecclesiastes");
-    m.insert("song", "Song of Solomon");
-    m.insert("song of solomon", "Song of Solomon");
-    m.insert("song of songs", "Song of Solomon");
-    m.insert("isa", "Isaiah");
-    m.insert("isaiah", "Isaiah");
-    m.insert("jer", "Jeremiah");
-    m.insert("jeremiah", "Jeremiah");
-    m.insert("lam", "Lamentations");
-    m.insert("lamentations", "Lamentations");
-    m.insert("ezek", "Ezekiel");
-    m.insert("ezekiel", "Ezekiel");
-    m.insert("dan", "Daniel");
-    m.insert("daniel", "Daniel");
-    m.insert("hos", "Hosea");
-    m.insert("hosea", "Hosea");
-    m.insert("joel", "Joel");
-    m.insert("amos", "Amos");
-    m.insert("obad", "Obadiah");
-    m.insert("obadiah", "Obadiah");
-    m.insert("jonah", "Jonah");
-    m.insert("mic", "Micah");
-    m.insert("micah", "Micah");
-    m.insert("nah", "Nahum");
-    m.insert("nahum", "Nahum");
-    m.insert("hab", "Habakkuk");
-    m.insert("habakkuk", "Habakkuk");
-    m.insert("zeph", "Zephaniah");
-    m.insert("zephaniah", "Zephaniah");
-    m.insert("hag", "Haggai");
-    m.insert("haggai", "Haggai");
-    m.insert("zech", "Zechariah");
-    m.insert("zechariah", "Zechariah");
-    m.insert("mal", "Malachi");
-    m.insert("malachi", "Malachi");
-    // New Testament
-    m.insert("matt", "Matthew");
-    m.insert("matthew", "Matthew");
-    m.insert("mark", "Mark");
-    m.insert("luke", "Luke");
-    m.insert("john", "John");
-    m.insert("acts", "Acts");
-    m.insert("rom", "Romans");
-    m.insert("romans", "Romans");
-    m.insert("1 cor", "1 Corinthians");
-    m.insert("1 corinthians", "1 Corinthians");
-    m.insert("1cor", "1 Corinthians");
-    m.insert("2 cor", "2 Corinthians");
-    m.insert("2 corinthians", "2 Corinthians");
-    m.insert("2cor", "2 Corinthians");
-    m.insert("gal", "Galatians");
-    m.insert("galatians", "Galatians");
-    m.insert("eph", "Ephesians");
-    m.insert("ephesians", "Ephesians");
-    m.insert("phil", "Philippians");
-    m.insert("philippians", "Philippians");
-    m.insert("col", "Colossians");
-    m.insert("colossians", "Colossians");
-    m.insert("1 thess", "1 Thessalonians");
-    m.insert("1 thessalonians", "1 Thessalonians");
-    m.insert("1thess", "1 Thessalonians");
-    m.insert("2 thess", "2 Thessalonians");
-    m.insert("2 thessalonians", "2 Thessalonians");
-    m.insert("2thess", "2 Thessalonians");
-    m.insert("1 tim", "1 Timothy");
-    m.insert("1 timothy", "1 Timothy");
-    m.insert("1tim", "1 Timothy");
-    m.insert("2 tim", "2 Timothy");
-    m.insert("2 timothy", "2 Timothy");
-    m.insert("2tim", "2 Timothy");
-    m.insert("titus", "Titus");
-    m.insert("philem", "Philemon");
-    m.insert("philemon", "Philemon");
-    m.insert("heb", "Hebrews");
-    m.insert("hebrews", "Hebrews");
-    m.insert("james", "James");
-    m.insert("jas", "James");
-    m.insert("1 pet", "1 Peter");
-    m.insert("1 peter", "1 Peter");
-    m.insert("1pet", "1 Peter");
-    m.insert("2 pet", "2 Peter");
-    m.insert("2 peter", "2 Peter");
-    m.insert("2pet", "2 Peter");
-    m.insert("1 john", "1 John");
-    m.insert("1john", "1 John");
-    m.insert("2 john", "2 John");
-    m.insert("2john", "2 John");
-    m.insert("3 john", "3 John");
-    m.insert("3john", "3 John");
-    m.insert("jude", "Jude");
-    m.insert("rev", "Revelation");
-    m.insert("revelation", "Revelation");
-    m.insert("revelations", "Revelation");
-    m
-});
-
 /// Superscript digit mapping
 const SUPERSCRIPT_DIGITS: &[char] = &['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
 
@@ -232,97 +135,70 @@ fn to_superscript(n: u32) -> String {
         .collect()
 }
 
-/// Normalize book name to canonical form
-fn normalize_book_name(name: &str) -> Option<&'static str> {
-    let lower = name.to_lowercase();
-    let trimmed = lower.trim();
-
-    // Direct lookup
-    if let Some(&canonical) = BOOK_ALIASES.get(trimmed) {
-        return Some(canonical);
-    }
-
-    // Try without spaces for numbered books
-    let no_space = trimmed.replace(' ', "");
-    if let Some(&canonical) = BOOK_ALIASES.get(no_space.as_str()) {
-        return Some(canonical);
-    }
-
-    None
-}
+/// Version abbreviations that may trail a reference without parentheses.
+const VERSION_SUFFIXES: &[&str] = &["NRSVue", "NRSVUE", "NRSV", "NIV", "KJV", "ESV", "RVR1960", "KRV"];
 
 /// Parse a scripture reference string like "Isaiah 32:15-17" or "1 John 3:1-3".
 ///
 /// Also handles complex titles like "Scripture: Isaiah 32:15-17; Luke 1:76-79
-/// `NRSVue` (Hope)" or "Scripture - Isaiah 35:1-10 (Adrian)".
+/// `NRSVue` (Hope)" or "Scripture - Isaiah 35:1-10 (Adrian)". Book names are
+/// resolved with the bundled tables; see [`parse_scripture_ref_with`].
 pub fn parse_scripture_ref(text: &str) -> Option<ScriptureRef> {
-    // Strip various "Scripture" prefix formats
-    let text = text.trim_start_matches("Scripture:")
-        .trim_start_matches("Scripture -")
-        .trim_start_matches("Scripture Reading:")
-        .trim_start_matches("Scripture Reading -")
-        .trim_start_matches("Reading:")
-        .trim_start_matches("Reading -")
-        .trim();
+    parse_scripture_ref_with(text, &books::BUNDLED)
+}
+
+/// Parse a scripture reference, resolving book names with `books`.
+pub fn parse_scripture_ref_with(text: &str, books: &BookNameRegistry) -> Option<ScriptureRef> {
+    let text = strip_scripture_prefix(text);
 
     // Take only the first reference if multiple (separated by ; or ,)
     let first_ref = text.split(';').next()
-        .or_else(|| text.split(',').next())?
-        .trim();
+        .or_else(|| text.split(',').next())?;
 
-    // Remove version and location indicators like "(NRSV)" or "(Hope)" or "NRSVue"
-    // Also handle version without parens at end
-    let cleaned = first_ref
-        .split('(').next()?
-        .trim()
-        .trim_end_matches("NRSVue")
-        .trim_end_matches("NRSVUE")
-        .trim_end_matches("NRSV")
-        .trim_end_matches("NIV")
-        .trim_end_matches("KJV")
-        .trim_end_matches("ESV")
-        .trim();
-
-    parse_single_reference(cleaned)
+    parse_single_reference(strip_version_suffix(first_ref)?, books)
 }
 
 /// Parse multiple scripture references from a title.
 pub fn parse_scripture_refs(text: &str) -> Vec<ScriptureRef> {
-    // Strip various prefix formats
-    let text = text.trim_start_matches("Scripture:")
+    parse_scripture_refs_with(text, &books::BUNDLED)
+}
+
+/// Parse multiple scripture references, resolving book names with `books`.
+pub fn parse_scripture_refs_with(text: &str, books: &BookNameRegistry) -> Vec<ScriptureRef> {
+    // Split by ; or , and parse each
+    strip_scripture_prefix(text)
+        .split([';', ','])
+        .filter_map(|part| parse_single_reference(strip_version_suffix(part)?, books))
+        .collect()
+}
+
+/// Strip various "Scripture" prefix formats from an item title.
+fn strip_scripture_prefix(text: &str) -> &str {
+    text.trim_start_matches("Scripture:")
         .trim_start_matches("Scripture -")
         .trim_start_matches("Scripture Reading:")
         .trim_start_matches("Scripture Reading -")
         .trim_start_matches("Reading:")
         .trim_start_matches("Reading -")
-        .trim();
+        .trim()
+}
 
-    // Split by ; or , and parse each
-    text.split([';', ','])
-        .filter_map(|part| {
-            let cleaned = part.trim()
-                .split('(').next()?
-                .trim()
-                .trim_end_matches("NRSVue")
-                .trim_end_matches("NRSVUE")
-                .trim_end_matches("NRSV")
-                .trim_end_matches("NIV")
-                .trim_end_matches("KJV")
-                .trim_end_matches("ESV")
-                .trim();
-            parse_single_reference(cleaned)
-        })
-        .collect()
+/// Remove version and location indicators like "(NRSV)", "(Hope)", or a bare
+/// trailing "`NRSVue`".
+fn strip_version_suffix(text: &str) -> Option<&str> {
+    let mut cleaned = text.trim().split('(').next()?.trim();
+    for suffix in VERSION_SUFFIXES {
+        cleaned = cleaned.trim_end_matches(suffix);
+    }
+    Some(cleaned.trim())
 }
 
 /// Parse a single scripture reference like "Isaiah 32:15-17"
-fn parse_single_reference(text: &str) -> Option<ScriptureRef> {
+fn parse_single_reference(text: &str, books: &BookNameRegistry) -> Option<ScriptureRef> {
+    // Find where the chapter:verse starts (last whitespace-separated token)
+    let mut parts = text.rsplitn(2, char::is_whitespace);
     // Handle "v" notation (e.g., "Luke 2v1-20")
-    let text = text.replace('v', ":");
-
-    // Find where the chapter:verse starts (look for digits followed by colon)
-    let mut parts = text.rsplitn(2, |c: char| c.is_whitespace());
-    let verse_part = parts.next()?;
+    let verse_part = parts.next()?.replace('v', ":");
     let book_part = parts.next()?.trim();
 
     // Parse chapter:verse-verse pattern
@@ -338,7 +214,7 @@ fn parse_single_reference(text: &str) -> Option<ScriptureRef> {
         (verse_range.parse().ok()?, None)
     };
 
-    let book = normalize_book_name(book_part)?;
+    let book = books.canonical(book_part)?;
 
     Some(ScriptureRef {
         book: book.to_string(),
@@ -354,6 +230,8 @@ pub struct BibleService {
     data_path: PathBuf,
     /// Cached Bible data keyed by version
     cache: HashMap<BibleVersion, BibleData>,
+    /// Book name tables used for parsing and localized headers
+    book_names: BookNameRegistry,
 }
 
 impl BibleService {
@@ -362,9 +240,27 @@ impl BibleService {
         Self {
             data_path,
             cache: HashMap::new(),
+            book_names: books::BUNDLED.clone(),
         }
     }
 
+    /// Replaces the book name tables (e.g., with user-supplied languages).
+    #[must_use]
+    pub fn with_book_names(mut self, book_names: BookNameRegistry) -> Self {
+        self.book_names = book_names;
+        self
+    }
+
+    /// Returns the book name tables used by this service.
+    pub const fn book_names(&self) -> &BookNameRegistry {
+        &self.book_names
+    }
+
+    /// Parse a scripture reference using this service's book name tables.
+    pub fn parse_reference(&self, text: &str) -> Option<ScriptureRef> {
+        parse_scripture_ref_with(text, &self.book_names)
+    }
+
     /// Load a Bible version into cache
     fn load_version(&mut self, version: BibleVersion) -> Result<(), String> {
         if self.cache.contains_key(&version) {
@@ -386,13 +282,36 @@ impl BibleService {
     ///
     /// Returns a header for display and the verse text lines.
     pub fn lookup(&mut self, reference: &ScriptureRef, version: BibleVersion) -> Result<(ScriptureHeader, Vec<String>), String> {
+        if !version.canon().includes(&reference.book) {
+            return Err(format!(
+                "{} is not in the {} canon; choose a version with the deuterocanonical books (e.g. NRSVue)",
+                reference.book,
+                version.name()
+            ));
+        }
+
         self.load_version(version)?;
 
         let bible = self.cache.get(&version)
             .ok_or_else(|| "Bible data not loaded".to_string())?;
 
+        // Translations may key books by canonical, localized, or alternate name
+        // (e.g., "Ecclesiasticus" for Sirach)
+        let book_name = self.book_names.display_name(&reference.book, version.language());
         let book_data = bible.get(&reference.book)
-            .ok_or_else(|| format!("Book not found: {}", reference.book))?;
+            .or_else(|| bible.get(book_name))
+            .or_else(|| {
+                bible.iter()
+                    .find(|(key, _)| self.book_names.canonical(key) == Some(reference.book.as_str()))
+                    .map(|(_, data)| data)
+            })
+            .ok_or_else(|| {
+                if books::is_deuterocanonical(&reference.book) {
+                    format!("{} is missing from {}; the data file has no deuterocanonical books", reference.book, version.file_name())
+                } else {
+                    format!("Book not found: {}", reference.book)
+                }
+            })?;
 
         let chapter_data = book_data.get(&reference.chapter.to_string())
             .ok_or_else(|| format!("Chapter {} not found in {}", reference.chapter, reference.book))?;
@@ -403,6 +322,7 @@ impl BibleService {
         // Build header info (for pane title, not content)
         let header = ScriptureHeader {
             book: reference.book.clone(),
+            book_name: book_name.to_string(),
             chapter: reference.chapter,
             start_verse: reference.start_verse,
             end_verse: reference.end_verse,
@@ -436,6 +356,8 @@ impl BibleService {
 pub struct ScriptureHeader {
     /// Canonical book name
     pub book: String,
+    /// Book name in the version's language (e.g., "Juan" for RVR1960)
+    pub book_name: String,
     /// Chapter number
     pub chapter: u32,
     /// First verse in the range
@@ -450,16 +372,16 @@ impl ScriptureHeader {
     /// Format for display (e.g., "Isaiah 32:15-17 `NRSVue`").
     pub fn display(&self) -> String {
         self.end_verse.map_or_else(
-            || format!("{} {}:{} {}", self.book, self.chapter, self.start_verse, self.version.name()),
-            |end| format!("{} {}:{}-{end} {}", self.book, self.chapter, self.start_verse, self.version.name()),
+            || format!("{} {}:{} {}", self.book_name, self.chapter, self.start_verse, self.version.name()),
+            |end| format!("{} {}:{}-{end} {}", self.book_name, self.chapter, self.start_verse, self.version.name()),
         )
     }
 
     /// Format for filename (colon replaced with v).
     pub fn filename(&self) -> String {
         self.end_verse.map_or_else(
-            || format!("{} {}v{} ({})", self.book, self.chapter, self.start_verse, self.version.name()),
-            |end| format!("{} {}v{}-{end} ({})", self.book, self.chapter, self.start_verse, self.version.name()),
+            || format!("{} {}v{} ({})", self.book_name, self.chapter, self.start_verse, self.version.name()),
+            |end| format!("{} {}v{}-{end} ({})", self.book_name, self.chapter, self.start_verse, self.version.name()),
         )
     }
 }
@@ -519,5 +441,67 @@ mod tests {
         assert_eq!(BibleVersion::from_text("NRSVue"), Some(BibleVersion::NRSVue));
         assert_eq!(BibleVersion::from_text("KJV"), Some(BibleVersion::KJV));
         assert_eq!(BibleVersion::from_text("NIV"), Some(BibleVersion::NIV));
+        assert_eq!(BibleVersion::from_text("(RVR1960)"), Some(BibleVersion::RVR1960));
+        assert_eq!(BibleVersion::from_text("KRV"), Some(BibleVersion::KRV));
+    }
+
+    #[test]
+    fn test_parse_abbreviations() {
+        assert_eq!(parse_scripture_ref("I Cor. 13:4-7").unwrap().book, "1 Corinthians");
+        assert_eq!(parse_scripture_ref("Gen. 1:1").unwrap().book, "Genesis");
+        assert_eq!(parse_scripture_ref("II Sam 7:1").unwrap().book, "2 Samuel");
+    }
+
+    #[test]
+    fn test_parse_books_containing_v() {
+        let r = parse_scripture_ref("Revelation 21v1-4").unwrap();
+        assert_eq!(r.book, "Revelation");
+        assert_eq!(r.chapter, 21);
+        assert_eq!(r.end_verse, Some(4));
+        assert_eq!(parse_scripture_ref("Leviticus 19:18").unwrap().book, "Leviticus");
+    }
+
+    #[test]
+    fn test_parse_localized_books() {
+        let r = parse_scripture_ref("Juan 3:16 (RVR1960)").unwrap();
+        assert_eq!(r.book, "John");
+        assert_eq!(r.start_verse, 16);
+        assert_eq!(parse_scripture_ref("Apocalipsis 21:1").unwrap().book, "Revelation");
+        assert_eq!(parse_scripture_ref("고전 13:4-7").unwrap().book, "1 Corinthians");
+    }
+
+    #[test]
+    fn test_parse_deuterocanonical_books() {
+        let r = parse_scripture_ref("Sirach 44:1-15 (NRSVue)").unwrap();
+        assert_eq!(r.book, "Sirach");
+        assert_eq!(r.chapter, 44);
+        assert_eq!(parse_scripture_ref("Wis 3:1-9").unwrap().book, "Wisdom of Solomon");
+        assert_eq!(parse_scripture_ref("Tobit 8:4-8").unwrap().book, "Tobit");
+        assert_eq!(parse_scripture_ref("Baruch 5:1-9").unwrap().book, "Baruch");
+        assert_eq!(parse_scripture_ref("1 Macc. 2:15-29").unwrap().book, "1 Maccabees");
+    }
+
+    #[test]
+    fn test_canon_rejects_missing_books() {
+        let reference = parse_scripture_ref("Sirach 44:1").unwrap();
+        let mut service = BibleService::new(PathBuf::from("/nonexistent"));
+        let err = service.lookup(&reference, BibleVersion::NIV).unwrap_err();
+        assert!(err.contains("not in the NIV canon"), "{err}");
+        assert!(BibleVersion::NRSVue.canon().includes("Sirach"));
+        assert!(BibleVersion::KJV.canon().includes("John"));
+    }
+
+    #[test]
+    fn test_header_uses_localized_book_name() {
+        let header = ScriptureHeader {
+            book: "John".to_string(),
+            book_name: books::BUNDLED.display_name("John", BibleVersion::RVR1960.language()).to_string(),
+            chapter: 3,
+            start_verse: 16,
+            end_verse: Some(17),
+            version: BibleVersion::RVR1960,
+        };
+        assert_eq!(header.display(), "Juan 3:16-17 RVR1960");
+        assert_eq!(header.filename(), "Juan 3v16-17 (RVR1960)");
     }
 }
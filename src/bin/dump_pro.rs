@@ -3,6 +3,10 @@
 //! Usage:
 //!   `cargo run --bin dump_pro -- <file.pro>`
 //!   `cargo run --bin dump_pro -- <file1.pro> <file2.pro> --diff`
+//!   `cargo run --bin dump_pro -- <file.proplaylist>`
+//!   `cargo run --bin dump_pro -- <file.probundle>`
+//!   `cargo run --bin dump_pro -- bundle <file.pro> [out.probundle]`
+//!   `cargo run --bin dump_pro -- import <file.probundle> <library dir>`
 //!
 //! This tool outputs a detailed structure of the presentation for debugging
 //! slide generation issues.
@@ -12,6 +16,8 @@
 
 use prost::Message;
 use proflow::propresenter::generated::rv_data;
+use proflow::propresenter::bundle::{default_media_dir, export_bundle, import_bundle, read_bundle};
+use proflow::propresenter::playlist::{item_file_name, read_playlist_file};
 use std::env;
 use std::fs;
 use std::path::Path;
@@ -22,12 +28,24 @@ fn main() {
     if args.len() < 2 {
         eprintln!("Usage: {} <file.pro> [file2.pro --diff]", args[0]);
         eprintln!("       {} <file.pro> --json", args[0]);
+        eprintln!("       {} <file.proplaylist | file.probundle>", args[0]);
+        eprintln!("       {} bundle <file.pro> [out.probundle]", args[0]);
+        eprintln!("       {} import <file.probundle> <library dir>", args[0]);
         std::process::exit(1);
     }
 
     let path = Path::new(&args[1]);
-
-    if args.contains(&"--json".to_string()) {
+    let has_extension = |ext: &str| path.extension().is_some_and(|e| e.eq_ignore_ascii_case(ext));
+
+    if args[1] == "bundle" && args.len() >= 3 {
+        bundle(Path::new(&args[2]), args.get(3).map(Path::new));
+    } else if args[1] == "import" && args.len() >= 4 {
+        import(Path::new(&args[2]), Path::new(&args[3]));
+    } else if has_extension("proplaylist") {
+        dump_playlist(path);
+    } else if has_extension("probundle") {
+        dump_bundle(path);
+    } else if args.contains(&"--json".to_string()) {
         dump_json(path);
     } else if args.len() >= 4 && args.contains(&"--diff".to_string()) {
         let path2 = Path::new(&args[2]);
@@ -153,6 +171,61 @@ fn dump_presentation(path: &Path) {
     }
 }
 
+fn dump_playlist(path: &Path) {
+    let playlist = read_playlist_file(path).unwrap_or_else(|e| {
+        eprintln!("Failed to read {}: {e}", path.display());
+        std::process::exit(1);
+    });
+
+    println!("📋 PLAYLIST: {}", playlist.name());
+    println!("🎬 ITEMS ({} total)", playlist.items().len());
+    for (i, item) in playlist.items().iter().enumerate() {
+        let prefix = if i == playlist.items().len() - 1 { "└" } else { "├" };
+        let file = item_file_name(item).unwrap_or_default();
+        let source = playlist.embedded_data(item)
+            .map_or_else(|| "external".to_string(), |data| format!("embedded, {} bytes", data.len()));
+        println!("{prefix}─ {i}: \"{}\" -> {file} ({source})", item.name);
+    }
+    println!();
+
+    println!("📦 FILES ({} total)", playlist.files.len());
+    for (i, (name, data)) in playlist.files.iter().enumerate() {
+        let prefix = if i == playlist.files.len() - 1 { "└" } else { "├" };
+        println!("{prefix}─ {name} ({} bytes)", data.size());
+    }
+}
+
+fn dump_bundle(path: &Path) {
+    let bundle = read_bundle(path).unwrap_or_else(|e| {
+        eprintln!("Failed to read {}: {e}", path.display());
+        std::process::exit(1);
+    });
+
+    println!("📦 BUNDLE: {}.pro ({} cues)", bundle.name, bundle.presentation.cues.len());
+    println!("🖼  MEDIA ({} total)", bundle.files.len());
+    for (i, (name, data)) in bundle.files.iter().enumerate() {
+        let prefix = if i == bundle.files.len() - 1 { "└" } else { "├" };
+        println!("{prefix}─ {name} ({} bytes)", data.size());
+    }
+}
+
+fn bundle(pro_path: &Path, bundle_path: Option<&Path>) {
+    let bundle_path = bundle_path.map_or_else(|| pro_path.with_extension("probundle"), Path::to_path_buf);
+    let bundle = export_bundle(pro_path, &bundle_path).unwrap_or_else(|e| {
+        eprintln!("Failed to bundle {}: {e}", pro_path.display());
+        std::process::exit(1);
+    });
+    println!("Wrote {} ({} media files)", bundle_path.display(), bundle.files.len());
+}
+
+fn import(bundle_path: &Path, library_dir: &Path) {
+    let pro_path = import_bundle(bundle_path, library_dir).unwrap_or_else(|e| {
+        eprintln!("Failed to import {}: {e}", bundle_path.display());
+        std::process::exit(1);
+    });
+    println!("Imported {} (media in {})", pro_path.display(), default_media_dir(library_dir).display());
+}
+
 fn dump_action_type_data(type_data: &rv_data::action::ActionTypeData, parent_prefix: &str, child_prefix: &str) {
     match type_data {
         rv_data::action::ActionTypeData::Slide(slide_type) => {
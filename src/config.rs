@@ -6,6 +6,9 @@ use dotenv::dotenv;
 use std::env;
 use std::path::PathBuf;
 use crate::error::Result;
+use crate::hymnal::HymnalSource;
+use crate::lyrics::lint::LintConfig;
+use crate::propresenter::copyright::CopyrightFooter;
 
 /// Configuration for the application.
 #[derive(Debug, Clone)]
@@ -22,8 +25,22 @@ pub struct Config {
     pub propresenter_path: Option<String>,
     /// How many days ahead to load services
     pub days_ahead: i64,
-    /// Path to hymnal directory containing curated .txt files
-    pub hymnal_path: Option<PathBuf>,
+    /// Hymnal directories containing curated .txt files, default first
+    pub hymnals: Vec<HymnalSource>,
+    /// Directories of importable lyric files (`OpenLyrics`, `ChordPro`, `SongSelect`)
+    pub lyrics_dirs: Vec<PathBuf>,
+    /// Repeat a hymn's refrain after every verse when loading it into the editor
+    pub hymn_repeat_refrain: bool,
+    /// House-style lint rules for lyrics
+    pub lint: LintConfig,
+    /// Directories searched for the font files templates use
+    pub font_dirs: Vec<PathBuf>,
+    /// Start the slide editor with vim-style modal keys
+    pub vim_mode: bool,
+    /// The church's CCLI license number, shown in song credits
+    pub ccli_license: Option<String>,
+    /// Where song copyright credits go on exported slides
+    pub ccli_footer: CopyrightFooter,
 }
 
 impl Config {
@@ -49,7 +66,14 @@ impl Default for Config {
             pco_secret: String::new(),
             propresenter_path: None,
             days_ahead: 30,
-            hymnal_path: None,
+            hymnals: Vec::new(),
+            lyrics_dirs: Vec::new(),
+            hymn_repeat_refrain: true,
+            lint: LintConfig::default(),
+            font_dirs: Vec::new(),
+            vim_mode: false,
+            ccli_license: None,
+            ccli_footer: CopyrightFooter::default(),
         }
     }
 }
@@ -87,19 +111,71 @@ impl Config {
             }
         }
 
-        // Hymnal path: env var override, or default ~/Documents/Presbyterian Hymnal/
-        config.hymnal_path = env::var("HYMNAL_PATH").ok().map_or_else(
+        // Hymnal paths: env var override (list of [CODE=]PATH entries),
+        // or default ~/Documents/Presbyterian Hymnal/
+        config.hymnals = env::var("HYMNAL_PATH").ok().map_or_else(
             || {
                 dirs::home_dir()
                     .map(|h| h.join("Documents/Presbyterian Hymnal"))
                     .filter(|p| p.is_dir())
+                    .map(HymnalSource::from_dir)
+                    .into_iter()
+                    .collect()
             },
-            |path| {
-                let p = PathBuf::from(shellexpand::tilde(&path).to_string());
-                p.is_dir().then_some(p)
+            |paths| {
+                HymnalSource::parse_list(&paths)
+                    .into_iter()
+                    .filter(|source| source.path.is_dir())
+                    .collect()
             },
         );
 
+        // Lyric file directories, separated like PATH
+        if let Ok(paths) = env::var("LYRICS_PATH") {
+            config.lyrics_dirs = env::split_paths(&paths)
+                .filter_map(|p| p.to_str().map(|p| PathBuf::from(shellexpand::tilde(p.trim()).to_string())))
+                .filter(|p| p.is_dir())
+                .collect();
+        }
+
+        // Refrain repetition can be turned off for hymnals that print it after every verse
+        if let Ok(repeat) = env::var("HYMN_REPEAT_REFRAIN") {
+            config.hymn_repeat_refrain = !matches!(repeat.trim().to_lowercase().as_str(), "0" | "false" | "no" | "off");
+        }
+
+        // Font directories, separated like PATH, or the system font folders
+        config.font_dirs = env::var("FONT_PATH").ok().map_or_else(detect_font_dirs, |paths| {
+            env::split_paths(&paths)
+                .filter_map(|p| p.to_str().map(|p| PathBuf::from(shellexpand::tilde(p.trim()).to_string())))
+                .filter(|p| p.is_dir())
+                .collect()
+        });
+
+        // Lyric lint rules: "rule=severity" pairs, e.g. "all-caps=error,divine-names=off"
+        if let Ok(rules) = env::var("LYRIC_LINT") {
+            config.lint.apply_overrides(&rules);
+        }
+        if let Ok(max) = env::var("LYRIC_LINT_MAX_LINES") {
+            if let Ok(max) = max.trim().parse::<usize>() {
+                config.lint.max_lines = max.max(1);
+            }
+        }
+        if let Ok(block) = env::var("LYRIC_LINT_BLOCK_EXPORT") {
+            config.lint.block_on_error = matches!(block.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on");
+        }
+
+        if let Ok(vim) = env::var("VIM_MODE") {
+            config.vim_mode = matches!(vim.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on");
+        }
+
+        // CCLI license number and where song credits go ("off", "line" or "slide")
+        config.ccli_license = env::var("CCLI_LICENSE").ok()
+            .map(|license| license.trim().to_string())
+            .filter(|license| !license.is_empty());
+        if let Some(footer) = env::var("CCLI_FOOTER").ok().and_then(|footer| CopyrightFooter::parse(&footer)) {
+            config.ccli_footer = footer;
+        }
+
         Ok(config)
     }
 
@@ -109,6 +185,20 @@ impl Config {
     }
 }
 
+/// System and user font directories that exist on this machine
+fn detect_font_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = if cfg!(target_os = "macos") {
+        ["/System/Library/Fonts", "/Library/Fonts"].iter().map(PathBuf::from).collect()
+    } else if cfg!(target_os = "windows") {
+        vec![PathBuf::from("C:\\Windows\\Fonts")]
+    } else {
+        ["/usr/share/fonts", "/usr/local/share/fonts"].iter().map(PathBuf::from).collect()
+    };
+    dirs.extend(dirs::font_dir());
+    dirs.retain(|p| p.is_dir());
+    dirs
+}
+
 /// Attempt to detect `ProPresenter` installation path
 fn detect_propresenter_path() -> Option<String> {
     // Common installation paths for different platforms
@@ -18,6 +18,9 @@ pub mod editor {
 
     /// Minimum wrap column used in visual line calculations.
     pub const MIN_VISUAL_WRAP: usize = 10;
+
+    /// Maximum undo steps kept per item.
+    pub const MAX_UNDO_HISTORY: usize = 200;
 }
 
 /// Search and file matching constants.
@@ -41,6 +44,12 @@ pub mod template {
     pub const MIN_SLIDE_WRAP: usize = 20;
 }
 
+/// Lyric lint constants.
+pub mod lint {
+    /// Default maximum lyric lines per slide.
+    pub const DEFAULT_MAX_LINES: usize = 4;
+}
+
 /// Async task constants.
 pub mod async_tasks {
     /// Channel buffer size for async task communication.
@@ -54,4 +63,10 @@ pub mod ui {
 
     /// Minimum pane width in characters.
     pub const MIN_PANE_WIDTH: u16 = 20;
+
+    /// Width of the lint gutter beside lyric editor content.
+    pub const LINT_GUTTER_WIDTH: u16 = 2;
+
+    /// Width of the editor's slide-break preview pane.
+    pub const SLIDE_PREVIEW_WIDTH: u16 = 32;
 }
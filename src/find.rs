@@ -0,0 +1,246 @@
+//! Regex search and substitute for the slide editor.
+//!
+//! Search patterns are smart-case: all-lowercase patterns match any case,
+//! a pattern with a capital letter matches exactly. Substitutes use vim's
+//! `:s/pattern/replacement/flags` form, with `\1`-style group references.
+
+use regex::{Regex, RegexBuilder};
+
+use crate::history::Cursor;
+
+/// A match in the editor content, as byte offsets into one line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// Content line index
+    pub line: usize,
+    /// Byte offset where the match starts
+    pub start: usize,
+    /// Byte offset where the match ends (exclusive)
+    pub end: usize,
+}
+
+/// Compile a search pattern, ignoring case unless it contains an uppercase letter.
+pub fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(!pattern.chars().any(char::is_uppercase))
+        .build()
+}
+
+/// All non-empty matches of `regex` in `content`, in order.
+pub fn find_all(content: &[String], regex: &Regex) -> Vec<Match> {
+    content.iter()
+        .enumerate()
+        .flat_map(|(line, text)| {
+            regex.find_iter(text)
+                .filter(|m| !m.as_str().is_empty())
+                .map(move |m| Match { line, start: m.start(), end: m.end() })
+        })
+        .collect()
+}
+
+/// The first match after (or before, if `!forward`) `cursor`, wrapping around.
+pub fn next_match(matches: &[Match], (x, y): Cursor, forward: bool) -> Option<Match> {
+    let found = if forward {
+        matches.iter().find(|m| (m.line, m.start) > (y, x)).or_else(|| matches.first())
+    } else {
+        matches.iter().rev().find(|m| (m.line, m.start) < (y, x)).or_else(|| matches.last())
+    };
+    found.copied()
+}
+
+/// A parsed `:s/pattern/replacement/flags` command.
+#[derive(Debug, Clone)]
+pub struct Substitution {
+    regex: Regex,
+    replacement: String,
+    global: bool,
+}
+
+impl Substitution {
+    /// Parse a substitute command (`s/a/b/g`, or `%s/...` for the whole buffer).
+    ///
+    /// Returns `None` if `command` isn't a substitute at all, so commands like
+    /// `split` fall through. Flags are `g` (every match on a line) and `i`
+    /// (ignore case); without `i` the pattern is smart-case like a search.
+    pub fn parse(command: &str) -> Option<Result<Self, regex::Error>> {
+        let rest = command.strip_prefix('%').unwrap_or(command).strip_prefix('s')?;
+        let delimiter = rest.chars().next().filter(|c| !c.is_alphanumeric() && !c.is_whitespace() && *c != '\\')?;
+        let parts = split_unescaped(&rest[delimiter.len_utf8()..], delimiter);
+        let (pattern, replacement, flags) = match parts.as_slice() {
+            [pattern] => (pattern.as_str(), "", ""),
+            [pattern, replacement] => (pattern.as_str(), replacement.as_str(), ""),
+            [pattern, replacement, flags] => (pattern.as_str(), replacement.as_str(), flags.as_str()),
+            _ => return None,
+        };
+        if flags.chars().any(|c| c != 'g' && c != 'i') {
+            return None;
+        }
+
+        let regex = if flags.contains('i') {
+            RegexBuilder::new(pattern).case_insensitive(true).build()
+        } else {
+            compile(pattern)
+        };
+        Some(regex.map(|regex| Self {
+            regex,
+            replacement: expand_replacement(replacement),
+            global: flags.contains('g'),
+        }))
+    }
+
+    /// Replace matches in `content`, limited to the `(start, end)` selection
+    /// if given. Returns the number of replacements made.
+    pub fn apply(&self, content: &mut [String], selection: Option<(Cursor, Cursor)>) -> usize {
+        let limit = usize::from(!self.global);
+        let mut count = 0;
+        for (y, line) in content.iter_mut().enumerate() {
+            let (from, to) = match selection {
+                Some(((_, start_y), (_, end_y))) if y < start_y || y > end_y => continue,
+                Some(((start_x, start_y), (end_x, end_y))) => (
+                    if y == start_y { start_x } else { 0 },
+                    if y == end_y { end_x } else { line.len() },
+                ),
+                None => (0, line.len()),
+            };
+            let to = to.min(line.len());
+            let from = from.min(to);
+            if !line.is_char_boundary(from) || !line.is_char_boundary(to) {
+                continue;
+            }
+
+            let segment = &line[from..to];
+            let found = self.regex.find_iter(segment).count();
+            if found == 0 {
+                continue;
+            }
+            count += if self.global { found } else { 1 };
+            let replaced = self.regex.replacen(segment, limit, self.replacement.as_str()).into_owned();
+            line.replace_range(from..to, &replaced);
+        }
+        count
+    }
+}
+
+/// Split on `delimiter`, treating `\<delimiter>` as a literal delimiter.
+fn split_unescaped(text: &str, delimiter: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let current = parts.last_mut();
+        match (c, chars.peek()) {
+            ('\\', Some(&next)) if next == delimiter => {
+                chars.next();
+                if let Some(part) = current {
+                    part.push(next);
+                }
+            }
+            (c, _) if c == delimiter => parts.push(String::new()),
+            (c, _) => {
+                if let Some(part) = current {
+                    part.push(c);
+                }
+            }
+        }
+    }
+    // A trailing delimiter ("s/a/b/") leaves an empty flags part
+    if parts.len() == 3 && parts[2].is_empty() {
+        parts.pop();
+    }
+    parts
+}
+
+/// Turn vim-style `\1` group references into the regex crate's `${1}`,
+/// escaping a literal `$` as `$$`.
+fn expand_replacement(replacement: &str) -> String {
+    let mut out = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&d)) if d.is_ascii_digit() => {
+                chars.next();
+                out.push_str("${");
+                out.push(d);
+                out.push('}');
+            }
+            ('\\', Some('\\')) => {
+                chars.next();
+                out.push('\\');
+            }
+            ('$', _) => out.push_str("$$"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.split('\n').map(str::to_string).collect()
+    }
+
+    fn substitute(command: &str) -> Substitution {
+        Substitution::parse(command).expect("substitute command").expect("valid pattern")
+    }
+
+    #[test]
+    fn test_search_is_smart_case_and_wraps() {
+        let content = lines("Holy, holy, holy\nLord God Almighty");
+        let matches = find_all(&content, &compile("holy").unwrap());
+        assert_eq!(matches.len(), 3);
+        assert_eq!(find_all(&content, &compile("Holy").unwrap()).len(), 1);
+
+        assert_eq!(next_match(&matches, (0, 0), true), Some(Match { line: 0, start: 6, end: 10 }));
+        assert_eq!(next_match(&matches, (12, 0), true), Some(matches[0]));
+        assert_eq!(next_match(&matches, (0, 0), false), Some(matches[2]));
+        assert_eq!(next_match(&[], (0, 0), true), None);
+    }
+
+    #[test]
+    fn test_parse_only_claims_substitute_commands() {
+        assert!(Substitution::parse("split").is_none());
+        assert!(Substitution::parse("save hymn").is_none());
+        assert!(Substitution::parse("structure").is_none());
+        assert!(Substitution::parse("s/a/b/x").is_none());
+        assert!(Substitution::parse("s/(/b/").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_substitute_whole_buffer() {
+        let mut content = lines("Thee we adore, thee we praise\nHow great thou art");
+        assert_eq!(substitute("%s/thee/you/g").apply(&mut content, None), 2);
+        assert_eq!(content, lines("you we adore, you we praise\nHow great thou art"));
+
+        let mut content = lines("a a\na a");
+        assert_eq!(substitute("s/a/b/").apply(&mut content, None), 2);
+        assert_eq!(content, lines("b a\nb a"));
+    }
+
+    #[test]
+    fn test_substitute_groups_and_escaped_delimiter() {
+        let mut content = lines("Amazing grace, how sweet");
+        substitute(r"s/(\w+) grace/grace \1/").apply(&mut content, None);
+        assert_eq!(content, lines("grace Amazing, how sweet"));
+
+        let mut content = lines("and/or");
+        substitute(r"s/\//, /g").apply(&mut content, None);
+        assert_eq!(content, lines("and, or"));
+
+        let mut content = lines("cost");
+        substitute("s/cost/$5/").apply(&mut content, None);
+        assert_eq!(content, lines("$5"));
+    }
+
+    #[test]
+    fn test_substitute_within_selection() {
+        let mut content = lines("thee thee\nthee thee\nthee thee");
+        // From the second word of line 0 to the end of the first word of line 1
+        let count = substitute("s/thee/you/g").apply(&mut content, Some(((5, 0), (4, 1))));
+        assert_eq!(count, 2);
+        assert_eq!(content, lines("thee you\nyou thee\nthee thee"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Undo/redo history for the slide editor.
+//!
+//! Each edit is stored as an [`EditOp`]: the range of lines it replaced and
+//! what replaced them, found by diffing the content before and after. Typed
+//! characters on one line are coalesced into a single operation per word so
+//! undo doesn't step back one keystroke at a time.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::editor::MAX_UNDO_HISTORY;
+
+/// Cursor position as `(x, y)`.
+pub type Cursor = (usize, usize);
+
+/// What kind of edit is being recorded, for coalescing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditKind {
+    /// A single typed character
+    Insert(char),
+    /// Anything else (newline, delete, paste, commands)
+    Other,
+}
+
+/// One undoable change: `removed` lines at `line` were replaced by `inserted`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditOp {
+    /// Index of the first changed line
+    pub line: usize,
+    /// Lines before the edit
+    pub removed: Vec<String>,
+    /// Lines after the edit
+    pub inserted: Vec<String>,
+    /// Cursor before the edit
+    pub cursor_before: Cursor,
+    /// Cursor after the edit
+    pub cursor_after: Cursor,
+}
+
+impl EditOp {
+    /// Diff two versions of the content, or `None` if they are equal.
+    pub fn between(before: &[String], after: &[String], cursor_before: Cursor, cursor_after: Cursor) -> Option<Self> {
+        if before == after {
+            return None;
+        }
+        let prefix = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+        let suffix = before[prefix..].iter().rev()
+            .zip(after[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        Some(Self {
+            line: prefix,
+            removed: before[prefix..before.len() - suffix].to_vec(),
+            inserted: after[prefix..after.len() - suffix].to_vec(),
+            cursor_before,
+            cursor_after,
+        })
+    }
+
+    fn replace(content: &mut Vec<String>, line: usize, old_len: usize, new: &[String]) {
+        let start = line.min(content.len());
+        let end = (start + old_len).min(content.len());
+        content.splice(start..end, new.iter().cloned());
+    }
+}
+
+/// Bounded undo and redo stacks for one editor.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditHistory {
+    undo: VecDeque<EditOp>,
+    redo: Vec<EditOp>,
+    /// Last typed character, while the newest operation can still absorb typing
+    #[serde(skip)]
+    typing: Option<char>,
+}
+
+impl EditHistory {
+    /// Create an empty history.
+    pub const fn new() -> Self {
+        Self { undo: VecDeque::new(), redo: Vec::new(), typing: None }
+    }
+
+    /// Whether there is nothing to undo or redo.
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty() && self.redo.is_empty()
+    }
+
+    /// Whether [`Self::undo`] would do anything.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether [`Self::redo`] would do anything.
+    pub const fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Record the change from `before` to `after`. Clears the redo stack.
+    pub fn record(&mut self, before: &[String], after: &[String], cursor_before: Cursor, cursor_after: Cursor, kind: EditKind) {
+        let Some(op) = EditOp::between(before, after, cursor_before, cursor_after) else {
+            return;
+        };
+        self.redo.clear();
+
+        let typed = match kind {
+            EditKind::Insert(c) => Some(c),
+            EditKind::Other => None,
+        };
+        if let (Some(c), Some(prev), Some(last)) = (typed, self.typing, self.undo.back_mut()) {
+            // Keep typing in one op until the cursor jumps or a word ends
+            let follows_last = (last.line, &last.inserted, last.cursor_after) == (op.line, &op.removed, op.cursor_before);
+            let continues = follows_last && c.is_whitespace() == prev.is_whitespace();
+            if continues {
+                last.inserted = op.inserted;
+                last.cursor_after = op.cursor_after;
+                self.typing = Some(c);
+                return;
+            }
+        }
+
+        self.undo.push_back(op);
+        while self.undo.len() > MAX_UNDO_HISTORY {
+            self.undo.pop_front();
+        }
+        self.typing = typed;
+    }
+
+    /// Revert the newest operation, returning the cursor to restore.
+    pub fn undo(&mut self, content: &mut Vec<String>) -> Option<Cursor> {
+        let op = self.undo.pop_back()?;
+        EditOp::replace(content, op.line, op.inserted.len(), &op.removed);
+        let cursor = op.cursor_before;
+        self.redo.push(op);
+        self.typing = None;
+        Some(cursor)
+    }
+
+    /// Re-apply the newest undone operation, returning the cursor to restore.
+    pub fn redo(&mut self, content: &mut Vec<String>) -> Option<Cursor> {
+        let op = self.redo.pop()?;
+        EditOp::replace(content, op.line, op.removed.len(), &op.inserted);
+        let cursor = op.cursor_after;
+        self.undo.push_back(op);
+        self.typing = None;
+        Some(cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.split('\n').map(str::to_string).collect()
+    }
+
+    /// Type `text` at the end of the last line, recording each keystroke.
+    fn type_text(history: &mut EditHistory, content: &mut [String], text: &str) {
+        for c in text.chars() {
+            let before = content.to_vec();
+            let y = content.len() - 1;
+            let x = content[y].len();
+            content[y].push(c);
+            history.record(&before, content, (x, y), (x + 1, y), EditKind::Insert(c));
+        }
+    }
+
+    #[test]
+    fn test_typing_coalesces_by_word() {
+        let mut history = EditHistory::default();
+        let mut content = lines("");
+        type_text(&mut history, &mut content, "Amazing grace");
+        assert_eq!(content, lines("Amazing grace"));
+
+        assert_eq!(history.undo(&mut content), Some((8, 0)));
+        assert_eq!(content, lines("Amazing "));
+        history.undo(&mut content);
+        history.undo(&mut content);
+        assert_eq!(content, lines(""));
+        assert!(!history.can_undo());
+
+        history.redo(&mut content);
+        history.redo(&mut content);
+        assert_eq!(content, lines("Amazing "));
+    }
+
+    #[test]
+    fn test_line_ops_round_trip() {
+        let mut history = EditHistory::default();
+        let mut content = lines("[Verse 1]\nAmazing grace\nhow sweet");
+
+        // Split: insert an empty line in the middle
+        let before = content.clone();
+        content.insert(2, String::new());
+        history.record(&before, &content, (0, 1), (0, 2), EditKind::Other);
+        // Paste over two lines
+        let before = content.clone();
+        content.splice(1..3, lines("pasted"));
+        history.record(&before, &content, (0, 2), (6, 1), EditKind::Other);
+
+        assert_eq!(history.undo(&mut content), Some((0, 2)));
+        assert_eq!(content, lines("[Verse 1]\nAmazing grace\n\nhow sweet"));
+        history.undo(&mut content);
+        assert_eq!(content, lines("[Verse 1]\nAmazing grace\nhow sweet"));
+        history.redo(&mut content);
+        history.redo(&mut content);
+        assert_eq!(content, lines("[Verse 1]\npasted\nhow sweet"));
+    }
+
+    #[test]
+    fn test_new_edit_clears_redo_and_history_is_bounded() {
+        let mut history = EditHistory::default();
+        let mut content = lines("a");
+        for i in 0..MAX_UNDO_HISTORY + 5 {
+            let before = content.clone();
+            content.push(i.to_string());
+            history.record(&before, &content, (0, 0), (0, 0), EditKind::Other);
+        }
+        assert_eq!(history.undo.len(), MAX_UNDO_HISTORY);
+
+        history.undo(&mut content);
+        assert!(history.can_redo());
+        let before = content.clone();
+        content.push("new".to_string());
+        history.record(&before, &content, (0, 0), (0, 0), EditKind::Other);
+        assert!(!history.can_redo());
+    }
+}
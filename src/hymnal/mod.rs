@@ -2,6 +2,26 @@
 //!
 //! Scans a directory of files named `#NUMBER - Title.txt` and provides
 //! lookup by hymn number (extracted from item titles) or fuzzy title match.
+//!
+//! A hymn file may start with a front-matter block of `Key: Value` lines
+//! between `---` delimiters:
+//!
+//! ```text
+//! ---
+//! Author: Isaac Watts
+//! Tune: DUKE STREET
+//! Meter: LM
+//! Year: 1719
+//! Copyright: Public Domain
+//! CCLI: 12345
+//! ---
+//! Jesus shall reign where'er the sun
+//! ```
+//!
+//! Stanzas are separated by blank lines. A stanza may start with its verse
+//! number ("2 Where'er the sun") or a `[Verse 2]` marker; a stanza headed by
+//! `Refrain:` / `Chorus:` (or `[Refrain]`) is the refrain, printed once and
+//! repeated after each verse when arranging.
 
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -11,6 +31,8 @@ use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use regex::Regex;
 
+use crate::propresenter::data_model as dm;
+
 /// Regex matching `#510` style hymn numbers.
 #[allow(clippy::expect_used)]
 static RE_HASH: LazyLock<Regex> = LazyLock::new(|| {
@@ -23,73 +45,286 @@ static RE_HYMN: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"(?i)[Hh]ymn\s*#?(\d+)").expect("valid regex: RE_HYMN")
 });
 
+/// Regex matching a hymnal code followed by a number, e.g. `GTG 510` or `PH #276`.
+#[allow(clippy::expect_used)]
+static RE_CODE_NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b([A-Za-z]{2,5})\s*#?\s*(\d+)\b").expect("valid regex: RE_CODE_NUMBER")
+});
+
 /// Regex matching hymnal filenames like `#510 - Jesus Shall Reign`.
 #[allow(clippy::expect_used)]
 static RE_FILENAME: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^#(\d+)\s*-\s*(.+)$").expect("valid regex: RE_FILENAME")
 });
 
+/// Regex matching a verse selection like `(vv. 1, 2, 5)`, `vv 1-3`, or `stanzas 1 & 4`.
+#[allow(clippy::expect_used)]
+static RE_VERSE_SELECTION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:vv|v|verses?|stanzas?|sts?)\.?\s*(\d+(?:\s*(?:,|&|\band\b|-|–)\s*\d+)*)")
+        .expect("valid regex: RE_VERSE_SELECTION")
+});
+
+/// Regex matching a stanza label line: `[Verse 2]`, `[Refrain]`, `Refrain:`, `Chorus`.
+#[allow(clippy::expect_used)]
+static RE_STANZA_LABEL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:\[([^\]]+)\]|(refrain|chorus):?)$").expect("valid regex: RE_STANZA_LABEL")
+});
+
+/// Regex matching an inline refrain prefix: `Refrain: Rejoice, rejoice!`.
+#[allow(clippy::expect_used)]
+static RE_REFRAIN_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:refrain|chorus)\s*:\s*(.+)$").expect("valid regex: RE_REFRAIN_PREFIX")
+});
+
+/// Regex matching a printed verse number at the start of a stanza: `2 Where'er` or `2. Where'er`.
+#[allow(clippy::expect_used)]
+static RE_VERSE_NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d{1,2})[.)]?\s+(\D.*)$").expect("valid regex: RE_VERSE_NUMBER")
+});
+
+/// Delimiter line that opens and closes a hymn file's front matter.
+const FRONT_MATTER_DELIMITER: &str = "---";
+
+/// Longest verse range a selection may expand, e.g. "vv. 1-40".
+const MAX_VERSE_RANGE: u32 = 40;
+
+/// Optional hymn metadata read from a file's front matter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HymnMetadata {
+    /// Author of the text (`Author:`, `Words:`, or `Text:`).
+    pub author: Option<String>,
+    /// Tune name, e.g. "DUKE STREET".
+    pub tune: Option<String>,
+    /// Poetic meter, e.g. "LM" or "8.7.8.7 D".
+    pub meter: Option<String>,
+    /// Year the text was written or copyrighted.
+    pub year: Option<u32>,
+    /// Copyright notice, e.g. "Public Domain".
+    pub copyright: Option<String>,
+    /// CCLI song number.
+    pub ccli: Option<u32>,
+}
+
+impl HymnMetadata {
+    /// Returns true if no fields are set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// One-line summary for the editor pane title.
+    ///
+    /// Example: "Isaac Watts · DUKE STREET (LM) · 1719 Public Domain · CCLI 12345"
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(author) = &self.author {
+            parts.push(author.clone());
+        }
+        match (&self.tune, &self.meter) {
+            (Some(tune), Some(meter)) => parts.push(format!("{tune} ({meter})")),
+            (Some(tune), None) => parts.push(tune.clone()),
+            (None, Some(meter)) => parts.push(meter.clone()),
+            (None, None) => {}
+        }
+        match (self.year, &self.copyright) {
+            (Some(year), Some(copyright)) => parts.push(format!("{year} {copyright}")),
+            (Some(year), None) => parts.push(year.to_string()),
+            (None, Some(copyright)) => parts.push(copyright.clone()),
+            (None, None) => {}
+        }
+        if let Some(ccli) = self.ccli {
+            parts.push(format!("CCLI {ccli}"));
+        }
+        parts.join(" · ")
+    }
+
+    /// Build presentation CCLI info for a hymn.
+    ///
+    /// The copyright notice goes in `publisher` and the tune/meter in
+    /// `artist_credits`, which is where `ProPresenter`'s copyright display
+    /// templates read them from.
+    pub fn to_ccli_info(&self, song_title: &str) -> dm::CCLIInfo {
+        let tune = match (&self.tune, &self.meter) {
+            (Some(tune), Some(meter)) => format!("{tune} ({meter})"),
+            (Some(tune), None) => tune.clone(),
+            (None, Some(meter)) => meter.clone(),
+            (None, None) => String::new(),
+        };
+        dm::CCLIInfo {
+            author: self.author.clone().unwrap_or_default(),
+            artist_credits: tune,
+            song_title: song_title.to_string(),
+            publisher: self.copyright.clone().unwrap_or_default(),
+            copyright_year: self.year.unwrap_or(0),
+            song_number: self.ccli.unwrap_or(0),
+            display: true,
+            album: String::new(),
+        }
+    }
+}
+
 /// A single hymn loaded from disk.
 #[derive(Debug, Clone)]
 pub struct HymnEntry {
+    /// Code of the hymnal this hymn belongs to.
+    pub hymnal: String,
     /// Hymn number in the hymnal.
     pub number: u32,
     /// Display title of the hymn.
     pub title: String,
     /// Lowercased title for case-insensitive matching.
     title_lower: String,
-    /// Lines of hymn content.
+    /// Lines of hymn content (front matter removed).
     pub content: Vec<String>,
+    /// Front-matter metadata, if the file had any.
+    pub metadata: HymnMetadata,
+    /// Content split into numbered verses and refrain, in printed order.
+    pub stanzas: Vec<HymnStanza>,
 }
 
-/// Lazily loaded hymnal directory index.
-pub struct HymnalService {
-    hymnal_path: PathBuf,
-    by_number: HashMap<u32, usize>,
-    entries: Vec<HymnEntry>,
-    loaded: bool,
+impl HymnEntry {
+    /// Stanzas to sing, in order.
+    ///
+    /// Verses outside `selection` are dropped (`None` keeps all). With
+    /// `repeat_refrain`, the refrain follows every verse; otherwise it appears
+    /// once, after the first verse sung. A refrain printed before the first
+    /// verse is also sung first.
+    pub fn arrange(&self, selection: Option<&[u32]>, repeat_refrain: bool) -> Vec<HymnStanza> {
+        let refrain = self.stanzas.iter().find(|s| s.kind == StanzaKind::Refrain);
+        let leads_with_refrain = self.stanzas.first().is_some_and(|s| s.kind == StanzaKind::Refrain);
+
+        let leading_refrain = refrain.filter(|_| leads_with_refrain);
+        let mut refrain_sung = leading_refrain.is_some();
+        let mut arranged: Vec<HymnStanza> = leading_refrain.into_iter().cloned().collect();
+
+        for stanza in &self.stanzas {
+            match &stanza.kind {
+                StanzaKind::Refrain => {}
+                StanzaKind::Verse(n) if selection.is_some_and(|sel| !sel.contains(n)) => {}
+                StanzaKind::Verse(_) => {
+                    arranged.push(stanza.clone());
+                    if let Some(refrain) = refrain.filter(|_| repeat_refrain || !refrain_sung) {
+                        arranged.push(refrain.clone());
+                        refrain_sung = true;
+                    }
+                }
+                StanzaKind::Other(_) => arranged.push(stanza.clone()),
+            }
+        }
+        arranged
+    }
 }
 
-impl HymnalService {
-    /// Create a new hymnal service backed by the given directory path.
-    pub fn new(path: PathBuf) -> Self {
-        Self {
-            hymnal_path: path,
-            by_number: HashMap::new(),
-            entries: Vec::new(),
-            loaded: false,
+/// What part of a hymn a stanza is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StanzaKind {
+    /// Numbered verse
+    Verse(u32),
+    /// Refrain / chorus sung between verses
+    Refrain,
+    /// Any other labeled section (e.g. "Descant"), kept in place
+    Other(String),
+}
+
+/// A labeled stanza of a hymn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HymnStanza {
+    /// Verse number, refrain, or other section
+    pub kind: StanzaKind,
+    /// Lyric lines with verse numbers and labels removed
+    pub lines: Vec<String>,
+}
+
+impl HymnStanza {
+    /// Editor label without brackets, e.g. "Verse 1" or "Refrain".
+    pub fn label(&self) -> String {
+        match &self.kind {
+            StanzaKind::Verse(n) => format!("Verse {n}"),
+            StanzaKind::Refrain => "Refrain".to_string(),
+            StanzaKind::Other(label) => label.clone(),
         }
     }
+}
 
-    /// Try to match an item title to a hymn, returning (hymn title, content lines).
-    ///
-    /// Checks by extracted number first (O(1)), then falls back to fuzzy title match.
-    pub fn lookup_from_title(&mut self, item_title: &str) -> Option<(String, Vec<String>)> {
-        self.ensure_loaded();
+/// Render stanzas as editor content: a `[Label]` line, the lyrics, and a blank line each.
+pub fn stanzas_to_lines(stanzas: &[HymnStanza]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for stanza in stanzas {
+        lines.push(format!("[{}]", stanza.label()));
+        lines.extend(stanza.lines.iter().cloned());
+        lines.push(String::new());
+    }
+    lines
+}
 
-        if let Some(num) = extract_hymn_number(item_title) {
-            if let Some(entry) = self.lookup_by_number(num) {
-                return Some((entry.title.clone(), entry.content.clone()));
-            }
-        }
+/// A hymnal directory and the short code used to cite it (e.g. "GTG").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HymnalSource {
+    /// Short uppercase code, e.g. "GTG", "PH", "STF".
+    pub code: String,
+    /// Directory containing `#NUMBER - Title.txt` files.
+    pub path: PathBuf,
+}
 
-        self.lookup_by_title(item_title)
+impl HymnalSource {
+    /// Create a source with an explicit code.
+    pub fn new(code: impl Into<String>, path: PathBuf) -> Self {
+        Self { code: code.into().to_uppercase(), path }
     }
 
-    fn ensure_loaded(&mut self) {
-        if !self.loaded {
-            self.load();
-        }
+    /// Create a source whose code is the initials of the directory name
+    /// ("Glory to God" → "GTG", "Presbyterian Hymnal" → "PH").
+    pub fn from_dir(path: PathBuf) -> Self {
+        let code = path.file_name()
+            .and_then(|n| n.to_str())
+            .map(|name| name.split_whitespace().filter_map(|w| w.chars().next()).collect::<String>())
+            .unwrap_or_default();
+        Self::new(code, path)
+    }
+
+    /// Parse a `HYMNAL_PATH`-style list of entries.
+    ///
+    /// Entries are separated like `PATH` (`:` on Unix, `;` on Windows) and may
+    /// be prefixed with `CODE=`: `GTG=~/Hymnals/Glory to God:PH=~/Hymnals/PH`.
+    /// Entries without a code use [`HymnalSource::from_dir`].
+    pub fn parse_list(value: &str) -> Vec<Self> {
+        std::env::split_paths(value)
+            .filter_map(|entry| {
+                let entry = entry.to_str()?.trim().to_string();
+                if entry.is_empty() {
+                    return None;
+                }
+                let coded = entry.split_once('=')
+                    .filter(|(code, _)| !code.is_empty() && code.chars().all(|c| c.is_ascii_alphanumeric()));
+                Some(match coded {
+                    Some((code, path)) => Self::new(code, expand_path(path)),
+                    None => Self::from_dir(expand_path(&entry)),
+                })
+            })
+            .collect()
     }
+}
 
-    fn load(&mut self) {
-        self.loaded = true;
+/// Expand a leading `~` in a configured path.
+fn expand_path(path: &str) -> PathBuf {
+    PathBuf::from(shellexpand::tilde(path.trim()).to_string())
+}
 
-        let dir = match std::fs::read_dir(&self.hymnal_path) {
+/// One hymnal's loaded entries.
+struct Hymnal {
+    source: HymnalSource,
+    by_number: HashMap<u32, usize>,
+    entries: Vec<HymnEntry>,
+}
+
+impl Hymnal {
+    fn load(source: HymnalSource) -> Self {
+        let mut hymnal = Self { source, by_number: HashMap::new(), entries: Vec::new() };
+
+        let dir = match std::fs::read_dir(&hymnal.source.path) {
             Ok(d) => d,
             Err(e) => {
-                tracing::warn!("Failed to read hymnal directory {}: {e}", self.hymnal_path.display());
-                return;
+                tracing::warn!("Failed to read hymnal directory {}: {e}", hymnal.source.path.display());
+                return hymnal;
             }
         };
 
@@ -107,60 +342,153 @@ impl HymnalService {
                 continue;
             };
 
-            let content = match std::fs::read_to_string(&path) {
+            let lines = match std::fs::read_to_string(&path) {
                 Ok(c) => c.lines().map(String::from).collect::<Vec<_>>(),
                 Err(_) => continue,
             };
+            let (metadata, content) = parse_front_matter(lines);
 
-            let idx = self.entries.len();
-            self.entries.push(HymnEntry {
+            let idx = hymnal.entries.len();
+            hymnal.entries.push(HymnEntry {
+                hymnal: hymnal.source.code.clone(),
                 number,
                 title_lower: title.to_lowercase(),
                 title,
+                stanzas: parse_hymn_stanzas(&content),
                 content,
+                metadata,
             });
-            self.by_number.insert(number, idx);
+            hymnal.by_number.insert(number, idx);
         }
 
-        tracing::info!("Loaded {} hymns from {}", self.entries.len(), self.hymnal_path.display());
+        tracing::info!(
+            "Loaded {} hymns ({}) from {}",
+            hymnal.entries.len(),
+            hymnal.source.code,
+            hymnal.source.path.display()
+        );
+        hymnal
     }
 
     fn lookup_by_number(&self, number: u32) -> Option<&HymnEntry> {
         self.by_number.get(&number).and_then(|&idx| self.entries.get(idx))
     }
+}
 
-    fn lookup_by_title(&self, query: &str) -> Option<(String, Vec<String>)> {
-        // Fuzzy match with a minimum quality threshold
-        const MIN_SCORE: i64 = 80;
+/// Lazily loaded index over one or more hymnal directories.
+///
+/// The first hymnal is the default for unqualified numbers like "Hymn 510".
+pub struct HymnalService {
+    sources: Vec<HymnalSource>,
+    hymnals: Vec<Hymnal>,
+    loaded: bool,
+    repeat_refrain: bool,
+}
+
+impl HymnalService {
+    /// Create a new hymnal service backed by the given hymnal directories.
+    pub const fn new(sources: Vec<HymnalSource>) -> Self {
+        Self {
+            sources,
+            hymnals: Vec::new(),
+            loaded: false,
+            repeat_refrain: true,
+        }
+    }
+
+    /// Set whether the refrain is repeated after every verse (default: true).
+    #[must_use]
+    pub const fn with_repeat_refrain(mut self, repeat_refrain: bool) -> Self {
+        self.repeat_refrain = repeat_refrain;
+        self
+    }
+
+    /// Codes of the configured hymnals, default first.
+    pub fn codes(&self) -> Vec<String> {
+        self.sources.iter().map(|s| s.code.clone()).collect()
+    }
+
+    /// Try to match an item title to a hymn.
+    ///
+    /// Checks a hymnal-qualified number ("GTG 510") first, then an unqualified
+    /// number in the default hymnal, then falls back to fuzzy title match.
+    pub fn lookup_from_title(&mut self, item_title: &str) -> Option<HymnEntry> {
+        self.ensure_loaded();
+
+        let codes = self.codes();
+        let by_number = if let Some((code, num)) = extract_hymnal_ref(item_title, &codes) {
+            self.hymnals.iter()
+                .find(|h| h.source.code == code)
+                .and_then(|h| h.lookup_by_number(num))
+        } else {
+            extract_hymn_number(item_title)
+                .and_then(|num| self.hymnals.first()?.lookup_by_number(num))
+        };
 
-        if self.entries.is_empty() {
-            return None;
+        by_number.or_else(|| self.lookup_by_title(item_title)).cloned()
+    }
+
+    /// Labeled editor content for a hymn.
+    ///
+    /// The verse selection is taken from the first of `selection_sources`
+    /// (item title, notes, ...) that contains one, e.g. "Hymn 510 (vv. 1, 2, 5)".
+    pub fn editor_lines(&self, entry: &HymnEntry, selection_sources: &[&str]) -> Vec<String> {
+        let selection = selection_sources.iter().find_map(|text| parse_verse_selection(text));
+        stanzas_to_lines(&entry.arrange(selection.as_deref(), self.repeat_refrain))
+    }
+
+    fn ensure_loaded(&mut self) {
+        if !self.loaded {
+            self.loaded = true;
+            self.hymnals = self.sources.iter().cloned().map(Hymnal::load).collect();
         }
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &HymnEntry> {
+        self.hymnals.iter().flat_map(|h| &h.entries)
+    }
+
+    fn lookup_by_title(&self, query: &str) -> Option<&HymnEntry> {
+        // Fuzzy match with a minimum quality threshold
+        const MIN_SCORE: i64 = 80;
 
         let matcher = SkimMatcherV2::default();
         let query_lower = query.to_lowercase();
 
         // Exact substring match wins outright
-        for entry in &self.entries {
+        for entry in self.entries() {
             if entry.title_lower == query_lower
                 || query_lower.contains(&entry.title_lower)
                 || entry.title_lower.contains(&query_lower)
             {
-                return Some((entry.title.clone(), entry.content.clone()));
+                return Some(entry);
             }
         }
 
-        let best = self.entries.iter()
+        let best = self.entries()
             .filter_map(|entry| {
                 let score = matcher.fuzzy_match(&entry.title, query)?;
                 (score >= MIN_SCORE).then_some((score, entry))
             })
             .max_by_key(|(score, _)| *score);
 
-        best.map(|(_, entry)| (entry.title.clone(), entry.content.clone()))
+        best.map(|(_, entry)| entry)
     }
 }
 
+/// Extract a hymnal-qualified number like "GTG 510" or "PH #276".
+///
+/// Only codes in `codes` are recognized (case-insensitive); returns the
+/// matching code as written in `codes` and the number.
+pub fn extract_hymnal_ref(text: &str, codes: &[String]) -> Option<(String, u32)> {
+    RE_CODE_NUMBER.captures_iter(text).find_map(|caps| {
+        let written = caps.get(1)?.as_str();
+        let code = codes.iter().find(|c| c.eq_ignore_ascii_case(written))?;
+        let number = caps.get(2)?.as_str().parse().ok()?;
+        Some((code.clone(), number))
+    })
+}
+
 /// Extract a hymn number from an item title.
 ///
 /// Recognizes patterns like `#510`, `Hymn #510`, `Hymn 510`.
@@ -171,6 +499,139 @@ fn extract_hymn_number(text: &str) -> Option<u32> {
         .and_then(|m| m.as_str().parse::<u32>().ok())
 }
 
+/// Parse a verse selection like "(vv. 1, 2, 5)", "vv. 1-3", or "verses 1 & 4".
+///
+/// Ranges are expanded; numbers are returned in the order written without
+/// duplicates. A range longer than [`MAX_VERSE_RANGE`] rejects the selection.
+pub fn parse_verse_selection(text: &str) -> Option<Vec<u32>> {
+    let list = RE_VERSE_SELECTION.captures(text)?.get(1)?.as_str();
+
+    let mut verses: Vec<u32> = Vec::new();
+    let normalized = list.to_lowercase().replace('&', ",").replace("and", ",").replace('–', "-");
+    for part in normalized.split(',') {
+        let range = if let Some((start, end)) = part.split_once('-') {
+            let start: u32 = start.trim().parse().ok()?;
+            let end: u32 = end.trim().parse().ok()?;
+            if end.saturating_sub(start) >= MAX_VERSE_RANGE {
+                return None;
+            }
+            start..=end
+        } else {
+            let n = part.trim().parse().ok()?;
+            n..=n
+        };
+        for n in range {
+            if !verses.contains(&n) {
+                verses.push(n);
+            }
+        }
+    }
+
+    (!verses.is_empty()).then_some(verses)
+}
+
+/// Split hymn content into stanzas.
+///
+/// Blank lines separate stanzas. A stanza's kind comes from a label line
+/// (`[Verse 2]`, `[Refrain]`, `Refrain:`), an inline `Refrain:` prefix, or a
+/// printed verse number; unmarked stanzas are numbered after the previous verse.
+pub fn parse_hymn_stanzas(content: &[String]) -> Vec<HymnStanza> {
+    let mut stanzas = Vec::new();
+    let mut pending: Option<StanzaKind> = None;
+    let mut lines: Vec<String> = Vec::new();
+    let mut last_verse = 0;
+
+    let mut finish = |pending: &mut Option<StanzaKind>, lines: &mut Vec<String>| {
+        if lines.is_empty() {
+            return;
+        }
+        let mut lines = std::mem::take(lines);
+        let kind = pending.take().unwrap_or_else(|| {
+            if let Some((n, rest)) = RE_VERSE_NUMBER.captures(&lines[0]).and_then(|caps| {
+                Some((caps.get(1)?.as_str().parse::<u32>().ok()?, caps.get(2)?.as_str().to_string()))
+            }) {
+                lines[0] = rest;
+                StanzaKind::Verse(n)
+            } else if let Some(rest) = RE_REFRAIN_PREFIX.captures(&lines[0]).and_then(|caps| caps.get(1)) {
+                lines[0] = rest.as_str().to_string();
+                StanzaKind::Refrain
+            } else {
+                StanzaKind::Verse(last_verse + 1)
+            }
+        });
+        if let StanzaKind::Verse(n) = kind {
+            last_verse = n;
+        }
+        stanzas.push(HymnStanza { kind, lines });
+    };
+
+    for line in content {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            finish(&mut pending, &mut lines);
+        } else if let Some(caps) = RE_STANZA_LABEL.captures(trimmed) {
+            finish(&mut pending, &mut lines);
+            let label = caps.get(1).or_else(|| caps.get(2)).map_or("", |m| m.as_str().trim());
+            pending = Some(stanza_kind_from_label(label));
+        } else {
+            lines.push(trimmed.to_string());
+        }
+    }
+    finish(&mut pending, &mut lines);
+
+    stanzas
+}
+
+/// Classify a `[Label]` marker.
+fn stanza_kind_from_label(label: &str) -> StanzaKind {
+    let lower = label.to_lowercase();
+    if lower == "refrain" || lower == "chorus" {
+        return StanzaKind::Refrain;
+    }
+    lower.strip_prefix("verse")
+        .and_then(|n| n.trim().parse().ok())
+        .map_or_else(|| StanzaKind::Other(label.to_string()), StanzaKind::Verse)
+}
+
+/// Split a hymn file into front-matter metadata and content lines.
+///
+/// Files without a leading `---` block are returned unchanged with empty
+/// metadata. Unknown keys are ignored.
+fn parse_front_matter(lines: Vec<String>) -> (HymnMetadata, Vec<String>) {
+    let mut metadata = HymnMetadata::default();
+
+    if lines.first().map(|l| l.trim()) != Some(FRONT_MATTER_DELIMITER) {
+        return (metadata, lines);
+    }
+    let Some(end) = lines.iter().skip(1).position(|l| l.trim() == FRONT_MATTER_DELIMITER) else {
+        return (metadata, lines);
+    };
+
+    for line in &lines[1..=end] {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        match key.trim().to_lowercase().as_str() {
+            "author" | "words" | "text" => metadata.author = Some(value.to_string()),
+            "tune" => metadata.tune = Some(value.to_string()),
+            "meter" | "metre" => metadata.meter = Some(value.to_string()),
+            "year" => metadata.year = value.parse().ok(),
+            "copyright" => metadata.copyright = Some(value.to_string()),
+            "ccli" => metadata.ccli = value.trim_start_matches('#').parse().ok(),
+            _ => {}
+        }
+    }
+
+    // Drop the front matter and any blank lines separating it from the text
+    let content = lines.into_iter()
+        .skip(end + 2)
+        .skip_while(|l| l.trim().is_empty())
+        .collect();
+    (metadata, content)
+}
+
 /// Parse a hymnal filename like `#510 - Jesus Shall Reign` into (510, "Jesus Shall Reign").
 fn parse_hymnal_filename(stem: &str) -> Option<(u32, String)> {
     let caps = RE_FILENAME.captures(stem)?;
@@ -223,10 +684,120 @@ mod tests {
         assert!(parse_hymnal_filename("510 - No Hash").is_none());
     }
 
+    #[test]
+    fn test_parse_front_matter() {
+        let lines: Vec<String> = "---\nAuthor: Isaac Watts\nTune: DUKE STREET\nMeter: LM\nYear: 1719\nCopyright: Public Domain\nCCLI: #12345\n---\n\nJesus shall reign"
+            .lines().map(String::from).collect();
+        let (meta, content) = parse_front_matter(lines);
+        assert_eq!(meta.author.as_deref(), Some("Isaac Watts"));
+        assert_eq!(meta.tune.as_deref(), Some("DUKE STREET"));
+        assert_eq!(meta.year, Some(1719));
+        assert_eq!(meta.ccli, Some(12345));
+        assert_eq!(content, vec!["Jesus shall reign".to_string()]);
+        assert_eq!(meta.summary(), "Isaac Watts · DUKE STREET (LM) · 1719 Public Domain · CCLI 12345");
+
+        let ccli = meta.to_ccli_info("Jesus Shall Reign");
+        assert_eq!(ccli.song_number, 12345);
+        assert_eq!(ccli.publisher, "Public Domain");
+    }
+
+    #[test]
+    fn test_parse_front_matter_absent() {
+        let lines = vec!["Amazing grace".to_string(), "---".to_string()];
+        let (meta, content) = parse_front_matter(lines.clone());
+        assert!(meta.is_empty());
+        assert_eq!(content, lines);
+    }
+
     #[test]
     fn test_lookup_from_filesystem() {
         // Construct a HymnalService pointing at a nonexistent directory — should load gracefully
-        let mut svc = HymnalService::new(PathBuf::from("/tmp/nonexistent_hymnal_dir_proflow_test"));
+        let mut svc = HymnalService::new(vec![HymnalSource::from_dir(PathBuf::from("/tmp/nonexistent_hymnal_dir_proflow_test"))]);
         assert!(svc.lookup_from_title("#999 Test").is_none());
     }
+
+    #[test]
+    fn test_hymnal_source_codes() {
+        assert_eq!(HymnalSource::from_dir(PathBuf::from("/h/Glory to God")).code, "GTG");
+        assert_eq!(HymnalSource::from_dir(PathBuf::from("/h/Presbyterian Hymnal")).code, "PH");
+
+        let sources = HymnalSource::parse_list("gtg=/h/GTG:/h/Sing the Faith");
+        assert_eq!(sources, vec![
+            HymnalSource::new("GTG", PathBuf::from("/h/GTG")),
+            HymnalSource::new("STF", PathBuf::from("/h/Sing the Faith")),
+        ]);
+    }
+
+    #[test]
+    fn test_extract_hymnal_ref() {
+        let codes = vec!["GTG".to_string(), "PH".to_string()];
+        assert_eq!(extract_hymnal_ref("GTG 510 Jesus Shall Reign", &codes), Some(("GTG".to_string(), 510)));
+        assert_eq!(extract_hymnal_ref("Hymn: PH #276", &codes), Some(("PH".to_string(), 276)));
+        assert_eq!(extract_hymnal_ref("Hymn 510", &codes), None);
+    }
+
+    #[test]
+    fn test_lookup_per_hymnal_numbers() {
+        let root = tempfile::tempdir().unwrap();
+        for (dir, file, text) in [("Glory to God", "#510 - Jesus Shall Reign.txt", "gtg"), ("Presbyterian Hymnal", "#510 - Other Hymn.txt", "ph")] {
+            let path = root.path().join(dir);
+            std::fs::create_dir(&path).unwrap();
+            std::fs::write(path.join(file), text).unwrap();
+        }
+        let mut svc = HymnalService::new(vec![
+            HymnalSource::from_dir(root.path().join("Glory to God")),
+            HymnalSource::from_dir(root.path().join("Presbyterian Hymnal")),
+        ]);
+        assert_eq!(svc.lookup_from_title("PH 510").unwrap().content, vec!["ph"]);
+        assert_eq!(svc.lookup_from_title("GTG #510").unwrap().hymnal, "GTG");
+        assert_eq!(svc.lookup_from_title("Hymn 510").unwrap().content, vec!["gtg"]);
+    }
+
+    #[test]
+    fn test_parse_verse_selection() {
+        assert_eq!(parse_verse_selection("Hymn 510 (vv. 1, 2, 5)"), Some(vec![1, 2, 5]));
+        assert_eq!(parse_verse_selection("Sing vv 1-3"), Some(vec![1, 2, 3]));
+        assert_eq!(parse_verse_selection("verses 1, 3 and 4"), Some(vec![1, 3, 4]));
+        assert_eq!(parse_verse_selection("Stanzas 4 & 1"), Some(vec![4, 1]));
+        assert_eq!(parse_verse_selection("Hymn 510"), None);
+        assert_eq!(parse_verse_selection("Leviticus 19"), None);
+        assert_eq!(parse_verse_selection("vv. 1-4000000000"), None);
+    }
+
+    #[test]
+    fn test_parse_hymn_stanzas() {
+        let content: Vec<String> = "1 Joy to the world\nthe Lord is come\n\nRefrain:\nRepeat the sounding joy\n\n2. Joy to the earth\n\nHe rules the world"
+            .lines().map(String::from).collect();
+        let stanzas = parse_hymn_stanzas(&content);
+        let kinds: Vec<_> = stanzas.iter().map(|s| s.kind.clone()).collect();
+        assert_eq!(kinds, vec![StanzaKind::Verse(1), StanzaKind::Refrain, StanzaKind::Verse(2), StanzaKind::Verse(3)]);
+        assert_eq!(stanzas[0].lines, vec!["Joy to the world", "the Lord is come"]);
+        assert_eq!(stanzas[2].lines, vec!["Joy to the earth"]);
+
+        let marked: Vec<String> = "[Verse 2]\nA\n\n[Chorus]\nB\n\n[Descant]\nC".lines().map(String::from).collect();
+        let kinds: Vec<_> = parse_hymn_stanzas(&marked).into_iter().map(|s| s.kind).collect();
+        assert_eq!(kinds, vec![StanzaKind::Verse(2), StanzaKind::Refrain, StanzaKind::Other("Descant".to_string())]);
+    }
+
+    #[test]
+    fn test_arrange_selection_and_refrain() {
+        let content: Vec<String> = "1 One\n\nRefrain: Chorus\n\n2 Two\n\n3 Three".lines().map(String::from).collect();
+        let entry = HymnEntry {
+            hymnal: "PH".to_string(),
+            number: 1,
+            title: "Test".to_string(),
+            title_lower: "test".to_string(),
+            stanzas: parse_hymn_stanzas(&content),
+            content,
+            metadata: HymnMetadata::default(),
+        };
+
+        let labels = |stanzas: Vec<HymnStanza>| stanzas.iter().map(HymnStanza::label).collect::<Vec<_>>();
+        assert_eq!(labels(entry.arrange(Some(&[1, 3]), true)), vec!["Verse 1", "Refrain", "Verse 3", "Refrain"]);
+        assert_eq!(labels(entry.arrange(None, false)), vec!["Verse 1", "Refrain", "Verse 2", "Verse 3"]);
+
+        let svc = HymnalService::new(Vec::new());
+        let lines = svc.editor_lines(&entry, &["Hymn 1", "Sing vv. 2"]);
+        assert_eq!(lines, vec!["[Verse 2]", "Two", "", "[Refrain]", "Chorus", ""]);
+    }
 }
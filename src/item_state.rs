@@ -5,6 +5,7 @@
 //! matched files, editor state, and slide types.
 
 use crate::app::EditorState;
+use crate::history::EditHistory;
 use crate::types::{ItemId, SlideType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -31,6 +32,10 @@ pub struct ItemState {
     /// Slide type override.
     #[serde(default)]
     pub slide_type: Option<SlideType>,
+
+    /// Undo/redo history for the editor content.
+    #[serde(default, skip_serializing_if = "EditHistory::is_empty")]
+    pub history: EditHistory,
 }
 
 impl ItemState {
@@ -42,6 +47,7 @@ impl ItemState {
             matched_file: None,
             editor: None,
             slide_type: None,
+            history: EditHistory::new(),
         }
     }
 
@@ -132,6 +138,16 @@ impl ItemStateStore {
         self.get_mut(id).editor = editor;
     }
 
+    /// Get the editor undo/redo history for an item.
+    pub fn get_history(&self, id: &ItemId) -> Option<&EditHistory> {
+        self.get(id).map(|s| &s.history)
+    }
+
+    /// Set the editor undo/redo history for an item.
+    pub fn set_history(&mut self, id: &ItemId, history: EditHistory) {
+        self.get_mut(id).history = history;
+    }
+
     /// Get the slide type for an item.
     pub fn get_slide_type(&self, id: &ItemId) -> Option<SlideType> {
         self.get(id).and_then(|s| s.slide_type)
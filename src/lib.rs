@@ -7,6 +7,7 @@
 // Re-export public modules for use in integration tests and as a library
 pub mod app;
 pub mod constants;
+pub mod history;
 pub mod input;
 pub mod item_state;
 pub mod services;
@@ -15,6 +16,7 @@ pub mod bible;
 pub mod config;
 pub mod hymnal;
 pub mod error;
+pub mod find;
 pub mod lyrics;
 pub mod planning_center;
 pub mod propresenter;
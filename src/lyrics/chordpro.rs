@@ -0,0 +1,353 @@
+//! `ChordPro` chart parsing.
+//!
+//! Supports the common directives: `title`, `artist` / `composer` /
+//! `lyricist`, `copyright`, `ccli`, `key`, `meta`, the section environments
+//! (`start_of_verse` / `sov`, `start_of_chorus` / `soc`, `start_of_bridge` /
+//! `sob`), and comments used as section labels (`{c: Chorus}`). Chords are
+//! inline in square brackets: `[G]Amazing [C]grace`.
+//!
+//! Editor content uses the same inline notation. A `{key: G}` line records
+//! the key the chords are written in and `{transpose: A}` (a target key) or
+//! `{transpose: -2}` (semitones) transposes them on export.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::transpose::{transpose_chord, Key};
+use super::{expand_section_code, is_chord, Chord, ImportedSong, LyricLine, SongMetadata, SongSection};
+
+/// Regex matching a directive line like `{title: Amazing Grace}` or `{soc}`.
+#[allow(clippy::expect_used)]
+static RE_DIRECTIVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\{\s*([A-Za-z_]+)\s*(?:[:\s]\s*(.*?))?\s*\}$").expect("valid regex: RE_DIRECTIVE")
+});
+
+/// Regex matching an inline chord like `[G]` or `[D/F#]`.
+#[allow(clippy::expect_used)]
+static RE_INLINE_CHORD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[([^\]]+)\]").expect("valid regex: RE_INLINE_CHORD")
+});
+
+/// Directives that identify a file as `ChordPro` even without a `.cho` extension.
+const MARKER_DIRECTIVES: &[&str] = &[
+    "title", "t", "artist", "key", "ccli", "copyright",
+    "start_of_verse", "sov", "start_of_chorus", "soc", "comment", "c",
+];
+
+/// Largest `{transpose: N}` taken, in semitones either way.
+const MAX_TRANSPOSE: i32 = 12;
+
+/// Returns true if `contents` contains recognizable `ChordPro` directives.
+pub fn has_directives(contents: &str) -> bool {
+    contents.lines().any(|line| {
+        RE_DIRECTIVE.captures(line.trim())
+            .and_then(|caps| caps.get(1))
+            .is_some_and(|name| MARKER_DIRECTIVES.contains(&name.as_str().to_lowercase().as_str()))
+    })
+}
+
+/// Returns true if `line` is a directive such as `{key: G}`.
+pub fn is_directive(line: &str) -> bool {
+    RE_DIRECTIVE.is_match(line.trim())
+}
+
+/// Split a directive line into its lowercase name and its value (empty if none).
+pub fn parse_directive(line: &str) -> Option<(String, String)> {
+    let caps = RE_DIRECTIVE.captures(line.trim())?;
+    let value = caps.get(2).map_or("", |value| value.as_str());
+    Some((caps[1].to_lowercase(), value.to_string()))
+}
+
+/// Split a `ChordPro` lyric line into text and chord positions.
+pub fn parse_chord_line(line: &str) -> LyricLine {
+    split_chords(line, |_| true)
+}
+
+/// Split an editor line into text and chords.
+///
+/// Unlike [`parse_chord_line`], only bracketed chord names are taken as
+/// chords, so other bracketed text (e.g. "[sic]") stays in the lyric.
+pub fn parse_editor_line(line: &str) -> LyricLine {
+    split_chords(line, is_chord)
+}
+
+fn split_chords(line: &str, accept: impl Fn(&str) -> bool) -> LyricLine {
+    let mut text = String::new();
+    let mut chords = Vec::new();
+    let mut last = 0;
+
+    for caps in RE_INLINE_CHORD.captures_iter(line) {
+        let (Some(whole), Some(name)) = (caps.get(0), caps.get(1)) else { continue };
+        if !accept(name.as_str().trim()) {
+            continue;
+        }
+        text.push_str(&line[last..whole.start()]);
+        chords.push(Chord { position: text.chars().count(), name: name.as_str().trim().to_string() });
+        last = whole.end();
+    }
+    text.push_str(&line[last..]);
+
+    LyricLine { text, chords }
+}
+
+/// How chords in editor content should be transposed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Transposition {
+    /// Semitones to move every chord
+    pub semitones: i32,
+    /// Spell transposed chords with flats
+    pub prefer_flats: bool,
+}
+
+impl Transposition {
+    /// Read `{key}` and `{transpose}` directives from editor content.
+    ///
+    /// A target key needs a `{key}` to measure from; without one it is ignored.
+    pub fn from_content(content: &[String]) -> Self {
+        let mut key = None;
+        let mut target = None;
+        for caps in content.iter().filter_map(|line| RE_DIRECTIVE.captures(line.trim())) {
+            let value = caps.get(2).map_or("", |m| m.as_str()).trim();
+            match caps.get(1).map(|m| m.as_str().to_lowercase()).as_deref() {
+                Some("key") => key = Key::parse(value),
+                Some("transpose") => target = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        let Some(target) = target else { return Self::default() };
+        if let Ok(semitones) = target.trim_start_matches('+').parse::<i32>() {
+            if !(-MAX_TRANSPOSE..=MAX_TRANSPOSE).contains(&semitones) {
+                return Self::default();
+            }
+            let prefer_flats = key.map_or(semitones < 0, |k| k.shifted(semitones).prefers_flats());
+            return Self { semitones, prefer_flats };
+        }
+        match (key, Key::parse(&target)) {
+            (Some(from), Some(to)) => {
+                // Move the shorter way round so the new key stays in a similar register
+                let up = from.distance_to(to);
+                let semitones = if up > 6 { up - 12 } else { up };
+                Self { semitones, prefer_flats: to.prefers_flats() }
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// Apply to a line's chords.
+    pub fn apply(self, line: &mut LyricLine) {
+        if self.semitones == 0 {
+            return;
+        }
+        for chord in &mut line.chords {
+            chord.name = transpose_chord(&chord.name, self.semitones, self.prefer_flats);
+        }
+    }
+}
+
+/// Set a directive line in editor content, replacing an existing one or
+/// inserting it at the top.
+pub fn set_directive(content: &mut Vec<String>, name: &str, value: &str) {
+    let line = format!("{{{name}: {value}}}");
+    let existing = content.iter().position(|l| {
+        RE_DIRECTIVE.captures(l.trim())
+            .and_then(|caps| caps.get(1))
+            .is_some_and(|n| n.as_str().eq_ignore_ascii_case(name))
+    });
+    match existing {
+        Some(idx) => content[idx] = line,
+        None => content.insert(0, line),
+    }
+}
+
+/// Parse a `ChordPro` chart.
+pub fn parse(contents: &str) -> ImportedSong {
+    let mut parser = Parser::default();
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(caps) = RE_DIRECTIVE.captures(trimmed) {
+            let name = caps.get(1).map_or("", |m| m.as_str()).to_lowercase();
+            let value = caps.get(2).map_or("", |m| m.as_str()).trim();
+            parser.directive(&name, value);
+        } else if trimmed.is_empty() {
+            // Blank lines split stanzas, except inside a section environment
+            if !parser.in_environment {
+                parser.flush();
+            }
+        } else {
+            parser.lines.push(parse_chord_line(trimmed));
+        }
+    }
+    parser.flush();
+
+    ImportedSong {
+        title: parser.title,
+        metadata: parser.metadata,
+        sections: parser.sections,
+        order: Vec::new(),
+    }
+}
+
+/// Parser state while walking a chart.
+#[derive(Default)]
+struct Parser {
+    title: String,
+    metadata: SongMetadata,
+    sections: Vec<SongSection>,
+    label: Option<String>,
+    lines: Vec<LyricLine>,
+    in_environment: bool,
+    verse_count: u32,
+}
+
+impl Parser {
+    fn directive(&mut self, name: &str, value: &str) {
+        match name {
+            "title" | "t" => self.title = value.to_string(),
+            "artist" | "composer" | "lyricist" | "author"
+                if !value.is_empty() && !self.metadata.authors.iter().any(|a| a == value) =>
+            {
+                self.metadata.authors.push(value.to_string());
+            }
+            "copyright" => self.metadata.copyright = Some(value.to_string()),
+            "ccli" => self.metadata.ccli = value.trim_start_matches('#').trim().parse().ok(),
+            "key" => self.metadata.key = Some(value.to_string()),
+            "meta" => {
+                if let Some((key, rest)) = value.split_once(char::is_whitespace) {
+                    self.directive(&key.to_lowercase(), rest.trim());
+                }
+            }
+            "start_of_verse" | "sov" | "start_of_chorus" | "soc" | "start_of_bridge" | "sob" => {
+                self.flush();
+                self.in_environment = true;
+                self.label = Some(if value.is_empty() {
+                    match name {
+                        "start_of_chorus" | "soc" => "Chorus".to_string(),
+                        "start_of_bridge" | "sob" => "Bridge".to_string(),
+                        _ => format!("Verse {}", self.verse_count + 1),
+                    }
+                } else {
+                    expand_section_code(value)
+                });
+            }
+            "end_of_verse" | "eov" | "end_of_chorus" | "eoc" | "end_of_bridge" | "eob" => {
+                self.flush();
+                self.in_environment = false;
+            }
+            "comment" | "c" | "comment_italic" | "ci" | "highlight" => {
+                self.flush();
+                self.label = Some(expand_section_code(value.trim_end_matches(':')));
+            }
+            _ => {}
+        }
+    }
+
+    /// Close the current stanza, labeling it if no label was given.
+    fn flush(&mut self) {
+        if self.lines.is_empty() {
+            return;
+        }
+        let label = self.label.take().unwrap_or_else(|| format!("Verse {}", self.verse_count + 1));
+        if let Some(n) = label.strip_prefix("Verse ").and_then(|n| n.parse::<u32>().ok()) {
+            self.verse_count = self.verse_count.max(n);
+        }
+        self.sections.push(SongSection { label, lines: std::mem::take(&mut self.lines) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    const CHART: &str = "\
+{title: Amazing Grace}
+{artist: John Newton}
+{copyright: Public Domain}
+{meta: ccli 22025}
+{key: G}
+# arranged for Sunday
+
+{sov}
+[G]Amazing [G7]grace how [C]sweet the [G]sound
+That saved a wretch like me
+{eov}
+
+{c: Chorus}
+My chains are gone
+
+[G]Twas grace that taught
+";
+
+    #[test]
+    fn test_parse_chord_line() {
+        let line = parse_chord_line("[G]Amazing [C]grace");
+        assert_eq!(line.text, "Amazing grace");
+        assert_eq!(line.chords, vec![
+            Chord { position: 0, name: "G".to_string() },
+            Chord { position: 8, name: "C".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_chart() {
+        let song = parse(CHART);
+        assert_eq!(song.title, "Amazing Grace");
+        assert_eq!(song.metadata.authors, vec!["John Newton"]);
+        assert_eq!(song.metadata.ccli, Some(22025));
+        assert_eq!(song.metadata.key.as_deref(), Some("G"));
+
+        let labels: Vec<_> = song.sections.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["Verse 1", "Chorus", "Verse 2"]);
+        assert_eq!(song.sections[0].lines[0].text, "Amazing grace how sweet the sound");
+        assert_eq!(song.sections[0].lines[0].chords.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_editor_line_keeps_non_chords() {
+        let line = parse_editor_line("[G]Grace [sic] and [D/F#]peace");
+        assert_eq!(line.text, "Grace [sic] and peace");
+        assert_eq!(line.chords[1], Chord { position: 16, name: "D/F#".to_string() });
+    }
+
+    #[test]
+    fn test_transposition_from_content() {
+        let content = |lines: &[&str]| lines.iter().map(|l| (*l).to_string()).collect::<Vec<_>>();
+
+        let to_a = Transposition::from_content(&content(&["{key: G}", "{transpose: A}"]));
+        assert_eq!(to_a, Transposition { semitones: 2, prefer_flats: false });
+
+        let to_f = Transposition::from_content(&content(&["{key: G}", "{transpose: F}"]));
+        assert_eq!(to_f, Transposition { semitones: -2, prefer_flats: true });
+
+        let mut line = parse_editor_line("[G]Amazing [D/F#]grace");
+        to_f.apply(&mut line);
+        assert_eq!(line.chords.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["F", "C/E"]);
+
+        assert_eq!(Transposition::from_content(&content(&["{transpose: 3}"])).semitones, 3);
+        assert_eq!(Transposition::from_content(&content(&["{transpose: A}"])), Transposition::default());
+        assert_eq!(Transposition::from_content(&content(&["{transpose: 2147483647}"])), Transposition::default());
+    }
+
+    #[test]
+    fn test_set_directive() {
+        let mut content = vec!["{key: G}".to_string(), "Amazing grace".to_string()];
+        set_directive(&mut content, "transpose", "A");
+        set_directive(&mut content, "key", "G");
+        set_directive(&mut content, "transpose", "Bb");
+        assert_eq!(content, vec!["{transpose: Bb}", "{key: G}", "Amazing grace"]);
+    }
+
+    #[test]
+    fn test_has_directives() {
+        assert!(has_directives("{title: X}"));
+        assert!(!has_directives("Jesus shall reign\n{not a directive"));
+    }
+}
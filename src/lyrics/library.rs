@@ -0,0 +1,114 @@
+//! Directory index of importable lyric files.
+
+use std::path::PathBuf;
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use walkdir::WalkDir;
+
+use super::{import_file, ImportedSong};
+
+/// Extensions of files that may hold importable lyrics.
+const LYRIC_EXTENSIONS: &[&str] = &["xml", "cho", "chordpro", "chopro", "crd", "txt"];
+
+/// Lazily loaded index over directories of `OpenLyrics`, `ChordPro` and
+/// `SongSelect` files (searched recursively).
+pub struct LyricsLibrary {
+    dirs: Vec<PathBuf>,
+    songs: Vec<ImportedSong>,
+    loaded: bool,
+}
+
+impl LyricsLibrary {
+    /// Create a library backed by the given directories.
+    pub const fn new(dirs: Vec<PathBuf>) -> Self {
+        Self { dirs, songs: Vec::new(), loaded: false }
+    }
+
+    /// Find a song by CCLI number, then by title.
+    ///
+    /// Title matching tries an exact (case-insensitive) match, then substring
+    /// containment either way, then a fuzzy match.
+    pub fn lookup(&mut self, title: &str, ccli: Option<u32>) -> Option<ImportedSong> {
+        self.ensure_loaded();
+
+        ccli.and_then(|number| self.songs.iter().find(|s| s.metadata.ccli == Some(number)))
+            .or_else(|| self.lookup_by_title(title))
+            .cloned()
+    }
+
+    fn ensure_loaded(&mut self) {
+        if self.loaded {
+            return;
+        }
+        self.loaded = true;
+
+        for dir in &self.dirs {
+            let before = self.songs.len();
+            let files = WalkDir::new(dir)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|e| e.file_type().is_file());
+            for entry in files {
+                let path = entry.path();
+                let ext = path.extension().and_then(|e| e.to_str()).map(str::to_lowercase);
+                if !ext.is_some_and(|e| LYRIC_EXTENSIONS.contains(&e.as_str())) {
+                    continue;
+                }
+                match import_file(path) {
+                    Ok(song) => self.songs.push(song),
+                    Err(e) => tracing::debug!("Skipping {}: {e}", path.display()),
+                }
+            }
+            tracing::info!("Loaded {} songs from {}", self.songs.len() - before, dir.display());
+        }
+    }
+
+    fn lookup_by_title(&self, query: &str) -> Option<&ImportedSong> {
+        // Fuzzy match with a minimum quality threshold
+        const MIN_SCORE: i64 = 80;
+
+        let query_lower = query.to_lowercase();
+        let titles: Vec<String> = self.songs.iter().map(|s| s.title.to_lowercase()).collect();
+
+        if let Some(idx) = titles.iter().position(|t| *t == query_lower) {
+            return self.songs.get(idx);
+        }
+        if let Some(idx) = titles.iter().position(|t| !t.is_empty() && (query_lower.contains(t.as_str()) || t.contains(&query_lower))) {
+            return self.songs.get(idx);
+        }
+
+        let matcher = SkimMatcherV2::default();
+        self.songs.iter()
+            .filter_map(|song| {
+                let score = matcher.fuzzy_match(&song.title, query)?;
+                (score >= MIN_SCORE).then_some((score, song))
+            })
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, song)| song)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn test_lookup_across_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("grace.cho"), "{title: Amazing Grace}\n{ccli: 22025}\n[G]Amazing grace\n").unwrap();
+        std::fs::create_dir(dir.path().join("songselect")).unwrap();
+        std::fs::write(
+            dir.path().join("songselect").join("10000 Reasons.txt"),
+            "10,000 Reasons (Bless The Lord)\n\nChorus\nBless the Lord O my soul\n\nCCLI Song # 6016351\nMatt Redman\n",
+        ).unwrap();
+        std::fs::write(dir.path().join("#510 - Jesus Shall Reign.txt"), "Jesus shall reign\n").unwrap();
+
+        let mut library = LyricsLibrary::new(vec![dir.path().to_path_buf()]);
+        assert_eq!(library.lookup("Anything", Some(22025)).unwrap().title, "Amazing Grace");
+        assert_eq!(library.lookup("10,000 Reasons", None).unwrap().metadata.ccli, Some(6_016_351));
+        assert!(library.lookup("Jesus Shall Reign", None).is_none());
+    }
+}
@@ -0,0 +1,430 @@
+//! House-style linting for lyric editor content.
+//!
+//! Each [`Rule`] checks lyric lines (markers, directives and inline chords
+//! are ignored), or for [`Rule::DirectiveValue`] the slide directives, and
+//! reports [`Diagnostic`]s at the severity set in [`LintConfig`]. Most rules
+//! can be fixed automatically with [`fix`]; chords stay attached to the same
+//! characters.
+//!
+//! Severities are configured as a list of `rule=severity` pairs, e.g.
+//! `trailing-punctuation=error,divine-names=off`.
+
+use super::{chordpro, is_section_marker};
+use crate::constants::lint::DEFAULT_MAX_LINES;
+use crate::propresenter::export::StanzaDirectives;
+
+/// Punctuation not allowed at the end of a slide line.
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':'];
+
+/// Names always capitalized.
+const DIVINE_NAMES: &[&str] = &["lord", "god", "jesus", "christ"];
+
+/// Words that mark a stanza as being about God, for pronoun capitalization.
+const DIVINE_CONTEXT: &[&str] = &["lord", "god", "jesus", "christ", "savior", "saviour", "redeemer", "messiah"];
+
+/// Pronouns capitalized when a stanza refers to God.
+const DIVINE_PRONOUNS: &[&str] = &["he", "him", "his", "himself"];
+
+/// How a rule's findings are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Rule disabled
+    Off,
+    /// Shown in the editor only
+    Warning,
+    /// Shown in the editor, and blocks export when configured
+    Error,
+}
+
+impl Severity {
+    /// Parse "off", "warn"/"warning" or "error".
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "off" | "none" => Some(Self::Off),
+            "warn" | "warning" => Some(Self::Warning),
+            "error" | "deny" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// A house-style rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// No `.`, `,`, `;` or `:` at the end of a line
+    TrailingPunctuation,
+    /// First word of each line capitalized
+    CapitalizeFirst,
+    /// "Lord", "God" and, where a stanza refers to God, "He"/"His"/"Him" capitalized
+    DivineNames,
+    /// No more than [`LintConfig::max_lines`] lines per slide
+    MaxLines,
+    /// No lines written in ALL CAPS
+    AllCaps,
+    /// Slide directives (`{transition}`, `{background}`, `{notes}`) have values export understands
+    DirectiveValue,
+}
+
+impl Rule {
+    /// All rules, in the order they are checked.
+    pub const ALL: [Self; 6] = [
+        Self::AllCaps,
+        Self::CapitalizeFirst,
+        Self::DivineNames,
+        Self::TrailingPunctuation,
+        Self::MaxLines,
+        Self::DirectiveValue,
+    ];
+
+    /// Name used in configuration.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::TrailingPunctuation => "trailing-punctuation",
+            Self::CapitalizeFirst => "capitalize-first",
+            Self::DivineNames => "divine-names",
+            Self::MaxLines => "max-lines",
+            Self::AllCaps => "all-caps",
+            Self::DirectiveValue => "directive-value",
+        }
+    }
+
+    /// Look up a rule by its configuration name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        Self::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+
+    /// Whether [`fix`] can correct this rule.
+    pub const fn is_fixable(self) -> bool {
+        !matches!(self, Self::MaxLines | Self::DirectiveValue)
+    }
+}
+
+/// Which rules run, and how strictly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintConfig {
+    /// Severity per rule; rules not listed are warnings
+    pub severities: Vec<(Rule, Severity)>,
+    /// Maximum lyric lines per slide
+    pub max_lines: usize,
+    /// Refuse to export or build a playlist while there are errors
+    pub block_on_error: bool,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self { severities: Vec::new(), max_lines: DEFAULT_MAX_LINES, block_on_error: false }
+    }
+}
+
+impl LintConfig {
+    /// Severity configured for `rule`.
+    pub fn severity(&self, rule: Rule) -> Severity {
+        self.severities.iter().rev()
+            .find(|(r, _)| *r == rule)
+            .map_or(Severity::Warning, |(_, severity)| *severity)
+    }
+
+    /// Apply `rule=severity` pairs separated by commas; unknown entries are skipped.
+    pub fn apply_overrides(&mut self, spec: &str) {
+        for entry in spec.split(',').filter(|e| !e.trim().is_empty()) {
+            let parsed = entry.split_once('=')
+                .and_then(|(rule, severity)| Some((Rule::from_name(rule)?, Severity::parse(severity)?)));
+            if let Some(setting) = parsed {
+                self.severities.push(setting);
+            } else {
+                tracing::warn!("Ignoring lint setting '{}'", entry.trim());
+            }
+        }
+    }
+
+    fn enabled(&self, rule: Rule) -> bool {
+        self.severity(rule) != Severity::Off
+    }
+}
+
+/// A style problem on one editor line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Editor line index
+    pub line: usize,
+    /// Rule that found it
+    pub rule: Rule,
+    /// Configured severity
+    pub severity: Severity,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Count of errors among `diagnostics`.
+pub fn error_count(diagnostics: &[Diagnostic]) -> usize {
+    diagnostics.iter().filter(|d| d.severity == Severity::Error).count()
+}
+
+/// Word spans `(start, end)` in character offsets.
+fn words(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in chars.iter().chain(std::iter::once(&' ')).enumerate() {
+        match (c.is_alphabetic(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+fn word_lower(chars: &[char], (start, end): (usize, usize)) -> String {
+    chars[start..end].iter().collect::<String>().to_lowercase()
+}
+
+fn capitalize(chars: &mut [char], at: usize) {
+    if let Some(upper) = chars[at].to_uppercase().next() {
+        chars[at] = upper;
+    }
+}
+
+fn is_all_caps(chars: &[char]) -> bool {
+    let letters = chars.iter().filter(|c| c.is_alphabetic()).count();
+    letters >= 4 && chars.iter().all(|c| !c.is_lowercase())
+}
+
+/// Lowercase words that should be capitalized under [`Rule::DivineNames`].
+fn lowercase_divine_words(chars: &[char], divine_context: bool) -> Vec<(usize, usize)> {
+    words(chars).into_iter()
+        .filter(|&span| chars[span.0].is_lowercase())
+        .filter(|&span| {
+            let word = word_lower(chars, span);
+            DIVINE_NAMES.contains(&word.as_str()) || (divine_context && DIVINE_PRONOUNS.contains(&word.as_str()))
+        })
+        .collect()
+}
+
+/// Check one lyric line (text without chords) against a rule.
+fn check(rule: Rule, chars: &[char], divine_context: bool) -> Option<String> {
+    match rule {
+        Rule::TrailingPunctuation => {
+            let last = chars.iter().rev().find(|c| !c.is_whitespace())?;
+            TRAILING_PUNCTUATION.contains(last).then(|| format!("Trailing '{last}'"))
+        }
+        Rule::CapitalizeFirst => {
+            let first = chars.iter().find(|c| c.is_alphabetic())?;
+            first.is_lowercase().then(|| "First word should be capitalized".to_string())
+        }
+        Rule::DivineNames => {
+            let span = *lowercase_divine_words(chars, divine_context).first()?;
+            Some(format!("Capitalize '{}'", word_lower(chars, span)))
+        }
+        Rule::AllCaps => is_all_caps(chars).then(|| "Line is in ALL CAPS".to_string()),
+        Rule::MaxLines | Rule::DirectiveValue => None,
+    }
+}
+
+/// Correct one lyric line for a rule, keeping its length where chords depend on it.
+fn apply(rule: Rule, chars: &mut Vec<char>, divine_context: bool) {
+    match rule {
+        Rule::TrailingPunctuation => {
+            while chars.last().is_some_and(|c| c.is_whitespace() || TRAILING_PUNCTUATION.contains(c)) {
+                chars.pop();
+            }
+        }
+        Rule::CapitalizeFirst => {
+            if let Some(at) = chars.iter().position(|c| c.is_alphabetic()) {
+                capitalize(chars, at);
+            }
+        }
+        Rule::DivineNames => {
+            for (start, _) in lowercase_divine_words(chars, divine_context) {
+                capitalize(chars, start);
+            }
+        }
+        Rule::AllCaps => {
+            if !is_all_caps(chars) {
+                return;
+            }
+            for c in chars.iter_mut() {
+                *c = c.to_lowercase().next().unwrap_or(*c);
+            }
+            // Sentence case, keeping "I" and divine names
+            for span in words(chars) {
+                let word = word_lower(chars, span);
+                if word == "i" || DIVINE_NAMES.contains(&word.as_str()) {
+                    capitalize(chars, span.0);
+                }
+            }
+            if let Some(at) = chars.iter().position(|c| c.is_alphabetic()) {
+                capitalize(chars, at);
+            }
+        }
+        Rule::MaxLines | Rule::DirectiveValue => {}
+    }
+}
+
+/// A slide's worth of lyric lines: editor indices, and whether it refers to God.
+struct Slide {
+    lines: Vec<usize>,
+    divine_context: bool,
+}
+
+/// Group lyric lines into slides separated by blank lines and markers.
+fn slides(content: &[String]) -> Vec<Slide> {
+    let mut slides = Vec::new();
+    let mut current = Vec::new();
+    for (idx, line) in content.iter().enumerate() {
+        let trimmed = line.trim();
+        if chordpro::is_directive(trimmed) {
+            continue;
+        }
+        if trimmed.is_empty() || is_section_marker(trimmed) {
+            if !current.is_empty() {
+                slides.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(idx);
+        }
+    }
+    if !current.is_empty() {
+        slides.push(current);
+    }
+
+    slides.into_iter()
+        .map(|lines| {
+            let divine_context = lines.iter().any(|&idx| {
+                let chars: Vec<char> = chordpro::parse_editor_line(&content[idx]).text.chars().collect();
+                words(&chars).into_iter().any(|span| DIVINE_CONTEXT.contains(&word_lower(&chars, span).as_str()))
+            });
+            Slide { lines, divine_context }
+        })
+        .collect()
+}
+
+/// Check editor content against the enabled rules.
+pub fn lint(content: &[String], config: &LintConfig) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for slide in slides(content) {
+        for &idx in &slide.lines {
+            let chars: Vec<char> = chordpro::parse_editor_line(&content[idx]).text.chars().collect();
+            for rule in Rule::ALL.into_iter().filter(|r| config.enabled(*r)) {
+                if let Some(message) = check(rule, &chars, slide.divine_context) {
+                    diagnostics.push(Diagnostic { line: idx, rule, severity: config.severity(rule), message });
+                }
+            }
+        }
+
+        if config.enabled(Rule::MaxLines) && slide.lines.len() > config.max_lines {
+            diagnostics.push(Diagnostic {
+                line: slide.lines[config.max_lines],
+                rule: Rule::MaxLines,
+                severity: config.severity(Rule::MaxLines),
+                message: format!("Slide has {} lines (max {})", slide.lines.len(), config.max_lines),
+            });
+        }
+    }
+
+    // Export skips slide directives it can't read; say so here
+    if config.enabled(Rule::DirectiveValue) {
+        for (idx, line) in content.iter().enumerate() {
+            let Some((name, value)) = chordpro::parse_directive(line) else { continue };
+            if StanzaDirectives::is_setting(&name) && !StanzaDirectives::default().apply(&name, &value) {
+                diagnostics.push(Diagnostic {
+                    line: idx,
+                    rule: Rule::DirectiveValue,
+                    severity: config.severity(Rule::DirectiveValue),
+                    message: format!("Unrecognized {name} '{value}'"),
+                });
+            }
+        }
+    }
+    diagnostics.sort_by_key(|d| d.line);
+    diagnostics
+}
+
+/// Apply every enabled automatic fix, returning the number of lines changed.
+pub fn fix(content: &mut [String], config: &LintConfig) -> usize {
+    let mut changed = 0;
+    for slide in slides(content) {
+        for idx in slide.lines {
+            let mut line = chordpro::parse_editor_line(&content[idx]);
+            let mut chars: Vec<char> = line.text.chars().collect();
+            for rule in Rule::ALL.into_iter().filter(|r| r.is_fixable() && config.enabled(*r)) {
+                apply(rule, &mut chars, slide.divine_context);
+            }
+            let text: String = chars.into_iter().collect();
+            if text != line.text {
+                line.text = text;
+                content[idx] = line.to_chordpro();
+                changed += 1;
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    fn rules(content: &[String], config: &LintConfig) -> Vec<(usize, Rule)> {
+        lint(content, config).into_iter().map(|d| (d.line, d.rule)).collect()
+    }
+
+    #[test]
+    fn test_lint_rules() {
+        let content = lines("[Verse 1]\n[G]Praise the lord,\nhe is good\n\n[Chorus]\nHOLY HOLY HOLY");
+        let found = rules(&content, &LintConfig::default());
+        assert_eq!(found, vec![
+            (1, Rule::DivineNames),
+            (1, Rule::TrailingPunctuation),
+            (2, Rule::CapitalizeFirst),
+            (2, Rule::DivineNames),
+            (5, Rule::AllCaps),
+        ]);
+    }
+
+    #[test]
+    fn test_pronouns_need_divine_context() {
+        let content = lines("she said that he was gone\nAnd his house was empty");
+        let found = rules(&content, &LintConfig::default());
+        assert_eq!(found, vec![(0, Rule::CapitalizeFirst)]);
+    }
+
+    #[test]
+    fn test_max_lines_and_severity() {
+        let mut config = LintConfig { max_lines: 2, ..LintConfig::default() };
+        config.apply_overrides("max-lines=error, capitalize-first=off, bogus=error");
+        let content = lines("one\ntwo\nthree");
+        let diagnostics = lint(&content, &config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, 2);
+        assert_eq!(error_count(&diagnostics), 1);
+    }
+
+    #[test]
+    fn test_directive_values() {
+        let content = lines("{transition: sparkle}\n{key: H}\n{bg: #123}\n{notes:}\n{transition: push left 1s}\nAmazing grace");
+        let diagnostics = lint(&content, &LintConfig::default());
+        let found: Vec<(usize, Rule)> = diagnostics.iter().map(|d| (d.line, d.rule)).collect();
+        assert_eq!(found, vec![(0, Rule::DirectiveValue), (3, Rule::DirectiveValue)]);
+        assert_eq!(diagnostics[0].message, "Unrecognized transition 'sparkle'");
+        assert!(!Rule::DirectiveValue.is_fixable());
+    }
+
+    #[test]
+    fn test_fix_keeps_chords() {
+        let mut content = lines("{key: G}\n[G]praise the [C]lord.\nAMAZING GRACE HOW [D]SWEET\n\nhe walks with me");
+        let changed = fix(&mut content, &LintConfig::default());
+        assert_eq!(changed, 3);
+        assert_eq!(content, lines("{key: G}\n[G]Praise the [C]Lord\nAmazing grace how [D]sweet\n\nHe walks with me"));
+        assert!(lint(&content, &LintConfig::default()).is_empty());
+    }
+}
@@ -2,3 +2,338 @@
 //!
 //! Provides functionality for processing, formatting, and manipulating lyric text,
 //! with focus on line wrapping and verse formatting.
+//!
+//! Songs can be imported from three file formats:
+//!
+//! - **`OpenLyrics`** XML (`OpenLP`, `FreeWorship` exports) — [`openlyrics`]
+//! - **`ChordPro`** charts (`.cho`, `.chordpro`, `.crd`) — [`chordpro`]
+//! - **CCLI `SongSelect`** lyric/chord sheet `.txt` downloads — [`songselect`]
+//!
+//! Each parser produces an [`ImportedSong`]: labeled sections of lyric lines
+//! with chord positions, plus CCLI metadata. [`LyricsLibrary`] indexes
+//! directories of such files for lookup by CCLI number or title.
+//!
+//! Unlabeled or loosely labeled lyrics can be rewritten into canonical
+//! section markers with [`structure::normalize`], and checked against the
+//! house style with [`lint`].
+//!
+//! In the editor, chords are written inline in `ChordPro` style
+//! (`[G]Amazing [C]grace`); they are stripped from audience text on export
+//! and carried as chord data for the stage display.
+
+pub mod chordpro;
+pub mod library;
+pub mod lint;
+pub mod openlyrics;
+pub mod songselect;
+pub mod structure;
+pub mod transpose;
+
+pub use library::LyricsLibrary;
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::propresenter::data_model as dm;
+
+/// Regex matching a chord name like `G`, `F#m7`, `D/F#`, or `Bbsus4`.
+#[allow(clippy::expect_used)]
+static RE_CHORD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[A-G][#b]?(?:m|maj|min|dim|aug|sus|add|M)?\d*(?:(?:sus|add|maj|b|#)\d+)*(?:/[A-G][#b]?)?$")
+        .expect("valid regex: RE_CHORD")
+});
+
+/// A chord placed above a lyric line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chord {
+    /// Character offset in the line's text where the chord is played
+    pub position: usize,
+    /// Chord name, e.g. "G", "D/F#", "Em7"
+    pub name: String,
+}
+
+/// One line of lyrics with its chords.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LyricLine {
+    /// Lyric text with chords removed
+    pub text: String,
+    /// Chords in position order
+    pub chords: Vec<Chord>,
+}
+
+impl LyricLine {
+    /// A line with no chords.
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self { text: text.into(), chords: Vec::new() }
+    }
+
+    /// Render with inline `ChordPro` chords: `[G]Amazing [C]grace`.
+    pub fn to_chordpro(&self) -> String {
+        let mut out = String::new();
+        let mut chords = self.chords.iter().peekable();
+        for (i, c) in self.text.chars().enumerate() {
+            while let Some(chord) = chords.next_if(|chord| chord.position <= i) {
+                push_chord(&mut out, &chord.name);
+            }
+            out.push(c);
+        }
+        for chord in chords {
+            push_chord(&mut out, &chord.name);
+        }
+        out
+    }
+}
+
+/// Append `[name]` to a line being rendered.
+fn push_chord(out: &mut String, name: &str) {
+    out.push('[');
+    out.push_str(name);
+    out.push(']');
+}
+
+/// A labeled section of a song ("Verse 1", "Chorus").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SongSection {
+    /// Display label used for the editor's `[Label]` marker
+    pub label: String,
+    /// Lyric lines
+    pub lines: Vec<LyricLine>,
+}
+
+/// Song credits read from an imported file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SongMetadata {
+    /// Writers, in the order given
+    pub authors: Vec<String>,
+    /// Copyright notice
+    pub copyright: Option<String>,
+    /// CCLI song number
+    pub ccli: Option<u32>,
+    /// Original key, e.g. "G"
+    pub key: Option<String>,
+}
+
+impl SongMetadata {
+    /// Build presentation CCLI info for a song.
+    pub fn to_ccli_info(&self, song_title: &str) -> dm::CCLIInfo {
+        dm::CCLIInfo {
+            author: self.authors.join(", "),
+            artist_credits: String::new(),
+            song_title: song_title.to_string(),
+            publisher: self.copyright.clone().unwrap_or_default(),
+            copyright_year: 0,
+            song_number: self.ccli.unwrap_or(0),
+            display: true,
+            album: String::new(),
+        }
+    }
+}
+
+/// A song parsed from a lyric file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedSong {
+    /// Song title
+    pub title: String,
+    /// Credits and CCLI number
+    pub metadata: SongMetadata,
+    /// Sections in the order they appear in the file
+    pub sections: Vec<SongSection>,
+    /// Sung order as section labels, if the file specifies one
+    pub order: Vec<String>,
+}
+
+impl ImportedSong {
+    /// Sections in sung order.
+    ///
+    /// Follows `order` when present (labels that don't match a section are
+    /// skipped), otherwise the file order.
+    pub fn arranged(&self) -> Vec<&SongSection> {
+        let ordered: Vec<_> = self.order.iter()
+            .filter_map(|label| self.sections.iter().find(|s| s.label.eq_ignore_ascii_case(label)))
+            .collect();
+        if ordered.is_empty() {
+            self.sections.iter().collect()
+        } else {
+            ordered
+        }
+    }
+
+    /// Whether any line carries chords.
+    pub fn has_chords(&self) -> bool {
+        self.sections.iter().flat_map(|s| &s.lines).any(|l| !l.chords.is_empty())
+    }
+
+    /// Editor content: a `[Label]` line, the lyrics, and a blank line per section.
+    ///
+    /// Chords are kept inline, preceded by a `{key: ...}` line when the key is known.
+    pub fn editor_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let with_chords = self.has_chords();
+        if let Some(key) = self.metadata.key.as_ref().filter(|_| with_chords) {
+            lines.push(format!("{{key: {key}}}"));
+            lines.push(String::new());
+        }
+        for section in self.arranged() {
+            lines.push(format!("[{}]", section.label));
+            lines.extend(section.lines.iter()
+                .filter(|l| with_chords || !l.text.is_empty())
+                .map(LyricLine::to_chordpro));
+            lines.push(String::new());
+        }
+        lines
+    }
+}
+
+/// Supported lyric file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LyricFormat {
+    /// `OpenLyrics` XML
+    OpenLyrics,
+    /// `ChordPro` chart
+    ChordPro,
+    /// CCLI `SongSelect` text download
+    SongSelect,
+}
+
+impl LyricFormat {
+    /// Detect the format from a file's extension and contents.
+    ///
+    /// Plain `.txt` files without `ChordPro` directives or a `SongSelect`
+    /// "CCLI Song #" footer are not recognized, so hymnal text files sharing
+    /// a directory are ignored.
+    pub fn detect(path: &Path, contents: &str) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "xml" if contents.contains("<song") => Some(Self::OpenLyrics),
+            "cho" | "chordpro" | "chopro" | "crd" => Some(Self::ChordPro),
+            "txt" if chordpro::has_directives(contents) => Some(Self::ChordPro),
+            "txt" if songselect::is_songselect(contents) => Some(Self::SongSelect),
+            _ => None,
+        }
+    }
+
+    /// Parse file contents in this format.
+    pub fn parse(self, contents: &str) -> Result<ImportedSong, String> {
+        match self {
+            Self::OpenLyrics => openlyrics::parse(contents),
+            Self::ChordPro => Ok(chordpro::parse(contents)),
+            Self::SongSelect => Ok(songselect::parse(contents)),
+        }
+    }
+}
+
+/// Import a lyric file, detecting its format.
+pub fn import_file(path: &Path) -> Result<ImportedSong, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let format = LyricFormat::detect(path, &contents)
+        .ok_or_else(|| format!("Unrecognized lyric file: {}", path.display()))?;
+
+    let mut song = format.parse(&contents)?;
+    if song.title.is_empty() {
+        song.title = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+    }
+    Ok(song)
+}
+
+/// Expand a short section code ("v1", "c", "b2", "pc") into a display label.
+///
+/// Used for `OpenLyrics` verse names and `ChordPro` section labels. Unknown
+/// codes are returned unchanged.
+pub fn expand_section_code(code: &str) -> String {
+    let split = code.find(|c: char| c.is_ascii_digit()).unwrap_or(code.len());
+    let (kind, number) = code.split_at(split);
+    let name = match kind.to_lowercase().as_str() {
+        "v" => "Verse",
+        "c" => "Chorus",
+        "b" => "Bridge",
+        "p" | "pc" => "Pre-Chorus",
+        "i" => "Intro",
+        "e" => "Ending",
+        "o" => "Other",
+        "t" => "Tag",
+        _ => return code.to_string(),
+    };
+    if number.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name} {number}")
+    }
+}
+
+/// Returns true if `line` is a group marker like `[Verse 1]` rather than
+/// lyrics (including a chord-only line such as `[G]`).
+pub fn is_section_marker(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .is_some_and(|inner| !inner.contains(['[', ']']) && !is_chord(inner.trim()))
+}
+
+/// Returns true if `token` looks like a chord name ("G", "F#m7", "D/F#", "Bbsus4").
+pub fn is_chord(token: &str) -> bool {
+    RE_CHORD.is_match(token)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn test_expand_section_code() {
+        assert_eq!(expand_section_code("v1"), "Verse 1");
+        assert_eq!(expand_section_code("c"), "Chorus");
+        assert_eq!(expand_section_code("b2"), "Bridge 2");
+        assert_eq!(expand_section_code("Tag"), "Tag");
+    }
+
+    #[test]
+    fn test_is_chord() {
+        for chord in ["G", "F#m7", "D/F#", "Bbsus4", "Cmaj7", "Em", "Asus2", "Dadd9"] {
+            assert!(is_chord(chord), "{chord}");
+        }
+        for word in ["Grace", "A-men", "Be", "Am I"] {
+            assert!(!is_chord(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn test_is_section_marker() {
+        assert!(is_section_marker("[Verse 1]"));
+        assert!(is_section_marker("  [Chorus] "));
+        assert!(!is_section_marker("[G]"));
+        assert!(!is_section_marker("[G]Amazing [C]grace [G]"));
+        assert!(!is_section_marker("Amazing grace"));
+    }
+
+    #[test]
+    fn test_to_chordpro_round_trip() {
+        let line = chordpro::parse_chord_line("[G]Amazing [C]grace[D]");
+        assert_eq!(line.to_chordpro(), "[G]Amazing [C]grace[D]");
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(LyricFormat::detect(Path::new("a.xml"), "<song xmlns=\"\">"), Some(LyricFormat::OpenLyrics));
+        assert_eq!(LyricFormat::detect(Path::new("a.cho"), ""), Some(LyricFormat::ChordPro));
+        assert_eq!(LyricFormat::detect(Path::new("a.txt"), "{title: X}\n"), Some(LyricFormat::ChordPro));
+        assert_eq!(LyricFormat::detect(Path::new("a.txt"), "X\n\nVerse 1\nA\n\nCCLI Song # 1\n"), Some(LyricFormat::SongSelect));
+        assert_eq!(LyricFormat::detect(Path::new("#510 - Hymn.txt"), "Jesus shall reign\n"), None);
+    }
+
+    #[test]
+    fn test_arranged_follows_order() {
+        let section = |label: &str| SongSection { label: label.to_string(), lines: vec![LyricLine::plain(label)] };
+        let song = ImportedSong {
+            title: "Song".to_string(),
+            metadata: SongMetadata::default(),
+            sections: vec![section("Verse 1"), section("Chorus"), section("Verse 2")],
+            order: vec!["Verse 1".to_string(), "Chorus".to_string(), "Verse 2".to_string(), "Chorus".to_string()],
+        };
+        assert_eq!(song.editor_lines().iter().filter(|l| *l == "[Chorus]").count(), 2);
+    }
+}
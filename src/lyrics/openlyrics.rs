@@ -0,0 +1,216 @@
+//! `OpenLyrics` XML parsing.
+//!
+//! Reads the subset of <https://docs.openlp.org/openlyrics/> that carries
+//! slide content and credits:
+//!
+//! ```xml
+//! <song xmlns="http://openlyrics.info/namespace/2009/song" version="0.9">
+//!   <properties>
+//!     <titles><title>Amazing Grace</title></titles>
+//!     <authors><author>John Newton</author></authors>
+//!     <copyright>Public Domain</copyright>
+//!     <ccliNo>22025</ccliNo>
+//!     <verseOrder>v1 c v2 c</verseOrder>
+//!   </properties>
+//!   <lyrics>
+//!     <verse name="v1">
+//!       <lines><chord name="G"/>Amazing grace<br/>how sweet the sound</lines>
+//!     </verse>
+//!   </lyrics>
+//! </song>
+//! ```
+//!
+//! Formatting tags and comments inside `<lines>` are dropped; their text is kept.
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::{expand_section_code, Chord, ImportedSong, LyricLine, SongMetadata, SongSection};
+
+/// Parse an `OpenLyrics` document.
+pub fn parse(xml: &str) -> Result<ImportedSong, String> {
+    let mut reader = Reader::from_str(xml);
+
+    let mut song = ImportedSong {
+        title: String::new(),
+        metadata: SongMetadata::default(),
+        sections: Vec::new(),
+        order: Vec::new(),
+    };
+    let mut verse_order = String::new();
+
+    // Element path from the root, by local name
+    let mut path: Vec<String> = Vec::new();
+    let mut section: Option<SongSection> = None;
+    let mut line = LyricLine::default();
+
+    loop {
+        let event = reader.read_event()
+            .map_err(|e| format!("Invalid OpenLyrics XML at {}: {e}", reader.buffer_position()))?;
+        match event {
+            Event::Start(e) => {
+                let name = local_name(&e);
+                match name.as_str() {
+                    "verse" => section = Some(new_section(&e)),
+                    "chord" => push_chord(&e, &mut line),
+                    _ => {}
+                }
+                path.push(name);
+            }
+            Event::Empty(e) => match local_name(&e).as_str() {
+                "br" => finish_line(&mut section, &mut line),
+                "chord" => push_chord(&e, &mut line),
+                _ => {}
+            },
+            Event::Text(t) => {
+                let text = t.unescape().map_err(|e| format!("Invalid OpenLyrics text: {e}"))?;
+                let in_comment = path.iter().any(|p| p == "comment");
+                match path.last().map(String::as_str) {
+                    _ if in_comment => {}
+                    Some("title") if song.title.is_empty() => song.title = collapse_whitespace(&text),
+                    Some("author") => song.metadata.authors.push(collapse_whitespace(&text)),
+                    Some("copyright") => song.metadata.copyright = Some(collapse_whitespace(&text)),
+                    Some("ccliNo") => song.metadata.ccli = text.trim().parse().ok(),
+                    Some("key") => song.metadata.key = Some(text.trim().to_string()),
+                    Some("verseOrder") => verse_order.push_str(&text),
+                    _ if section.is_some() && path.iter().any(|p| p == "lines") => {
+                        line.text.push_str(&collapse_whitespace_inline(&text, line.text.is_empty()));
+                    }
+                    _ => {}
+                }
+            }
+            Event::End(e) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                if name == "lines" {
+                    finish_line(&mut section, &mut line);
+                } else if name == "verse" {
+                    finish_line(&mut section, &mut line);
+                    song.sections.extend(section.take().filter(|s| !s.lines.is_empty()));
+                }
+                path.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if song.sections.is_empty() {
+        return Err("OpenLyrics document has no verses".to_string());
+    }
+
+    song.order = verse_order.split_whitespace().map(expand_section_code).collect();
+    Ok(song)
+}
+
+fn local_name(e: &BytesStart<'_>) -> String {
+    String::from_utf8_lossy(e.local_name().as_ref()).to_string()
+}
+
+/// Start a section labeled from the verse's `name` attribute ("v1" → "Verse 1").
+fn new_section(e: &BytesStart<'_>) -> SongSection {
+    let name = e.try_get_attribute("name").ok().flatten()
+        .and_then(|a| a.unescape_value().ok().map(|v| v.to_string()))
+        .unwrap_or_default();
+    SongSection { label: expand_section_code(&name), lines: Vec::new() }
+}
+
+/// Record a chord at the current position (`name` in 0.8, `root` + `structure` in 0.9).
+fn push_chord(e: &BytesStart<'_>, line: &mut LyricLine) {
+    let attr = |key: &str| {
+        e.try_get_attribute(key).ok().flatten()
+            .and_then(|a| a.unescape_value().ok().map(|v| v.to_string()))
+    };
+    let name = attr("name").or_else(|| {
+        let root = attr("root")?;
+        Some(format!("{root}{}", attr("structure").unwrap_or_default()))
+    });
+    if let Some(name) = name {
+        line.chords.push(Chord { position: line.text.chars().count(), name });
+    }
+}
+
+/// End the current line and add it to the open section.
+fn finish_line(section: &mut Option<SongSection>, line: &mut LyricLine) {
+    let mut finished = std::mem::take(line);
+    finished.text = finished.text.trim_end().to_string();
+    if let Some(section) = section.as_mut() {
+        if !finished.text.is_empty() || !finished.chords.is_empty() {
+            section.lines.push(finished);
+        }
+    }
+}
+
+/// Collapse runs of whitespace (including pretty-printing newlines) to single spaces.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Collapse whitespace in a text run that continues a line, keeping a single
+/// space at either edge so words around tags and chords stay separated.
+fn collapse_whitespace_inline(text: &str, at_line_start: bool) -> String {
+    let collapsed = collapse_whitespace(text);
+    if collapsed.is_empty() {
+        return if at_line_start || text.is_empty() { String::new() } else { " ".to_string() };
+    }
+    let lead = if !at_line_start && text.starts_with(char::is_whitespace) { " " } else { "" };
+    let trail = if text.ends_with(char::is_whitespace) { " " } else { "" };
+    format!("{lead}{collapsed}{trail}")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    const DOC: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<song xmlns="http://openlyrics.info/namespace/2009/song" version="0.9">
+  <properties>
+    <titles><title>Amazing Grace</title><title lang="es">Sublime Gracia</title></titles>
+    <authors><author>John Newton</author></authors>
+    <copyright>Public Domain</copyright>
+    <ccliNo>22025</ccliNo>
+    <verseOrder>v1 c v2 c</verseOrder>
+  </properties>
+  <lyrics>
+    <verse name="v1">
+      <lines><chord root="G"/>Amazing grace how <chord name="C"/>sweet the sound<br/>
+        That saved a <tag name="it">wretch</tag> like me</lines>
+    </verse>
+    <verse name="c">
+      <lines><comment>softly</comment>My chains are gone</lines>
+      <lines>I've been set free</lines>
+    </verse>
+    <verse name="v2">
+      <lines>'Twas grace that taught</lines>
+    </verse>
+  </lyrics>
+</song>"#;
+
+    #[test]
+    fn test_parse_openlyrics() {
+        let song = parse(DOC).unwrap();
+        assert_eq!(song.title, "Amazing Grace");
+        assert_eq!(song.metadata.authors, vec!["John Newton"]);
+        assert_eq!(song.metadata.ccli, Some(22025));
+        assert_eq!(song.order, vec!["Verse 1", "Chorus", "Verse 2", "Chorus"]);
+
+        let verse = &song.sections[0];
+        assert_eq!(verse.label, "Verse 1");
+        assert_eq!(verse.lines[0].text, "Amazing grace how sweet the sound");
+        assert_eq!(verse.lines[0].chords, vec![
+            Chord { position: 0, name: "G".to_string() },
+            Chord { position: 18, name: "C".to_string() },
+        ]);
+        assert_eq!(verse.lines[1].text, "That saved a wretch like me");
+
+        assert_eq!(song.sections[1].lines.iter().map(|l| l.text.as_str()).collect::<Vec<_>>(), vec!["My chains are gone", "I've been set free"]);
+        assert_eq!(song.arranged().len(), 4);
+    }
+
+    #[test]
+    fn test_parse_rejects_empty() {
+        assert!(parse("<song><properties/></song>").is_err());
+        assert!(parse("<song><lyrics><verse name=\"v1\"></lyrics></song>").is_err());
+    }
+}
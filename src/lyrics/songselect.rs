@@ -0,0 +1,204 @@
+//! CCLI `SongSelect` text file parsing.
+//!
+//! A `SongSelect` lyrics download looks like:
+//!
+//! ```text
+//! Amazing Grace (My Chains Are Gone)
+//!
+//! Verse 1
+//! Amazing grace how sweet the sound
+//!
+//! Chorus
+//! My chains are gone
+//!
+//! CCLI Song # 4768151
+//! Chris Tomlin | John Newton | Louie Giglio
+//! © 2006 sixsteps Music
+//! For use solely with the SongSelect® Terms of Use. All rights reserved. www.ccli.com
+//! CCLI License # 123456
+//! ```
+//!
+//! Chord sheets put chords on their own line above the lyric; those are
+//! attached to the following line by column.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{is_chord, Chord, ImportedSong, LyricLine, SongMetadata, SongSection};
+
+/// Regex matching the footer's song number line: `CCLI Song # 4768151`.
+#[allow(clippy::expect_used)]
+static RE_CCLI_SONG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^CCLI Song\s*(?:#|No\.?)\s*(\d+)").expect("valid regex: RE_CCLI_SONG")
+});
+
+/// Regex matching a section header line: `Verse 1`, `Chorus 2`, `Pre-Chorus`.
+#[allow(clippy::expect_used)]
+static RE_SECTION_HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:verse|chorus|bridge|pre-?chorus|tag|intro|outro|ending|interlude|refrain|instrumental|vamp|turnaround|misc)(?:\s+\d+)?$")
+        .expect("valid regex: RE_SECTION_HEADER")
+});
+
+/// Returns true if `contents` has a `SongSelect` footer.
+pub fn is_songselect(contents: &str) -> bool {
+    contents.lines().any(|line| RE_CCLI_SONG.is_match(line.trim()))
+}
+
+/// Parse a `SongSelect` text file.
+pub fn parse(contents: &str) -> ImportedSong {
+    let lines: Vec<&str> = contents.lines().map(str::trim_end).collect();
+    let footer_start = lines.iter().position(|l| RE_CCLI_SONG.is_match(l.trim())).unwrap_or(lines.len());
+    let (body, footer) = lines.split_at(footer_start);
+
+    let mut body = body.iter().copied().skip_while(|l| l.trim().is_empty());
+    let title = body.next().unwrap_or_default().trim().to_string();
+
+    ImportedSong {
+        title,
+        metadata: parse_footer(footer),
+        sections: parse_sections(body),
+        order: Vec::new(),
+    }
+}
+
+/// Read the song number, authors and copyright from the footer lines.
+fn parse_footer(footer: &[&str]) -> SongMetadata {
+    let mut metadata = SongMetadata::default();
+    for line in footer.iter().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        if let Some(caps) = RE_CCLI_SONG.captures(line) {
+            metadata.ccli = caps.get(1).and_then(|m| m.as_str().parse().ok());
+        } else if let Some(rest) = line.strip_prefix('©') {
+            metadata.copyright = Some(rest.trim().to_string());
+        } else if line.starts_with("Copyright") {
+            metadata.copyright = Some(line.to_string());
+        } else if line.starts_with("For use solely") || line.starts_with("CCLI License") {
+            // Licensing boilerplate
+        } else if metadata.authors.is_empty() {
+            metadata.authors = line.split('|').map(|a| a.trim().to_string()).filter(|a| !a.is_empty()).collect();
+        }
+    }
+    metadata
+}
+
+/// Split the body into labeled sections, attaching chord lines to lyrics.
+fn parse_sections<'a>(body: impl Iterator<Item = &'a str>) -> Vec<SongSection> {
+    let mut sections = Vec::new();
+    let mut label: Option<String> = None;
+    let mut lines: Vec<LyricLine> = Vec::new();
+    let mut pending_chords: Vec<Chord> = Vec::new();
+    let mut verse_count = 0;
+
+    let mut flush = |label: &mut Option<String>, lines: &mut Vec<LyricLine>| {
+        if lines.is_empty() {
+            return;
+        }
+        let label = label.take().unwrap_or_else(|| format!("Verse {}", verse_count + 1));
+        if let Some(n) = label.strip_prefix("Verse ").and_then(|n| n.parse::<u32>().ok()) {
+            verse_count = verse_count.max(n);
+        }
+        sections.push(SongSection { label, lines: std::mem::take(lines) });
+    };
+
+    for line in body {
+        let trimmed = line.trim();
+        if let Some(chords) = chord_line(line) {
+            pending_chords.extend(chords);
+            continue;
+        }
+        if !pending_chords.is_empty() && (trimmed.is_empty() || RE_SECTION_HEADER.is_match(trimmed)) {
+            // Chords with no lyric beneath (e.g. an instrumental line)
+            lines.push(LyricLine { text: String::new(), chords: std::mem::take(&mut pending_chords) });
+        }
+
+        if trimmed.is_empty() {
+            flush(&mut label, &mut lines);
+        } else if RE_SECTION_HEADER.is_match(trimmed) {
+            flush(&mut label, &mut lines);
+            label = Some(trimmed.to_string());
+        } else {
+            // Chord columns are relative to the untrimmed line
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+            let len = trimmed.chars().count();
+            let chords = std::mem::take(&mut pending_chords)
+                .into_iter()
+                .map(|c| Chord { position: c.position.saturating_sub(indent).min(len), name: c.name })
+                .collect();
+            lines.push(LyricLine { text: trimmed.to_string(), chords });
+        }
+    }
+    if !pending_chords.is_empty() {
+        lines.push(LyricLine { text: String::new(), chords: pending_chords });
+    }
+    flush(&mut label, &mut lines);
+
+    sections
+}
+
+/// If every token on the line is a chord, return them with their columns.
+fn chord_line(line: &str) -> Option<Vec<Chord>> {
+    let mut chords = Vec::new();
+    let mut token_start: Option<(usize, String)> = None;
+
+    for (column, c) in line.chars().chain(std::iter::once(' ')).enumerate() {
+        if c.is_whitespace() {
+            if let Some((position, name)) = token_start.take() {
+                if !is_chord(&name) {
+                    return None;
+                }
+                chords.push(Chord { position, name });
+            }
+        } else {
+            token_start.get_or_insert_with(|| (column, String::new())).1.push(c);
+        }
+    }
+
+    (!chords.is_empty()).then_some(chords)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    const SHEET: &str = "\
+Amazing Grace (My Chains Are Gone)
+
+Verse 1
+G          C        G
+Amazing grace how sweet the sound
+That saved a wretch like me
+
+Chorus
+My chains are gone
+
+CCLI Song # 4768151
+Chris Tomlin | John Newton | Louie Giglio
+© 2006 sixsteps Music
+For use solely with the SongSelect® Terms of Use. All rights reserved. www.ccli.com
+CCLI License # 123456
+";
+
+    #[test]
+    fn test_parse_songselect() {
+        let song = parse(SHEET);
+        assert_eq!(song.title, "Amazing Grace (My Chains Are Gone)");
+        assert_eq!(song.metadata.ccli, Some(4_768_151));
+        assert_eq!(song.metadata.authors, vec!["Chris Tomlin", "John Newton", "Louie Giglio"]);
+        assert_eq!(song.metadata.copyright.as_deref(), Some("2006 sixsteps Music"));
+
+        let labels: Vec<_> = song.sections.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["Verse 1", "Chorus"]);
+        let first = &song.sections[0].lines[0];
+        assert_eq!(first.text, "Amazing grace how sweet the sound");
+        assert_eq!(first.chords.iter().map(|c| c.position).collect::<Vec<_>>(), vec![0, 11, 20]);
+    }
+
+    #[test]
+    fn test_chord_line_detection() {
+        assert!(chord_line("G   D/F#  Em7").is_some());
+        assert!(chord_line("A mighty fortress").is_none());
+        assert!(chord_line("").is_none());
+    }
+}
@@ -0,0 +1,332 @@
+//! Lyric structure detection.
+//!
+//! Planning Center and pasted lyrics arrive either as unlabeled
+//! blank-line-separated blocks or with ad hoc headers ("CHORUS:", "V1",
+//! "(Refrain)"). [`normalize`] rewrites them into the editor's canonical
+//! `[Verse 1]` / `[Chorus]` markers:
+//!
+//! - Recognized headers are replaced with a marker using the verse group's
+//!   own spelling; numberless verses are numbered in order.
+//! - A header with no lines under it ("Repeat Chorus") repeats the lines of
+//!   the section it names.
+//! - An unlabeled block that matches another block's text takes that
+//!   block's label, or becomes a chorus when it is only repeated.
+//! - Other unlabeled blocks become the next verse, unless they follow a
+//!   labeled section, in which case they stay part of it.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{chordpro, is_section_marker};
+
+/// Regex splitting a header into name and optional number: `Verse 2`, `V1`, `Pre-Chorus`.
+#[allow(clippy::expect_used)]
+static RE_HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?i)(?:repeat\s+)?([a-z][a-z -]*?)\s*(\d+)?(?:\s*\(?x\s*\d+\)?)?$").expect("valid regex: RE_HEADER")
+});
+
+/// Header spellings and the verse group each one names.
+const HEADER_ALIASES: &[(&str, &str)] = &[
+    ("verse", "Verse"),
+    ("vs", "Verse"),
+    ("v", "Verse"),
+    ("chorus", "Chorus"),
+    ("ch", "Chorus"),
+    ("cho", "Chorus"),
+    ("c", "Chorus"),
+    ("refrain", "Refrain"),
+    ("ref", "Refrain"),
+    ("bridge", "Bridge"),
+    ("br", "Bridge"),
+    ("b", "Bridge"),
+    ("pre-chorus", "Pre-Chorus"),
+    ("pre chorus", "Pre-Chorus"),
+    ("prechorus", "Pre-Chorus"),
+    ("pc", "Pre-Chorus"),
+    ("tag", "Tag"),
+    ("ending", "Ending"),
+    ("end", "Ending"),
+    ("outro", "Ending"),
+    ("coda", "Ending"),
+    ("interlude", "Interlude"),
+    ("intro", "Interlude"),
+    ("instrumental", "Interlude"),
+];
+
+/// One section of lyrics as found, and the label it will be given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionChange {
+    /// Header line as written, if the block had one
+    pub before: Option<String>,
+    /// Canonical label, or `None` when the block continues the section above
+    pub after: Option<String>,
+    /// First lyric line, for the preview
+    pub first_line: String,
+}
+
+/// Result of [`normalize`]: rewritten editor content and a per-section summary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructureProposal {
+    /// Editor lines with canonical markers
+    pub content: Vec<String>,
+    /// Sections in order, for previewing the change
+    pub sections: Vec<SectionChange>,
+}
+
+/// A header naming a verse group, e.g. "Verse" 2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Header {
+    group: String,
+    number: Option<u32>,
+}
+
+impl Header {
+    fn label(&self) -> String {
+        self.number.map_or_else(|| self.group.clone(), |n| format!("{} {n}", self.group))
+    }
+}
+
+/// A blank-line-separated block of lyrics, with its header if any.
+struct Block {
+    header: Option<(String, Header)>,
+    lines: Vec<String>,
+}
+
+/// Recognize a header line such as "CHORUS:", "V1", "(Refrain)" or "[Verse 2]".
+///
+/// Only names of groups in `groups` are accepted, and the group's own
+/// spelling is returned. Single-letter abbreviations ("C", "B") are chord
+/// names too, so they need brackets, parentheses or a colon.
+fn parse_header(line: &str, groups: &[&str]) -> Option<Header> {
+    let trimmed = line.trim();
+    let wrapped = trimmed.strip_suffix(':')
+        .or_else(|| trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']')))
+        .or_else(|| trimmed.strip_prefix('(').and_then(|s| s.strip_suffix(')')));
+    let delimited = wrapped.is_some();
+    let inner = wrapped.unwrap_or(trimmed);
+
+    let caps = RE_HEADER.captures(inner.trim())?;
+    let name = caps.get(1)?.as_str().trim().to_lowercase();
+    let number = caps.get(2).and_then(|m| m.as_str().parse().ok());
+    if name.len() == 1 && name != "v" && !delimited {
+        return None;
+    }
+
+    let canonical = HEADER_ALIASES.iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or(name.as_str(), |(_, group)| group);
+    let group = groups.iter().find(|g| g.eq_ignore_ascii_case(canonical))?;
+    Some(Header { group: (*group).to_string(), number })
+}
+
+/// Comparison key for a block: its words, lowercased, without chords or punctuation.
+fn block_key(lines: &[String]) -> String {
+    lines.iter()
+        .map(|line| chordpro::parse_chord_line(line).text.to_lowercase())
+        .flat_map(|text| {
+            text.split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split editor content into directives and blocks.
+fn split_blocks(content: &[String], groups: &[&str]) -> (Vec<String>, Vec<Block>) {
+    let mut directives = Vec::new();
+    let mut blocks = Vec::new();
+    let mut current = Block { header: None, lines: Vec::new() };
+
+    for line in content {
+        let trimmed = line.trim();
+        if chordpro::is_directive(trimmed) {
+            directives.push(line.clone());
+            continue;
+        }
+
+        // A bare word right under a header ("[Chorus]" then "Refrain") is lyrics
+        let under_header = current.header.is_some() && current.lines.is_empty();
+        let delimited = trimmed.ends_with(':') || trimmed.starts_with(['[', '(']);
+
+        // Existing markers keep their label even when it isn't a known group
+        let header = (!under_header || delimited).then(|| parse_header(trimmed, groups)).flatten().or_else(|| {
+            is_section_marker(trimmed).then(|| Header {
+                group: trimmed[1..trimmed.len() - 1].trim().to_string(),
+                number: None,
+            })
+        });
+
+        if let Some(header) = header {
+            if current.header.is_some() || !current.lines.is_empty() {
+                blocks.push(std::mem::replace(&mut current, Block { header: None, lines: Vec::new() }));
+            }
+            current.header = Some((trimmed.to_string(), header));
+        } else if trimmed.is_empty() {
+            // A header's lines may start after a blank line
+            if !current.lines.is_empty() {
+                blocks.push(std::mem::replace(&mut current, Block { header: None, lines: Vec::new() }));
+            }
+        } else {
+            current.lines.push(line.clone());
+        }
+    }
+    if current.header.is_some() || !current.lines.is_empty() {
+        blocks.push(current);
+    }
+
+    (directives, blocks)
+}
+
+/// Rewrite lyrics into canonical section markers for the verse groups `groups`.
+pub fn normalize(content: &[String], groups: &[&str]) -> StructureProposal {
+    let (directives, mut blocks) = split_blocks(content, groups);
+
+    // "Repeat Chorus": copy the lines of the section it names
+    for i in 0..blocks.len() {
+        if !blocks[i].lines.is_empty() {
+            continue;
+        }
+        let Some((_, header)) = &blocks[i].header else { continue };
+        let group = header.group.clone();
+        let number = header.number;
+        let lines = blocks[..i].iter().rev()
+            .find(|b| b.header.as_ref().is_some_and(|(_, h)| h.group == group && (number.is_none() || h.number == number)))
+            .map(|b| b.lines.clone())
+            .unwrap_or_default();
+        blocks[i].lines = lines;
+    }
+
+    let keys: Vec<String> = blocks.iter().map(|b| block_key(&b.lines)).collect();
+    let mut occurrences: HashMap<&str, usize> = HashMap::new();
+    for key in &keys {
+        *occurrences.entry(key.as_str()).or_default() += 1;
+    }
+
+    // Explicit labels apply to every block with the same text, even earlier ones
+    let mut label_for_key: HashMap<&str, String> = HashMap::new();
+    for (block, key) in blocks.iter().zip(&keys) {
+        if let Some((_, header)) = &block.header {
+            if header.group != "Verse" || header.number.is_some() {
+                label_for_key.entry(key.as_str()).or_insert_with(|| header.label());
+            }
+        }
+    }
+    let mut used: HashSet<String> = label_for_key.values().cloned().collect();
+
+    let mut verse_count = 0;
+    let mut in_labeled_section = false;
+    let mut sections = Vec::new();
+    let mut out = directives;
+
+    for (block, key) in blocks.iter().zip(&keys) {
+        if block.lines.is_empty() {
+            continue;
+        }
+
+        let label = if let Some((_, header)) = &block.header {
+            in_labeled_section = true;
+            let label = if header.group == "Verse" {
+                let number = header.number.unwrap_or(verse_count + 1);
+                verse_count = verse_count.max(number);
+                format!("Verse {number}")
+            } else {
+                header.label()
+            };
+            label_for_key.entry(key.as_str()).or_insert_with(|| label.clone());
+            Some(label)
+        } else if let Some(label) = label_for_key.get(key.as_str()) {
+            Some(label.clone())
+        } else if occurrences.get(key.as_str()).copied().unwrap_or(0) > 1 {
+            let mut label = "Chorus".to_string();
+            for n in 2.. {
+                if !used.contains(&label) {
+                    break;
+                }
+                label = format!("Chorus {n}");
+            }
+            used.insert(label.clone());
+            label_for_key.insert(key.as_str(), label.clone());
+            Some(label)
+        } else if in_labeled_section {
+            None
+        } else {
+            verse_count += 1;
+            Some(format!("Verse {verse_count}"))
+        };
+
+        if !out.is_empty() {
+            out.push(String::new());
+        }
+        if let Some(label) = &label {
+            out.push(format!("[{label}]"));
+        }
+        out.extend(block.lines.iter().cloned());
+
+        sections.push(SectionChange {
+            before: block.header.as_ref().map(|(raw, _)| raw.clone()),
+            after: label,
+            first_line: chordpro::parse_chord_line(&block.lines[0]).text.trim().to_string(),
+        });
+    }
+
+    StructureProposal { content: out, sections }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    const GROUPS: &[&str] = &["Verse", "Chorus", "Pre-Chorus", "Bridge", "Tag", "Refrain", "Ending"];
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_header_spellings() {
+        let label = |line: &str| parse_header(line, GROUPS).map(|h| h.label());
+        assert_eq!(label("CHORUS:").as_deref(), Some("Chorus"));
+        assert_eq!(label("V1").as_deref(), Some("Verse 1"));
+        assert_eq!(label("(Refrain)").as_deref(), Some("Refrain"));
+        assert_eq!(label("Verse 2").as_deref(), Some("Verse 2"));
+        assert_eq!(label("pre chorus").as_deref(), Some("Pre-Chorus"));
+        assert_eq!(label("Chorus x2").as_deref(), Some("Chorus"));
+        assert_eq!(label("C:").as_deref(), Some("Chorus"));
+        assert_eq!(label("C"), None);
+        assert_eq!(label("Amazing grace"), None);
+    }
+
+    #[test]
+    fn test_normalize_unlabeled_blocks() {
+        let content = lines("Amazing grace\nhow sweet\n\nMy chains are gone\n\nTwas grace\n\nMy chains are gone");
+        let proposal = normalize(&content, GROUPS);
+        assert_eq!(proposal.content, lines(
+            "[Verse 1]\nAmazing grace\nhow sweet\n\n[Chorus]\nMy chains are gone\n\n[Verse 2]\nTwas grace\n\n[Chorus]\nMy chains are gone"
+        ));
+        assert_eq!(proposal.sections[0].first_line, "Amazing grace");
+    }
+
+    #[test]
+    fn test_normalize_headers_and_repeats() {
+        let content = lines("{key: G}\nVERSE:\n[G]Amazing grace\n\nCHORUS:\nMy chains are gone\n\nV2\nTwas grace\nand more\n\nRepeat Chorus");
+        let proposal = normalize(&content, GROUPS);
+        assert_eq!(proposal.content, lines(
+            "{key: G}\n\n[Verse 1]\n[G]Amazing grace\n\n[Chorus]\nMy chains are gone\n\n[Verse 2]\nTwas grace\nand more\n\n[Chorus]\nMy chains are gone"
+        ));
+        assert_eq!(proposal.sections[1].before.as_deref(), Some("CHORUS:"));
+    }
+
+    #[test]
+    fn test_continuation_and_existing_markers() {
+        let content = lines("[Verse 1]\nline one\n\nline two\n\n[Chorus]\nrefrain");
+        let proposal = normalize(&content, GROUPS);
+        assert_eq!(proposal.content, content);
+        assert_eq!(proposal.sections[1].after, None);
+    }
+}
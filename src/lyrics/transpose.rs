@@ -0,0 +1,134 @@
+//! Chord transposition between keys.
+//!
+//! Chords are moved by semitones and spelled with sharps or flats according
+//! to the target key, so "G → F" gives "Bb" rather than "A#".
+
+/// Note names spelled with sharps, indexed by semitone above C.
+const SHARP_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+/// Note names spelled with flats, indexed by semitone above C.
+const FLAT_NAMES: [&str; 12] = ["C", "Db", "D", "Eb", "E", "F", "Gb", "G", "Ab", "A", "Bb", "B"];
+
+/// Major keys conventionally written with flats.
+const FLAT_MAJOR_KEYS: &[usize] = &[5, 10, 3, 8, 1, 6]; // F Bb Eb Ab Db Gb
+
+/// Split a leading note name off `text`: ("F#", "m7") → (6, "m7").
+fn split_note(text: &str) -> Option<(usize, &str)> {
+    let mut chars = text.chars();
+    let base = match chars.next()? {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => return None,
+    };
+    let rest = chars.as_str();
+    let (accidental, rest) = rest.strip_prefix('#').map_or_else(
+        || rest.strip_prefix('b').map_or((0, rest), |flat| (11, flat)),
+        |sharp| (1, sharp),
+    );
+    Some(((base + accidental) % 12, rest))
+}
+
+/// A key parsed from a name like "G", "Bb", or "F#m".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    /// Tonic, in semitones above C
+    pub tonic: usize,
+    /// Minor key
+    pub minor: bool,
+}
+
+impl Key {
+    /// Parse a key name ("G", "Eb", "C#m", "D minor").
+    pub fn parse(name: &str) -> Option<Self> {
+        let (tonic, rest) = split_note(name.trim())?;
+        let rest = rest.trim().to_lowercase();
+        let minor = match rest.as_str() {
+            "" | "maj" | "major" => false,
+            "m" | "min" | "minor" => true,
+            _ => return None,
+        };
+        Some(Self { tonic, minor })
+    }
+
+    /// Whether chords in this key are spelled with flats.
+    pub fn prefers_flats(self) -> bool {
+        // A minor key uses its relative major's signature
+        let major = if self.minor { (self.tonic + 3) % 12 } else { self.tonic };
+        FLAT_MAJOR_KEYS.contains(&major)
+    }
+
+    /// This key moved by `semitones`.
+    #[must_use]
+    pub const fn shifted(self, semitones: i32) -> Self {
+        Self { tonic: shift(self.tonic, semitones), minor: self.minor }
+    }
+
+    /// Semitones up from this key to `other` (0–11).
+    pub const fn distance_to(self, other: Self) -> i32 {
+        #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)] // tonic < 12
+        let diff = other.tonic as i32 - self.tonic as i32;
+        diff.rem_euclid(12)
+    }
+}
+
+/// Move a semitone index by `semitones`, wrapping at the octave.
+const fn shift(note: usize, semitones: i32) -> usize {
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation, clippy::cast_sign_loss)] // values < 12
+    let shifted = (note as i32 + semitones.rem_euclid(12)).rem_euclid(12) as usize;
+    shifted
+}
+
+/// Transpose a chord name by `semitones`, including a slash bass note.
+///
+/// Names that don't start with a note are returned unchanged.
+pub fn transpose_chord(chord: &str, semitones: i32, prefer_flats: bool) -> String {
+    let names = if prefer_flats { &FLAT_NAMES } else { &SHARP_NAMES };
+    let transpose_note = |text: &str| -> Option<String> {
+        let (note, rest) = split_note(text)?;
+        Some(format!("{}{rest}", names[shift(note, semitones)]))
+    };
+
+    match chord.split_once('/') {
+        Some((upper, bass)) => {
+            let upper = transpose_note(upper).unwrap_or_else(|| upper.to_string());
+            let bass = transpose_note(bass).unwrap_or_else(|| bass.to_string());
+            format!("{upper}/{bass}")
+        }
+        None => transpose_note(chord).unwrap_or_else(|| chord.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn test_transpose_chord() {
+        assert_eq!(transpose_chord("G", 2, false), "A");
+        assert_eq!(transpose_chord("D/F#", 2, false), "E/G#");
+        assert_eq!(transpose_chord("Em7", -2, true), "Dm7");
+        assert_eq!(transpose_chord("C", 10, true), "Bb");
+        assert_eq!(transpose_chord("N.C.", 3, false), "N.C.");
+        assert_eq!(transpose_chord("G", i32::MAX, false), "D");
+        assert_eq!(transpose_chord("G", i32::MIN, false), "B");
+    }
+
+    #[test]
+    fn test_keys() {
+        let g = Key::parse("G").unwrap();
+        let f = Key::parse("F").unwrap();
+        assert_eq!(g.distance_to(f), 10);
+        assert!(f.prefers_flats());
+        assert!(!g.prefers_flats());
+        assert!(Key::parse("Dm").unwrap().prefers_flats());
+        assert_eq!(g.shifted(-7), Key::parse("C").unwrap());
+        assert!(Key::parse("H").is_none());
+    }
+}
@@ -13,8 +13,10 @@ mod app;
 mod bible;
 mod config;
 mod hymnal;
+mod history;
 mod constants;
 mod error;
+mod find;
 mod input;
 mod item_state;
 mod lyrics;
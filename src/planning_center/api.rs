@@ -321,7 +321,9 @@ fn parse_song(
     let title = attrs["title"].as_str().unwrap_or("").to_string();
     let author = attrs["author"].as_str().map(String::from);
     let copyright = attrs["copyright"].as_str().map(String::from);
-    let ccli = attrs["ccli_number"].as_str().map(String::from);
+    // Planning Center sends the CCLI number as an integer
+    let ccli = attrs["ccli_number"].as_u64().map(|n| n.to_string())
+        .or_else(|| attrs["ccli_number"].as_str().map(String::from));
 
     // Get lyrics from arrangement
     let (lyrics, arrangement) = rels.get("arrangement")
@@ -4,6 +4,8 @@
 
 use chrono::{DateTime, Utc};
 
+use crate::propresenter::data_model as dm;
+
 /// Represents a type of service (e.g., "Sunday Morning")
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Service {
@@ -75,7 +77,7 @@ pub struct Song {
     pub author: Option<String>,
     /// Copyright information
     pub copyright: Option<String>,
-    /// CCLI license number
+    /// CCLI song number
     pub ccli: Option<String>,
     /// Associated theme tags
     pub themes: Option<Vec<String>>,
@@ -85,6 +87,30 @@ pub struct Song {
     pub arrangement: Option<String>,
 }
 
+impl Song {
+    /// Build presentation CCLI info for the song.
+    ///
+    /// The copyright year is taken from the notice ("© 2001 Worship Together").
+    pub fn to_ccli_info(&self) -> dm::CCLIInfo {
+        let copyright = self.copyright.clone().unwrap_or_default();
+        let copyright_year = copyright
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|digits| digits.len() == 4)
+            .and_then(|year| year.parse().ok())
+            .unwrap_or(0);
+        dm::CCLIInfo {
+            author: self.author.clone().unwrap_or_default(),
+            artist_credits: String::new(),
+            song_title: self.title.clone(),
+            publisher: copyright,
+            copyright_year,
+            song_number: self.ccli.as_deref().and_then(|n| n.trim().parse().ok()).unwrap_or(0),
+            display: true,
+            album: String::new(),
+        }
+    }
+}
+
 /// Scripture reference
 #[derive(Debug, Clone)]
 pub struct Scripture {
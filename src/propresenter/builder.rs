@@ -39,6 +39,7 @@ impl PresentationBuilder {
                 path: None,
                 last_used: None,
                 last_modified: None,
+                source: None,
             },
             cues: Vec::new(),
             cue_groups: Vec::new(),
@@ -195,6 +196,7 @@ mod tests {
             completion_action_type: dm::CompletionActionType::First,
             completion_action_uuid: None,
             completion_time: 0.0,
+            source: None,
         };
 
         let group = dm::CueGroup {
@@ -0,0 +1,256 @@
+//! `ProPresenter` bundle file support (.probundle).
+//!
+//! A bundle is a zip holding one presentation and the media it uses, for
+//! sharing a song between machines. The .pro file sits at the root of the
+//! zip and media under [`media::MEDIA_DIR`], with the presentation's URLs
+//! pointing into the bundle. Importing unpacks both into the library and
+//! points the URLs at the unpacked media.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use prost::Message;
+use zip::write::FileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::propresenter::generated::rv_data;
+use crate::propresenter::media::{self, FileData};
+
+/// Errors that can occur when reading or writing bundles
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    /// An I/O error occurred during file operations
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The presentation couldn't be decoded, or the bundle has none
+    #[error("Decoding error: {0}")]
+    Decode(String),
+
+    /// A zip archive error occurred
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+}
+
+/// A presentation with the media files it uses
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    /// Presentation name, the .pro file's stem
+    pub name: String,
+    /// The presentation, with media URLs pointing into the bundle
+    pub presentation: rv_data::Presentation,
+    /// Media as `(bundle path, contents)`
+    pub files: Vec<(String, FileData)>,
+}
+
+impl Bundle {
+    /// Bundle `presentation` with the local media it references.
+    ///
+    /// Media that can't be found on this machine keeps its original URL.
+    pub fn new(name: &str, presentation: &rv_data::Presentation) -> Self {
+        let mut presentation = presentation.clone();
+        let mut files = Vec::new();
+        media::bundle_media(&mut presentation, &mut files);
+        Self { name: name.to_string(), presentation, files }
+    }
+
+    /// Write the bundle zip: the .pro file, then its media.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), BundleError> {
+        let mut zip = ZipWriter::new(File::create(path)?);
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+        zip.start_file(format!("{}.pro", self.name), options)?;
+        zip.write_all(&self.presentation.encode_to_vec())?;
+        for (name, data) in &self.files {
+            zip.start_file(name.as_str(), options)?;
+            data.write_to(&mut zip)?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// Unpack into `library_dir`, with media in `media_dir`. Returns the .pro file's path.
+    ///
+    /// Files already there with the same contents are reused; different files
+    /// with a taken name get a numbered name, so nothing is overwritten.
+    pub fn unpack(&self, library_dir: &Path, media_dir: &Path) -> Result<PathBuf, BundleError> {
+        let mut unpacked = HashMap::new();
+        if !self.files.is_empty() {
+            std::fs::create_dir_all(media_dir)?;
+        }
+        for (name, data) in &self.files {
+            // Only the file name is used, so entries like `../x` stay in `media_dir`
+            let file_name = Path::new(name).file_name().and_then(|n| n.to_str())
+                .ok_or_else(|| BundleError::Decode(format!("bad media path in bundle: {name}")))?;
+            unpacked.insert(name.as_str(), unpack_file(media_dir, file_name, data)?);
+        }
+
+        let mut presentation = self.presentation.clone();
+        media::unbundle_media(&mut presentation, |path| unpacked.get(path).cloned());
+        std::fs::create_dir_all(library_dir)?;
+        unpack_file(library_dir, &format!("{}.pro", self.name), &FileData::Bytes(presentation.encode_to_vec()))
+    }
+}
+
+/// Write `data` to `dir`/`name`, or a numbered name if that holds something else
+fn unpack_file(dir: &Path, name: &str, data: &FileData) -> Result<PathBuf, BundleError> {
+    let mut n = 1u32;
+    loop {
+        let path = dir.join(media::numbered_name(name, n));
+        match data.matches_file(&path) {
+            Ok(true) => return Ok(path),
+            Ok(false) => n += 1,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                data.write_to(&mut File::create(&path)?)?;
+                return Ok(path);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Bundle the .pro file at `pro_path` and write it to `bundle_path`.
+pub fn export_bundle(pro_path: &Path, bundle_path: &Path) -> Result<Bundle, BundleError> {
+    let data = std::fs::read(pro_path)?;
+    let presentation = rv_data::Presentation::decode(data.as_slice())
+        .map_err(|e| BundleError::Decode(e.to_string()))?;
+    let name = pro_path.file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled");
+    let bundle = Bundle::new(name, &presentation);
+    bundle.write(bundle_path)?;
+    Ok(bundle)
+}
+
+/// Read a .probundle zip
+pub fn read_bundle(path: impl AsRef<Path>) -> Result<Bundle, BundleError> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut presentation = None;
+    let mut files = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        let name = entry.name().to_string();
+        let is_pro = Path::new(&name).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("pro"));
+        if is_pro && presentation.is_none() {
+            let decoded = rv_data::Presentation::decode(data.as_slice())
+                .map_err(|e| BundleError::Decode(e.to_string()))?;
+            let stem = Path::new(&name).file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled").to_string();
+            presentation = Some((stem, decoded));
+        } else {
+            files.push((name, FileData::Bytes(data)));
+        }
+    }
+
+    let (name, presentation) = presentation.ok_or_else(|| BundleError::Decode("no .pro file in bundle".to_string()))?;
+    Ok(Bundle { name, presentation, files })
+}
+
+/// Unpack the bundle at `bundle_path` into `library_dir`. Returns the imported .pro file's path.
+///
+/// Media goes in [`default_media_dir`] for the library.
+pub fn import_bundle(bundle_path: &Path, library_dir: &Path) -> Result<PathBuf, BundleError> {
+    read_bundle(bundle_path)?.unpack(library_dir, &default_media_dir(library_dir))
+}
+
+/// Where imported media goes for a library
+///
+/// `ProPresenter` keeps libraries in `<ProPresenter>/Libraries/<name>` and
+/// media in `<ProPresenter>/Media/Assets`; other folders get a `Media`
+/// folder of their own.
+pub fn default_media_dir(library_dir: &Path) -> PathBuf {
+    let parent = library_dir.parent().filter(|parent| parent.file_name().is_some_and(|n| n == "Libraries"));
+    parent.and_then(Path::parent).map_or_else(
+        || library_dir.join(media::MEDIA_DIR),
+        |root| root.join("Media").join("Assets"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+    use crate::propresenter::generated::rv_data::url;
+
+    fn background_presentation(path: &Path) -> rv_data::Presentation {
+        let url = rv_data::Url {
+            platform: url::Platform::Macos as i32,
+            storage: Some(url::Storage::AbsoluteString(media::file_url(path))),
+            relative_file_path: None,
+        };
+        rv_data::Presentation {
+            chord_chart: Some(url),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_export_and_import_round_trip() {
+        let campus = tempfile::tempdir().unwrap();
+        let background = campus.path().join("Hymn Background.jpg");
+        std::fs::write(&background, b"jpeg").unwrap();
+        let pro_path = campus.path().join("Amazing Grace.pro");
+        std::fs::write(&pro_path, background_presentation(&background).encode_to_vec()).unwrap();
+
+        let bundle_path = campus.path().join("Amazing Grace.probundle");
+        let exported = export_bundle(&pro_path, &bundle_path).unwrap();
+        // Media is read from disk when the zip is written, not held in the bundle
+        assert_eq!(exported.files, [("Media/Hymn Background.jpg".to_string(), FileData::Local(background))]);
+
+        let bundle = read_bundle(&bundle_path).unwrap();
+        assert_eq!(bundle.name, "Amazing Grace");
+        assert_eq!(bundle.files, [("Media/Hymn Background.jpg".to_string(), FileData::Bytes(b"jpeg".to_vec()))]);
+        assert_eq!(
+            bundle.presentation.chord_chart.as_ref().and_then(media::url_bundle_path),
+            Some("Media/Hymn Background.jpg")
+        );
+
+        // Another campus already has a different file by that name
+        let other = tempfile::tempdir().unwrap();
+        let library = other.path().join("ProPresenter").join("Libraries").join("Default");
+        let assets = default_media_dir(&library);
+        assert_eq!(assets, other.path().join("ProPresenter").join("Media").join("Assets"));
+        std::fs::create_dir_all(&assets).unwrap();
+        std::fs::write(assets.join("Hymn Background.jpg"), b"png").unwrap();
+
+        let imported = import_bundle(&bundle_path, &library).unwrap();
+        assert_eq!(imported, library.join("Amazing Grace.pro"));
+        let unpacked_media = assets.join("Hymn Background (2).jpg");
+        assert_eq!(std::fs::read(&unpacked_media).unwrap(), b"jpeg");
+        let presentation = rv_data::Presentation::decode(std::fs::read(&imported).unwrap().as_slice()).unwrap();
+        assert_eq!(presentation.chord_chart.as_ref().and_then(media::url_file_path), Some(unpacked_media));
+
+        // Importing again reuses what's there
+        assert_eq!(import_bundle(&bundle_path, &library).unwrap(), imported);
+        assert_eq!(std::fs::read_dir(&assets).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_unpack_rejects_paths_without_file_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let media_dir = dir.path().join("Media");
+        for name in ["..", "Media/..", "/"] {
+            let bundle = Bundle {
+                name: "Amazing Grace".to_string(),
+                presentation: rv_data::Presentation::default(),
+                files: vec![(name.to_string(), FileData::Bytes(b"jpeg".to_vec()))],
+            };
+            assert!(matches!(bundle.unpack(dir.path(), &media_dir), Err(BundleError::Decode(_))), "{name}");
+        }
+
+        // Other paths are flattened into the media folder
+        let bundle = Bundle {
+            name: "Amazing Grace".to_string(),
+            presentation: rv_data::Presentation::default(),
+            files: vec![("../../Hymn Background.jpg".to_string(), FileData::Bytes(b"jpeg".to_vec()))],
+        };
+        bundle.unpack(dir.path(), &media_dir).unwrap();
+        assert_eq!(std::fs::read(media_dir.join("Hymn Background.jpg")).unwrap(), b"jpeg");
+        assert!(!dir.path().parent().unwrap().join("Hymn Background.jpg").exists());
+    }
+}
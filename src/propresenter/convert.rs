@@ -5,6 +5,7 @@
 
 #![allow(dead_code)]
 
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use crate::propresenter::{
     data_model as dm,
@@ -195,114 +196,53 @@ impl From<dm::Timeline> for presentation::Timeline {
             timecode_enable: timeline.timecode_enabled,
             timecode_offset: timeline.timecode_offset,
             cues: Vec::new(), // Legacy cues field, use cues_v2 instead
-            cues_v2: timeline.cues.into_iter().map(|cue| {
-                presentation::timeline::Cue {
-                    trigger_time: cue.trigger_time,
-                    name: cue.name,
-                    trigger_info: Some(presentation::timeline::cue::TriggerInfo::CueId(cue.uuid.into())),
-                }
-            }).collect(),
+            cues_v2: timeline.cues.into_iter().map(std::convert::Into::into).collect(),
             audio_action: None, // Not supported in data model
         }
     }
 }
 
 impl From<presentation::Timeline> for dm::Timeline {
-    #[allow(clippy::too_many_lines)] // Complex protobuf conversion logic that is clearer as one block
     fn from(timeline: presentation::Timeline) -> Self {
         Self {
             duration: timeline.duration,
             loop_enabled: timeline.r#loop,
             timecode_enabled: timeline.timecode_enable,
             timecode_offset: timeline.timecode_offset,
-            cues: timeline.cues_v2.into_iter().map(|cue| {
-                let uuid = extract_timeline_cue_uuid(&cue);
-                let action = extract_timeline_cue_action(&cue);
-
-                dm::TimelineCue {
-                    trigger_time: cue.trigger_time,
-                    name: cue.name,
-                    uuid,
-                    action: action.unwrap_or(dm::Action::Clear {
-                        target_layer: 0,
-                        content_destination: dm::ContentDestination::Global,
-                    }),
-                }
-            }).collect(),
+            cues: timeline.cues_v2.into_iter().map(std::convert::Into::into).collect(),
         }
     }
 }
 
-/// Extracts the UUID from a timeline cue's trigger info.
-/// Falls back to a new v4 UUID when the trigger has no parseable UUID.
-fn extract_timeline_cue_uuid(cue: &presentation::timeline::Cue) -> Uuid {
-    let Some(presentation::timeline::cue::TriggerInfo::Action(action)) = &cue.trigger_info else {
-        return cue.trigger_info.as_ref().map_or_else(
-            Uuid::new_v4,
-            |info| match info {
-                presentation::timeline::cue::TriggerInfo::CueId(uuid) => {
-                    uuid.string.parse().unwrap_or_else(|_| Uuid::new_v4())
-                }
-                presentation::timeline::cue::TriggerInfo::Action(_) => unreachable!(),
-            },
-        );
-    };
-
-    if rv_data::action::ActionType::try_from(action.r#type).unwrap_or(rv_data::action::ActionType::Unknown)
-        != rv_data::action::ActionType::AudienceLook
-    {
-        return Uuid::new_v4();
-    }
-
-    if let Some(rv_data::action::ActionTypeData::AudienceLook(look)) = &action.action_type_data {
-        look.identification
-            .as_ref()
-            .and_then(|id| id.parameter_uuid.as_ref())
-            .map_or_else(Uuid::new_v4, |uuid| {
-                uuid.string.parse().unwrap_or_else(|_| Uuid::new_v4())
-            })
-    } else {
-        Uuid::new_v4()
+impl From<dm::TimelineCue> for presentation::timeline::Cue {
+    fn from(cue: dm::TimelineCue) -> Self {
+        let trigger_info = match cue.action {
+            Some(action) => presentation::timeline::cue::TriggerInfo::Action(convert_action_to_rv_data(action, None)),
+            None => presentation::timeline::cue::TriggerInfo::CueId(cue.uuid.into()),
+        };
+        Self {
+            trigger_time: cue.trigger_time,
+            name: cue.name,
+            trigger_info: Some(trigger_info),
+        }
     }
 }
 
-/// Extracts the action from a timeline cue's trigger info, returning `None`
-/// for unsupported action types.
-fn extract_timeline_cue_action(cue: &presentation::timeline::Cue) -> Option<dm::Action> {
-    let Some(presentation::timeline::cue::TriggerInfo::Action(action)) = &cue.trigger_info else {
-        return None;
-    };
-
-    match rv_data::action::ActionType::try_from(action.r#type).unwrap_or(rv_data::action::ActionType::Unknown) {
-        rv_data::action::ActionType::Clear => {
-            let Some(rv_data::action::ActionTypeData::Clear(clear)) = &action.action_type_data else {
-                return None;
-            };
-            // Only Global destination is currently supported
-            let _dest = rv_data::action::ContentDestination::try_from(clear.content_destination)
-                .unwrap_or(rv_data::action::ContentDestination::Global);
-            Some(dm::Action::Clear {
-                target_layer: clear.target_layer,
-                content_destination: dm::ContentDestination::Global,
-            })
-        }
-        rv_data::action::ActionType::AudienceLook => {
-            let Some(rv_data::action::ActionTypeData::AudienceLook(look)) = &action.action_type_data else {
-                return None;
-            };
-            let Some(identification) = &look.identification else {
-                return None;
-            };
-            Some(dm::Action::AudienceLook {
-                name: action.name.clone(),
-                uuid: identification.parameter_uuid.as_ref().map_or_else(
-                    Uuid::new_v4,
-                    |u| u.string.parse().unwrap_or_else(|_| Uuid::new_v4()),
-                ),
-                parameter_name: identification.parameter_name.clone(),
-            })
+impl From<presentation::timeline::Cue> for dm::TimelineCue {
+    fn from(cue: presentation::timeline::Cue) -> Self {
+        let (uuid, action) = match cue.trigger_info {
+            Some(presentation::timeline::cue::TriggerInfo::Action(action)) => {
+                (parse_uuid(action.uuid.as_ref()), Some(action.into()))
+            }
+            Some(presentation::timeline::cue::TriggerInfo::CueId(uuid)) => (parse_uuid(Some(&uuid)), None),
+            None => (Uuid::nil(), None),
+        };
+        Self {
+            trigger_time: cue.trigger_time,
+            name: cue.name,
+            uuid,
+            action,
         }
-        _ => None,
     }
 }
 
@@ -320,9 +260,9 @@ impl From<dm::Arrangement> for presentation::Arrangement {
 impl From<presentation::Arrangement> for dm::Arrangement {
     fn from(arrangement: presentation::Arrangement) -> Self {
         Self {
-            uuid: arrangement.uuid.map_or_else(Uuid::new_v4, |uuid| uuid.string.parse().unwrap_or_else(|_| Uuid::new_v4())),
+            uuid: parse_uuid(arrangement.uuid.as_ref()),
             name: arrangement.name,
-            group_identifiers: arrangement.group_identifiers.into_iter().map(|uuid| uuid.string.parse().unwrap_or_else(|_| Uuid::new_v4())).collect(),
+            group_identifiers: arrangement.group_identifiers.iter().map(|uuid| parse_uuid(Some(uuid))).collect(),
         }
     }
 }
@@ -330,7 +270,11 @@ impl From<presentation::Arrangement> for dm::Arrangement {
 // Presentation conversions
 impl From<dm::Presentation> for rv_data::Presentation {
     #[allow(clippy::too_many_lines)] // Complex protobuf struct assembly
-    fn from(presentation: dm::Presentation) -> Self {
+    fn from(mut presentation: dm::Presentation) -> Self {
+        if let Some(source) = presentation.source.take() {
+            return overlay_presentation(*source, presentation);
+        }
+
         let background = Some(rv_data::Background {
             is_enabled: false,
             fill: Some(rv_data::background::Fill::Color(rv_data::Color {
@@ -346,316 +290,10 @@ impl From<dm::Presentation> for rv_data::Presentation {
         });
 
         // Create rv_data::Cues from our data model
-        let cues = presentation.cues.into_iter().map(|cue| {
-            let actions = cue.actions.into_iter().map(|action| {
-                match action {
-                    dm::Action::Slide { uuid, name, slide, delay_time, duration, enabled, layer_identification } => {
-                        // Create rv_data::Action for the slide
-                        rv_data::Action {
-                            uuid: Some(rv_data::Uuid { string: uuid.to_string() }),
-                            name,
-                            label: None,
-                            delay_time,
-                            old_type: None,
-                            is_enabled: enabled,
-                            layer_identification: layer_identification.map(|li| rv_data::action::LayerIdentification {
-                                uuid: Some(rv_data::Uuid { string: li.uuid.to_string() }),
-                                name: li.name,
-                            }),
-                            duration,
-                            r#type: rv_data::action::ActionType::PresentationSlide as i32,
-                            action_type_data: Some(rv_data::action::ActionTypeData::Slide(
-                                rv_data::action::SlideType {
-                                    slide: Some(rv_data::action::slide_type::Slide::Presentation(convert_slide_to_rv_data(slide)))
-                                }
-                            )),
-                        }
-                    },
-                    dm::Action::Clear { target_layer, content_destination: _ } => {
-                        rv_data::Action {
-                            uuid: Some(rv_data::Uuid { string: Uuid::new_v4().to_string() }),
-                            name: "Clear".to_string(),
-                            label: None,
-                            delay_time: 0.0,
-                            old_type: None,
-                            is_enabled: true,
-                            layer_identification: None,
-                            duration: 0.0,
-                            r#type: rv_data::action::ActionType::Clear as i32,
-                            action_type_data: Some(rv_data::action::ActionTypeData::Clear(rv_data::action::ClearType {
-                                target_layer,
-                                content_destination: rv_data::action::ContentDestination::Global as i32,
-                            })),
-                        }
-                    },
-                    dm::Action::AudienceLook { name, uuid, parameter_name } => {
-                        rv_data::Action {
-                            uuid: Some(rv_data::Uuid { string: uuid.to_string() }),
-                            name,
-                            label: None,
-                            delay_time: 0.0,
-                            old_type: None,
-                            is_enabled: true,
-                            layer_identification: None,
-                            duration: 0.0,
-                            r#type: rv_data::action::ActionType::AudienceLook as i32,
-                            action_type_data: Some(rv_data::action::ActionTypeData::AudienceLook(
-                                rv_data::action::AudienceLookType {
-                                    identification: Some(rv_data::CollectionElementType {
-                                        parameter_uuid: Some(rv_data::Uuid { string: uuid.to_string() }),
-                                        parameter_name,
-                                        parent_collection: None,
-                                    }),
-                                }
-                            )),
-                        }
-                    },
-                    dm::Action::Media {
-                        uuid,
-                        name,
-                        source,
-                        fit,
-                        opacity: _,
-                        volume,
-                        delay_time,
-                        duration,
-                        enabled,
-                    } => {
-                        let mut action = rv_data::Action::default();
-                        action.uuid = Some(uuid.into());
-                        action.name = name;
-                        action.delay_time = delay_time;
-                        action.duration = duration;
-                        action.is_enabled = enabled;
-                        action.r#type = rv_data::action::ActionType::Media as i32;
-                        action.action_type_data = Some(rv_data::action::ActionTypeData::Media(
-                            rv_data::action::MediaType {
-                                transition_duration: 0.0,
-                                selected_effect_preset_uuid: None,
-                                transition: None,
-                                effects: vec![],
-                                element: Some(rv_data::Media {
-                                    uuid: Some(uuid.into()),
-                                    url: None,
-                                    metadata: None,
-                                    type_properties: Some(match source {
-                                        dm::MediaSource::File(path) => rv_data::media::TypeProperties::Image(
-                                            rv_data::media::ImageTypeProperties {
-                                                drawing: Some(rv_data::media::DrawingProperties {
-                                                    scale_behavior: match fit {
-                                                        dm::MediaFit::Scale => rv_data::media::ScaleBehavior::Fit as i32,
-                                                        dm::MediaFit::Stretch => rv_data::media::ScaleBehavior::Stretch as i32,
-                                                        dm::MediaFit::Center => rv_data::media::ScaleBehavior::Fill as i32,
-                                                    },
-                                                    is_blurred: false,
-                                                    scale_alignment: rv_data::media::ScaleAlignment::MiddleCenter as i32,
-                                                    flipped_horizontally: false,
-                                                    flipped_vertically: false,
-                                                    natural_size: None,
-                                                    custom_image_rotation: 0.0,
-                                                    custom_image_bounds: None,
-                                                    custom_image_aspect_locked: true,
-                                                    alpha_inverted: false,
-                                                    native_rotation: rv_data::media::drawing_properties::NativeRotationType::RotateStandard as i32,
-                                                    selected_effect_preset_uuid: None,
-                                                    effects: vec![],
-                                                    crop_enable: false,
-                                                    crop_insets: Some(rv_data::graphics::EdgeInsets {
-                                                        left: 0.0,
-                                                        right: 0.0,
-                                                        top: 0.0,
-                                                        bottom: 0.0,
-                                                    }),
-                                                    alpha_type: rv_data::AlphaType::Straight as i32,
-                                                }),
-                                                file: Some(rv_data::FileProperties {
-                                                    local_url: Some(rv_data::Url {
-                                                        platform: rv_data::url::Platform::Macos as i32,
-                                                        storage: Some(rv_data::url::Storage::AbsoluteString(
-                                                            path.to_string_lossy().to_string(),
-                                                        )),
-                                                        relative_file_path: None,
-                                                    }),
-                                                    remote_properties: None,
-                                                }),
-                                            }
-                                        ),
-                                        dm::MediaSource::VideoInput { input_id: _, input_name: _ } => rv_data::media::TypeProperties::LiveVideo(
-                                            rv_data::media::LiveVideoTypeProperties {
-                                                drawing: Some(rv_data::media::DrawingProperties {
-                                                    scale_behavior: match fit {
-                                                        dm::MediaFit::Scale => rv_data::media::ScaleBehavior::Fit as i32,
-                                                        dm::MediaFit::Stretch => rv_data::media::ScaleBehavior::Stretch as i32,
-                                                        dm::MediaFit::Center => rv_data::media::ScaleBehavior::Fill as i32,
-                                                    },
-                                                    is_blurred: false,
-                                                    scale_alignment: rv_data::media::ScaleAlignment::MiddleCenter as i32,
-                                                    flipped_horizontally: false,
-                                                    flipped_vertically: false,
-                                                    natural_size: None,
-                                                    custom_image_rotation: 0.0,
-                                                    custom_image_bounds: None,
-                                                    custom_image_aspect_locked: true,
-                                                    alpha_inverted: false,
-                                                    native_rotation: rv_data::media::drawing_properties::NativeRotationType::RotateStandard as i32,
-                                                    selected_effect_preset_uuid: None,
-                                                    effects: vec![],
-                                                    crop_enable: false,
-                                                    crop_insets: Some(rv_data::graphics::EdgeInsets {
-                                                        left: 0.0,
-                                                        right: 0.0,
-                                                        top: 0.0,
-                                                        bottom: 0.0,
-                                                    }),
-                                                    alpha_type: rv_data::AlphaType::Straight as i32,
-                                                }),
-                                                audio: Some(rv_data::media::AudioProperties {
-                                                    volume: f64::from(volume),
-                                                    audio_channels: vec![],
-                                                    is_custom_mapping: false,
-                                                }),
-                                                live_video: Some(rv_data::media::LiveVideoProperties {
-                                                    video_device: None,
-                                                    audio_device: None,
-                                                    live_video_index: 0,
-                                                }),
-                                            }
-                                        ),
-                                        dm::MediaSource::Url(url) => rv_data::media::TypeProperties::WebContent(
-                                            rv_data::media::WebContentTypeProperties {
-                                                drawing: Some(rv_data::media::DrawingProperties {
-                                                    scale_behavior: match fit {
-                                                        dm::MediaFit::Scale => rv_data::media::ScaleBehavior::Fit as i32,
-                                                        dm::MediaFit::Stretch => rv_data::media::ScaleBehavior::Stretch as i32,
-                                                        dm::MediaFit::Center => rv_data::media::ScaleBehavior::Fill as i32,
-                                                    },
-                                                    is_blurred: false,
-                                                    scale_alignment: rv_data::media::ScaleAlignment::MiddleCenter as i32,
-                                                    flipped_horizontally: false,
-                                                    flipped_vertically: false,
-                                                    natural_size: None,
-                                                    custom_image_rotation: 0.0,
-                                                    custom_image_bounds: None,
-                                                    custom_image_aspect_locked: true,
-                                                    alpha_inverted: false,
-                                                    native_rotation: rv_data::media::drawing_properties::NativeRotationType::RotateStandard as i32,
-                                                    selected_effect_preset_uuid: None,
-                                                    effects: vec![],
-                                                    crop_enable: false,
-                                                    crop_insets: Some(rv_data::graphics::EdgeInsets {
-                                                        left: 0.0,
-                                                        right: 0.0,
-                                                        top: 0.0,
-                                                        bottom: 0.0,
-                                                    }),
-                                                    alpha_type: rv_data::AlphaType::Straight as i32,
-                                                }),
-                                                url: Some(rv_data::Url {
-                                                    platform: rv_data::url::Platform::Web as i32,
-                                                    storage: Some(rv_data::url::Storage::AbsoluteString(url)),
-                                                    relative_file_path: None,
-                                                }),
-                                            }
-                                        ),
-                                    }),
-                                }),
-                                layer_type: rv_data::action::LayerType::Background as i32,
-                                always_retrigger: false,
-                                markers: vec![],
-                                media_type: None,
-                            }
-                        ));
-                        action
-                    },
-                    dm::Action::Macro { uuid, name, parameter_uuid, parameter_name, parent_collection_uuid, parent_collection_name, delay_time, duration, enabled } => {
-                        let mut action = rv_data::Action::default();
-                        action.uuid = Some(uuid.into());
-                        action.name = name;
-                        action.delay_time = delay_time;
-                        action.duration = duration;
-                        action.is_enabled = enabled;
-                        action.r#type = rv_data::action::ActionType::Macro as i32;
-                        action.action_type_data = Some(rv_data::action::ActionTypeData::Macro(
-                            rv_data::action::MacroType {
-                                identification: Some(rv_data::CollectionElementType {
-                                    parameter_uuid: Some(rv_data::Uuid { string: parameter_uuid.to_string() }),
-                                    parameter_name,
-                                    parent_collection: parent_collection_uuid.map(|uuid| {
-                                        Box::new(rv_data::CollectionElementType {
-                                            parameter_uuid: Some(rv_data::Uuid { string: uuid.to_string() }),
-                                            parameter_name: parent_collection_name.unwrap_or_else(|| "Default Collection".to_string()),
-                                            parent_collection: None,
-                                        })
-                                    }),
-                                }),
-                            }
-                        ));
-                        action
-                    },
-                }
-            }).collect();
-
-            rv_data::Cue {
-                uuid: Some(rv_data::Uuid { string: cue.uuid.to_string() }),
-                name: cue.name,
-                completion_target_type: match cue.completion_target_type {
-                    dm::CompletionTargetType::None => rv_data::cue::CompletionTargetType::None as i32,
-                    dm::CompletionTargetType::Next => rv_data::cue::CompletionTargetType::Next as i32,
-                    dm::CompletionTargetType::Random => rv_data::cue::CompletionTargetType::Random as i32,
-                    dm::CompletionTargetType::Cue => rv_data::cue::CompletionTargetType::Cue as i32,
-                    dm::CompletionTargetType::First => rv_data::cue::CompletionTargetType::First as i32,
-                },
-                completion_target_uuid: cue.completion_target_uuid.map(|uuid| rv_data::Uuid { string: uuid.to_string() }),
-                completion_action_type: match cue.completion_action_type {
-                    dm::CompletionActionType::First => rv_data::cue::CompletionActionType::First as i32,
-                    dm::CompletionActionType::Last => rv_data::cue::CompletionActionType::Last as i32,
-                    dm::CompletionActionType::AfterAction => rv_data::cue::CompletionActionType::AfterAction as i32,
-                    dm::CompletionActionType::AfterTime => rv_data::cue::CompletionActionType::AfterTime as i32,
-                },
-                completion_action_uuid: cue.completion_action_uuid.map(|uuid| rv_data::Uuid { string: uuid.to_string() }),
-                trigger_time: Some(rv_data::cue::TimecodeTime { time: 0.0 }),
-                #[allow(clippy::cast_possible_wrap)] // key codes are small enough
-                hot_key: cue.hot_key.map(|hk| {
-                    rv_data::HotKey {
-                        code: hk.key_code.cast_signed(),
-                        control_identifier: String::new(),
-                    }
-                }),
-                actions,
-                pending_imports: Vec::new(),
-                is_enabled: cue.enabled,
-                completion_time: cue.completion_time,
-            }
-        }).collect();
+        let cues = presentation.cues.into_iter().map(convert_cue_to_rv_data).collect();
 
         // Create rv_data::CueGroups from our data model
-        let cue_groups = presentation.cue_groups.into_iter().map(|cue_group| {
-            let group = rv_data::Group {
-                uuid: Some(rv_data::Uuid { string: cue_group.group.uuid.to_string() }),
-                name: cue_group.group.name.clone(),
-                color: Some(cue_group.group.color.into()),
-                #[allow(clippy::cast_possible_wrap)] // key codes are small enough
-                hot_key: cue_group.group.hot_key.map(|hk| rv_data::HotKey {
-                    code: hk.key_code.cast_signed(),
-                    control_identifier: String::new(),
-                }),
-                application_group_identifier: if cue_group.group.application_group_identifier.is_empty() {
-                    None
-                } else {
-                    Some(rv_data::Uuid { string: cue_group.group.application_group_identifier.clone() })
-                },
-                application_group_name: if cue_group.group.application_group_identifier.is_empty() {
-                    String::new()
-                } else {
-                    cue_group.group.name.clone()
-                },
-            };
-
-            rv_data::presentation::CueGroup {
-                group: Some(group),
-                cue_identifiers: cue_group.cue_identifiers.into_iter().map(|uuid| rv_data::Uuid { string: uuid.to_string() }).collect(),
-            }
-        }).collect();
+        let cue_groups = presentation.cue_groups.into_iter().map(std::convert::Into::into).collect();
 
         Self {
             application_info: Some(rv_data::ApplicationInfo {
@@ -676,11 +314,8 @@ impl From<dm::Presentation> for rv_data::Presentation {
             }),
             uuid: Some(rv_data::Uuid { string: presentation.uuid.to_string() }),
             name: presentation.name,
-            last_date_used: None,
-            last_modified_date: Some(rv_data::Timestamp {
-                seconds: 1_732_047_766,
-                nanos: 0,
-            }),
+            last_date_used: presentation.last_used.map(datetime_to_timestamp),
+            last_modified_date: presentation.last_modified.map(datetime_to_timestamp),
             category: presentation.category,
             notes: presentation.notes,
             background,
@@ -693,15 +328,10 @@ impl From<dm::Presentation> for rv_data::Presentation {
             cues,
             ccli: presentation.ccli.map(std::convert::Into::into),
             bible_reference: presentation.bible_reference.map(std::convert::Into::into),
-            timeline: Some(rv_data::presentation::Timeline {
-                audio_action: None,
-                cues: Vec::new(),
-                cues_v2: Vec::new(),
-                duration: presentation.timeline.as_ref().map_or(300.0, |t| t.duration),
-                r#loop: presentation.timeline.as_ref().is_some_and(|t| t.loop_enabled),
-                timecode_enable: presentation.timeline.as_ref().is_some_and(|t| t.timecode_enabled),
-                timecode_offset: presentation.timeline.as_ref().map_or(0.0, |t| t.timecode_offset),
-            }),
+            timeline: Some(presentation.timeline.map_or_else(
+                || dm::Timeline::new().with_duration(300.0).into(),
+                std::convert::Into::into,
+            )),
             transition: None,
             content_destination: rv_data::action::ContentDestination::Global as i32,
             multi_tracks_licensing: None,
@@ -712,198 +342,419 @@ impl From<dm::Presentation> for rv_data::Presentation {
     }
 }
 
-/// Converts a data model `Slide` into the protobuf `PresentationSlide` representation.
-#[allow(clippy::too_many_lines)] // Complex protobuf struct assembly
-fn convert_slide_to_rv_data(slide: dm::Slide) -> rv_data::PresentationSlide {
-    use rv_data::graphics;
-    use crate::propresenter::rtf::text_to_rtf_bytes;
-
-    let mut elements = Vec::new();
-    let mut element_uuids = Vec::new();
+/// Converts a data model `Cue` into a protobuf `Cue`, written over its decoded
+/// source if it has one.
+fn convert_cue_to_rv_data(mut cue: dm::Cue) -> rv_data::Cue {
+    if let Some(source) = cue.source.take() {
+        return overlay_cue(source, cue);
+    }
+    let actions = cue.actions.into_iter().map(|action| convert_action_to_rv_data(action, None)).collect();
 
-    for element in slide.base.elements {
-        if let dm::Element::Text(text_element) = element {
-            let element_uuid = Uuid::new_v4().to_string();
-            element_uuids.push(rv_data::Uuid { string: element_uuid.clone() });
-            
-            // Use proper RTF conversion that handles superscripts
-            let rtf_data = text_to_rtf_bytes(&text_element.content);
-
-            let text = graphics::Text {
-                attributes: Some(graphics::text::Attributes {
-                    font: Some(rv_data::Font {
-                        name: text_element.font.name.clone(),
-                        size: text_element.font.size,
-                        italic: text_element.font.italic,
-                        bold: text_element.font.bold,
-                        family: text_element.font.family.clone(),
-                        face: text_element.font.face.clone(),
-                    }),
-                    capitalization: graphics::text::attributes::Capitalization::None as i32,
-                    underline_style: None,
-                    underline_color: None,
-                    paragraph_style: Some(text_element.paragraph_style.into()),
-                    kerning: 0.0,
-                    superscript: 0,
-                    strikethrough_style: None,
-                    strikethrough_color: None,
-                    stroke_width: 0.0,
-                    stroke_color: Some(rv_data::Color {
-                        red: 1.0,
-                        green: 1.0,
-                        blue: 1.0,
-                        alpha: 1.0,
-                    }),
-                    custom_attributes: text_element.custom_attributes.into_iter().map(std::convert::Into::into).collect(),
-                    background_color: None,
-                    ligature_style: graphics::text::attributes::LigatureStyle::Default as i32,
-                    fill: Some(graphics::text::attributes::Fill::TextSolidFill(text_element.color.into())),
-                }),
-                shadow: text_element.shadow.map(std::convert::Into::into),
-                rtf_data,
-                vertical_alignment: graphics::text::VerticalAlignment::Middle as i32,
-                scale_behavior: graphics::text::ScaleBehavior::ScaleFontUpDown as i32,
-                margins: Some(graphics::EdgeInsets {
-                    left: 0.0,
-                    right: 0.0,
-                    top: 0.0,
-                    bottom: 0.0,
-                }),
-                is_superscript_standardized: true,
-                transform: graphics::text::Transform::None as i32,
-                transform_delimiter: "  •  ".to_string(),
-                chord_pro: Some(graphics::text::ChordPro {
-                    enabled: false,
-                    notation: graphics::text::chord_pro::Notation::Chords as i32,
-                    color: Some(rv_data::Color {
-                        red: 1.0,
-                        green: 1.0,
-                        blue: 1.0,
-                        alpha: 1.0,
-                    }),
-                }),
-                alternate_texts: Vec::new(),
-            };
+    rv_data::Cue {
+        uuid: Some(rv_data::Uuid { string: cue.uuid.to_string() }),
+        name: cue.name,
+        completion_target_type: rv_data::cue::CompletionTargetType::from(cue.completion_target_type) as i32,
+        completion_target_uuid: cue.completion_target_uuid.map(|uuid| rv_data::Uuid { string: uuid.to_string() }),
+        completion_action_type: rv_data::cue::CompletionActionType::from(cue.completion_action_type) as i32,
+        completion_action_uuid: cue.completion_action_uuid.map(|uuid| rv_data::Uuid { string: uuid.to_string() }),
+        trigger_time: Some(rv_data::cue::TimecodeTime { time: 0.0 }),
+        hot_key: cue.hot_key.map(std::convert::Into::into),
+        actions,
+        pending_imports: Vec::new(),
+        is_enabled: cue.enabled,
+        completion_time: cue.completion_time,
+    }
+}
 
-            let text_element_shadow = text_element.shadow;
+/// Converts a data model `Action` into a protobuf `Action`. A slide action
+/// paired with a decoded `source` slide action keeps the source's other fields.
+#[allow(clippy::too_many_lines)] // Complex protobuf struct assembly
+pub(crate) fn convert_action_to_rv_data(action: dm::Action, source: Option<&rv_data::Action>) -> rv_data::Action {
+    if let (dm::Action::Slide { .. }, Some(source)) = (&action, source) {
+        if presentation_slide(source).is_some() {
+            return overlay_slide_action(source.clone(), action);
+        }
+    }
 
-            let graphics_element = rv_data::graphics::Element {
-                uuid: Some(rv_data::Uuid { string: element_uuid.clone() }),
-                name: "Text Element".to_string(),
-                bounds: Some(rv_data::graphics::Rect {
-                    origin: Some(rv_data::graphics::Point {
-                        x: text_element.bounds.as_ref().map_or(0.0, |b| b.origin.x),
-                        y: text_element.bounds.as_ref().map_or(0.0, |b| b.origin.y),
-                    }),
-                    size: Some(rv_data::graphics::Size {
-                        width: text_element.bounds.as_ref().map_or(1920.0, |b| b.size.width),
-                        height: text_element.bounds.as_ref().map_or(1080.0, |b| b.size.height),
-                    }),
-                }),
-                rotation: 0.0,
-                opacity: 1.0,
-                locked: false,
-                aspect_ratio_locked: false,
-                path: Some(rv_data::graphics::Path {
-                    closed: true,
-                    points: vec![
-                        rv_data::graphics::path::BezierPoint {
-                            point: Some(rv_data::graphics::Point { x: 0.0, y: 0.0 }),
-                            q0: Some(rv_data::graphics::Point { x: 0.0, y: 0.0 }),
-                            q1: Some(rv_data::graphics::Point { x: 0.0, y: 0.0 }),
-                            curved: false,
-                        },
-                        rv_data::graphics::path::BezierPoint {
-                            point: Some(rv_data::graphics::Point { x: 1.0, y: 0.0 }),
-                            q0: Some(rv_data::graphics::Point { x: 1.0, y: 0.0 }),
-                            q1: Some(rv_data::graphics::Point { x: 1.0, y: 0.0 }),
-                            curved: false,
-                        },
-                        rv_data::graphics::path::BezierPoint {
-                            point: Some(rv_data::graphics::Point { x: 1.0, y: 1.0 }),
-                            q0: Some(rv_data::graphics::Point { x: 1.0, y: 1.0 }),
-                            q1: Some(rv_data::graphics::Point { x: 1.0, y: 1.0 }),
-                            curved: false,
-                        },
-                        rv_data::graphics::path::BezierPoint {
-                            point: Some(rv_data::graphics::Point { x: 0.0, y: 1.0 }),
-                            q0: Some(rv_data::graphics::Point { x: 0.0, y: 1.0 }),
-                            q1: Some(rv_data::graphics::Point { x: 0.0, y: 1.0 }),
-                            curved: false,
+    match action {
+        dm::Action::Slide { uuid, name, slide, delay_time, duration, enabled, layer_identification } => {
+            // Create rv_data::Action for the slide
+            rv_data::Action {
+                uuid: Some(rv_data::Uuid { string: uuid.to_string() }),
+                name,
+                label: None,
+                delay_time,
+                old_type: None,
+                is_enabled: enabled,
+                layer_identification: layer_identification.map(std::convert::Into::into),
+                duration,
+                r#type: rv_data::action::ActionType::PresentationSlide as i32,
+                action_type_data: Some(rv_data::action::ActionTypeData::Slide(
+                    rv_data::action::SlideType {
+                        slide: Some(rv_data::action::slide_type::Slide::Presentation(convert_slide_to_rv_data(slide)))
+                    }
+                )),
+            }
+        },
+        dm::Action::Clear { target_layer, content_destination: _ } => {
+            rv_data::Action {
+                uuid: Some(rv_data::Uuid { string: Uuid::new_v4().to_string() }),
+                name: "Clear".to_string(),
+                label: None,
+                delay_time: 0.0,
+                old_type: None,
+                is_enabled: true,
+                layer_identification: None,
+                duration: 0.0,
+                r#type: rv_data::action::ActionType::Clear as i32,
+                action_type_data: Some(rv_data::action::ActionTypeData::Clear(rv_data::action::ClearType {
+                    target_layer,
+                    content_destination: rv_data::action::ContentDestination::Global as i32,
+                })),
+            }
+        },
+        dm::Action::AudienceLook { name, uuid, parameter_name } => {
+            rv_data::Action {
+                uuid: Some(rv_data::Uuid { string: uuid.to_string() }),
+                name,
+                label: None,
+                delay_time: 0.0,
+                old_type: None,
+                is_enabled: true,
+                layer_identification: None,
+                duration: 0.0,
+                r#type: rv_data::action::ActionType::AudienceLook as i32,
+                action_type_data: Some(rv_data::action::ActionTypeData::AudienceLook(
+                    rv_data::action::AudienceLookType {
+                        identification: Some(rv_data::CollectionElementType {
+                            parameter_uuid: Some(rv_data::Uuid { string: uuid.to_string() }),
+                            parameter_name,
+                            parent_collection: None,
+                        }),
+                    }
+                )),
+            }
+        },
+        dm::Action::Media {
+            uuid,
+            name,
+            source,
+            fit,
+            opacity: _,
+            volume,
+            delay_time,
+            duration,
+            enabled,
+        } => {
+            let mut action = rv_data::Action::default();
+            action.uuid = Some(uuid.into());
+            action.name = name;
+            action.delay_time = delay_time;
+            action.duration = duration;
+            action.is_enabled = enabled;
+            action.r#type = rv_data::action::ActionType::Media as i32;
+            let media_type = match &source {
+                dm::MediaSource::File(path) if is_video_file(path) => Some(rv_data::action::media_type::MediaType::Video(
+                    rv_data::action::media_type::Video::default(),
+                )),
+                dm::MediaSource::File(_) => Some(rv_data::action::media_type::MediaType::Image(
+                    rv_data::action::media_type::Image {},
+                )),
+                _ => None,
+            };
+            action.action_type_data = Some(rv_data::action::ActionTypeData::Media(
+                rv_data::action::MediaType {
+                    transition_duration: 0.0,
+                    selected_effect_preset_uuid: None,
+                    transition: None,
+                    effects: vec![],
+                    element: Some(rv_data::Media {
+                        uuid: Some(uuid.into()),
+                        url: match &source {
+                            dm::MediaSource::File(path) => Some(file_url(path)),
+                            _ => None,
                         },
-                    ],
-                    shape: Some(rv_data::graphics::path::Shape {
-                        r#type: rv_data::graphics::path::shape::Type::Rectangle as i32,
-                        additional_data: None,
+                        metadata: None,
+                        type_properties: Some(match source {
+                            dm::MediaSource::File(path) if is_video_file(&path) => rv_data::media::TypeProperties::Video(
+                                rv_data::media::VideoTypeProperties {
+                                    drawing: Some(rv_data::media::DrawingProperties {
+                                        scale_behavior: match fit {
+                                            dm::MediaFit::Scale => rv_data::media::ScaleBehavior::Fit as i32,
+                                            dm::MediaFit::Stretch => rv_data::media::ScaleBehavior::Stretch as i32,
+                                            dm::MediaFit::Center => rv_data::media::ScaleBehavior::Fill as i32,
+                                        },
+                                        is_blurred: false,
+                                        scale_alignment: rv_data::media::ScaleAlignment::MiddleCenter as i32,
+                                        flipped_horizontally: false,
+                                        flipped_vertically: false,
+                                        natural_size: None,
+                                        custom_image_rotation: 0.0,
+                                        custom_image_bounds: None,
+                                        custom_image_aspect_locked: true,
+                                        alpha_inverted: false,
+                                        native_rotation: rv_data::media::drawing_properties::NativeRotationType::RotateStandard as i32,
+                                        selected_effect_preset_uuid: None,
+                                        effects: vec![],
+                                        crop_enable: false,
+                                        crop_insets: Some(rv_data::graphics::EdgeInsets {
+                                            left: 0.0,
+                                            right: 0.0,
+                                            top: 0.0,
+                                            bottom: 0.0,
+                                        }),
+                                        alpha_type: rv_data::AlphaType::Straight as i32,
+                                    }),
+                                    audio: Some(rv_data::media::AudioProperties {
+                                        volume: f64::from(volume),
+                                        audio_channels: vec![],
+                                        is_custom_mapping: false,
+                                    }),
+                                    transport: None,
+                                    video: None,
+                                    file: Some(rv_data::FileProperties {
+                                        local_url: Some(file_url(&path)),
+                                        remote_properties: None,
+                                    }),
+                                }
+                            ),
+                            dm::MediaSource::File(path) => rv_data::media::TypeProperties::Image(
+                                rv_data::media::ImageTypeProperties {
+                                    drawing: Some(rv_data::media::DrawingProperties {
+                                        scale_behavior: match fit {
+                                            dm::MediaFit::Scale => rv_data::media::ScaleBehavior::Fit as i32,
+                                            dm::MediaFit::Stretch => rv_data::media::ScaleBehavior::Stretch as i32,
+                                            dm::MediaFit::Center => rv_data::media::ScaleBehavior::Fill as i32,
+                                        },
+                                        is_blurred: false,
+                                        scale_alignment: rv_data::media::ScaleAlignment::MiddleCenter as i32,
+                                        flipped_horizontally: false,
+                                        flipped_vertically: false,
+                                        natural_size: None,
+                                        custom_image_rotation: 0.0,
+                                        custom_image_bounds: None,
+                                        custom_image_aspect_locked: true,
+                                        alpha_inverted: false,
+                                        native_rotation: rv_data::media::drawing_properties::NativeRotationType::RotateStandard as i32,
+                                        selected_effect_preset_uuid: None,
+                                        effects: vec![],
+                                        crop_enable: false,
+                                        crop_insets: Some(rv_data::graphics::EdgeInsets {
+                                            left: 0.0,
+                                            right: 0.0,
+                                            top: 0.0,
+                                            bottom: 0.0,
+                                        }),
+                                        alpha_type: rv_data::AlphaType::Straight as i32,
+                                    }),
+                                    file: Some(rv_data::FileProperties {
+                                        local_url: Some(file_url(&path)),
+                                        remote_properties: None,
+                                    }),
+                                }
+                            ),
+                            dm::MediaSource::VideoInput { input_id: _, input_name: _ } => rv_data::media::TypeProperties::LiveVideo(
+                                rv_data::media::LiveVideoTypeProperties {
+                                    drawing: Some(rv_data::media::DrawingProperties {
+                                        scale_behavior: match fit {
+                                            dm::MediaFit::Scale => rv_data::media::ScaleBehavior::Fit as i32,
+                                            dm::MediaFit::Stretch => rv_data::media::ScaleBehavior::Stretch as i32,
+                                            dm::MediaFit::Center => rv_data::media::ScaleBehavior::Fill as i32,
+                                        },
+                                        is_blurred: false,
+                                        scale_alignment: rv_data::media::ScaleAlignment::MiddleCenter as i32,
+                                        flipped_horizontally: false,
+                                        flipped_vertically: false,
+                                        natural_size: None,
+                                        custom_image_rotation: 0.0,
+                                        custom_image_bounds: None,
+                                        custom_image_aspect_locked: true,
+                                        alpha_inverted: false,
+                                        native_rotation: rv_data::media::drawing_properties::NativeRotationType::RotateStandard as i32,
+                                        selected_effect_preset_uuid: None,
+                                        effects: vec![],
+                                        crop_enable: false,
+                                        crop_insets: Some(rv_data::graphics::EdgeInsets {
+                                            left: 0.0,
+                                            right: 0.0,
+                                            top: 0.0,
+                                            bottom: 0.0,
+                                        }),
+                                        alpha_type: rv_data::AlphaType::Straight as i32,
+                                    }),
+                                    audio: Some(rv_data::media::AudioProperties {
+                                        volume: f64::from(volume),
+                                        audio_channels: vec![],
+                                        is_custom_mapping: false,
+                                    }),
+                                    live_video: Some(rv_data::media::LiveVideoProperties {
+                                        video_device: None,
+                                        audio_device: None,
+                                        live_video_index: 0,
+                                    }),
+                                }
+                            ),
+                            dm::MediaSource::Url(url) => rv_data::media::TypeProperties::WebContent(
+                                rv_data::media::WebContentTypeProperties {
+                                    drawing: Some(rv_data::media::DrawingProperties {
+                                        scale_behavior: match fit {
+                                            dm::MediaFit::Scale => rv_data::media::ScaleBehavior::Fit as i32,
+                                            dm::MediaFit::Stretch => rv_data::media::ScaleBehavior::Stretch as i32,
+                                            dm::MediaFit::Center => rv_data::media::ScaleBehavior::Fill as i32,
+                                        },
+                                        is_blurred: false,
+                                        scale_alignment: rv_data::media::ScaleAlignment::MiddleCenter as i32,
+                                        flipped_horizontally: false,
+                                        flipped_vertically: false,
+                                        natural_size: None,
+                                        custom_image_rotation: 0.0,
+                                        custom_image_bounds: None,
+                                        custom_image_aspect_locked: true,
+                                        alpha_inverted: false,
+                                        native_rotation: rv_data::media::drawing_properties::NativeRotationType::RotateStandard as i32,
+                                        selected_effect_preset_uuid: None,
+                                        effects: vec![],
+                                        crop_enable: false,
+                                        crop_insets: Some(rv_data::graphics::EdgeInsets {
+                                            left: 0.0,
+                                            right: 0.0,
+                                            top: 0.0,
+                                            bottom: 0.0,
+                                        }),
+                                        alpha_type: rv_data::AlphaType::Straight as i32,
+                                    }),
+                                    url: Some(rv_data::Url {
+                                        platform: rv_data::url::Platform::Web as i32,
+                                        storage: Some(rv_data::url::Storage::AbsoluteString(url)),
+                                        relative_file_path: None,
+                                    }),
+                                }
+                            ),
+                        }),
                     }),
-                }),
-                fill: Some(rv_data::graphics::Fill {
-                    enable: false,
-                    #[allow(clippy::cast_possible_truncation)] // color values are 0.0..=1.0
-                    fill_type: Some(rv_data::graphics::fill::FillType::Color(rv_data::Color {
-                        red: text_element.color.red as f32,
-                        green: text_element.color.green as f32,
-                        blue: text_element.color.blue as f32,
-                        alpha: 1.0,
-                    })),
-                }),
-                stroke: Some(graphics::Stroke {
-                    enable: false,
-                    width: 3.0,
-                    color: Some(rv_data::Color {
-                        red: 1.0,
-                        green: 1.0,
-                        blue: 1.0,
-                        alpha: 1.0,
+                    layer_type: rv_data::action::LayerType::Background as i32,
+                    always_retrigger: false,
+                    markers: vec![],
+                    media_type,
+                }
+            ));
+            action
+        },
+        dm::Action::Macro { uuid, name, parameter_uuid, parameter_name, parent_collection_uuid, parent_collection_name, delay_time, duration, enabled } => {
+            let mut action = rv_data::Action::default();
+            action.uuid = Some(uuid.into());
+            action.name = name;
+            action.delay_time = delay_time;
+            action.duration = duration;
+            action.is_enabled = enabled;
+            action.r#type = rv_data::action::ActionType::Macro as i32;
+            action.action_type_data = Some(rv_data::action::ActionTypeData::Macro(
+                rv_data::action::MacroType {
+                    identification: Some(rv_data::CollectionElementType {
+                        parameter_uuid: Some(rv_data::Uuid { string: parameter_uuid.to_string() }),
+                        parameter_name,
+                        parent_collection: parent_collection_uuid.map(|uuid| {
+                            Box::new(rv_data::CollectionElementType {
+                                parameter_uuid: Some(rv_data::Uuid { string: uuid.to_string() }),
+                                parameter_name: parent_collection_name.unwrap_or_else(|| "Default Collection".to_string()),
+                                parent_collection: None,
+                            })
+                        }),
                     }),
-                    pattern: vec![],
-                    style: 0, // SolidLine
-                }),
-                shadow: text_element_shadow.map(std::convert::Into::into),
-                feather: Some(rv_data::graphics::Feather {
-                    style: rv_data::graphics::feather::Style::Inside as i32,
-                    radius: 0.05,
-                    enable: false,
-                }),
-                text: Some(text),
-                flip_mode: graphics::element::FlipMode::None as i32,
-                hidden: false,
-                mask: Some(graphics::element::Mask::TextLineMask(graphics::text::LineFillMask {
-                    enabled: true,
-                    height_offset: 0.0,
-                    vertical_offset: 0.0,
-                    mask_style: graphics::text::line_fill_mask::LineMaskStyle::FullWidth as i32,
-                    width_offset: 0.0,
-                    horizontal_offset: 0.0,
-                })),
-            };
-            elements.push(rv_data::slide::Element {
-                element: Some(graphics_element),
-                build_in: None,
-                build_out: None,
-                info: 3, // Match the original value
-                reveal_type: rv_data::slide::element::TextRevealType::None as i32,
-                data_links: Vec::new(),
-                child_builds: Vec::new(),
-                reveal_from_index: 0,
-                text_scroller: text_element.text_scroller.map(|ts| rv_data::slide::element::TextScroller {
-                    should_scroll: ts.should_scroll,
-                    scroll_rate: ts.scroll_rate,
-                    should_repeat: ts.should_repeat,
-                    repeat_distance: ts.repeat_distance,
-                    scrolling_direction: match ts.scrolling_direction {
-                        dm::ScrollDirection::Left => rv_data::slide::element::text_scroller::Direction::Left as i32,
-                        dm::ScrollDirection::Right => rv_data::slide::element::text_scroller::Direction::Right as i32,
-                        dm::ScrollDirection::Up => rv_data::slide::element::text_scroller::Direction::Up as i32,
-                        dm::ScrollDirection::Down => rv_data::slide::element::text_scroller::Direction::Down as i32,
-                    },
-                    starts_off_screen: ts.starts_off_screen,
-                    fade_left: ts.fade_left,
-                    fade_right: ts.fade_right,
-                }),
-            });
+                }
+            ));
+            action
+        },
+        dm::Action::Other(action) => *action,
+    }
+}
+
+impl From<dm::CompletionTargetType> for rv_data::cue::CompletionTargetType {
+    fn from(target: dm::CompletionTargetType) -> Self {
+        match target {
+            dm::CompletionTargetType::None => Self::None,
+            dm::CompletionTargetType::Next => Self::Next,
+            dm::CompletionTargetType::Random => Self::Random,
+            dm::CompletionTargetType::Cue => Self::Cue,
+            dm::CompletionTargetType::First => Self::First,
+        }
+    }
+}
+
+impl From<dm::CompletionActionType> for rv_data::cue::CompletionActionType {
+    fn from(action: dm::CompletionActionType) -> Self {
+        match action {
+            dm::CompletionActionType::First => Self::First,
+            dm::CompletionActionType::Last => Self::Last,
+            dm::CompletionActionType::AfterAction => Self::AfterAction,
+            dm::CompletionActionType::AfterTime => Self::AfterTime,
         }
     }
+}
+
+impl From<dm::HotKey> for rv_data::HotKey {
+    fn from(hot_key: dm::HotKey) -> Self {
+        Self {
+            code: hot_key.key_code.cast_signed(),
+            control_identifier: hot_key.control_identifier,
+        }
+    }
+}
+
+impl From<dm::LayerIdentification> for rv_data::action::LayerIdentification {
+    fn from(layer: dm::LayerIdentification) -> Self {
+        Self {
+            uuid: Some(rv_data::Uuid { string: layer.uuid.to_string() }),
+            name: layer.name,
+        }
+    }
+}
+
+impl From<dm::CueGroup> for presentation::CueGroup {
+    fn from(cue_group: dm::CueGroup) -> Self {
+        let group = rv_data::Group {
+            uuid: Some(rv_data::Uuid { string: cue_group.group.uuid.to_string() }),
+            name: cue_group.group.name.clone(),
+            color: Some(cue_group.group.color.into()),
+            hot_key: cue_group.group.hot_key.map(std::convert::Into::into),
+            application_group_identifier: if cue_group.group.application_group_identifier.is_empty() {
+                None
+            } else {
+                Some(rv_data::Uuid { string: cue_group.group.application_group_identifier.clone() })
+            },
+            application_group_name: if cue_group.group.application_group_identifier.is_empty() {
+                String::new()
+            } else {
+                cue_group.group.name.clone()
+            },
+        };
+
+        Self {
+            group: Some(group),
+            cue_identifiers: cue_group.cue_identifiers.into_iter().map(|uuid| rv_data::Uuid { string: uuid.to_string() }).collect(),
+        }
+    }
+}
+
+/// Converts a data model `Slide` into the protobuf `PresentationSlide` representation.
+#[allow(clippy::too_many_lines)] // Complex protobuf struct assembly
+pub(crate) fn convert_slide_to_rv_data(mut slide: dm::Slide) -> rv_data::PresentationSlide {
+    if let Some(source) = slide.source.take() {
+        return overlay_slide(source, slide);
+    }
+
+    let mut elements = Vec::new();
+    let mut element_uuids = Vec::new();
+
+    for element in slide.base.elements {
+        let element = match element {
+            dm::Element::Text(text_element) => convert_text_element_to_rv_data(text_element),
+            dm::Element::Other(element) => *element,
+            _ => continue,
+        };
+        element_uuids.extend(element.element.as_ref().and_then(|e| e.uuid.clone()));
+        elements.push(element);
+    }
 
     let element_build_order = if elements.is_empty() {
         Vec::new()
@@ -914,14 +765,7 @@ fn convert_slide_to_rv_data(slide: dm::Slide) -> rv_data::PresentationSlide {
     let base_slide = rv_data::Slide {
         elements,
         element_build_order,
-        guidelines: slide.base.guidelines.into_iter().map(|g| rv_data::AlignmentGuide {
-            uuid: Some(rv_data::Uuid { string: Uuid::new_v4().to_string() }),
-            orientation: match g.orientation {
-                dm::GuidelineOrientation::Horizontal => rv_data::alignment_guide::GuidelineOrientation::Horizontal as i32,
-                dm::GuidelineOrientation::Vertical => rv_data::alignment_guide::GuidelineOrientation::Vertical as i32,
-            },
-            location: g.position,
-        }).collect(),
+        guidelines: slide.base.guidelines.into_iter().map(std::convert::Into::into).collect(),
         draws_background_color: slide.base.draws_background_color,
         background_color: slide.base.background_color.map(std::convert::Into::into),
         size: Some(slide.base.size.into()),
@@ -930,38 +774,300 @@ fn convert_slide_to_rv_data(slide: dm::Slide) -> rv_data::PresentationSlide {
 
     rv_data::PresentationSlide {
         base_slide: Some(base_slide),
-        notes: slide.notes.map(|note_text| {
-            rv_data::presentation_slide::Notes {
-                rtf_data: note_text.into_bytes(),
-                attributes: None,
-            }
-        }),
-        template_guidelines: slide.template_guidelines.into_iter().map(|g| rv_data::AlignmentGuide {
+        notes: slide.notes.as_deref().map(encode_notes),
+        template_guidelines: slide.template_guidelines.into_iter().map(std::convert::Into::into).collect(),
+        chord_chart: slide.chord_chart.map(std::convert::Into::into),
+        transition: slide.transition.map(std::convert::Into::into),
+    }
+}
+
+impl From<dm::Guideline> for rv_data::AlignmentGuide {
+    fn from(guideline: dm::Guideline) -> Self {
+        Self {
             uuid: Some(rv_data::Uuid { string: Uuid::new_v4().to_string() }),
-            orientation: match g.orientation {
+            orientation: match guideline.orientation {
                 dm::GuidelineOrientation::Horizontal => rv_data::alignment_guide::GuidelineOrientation::Horizontal as i32,
                 dm::GuidelineOrientation::Vertical => rv_data::alignment_guide::GuidelineOrientation::Vertical as i32,
             },
-            location: g.position,
-        }).collect(),
-        chord_chart: slide.chord_chart.map(|url| rv_data::Url {
+            location: guideline.position,
+        }
+    }
+}
+
+impl From<dm::Url> for rv_data::Url {
+    fn from(url: dm::Url) -> Self {
+        Self {
             platform: rv_data::url::Platform::Macos as i32,
             storage: Some(rv_data::url::Storage::AbsoluteString(url.url)),
             relative_file_path: None,
-        }),
-        transition: slide.transition.map(|t| rv_data::Transition {
-            duration: t.duration,
+        }
+    }
+}
+
+/// A `file://` URL for a local media file
+fn file_url(path: &std::path::Path) -> rv_data::Url {
+    rv_data::Url {
+        platform: rv_data::url::Platform::Macos as i32,
+        storage: Some(rv_data::url::Storage::AbsoluteString(crate::propresenter::media::file_url(path))),
+        relative_file_path: None,
+    }
+}
+
+/// File extensions `ProPresenter` plays as video rather than showing as an image
+const VIDEO_EXTENSIONS: &[&str] = &["mov", "mp4", "m4v", "avi", "mkv", "webm", "mpg", "mpeg", "wmv"];
+
+/// Whether a local media file is a video, going by its extension
+fn is_video_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| VIDEO_EXTENSIONS.iter().any(|video| video.eq_ignore_ascii_case(ext)))
+}
+
+/// Render ID prefix of `ProPresenter`'s built-in transitions
+const TRANSITION_RENDER_ID: &str = "com.renewedvision.transition.";
+
+/// Name of the effect variable holding a transition's direction
+const DIRECTION_VARIABLE: &str = "Direction";
+
+impl From<dm::Transition> for rv_data::Transition {
+    fn from(transition: dm::Transition) -> Self {
+        let (name, id, direction) = match transition.transition_type {
+            dm::TransitionType::Cut => ("Cut", None, None),
+            dm::TransitionType::Dissolve => ("Dissolve", Some("dissolve"), None),
+            dm::TransitionType::Push { direction } => ("Push", Some("push"), Some(direction)),
+            dm::TransitionType::Wipe { direction } => ("Wipe", Some("wipe"), Some(direction)),
+            dm::TransitionType::FadeThroughBlack => ("Fade Through Black", Some("fadethroughblack"), None),
+        };
+
+        Self {
+            duration: transition.duration,
             favorite_uuid: None,
-            effect: Some(rv_data::Effect {
+            // A cut is a transition without an effect
+            effect: id.map(|id| rv_data::Effect {
                 uuid: Some(rv_data::Uuid { string: Uuid::new_v4().to_string() }),
                 enabled: true,
-                name: "Dissolve".to_string(),
-                render_id: "com.renewedvision.transition.dissolve".to_string(),
-                behavior_description: "Dissolve transition".to_string(),
+                name: name.to_string(),
+                render_id: format!("{TRANSITION_RENDER_ID}{id}"),
+                behavior_description: format!("{name} transition"),
                 category: "Standard".to_string(),
-                variables: Vec::new(),
+                variables: direction.as_ref().map(direction_variable).into_iter().collect(),
             }),
+        }
+    }
+}
+
+/// The effect variable for a push or wipe direction
+fn direction_variable(direction: &dm::Direction) -> rv_data::effect::EffectVariable {
+    use rv_data::effect::effect_variable::{self, effect_direction::EffectDirection};
+
+    let direction = match direction {
+        dm::Direction::Left => EffectDirection::Left,
+        dm::Direction::Right => EffectDirection::Right,
+        dm::Direction::Up => EffectDirection::Top,
+        dm::Direction::Down => EffectDirection::Bottom,
+    };
+    let available = [EffectDirection::Left, EffectDirection::Right, EffectDirection::Top, EffectDirection::Bottom];
+    rv_data::effect::EffectVariable {
+        name: DIRECTION_VARIABLE.to_string(),
+        description: String::new(),
+        r#type: Some(effect_variable::Type::Direction(effect_variable::EffectDirection {
+            direction: direction as i32,
+            default_direction: EffectDirection::Left as i32,
+            available_directions: available.iter().map(|d| *d as u32).sum(),
+        })),
+    }
+}
+
+/// Converts a data model `TextElement` into a protobuf slide element.
+#[allow(clippy::too_many_lines)] // Complex protobuf struct assembly
+fn convert_text_element_to_rv_data(text_element: dm::TextElement) -> rv_data::slide::Element {
+    use rv_data::graphics;
+    use crate::propresenter::rtf::text_to_rtf_bytes;
+
+    let element_uuid = Uuid::new_v4().to_string();
+    
+    // Use proper RTF conversion that handles superscripts
+    let rtf_data = text_to_rtf_bytes(&text_element.content);
+
+    let text = graphics::Text {
+        attributes: Some(graphics::text::Attributes {
+            font: Some(text_element.font.into()),
+            capitalization: graphics::text::attributes::Capitalization::None as i32,
+            underline_style: None,
+            underline_color: None,
+            paragraph_style: Some(text_element.paragraph_style.into()),
+            kerning: 0.0,
+            superscript: 0,
+            strikethrough_style: None,
+            strikethrough_color: None,
+            stroke_width: 0.0,
+            stroke_color: Some(rv_data::Color {
+                red: 1.0,
+                green: 1.0,
+                blue: 1.0,
+                alpha: 1.0,
+            }),
+            custom_attributes: text_element.custom_attributes.into_iter().map(std::convert::Into::into).collect(),
+            background_color: None,
+            ligature_style: graphics::text::attributes::LigatureStyle::Default as i32,
+            fill: Some(graphics::text::attributes::Fill::TextSolidFill(text_element.color.into())),
+        }),
+        shadow: text_element.shadow.map(std::convert::Into::into),
+        rtf_data,
+        vertical_alignment: graphics::text::VerticalAlignment::Middle as i32,
+        scale_behavior: graphics::text::ScaleBehavior::ScaleFontUpDown as i32,
+        margins: Some(graphics::EdgeInsets {
+            left: 0.0,
+            right: 0.0,
+            top: 0.0,
+            bottom: 0.0,
+        }),
+        is_superscript_standardized: true,
+        transform: graphics::text::Transform::None as i32,
+        transform_delimiter: "  •  ".to_string(),
+        chord_pro: Some(graphics::text::ChordPro {
+            enabled: false,
+            notation: graphics::text::chord_pro::Notation::Chords as i32,
+            color: Some(rv_data::Color {
+                red: 1.0,
+                green: 1.0,
+                blue: 1.0,
+                alpha: 1.0,
+            }),
+        }),
+        alternate_texts: Vec::new(),
+    };
+
+    let text_element_shadow = text_element.shadow;
+
+    let graphics_element = rv_data::graphics::Element {
+        uuid: Some(rv_data::Uuid { string: element_uuid }),
+        name: "Text Element".to_string(),
+        bounds: Some(rv_data::graphics::Rect {
+            origin: Some(rv_data::graphics::Point {
+                x: text_element.bounds.as_ref().map_or(0.0, |b| b.origin.x),
+                y: text_element.bounds.as_ref().map_or(0.0, |b| b.origin.y),
+            }),
+            size: Some(rv_data::graphics::Size {
+                width: text_element.bounds.as_ref().map_or(1920.0, |b| b.size.width),
+                height: text_element.bounds.as_ref().map_or(1080.0, |b| b.size.height),
+            }),
+        }),
+        rotation: 0.0,
+        opacity: 1.0,
+        locked: false,
+        aspect_ratio_locked: false,
+        path: Some(rv_data::graphics::Path {
+            closed: true,
+            points: vec![
+                rv_data::graphics::path::BezierPoint {
+                    point: Some(rv_data::graphics::Point { x: 0.0, y: 0.0 }),
+                    q0: Some(rv_data::graphics::Point { x: 0.0, y: 0.0 }),
+                    q1: Some(rv_data::graphics::Point { x: 0.0, y: 0.0 }),
+                    curved: false,
+                },
+                rv_data::graphics::path::BezierPoint {
+                    point: Some(rv_data::graphics::Point { x: 1.0, y: 0.0 }),
+                    q0: Some(rv_data::graphics::Point { x: 1.0, y: 0.0 }),
+                    q1: Some(rv_data::graphics::Point { x: 1.0, y: 0.0 }),
+                    curved: false,
+                },
+                rv_data::graphics::path::BezierPoint {
+                    point: Some(rv_data::graphics::Point { x: 1.0, y: 1.0 }),
+                    q0: Some(rv_data::graphics::Point { x: 1.0, y: 1.0 }),
+                    q1: Some(rv_data::graphics::Point { x: 1.0, y: 1.0 }),
+                    curved: false,
+                },
+                rv_data::graphics::path::BezierPoint {
+                    point: Some(rv_data::graphics::Point { x: 0.0, y: 1.0 }),
+                    q0: Some(rv_data::graphics::Point { x: 0.0, y: 1.0 }),
+                    q1: Some(rv_data::graphics::Point { x: 0.0, y: 1.0 }),
+                    curved: false,
+                },
+            ],
+            shape: Some(rv_data::graphics::path::Shape {
+                r#type: rv_data::graphics::path::shape::Type::Rectangle as i32,
+                additional_data: None,
+            }),
+        }),
+        fill: Some(rv_data::graphics::Fill {
+            enable: false,
+            #[allow(clippy::cast_possible_truncation)] // color values are 0.0..=1.0
+            fill_type: Some(rv_data::graphics::fill::FillType::Color(rv_data::Color {
+                red: text_element.color.red as f32,
+                green: text_element.color.green as f32,
+                blue: text_element.color.blue as f32,
+                alpha: 1.0,
+            })),
+        }),
+        stroke: Some(graphics::Stroke {
+            enable: false,
+            width: 3.0,
+            color: Some(rv_data::Color {
+                red: 1.0,
+                green: 1.0,
+                blue: 1.0,
+                alpha: 1.0,
+            }),
+            pattern: vec![],
+            style: 0, // SolidLine
+        }),
+        shadow: text_element_shadow.map(std::convert::Into::into),
+        feather: Some(rv_data::graphics::Feather {
+            style: rv_data::graphics::feather::Style::Inside as i32,
+            radius: 0.05,
+            enable: false,
         }),
+        text: Some(text),
+        flip_mode: graphics::element::FlipMode::None as i32,
+        hidden: false,
+        mask: Some(graphics::element::Mask::TextLineMask(graphics::text::LineFillMask {
+            enabled: true,
+            height_offset: 0.0,
+            vertical_offset: 0.0,
+            mask_style: graphics::text::line_fill_mask::LineMaskStyle::FullWidth as i32,
+            width_offset: 0.0,
+            horizontal_offset: 0.0,
+        })),
+    };
+    rv_data::slide::Element {
+        element: Some(graphics_element),
+        build_in: None,
+        build_out: None,
+        info: 3, // Match the original value
+        reveal_type: rv_data::slide::element::TextRevealType::None as i32,
+        data_links: Vec::new(),
+        child_builds: Vec::new(),
+        reveal_from_index: 0,
+        text_scroller: text_element.text_scroller.map(std::convert::Into::into),
+    }
+}
+
+impl From<dm::Font> for rv_data::Font {
+    fn from(font: dm::Font) -> Self {
+        Self {
+            name: font.name,
+            size: font.size,
+            italic: font.italic,
+            bold: font.bold,
+            family: font.family,
+            face: font.face,
+        }
+    }
+}
+
+impl From<dm::TextScroller> for rv_data::slide::element::TextScroller {
+    fn from(scroller: dm::TextScroller) -> Self {
+        Self {
+            should_scroll: scroller.should_scroll,
+            scroll_rate: scroller.scroll_rate,
+            should_repeat: scroller.should_repeat,
+            repeat_distance: scroller.repeat_distance,
+            scrolling_direction: rv_data::slide::element::text_scroller::Direction::from(scroller.scrolling_direction) as i32,
+            starts_off_screen: scroller.starts_off_screen,
+            fade_left: scroller.fade_left,
+            fade_right: scroller.fade_right,
+        }
     }
 }
 
@@ -1036,6 +1142,7 @@ impl From<dm::CustomAttribute> for rv_data::graphics::text::attributes::CustomAt
                 dm::CustomAttributeType::Capitalization(cap) => Attribute::Capitalization(cap as i32),
                 dm::CustomAttributeType::OriginalFontSize(size) => Attribute::OriginalFontSize(size),
                 dm::CustomAttributeType::FontScaleFactor(factor) => Attribute::FontScaleFactor(factor),
+                dm::CustomAttributeType::Chord(chord) => Attribute::Chord(chord),
                 // Other attribute types would go here - simplified for now
                 _ => Attribute::Capitalization(0),
             }),
@@ -1058,4 +1165,881 @@ impl From<dm::ScrollDirection> for rv_data::slide::element::text_scroller::Direc
             dm::ScrollDirection::Down => Self::Down,
         }
     }
-}
\ No newline at end of file
+}
+// Protobuf → data model conversions
+//
+// Decoded presentations, cues and slides keep the protobuf they came from as
+// `source`. Converting them back starts from that source and only writes the
+// fields that differ from what was decoded, so anything the data model
+// doesn't represent (media, build order, RTF styling) survives the round trip.
+
+/// Parses a protobuf UUID, using the nil UUID if it's missing or malformed so
+/// decoding the same file twice gives the same result.
+fn parse_uuid(uuid: Option<&rv_data::Uuid>) -> Uuid {
+    uuid.and_then(|uuid| uuid.string.parse().ok()).unwrap_or_default()
+}
+
+/// Sets a protobuf UUID field unless it already parses to `uuid`, keeping the
+/// original spelling (case, braces) of unchanged identifiers.
+fn uuid_field(field: &mut Option<rv_data::Uuid>, uuid: Uuid) {
+    if parse_uuid(field.as_ref()) != uuid || field.is_none() {
+        *field = Some(uuid.into());
+    }
+}
+
+/// Encodes `value` into `field` unless decoding the field already gives `value`.
+fn set_if_changed<S, T: PartialEq>(field: &mut S, value: T, decode: impl FnOnce(&S) -> T, encode: impl FnOnce(T) -> S) {
+    if decode(field) != value {
+        *field = encode(value);
+    }
+}
+
+/// Encodes `items`, reusing the source entry at the same position wherever it
+/// decodes to the same item.
+fn merge_list<S, T>(source: &[S], items: Vec<T>) -> Vec<S>
+where
+    S: Clone + From<T>,
+    T: PartialEq + From<S>,
+{
+    items.into_iter().enumerate().map(|(index, item)| {
+        match source.get(index) {
+            Some(original) if T::from(original.clone()) == item => original.clone(),
+            _ => item.into(),
+        }
+    }).collect()
+}
+
+const fn timestamp_to_datetime(timestamp: &rv_data::Timestamp) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(timestamp.seconds, timestamp.nanos.cast_unsigned())
+}
+
+const fn datetime_to_timestamp(datetime: DateTime<Utc>) -> rv_data::Timestamp {
+    rv_data::Timestamp {
+        seconds: datetime.timestamp(),
+        nanos: datetime.timestamp_subsec_nanos().cast_signed(),
+    }
+}
+
+impl From<rv_data::Presentation> for dm::Presentation {
+    fn from(presentation: rv_data::Presentation) -> Self {
+        let source = Box::new(presentation.clone());
+
+        Self {
+            name: presentation.name,
+            path: None,
+            uuid: parse_uuid(presentation.uuid.as_ref()),
+            last_used: presentation.last_date_used.as_ref().and_then(timestamp_to_datetime),
+            last_modified: presentation.last_modified_date.as_ref().and_then(timestamp_to_datetime),
+            category: presentation.category,
+            notes: presentation.notes,
+            ccli: presentation.ccli.map(std::convert::Into::into),
+            bible_reference: presentation.bible_reference.map(std::convert::Into::into),
+            cues: presentation.cues.into_iter().map(std::convert::Into::into).collect(),
+            cue_groups: presentation.cue_groups.into_iter().map(std::convert::Into::into).collect(),
+            arrangements: presentation.arrangements.into_iter().map(std::convert::Into::into).collect(),
+            timeline: presentation.timeline.map(std::convert::Into::into),
+            application_info: presentation.application_info.map(std::convert::Into::into),
+            music_key: presentation.music_key,
+            music: None,
+            slide_show: presentation.slide_show.map(|presentation::SlideShow::SlideShowDuration(duration)| dm::SlideShow {
+                playback_mode: dm::PlaybackMode::Normal,
+                loop_enabled: false,
+                transition_duration: 0.0,
+                background_color: None,
+                background_image: None,
+                background_video: None,
+                transition: None,
+                slide_duration: duration,
+                loop_presentation: false,
+            }),
+            source: Some(source),
+        }
+    }
+}
+
+/// Writes a presentation's modelled fields over the protobuf it was decoded from.
+///
+/// Application info and music aren't modelled and always come from the
+/// source, as do the timeline's legacy cues and audio action.
+fn overlay_presentation(mut source: rv_data::Presentation, presentation: dm::Presentation) -> rv_data::Presentation {
+    uuid_field(&mut source.uuid, presentation.uuid);
+    source.name = presentation.name;
+    set_if_changed(
+        &mut source.last_date_used,
+        presentation.last_used,
+        |timestamp| timestamp.as_ref().and_then(timestamp_to_datetime),
+        |datetime| datetime.map(datetime_to_timestamp),
+    );
+    set_if_changed(
+        &mut source.last_modified_date,
+        presentation.last_modified,
+        |timestamp| timestamp.as_ref().and_then(timestamp_to_datetime),
+        |datetime| datetime.map(datetime_to_timestamp),
+    );
+    source.category = presentation.category;
+    source.notes = presentation.notes;
+
+    let artwork = source.ccli.as_ref().map(|ccli| ccli.artwork.clone()).unwrap_or_default();
+    set_if_changed(
+        &mut source.ccli,
+        presentation.ccli,
+        |ccli| ccli.clone().map(std::convert::Into::into),
+        |ccli| ccli.map(|ccli| presentation::Ccli { artwork, ..ccli.into() }),
+    );
+    set_if_changed(
+        &mut source.bible_reference,
+        presentation.bible_reference,
+        |reference| reference.clone().map(std::convert::Into::into),
+        |reference| reference.map(std::convert::Into::into),
+    );
+
+    source.cues = presentation.cues.into_iter().map(convert_cue_to_rv_data).collect();
+    source.cue_groups = merge_list(&source.cue_groups, presentation.cue_groups);
+    source.arrangements = merge_list(&source.arrangements, presentation.arrangements);
+
+    match (source.timeline.as_mut(), presentation.timeline) {
+        (Some(timeline), Some(modified)) => {
+            timeline.duration = modified.duration;
+            timeline.r#loop = modified.loop_enabled;
+            timeline.timecode_enable = modified.timecode_enabled;
+            timeline.timecode_offset = modified.timecode_offset;
+            timeline.cues_v2 = merge_list(&timeline.cues_v2, modified.cues);
+        }
+        (None, Some(modified)) => source.timeline = Some(modified.into()),
+        (_, None) => source.timeline = None,
+    }
+
+    source.music_key = presentation.music_key;
+    source.slide_show = presentation.slide_show.map(|s| presentation::SlideShow::SlideShowDuration(s.slide_duration));
+    source
+}
+
+impl From<rv_data::ApplicationInfo> for dm::ApplicationInfo {
+    fn from(info: rv_data::ApplicationInfo) -> Self {
+        let version = info.application_version.unwrap_or_default();
+
+        Self {
+            name: rv_data::application_info::Application::try_from(info.application)
+                .map_or_else(|_| String::new(), |application| application.as_str_name().to_string()),
+            version: format!("{}.{}.{}", version.major_version, version.minor_version, version.patch_version),
+            build_number: version.build,
+            platform: rv_data::application_info::Platform::try_from(info.platform)
+                .map_or_else(|_| String::new(), |platform| platform.as_str_name().to_string()),
+        }
+    }
+}
+
+impl From<rv_data::Cue> for dm::Cue {
+    fn from(cue: rv_data::Cue) -> Self {
+        let source = cue.clone();
+
+        Self {
+            uuid: parse_uuid(cue.uuid.as_ref()),
+            name: cue.name,
+            actions: cue.actions.into_iter().map(std::convert::Into::into).collect(),
+            enabled: cue.is_enabled,
+            hot_key: cue.hot_key.map(std::convert::Into::into),
+            completion_target_type: completion_target_type(cue.completion_target_type),
+            completion_target_uuid: cue.completion_target_uuid.as_ref().map(|uuid| parse_uuid(Some(uuid))),
+            completion_action_type: completion_action_type(cue.completion_action_type),
+            completion_action_uuid: cue.completion_action_uuid.as_ref().map(|uuid| parse_uuid(Some(uuid))),
+            completion_time: cue.completion_time,
+            source: Some(source),
+        }
+    }
+}
+
+/// Writes a cue's modelled fields over the protobuf it was decoded from.
+fn overlay_cue(mut source: rv_data::Cue, cue: dm::Cue) -> rv_data::Cue {
+    uuid_field(&mut source.uuid, cue.uuid);
+    source.name = cue.name;
+    set_if_changed(
+        &mut source.completion_target_type,
+        cue.completion_target_type,
+        |target| completion_target_type(*target),
+        |target| rv_data::cue::CompletionTargetType::from(target) as i32,
+    );
+    set_if_changed(
+        &mut source.completion_target_uuid,
+        cue.completion_target_uuid,
+        |uuid| uuid.as_ref().map(|uuid| parse_uuid(Some(uuid))),
+        |uuid| uuid.map(std::convert::Into::into),
+    );
+    set_if_changed(
+        &mut source.completion_action_type,
+        cue.completion_action_type,
+        |action| completion_action_type(*action),
+        |action| rv_data::cue::CompletionActionType::from(action) as i32,
+    );
+    set_if_changed(
+        &mut source.completion_action_uuid,
+        cue.completion_action_uuid,
+        |uuid| uuid.as_ref().map(|uuid| parse_uuid(Some(uuid))),
+        |uuid| uuid.map(std::convert::Into::into),
+    );
+    set_if_changed(
+        &mut source.hot_key,
+        cue.hot_key,
+        |hot_key| hot_key.clone().map(std::convert::Into::into),
+        |hot_key| hot_key.map(std::convert::Into::into),
+    );
+
+    let actions = std::mem::take(&mut source.actions);
+    source.actions = cue.actions.into_iter().enumerate()
+        .map(|(index, action)| convert_action_to_rv_data(action, actions.get(index)))
+        .collect();
+    source.is_enabled = cue.enabled;
+    source.completion_time = cue.completion_time;
+    source
+}
+
+fn completion_target_type(value: i32) -> dm::CompletionTargetType {
+    use rv_data::cue::CompletionTargetType;
+
+    match CompletionTargetType::try_from(value).unwrap_or(CompletionTargetType::None) {
+        CompletionTargetType::None => dm::CompletionTargetType::None,
+        CompletionTargetType::Next => dm::CompletionTargetType::Next,
+        CompletionTargetType::Random => dm::CompletionTargetType::Random,
+        CompletionTargetType::Cue => dm::CompletionTargetType::Cue,
+        CompletionTargetType::First => dm::CompletionTargetType::First,
+    }
+}
+
+fn completion_action_type(value: i32) -> dm::CompletionActionType {
+    use rv_data::cue::CompletionActionType;
+
+    match CompletionActionType::try_from(value).unwrap_or(CompletionActionType::First) {
+        CompletionActionType::First => dm::CompletionActionType::First,
+        CompletionActionType::Last => dm::CompletionActionType::Last,
+        CompletionActionType::AfterAction => dm::CompletionActionType::AfterAction,
+        CompletionActionType::AfterTime => dm::CompletionActionType::AfterTime,
+    }
+}
+
+impl From<rv_data::HotKey> for dm::HotKey {
+    fn from(hot_key: rv_data::HotKey) -> Self {
+        Self {
+            key_code: hot_key.code.cast_unsigned(),
+            modifiers: 0,
+            control_identifier: hot_key.control_identifier,
+        }
+    }
+}
+
+impl From<presentation::CueGroup> for dm::CueGroup {
+    fn from(cue_group: presentation::CueGroup) -> Self {
+        let group = cue_group.group.unwrap_or_default();
+
+        Self {
+            group: dm::Group {
+                uuid: parse_uuid(group.uuid.as_ref()),
+                name: group.name,
+                color: group.color.map_or(dm::Color { red: 0.0, green: 0.0, blue: 0.0, alpha: 0.0 }, std::convert::Into::into),
+                hot_key: group.hot_key.map(std::convert::Into::into),
+                application_group_identifier: group.application_group_identifier.map(|uuid| uuid.string).unwrap_or_default(),
+            },
+            cue_identifiers: cue_group.cue_identifiers.iter().map(|uuid| parse_uuid(Some(uuid))).collect(),
+        }
+    }
+}
+
+/// Returns the presentation slide carried by a slide action.
+const fn presentation_slide(action: &rv_data::Action) -> Option<&rv_data::PresentationSlide> {
+    match &action.action_type_data {
+        Some(rv_data::action::ActionTypeData::Slide(rv_data::action::SlideType {
+            slide: Some(rv_data::action::slide_type::Slide::Presentation(slide)),
+        })) => Some(slide),
+        _ => None,
+    }
+}
+
+impl From<rv_data::Action> for dm::Action {
+    fn from(action: rv_data::Action) -> Self {
+        let Some(slide) = presentation_slide(&action).cloned() else {
+            return Self::Other(Box::new(action));
+        };
+
+        Self::Slide {
+            uuid: parse_uuid(action.uuid.as_ref()),
+            name: action.name,
+            slide: slide.into(),
+            delay_time: action.delay_time,
+            duration: action.duration,
+            enabled: action.is_enabled,
+            layer_identification: action.layer_identification.map(std::convert::Into::into),
+        }
+    }
+}
+
+impl From<rv_data::action::LayerIdentification> for dm::LayerIdentification {
+    fn from(layer: rv_data::action::LayerIdentification) -> Self {
+        Self {
+            uuid: parse_uuid(layer.uuid.as_ref()),
+            name: layer.name,
+        }
+    }
+}
+
+/// Writes a slide action's modelled fields over the protobuf it was decoded from.
+fn overlay_slide_action(mut source: rv_data::Action, action: dm::Action) -> rv_data::Action {
+    let dm::Action::Slide { uuid, name, slide, delay_time, duration, enabled, layer_identification } = action else {
+        return source;
+    };
+
+    uuid_field(&mut source.uuid, uuid);
+    source.name = name;
+    source.delay_time = delay_time;
+    source.duration = duration;
+    source.is_enabled = enabled;
+    set_if_changed(
+        &mut source.layer_identification,
+        layer_identification,
+        |layer| layer.clone().map(std::convert::Into::into),
+        |layer| layer.map(std::convert::Into::into),
+    );
+    if let Some(rv_data::action::ActionTypeData::Slide(rv_data::action::SlideType {
+        slide: Some(rv_data::action::slide_type::Slide::Presentation(presentation_slide)),
+    })) = &mut source.action_type_data {
+        *presentation_slide = convert_slide_to_rv_data(slide);
+    }
+    source
+}
+
+impl From<rv_data::PresentationSlide> for dm::Slide {
+    fn from(slide: rv_data::PresentationSlide) -> Self {
+        let source = slide.clone();
+        let base = slide.base_slide.unwrap_or_default();
+
+        Self {
+            base: dm::BaseSlide {
+                uuid: parse_uuid(base.uuid.as_ref()),
+                elements: base.elements.into_iter().map(std::convert::Into::into).collect(),
+                element_build_order: base.element_build_order.iter().map(|uuid| parse_uuid(Some(uuid))).collect(),
+                guidelines: base.guidelines.into_iter().map(std::convert::Into::into).collect(),
+                draws_background_color: base.draws_background_color,
+                background_color: base.background_color.map(std::convert::Into::into),
+                size: decode_size(base.size.as_ref()),
+            },
+            notes: slide.notes.as_ref().map(decode_notes),
+            template_guidelines: slide.template_guidelines.into_iter().map(std::convert::Into::into).collect(),
+            chord_chart: slide.chord_chart.map(std::convert::Into::into),
+            transition: slide.transition.map(std::convert::Into::into),
+            source: Some(source),
+        }
+    }
+}
+
+fn decode_size(size: Option<&graphics::Size>) -> dm::Size {
+    size.map_or(dm::Size { width: 1920.0, height: 1080.0 }, |size| dm::Size { width: size.width, height: size.height })
+}
+
+/// Slide notes are RTF; files written by older versions of this tool have plain text.
+fn decode_notes(notes: &rv_data::presentation_slide::Notes) -> String {
+    let text = String::from_utf8_lossy(&notes.rtf_data);
+    crate::propresenter::rtf::rtf_to_text(&text).unwrap_or_else(|| text.into_owned())
+}
+
+/// Slide notes as the RTF `ProPresenter` shows in its notes panel
+fn encode_notes(text: &str) -> rv_data::presentation_slide::Notes {
+    rv_data::presentation_slide::Notes {
+        rtf_data: crate::propresenter::rtf::text_to_rtf_bytes(text),
+        attributes: None,
+    }
+}
+
+/// Writes a slide's modelled fields over the protobuf it was decoded from.
+///
+/// Text elements are matched to the source by position; unmodelled elements
+/// come back through [`dm::Element::Other`].
+fn overlay_slide(mut source: rv_data::PresentationSlide, slide: dm::Slide) -> rv_data::PresentationSlide {
+    let mut base = source.base_slide.take().unwrap_or_default();
+    uuid_field(&mut base.uuid, slide.base.uuid);
+
+    let elements = std::mem::take(&mut base.elements);
+    base.elements = slide.base.elements.into_iter().enumerate().filter_map(|(index, element)| match element {
+        dm::Element::Text(text_element) => Some(match elements.get(index) {
+            Some(original) if text_of(original).is_some() => overlay_text_element(original.clone(), text_element),
+            _ => convert_text_element_to_rv_data(text_element),
+        }),
+        dm::Element::Other(element) => Some(*element),
+        _ => None,
+    }).collect();
+
+    set_if_changed(
+        &mut base.element_build_order,
+        slide.base.element_build_order,
+        |order| order.iter().map(|uuid| parse_uuid(Some(uuid))).collect(),
+        |order| order.into_iter().map(std::convert::Into::into).collect(),
+    );
+    base.guidelines = merge_list(&base.guidelines, slide.base.guidelines);
+    base.draws_background_color = slide.base.draws_background_color;
+    set_if_changed(
+        &mut base.background_color,
+        slide.base.background_color,
+        |color| color.clone().map(std::convert::Into::into),
+        |color| color.map(std::convert::Into::into),
+    );
+    set_if_changed(
+        &mut base.size,
+        slide.base.size,
+        |size| decode_size(size.as_ref()),
+        |size| Some(size.into()),
+    );
+    source.base_slide = Some(base);
+
+    set_if_changed(
+        &mut source.notes,
+        slide.notes,
+        |notes| notes.as_ref().map(decode_notes),
+        |notes| notes.as_deref().map(encode_notes),
+    );
+    source.template_guidelines = merge_list(&source.template_guidelines, slide.template_guidelines);
+    set_if_changed(
+        &mut source.chord_chart,
+        slide.chord_chart,
+        |url| url.clone().map(std::convert::Into::into),
+        |url| url.map(std::convert::Into::into),
+    );
+    set_if_changed(
+        &mut source.transition,
+        slide.transition,
+        |transition| transition.clone().map(std::convert::Into::into),
+        |transition| transition.map(std::convert::Into::into),
+    );
+    source
+}
+
+impl From<rv_data::AlignmentGuide> for dm::Guideline {
+    fn from(guide: rv_data::AlignmentGuide) -> Self {
+        use rv_data::alignment_guide::GuidelineOrientation;
+
+        Self {
+            position: guide.location,
+            orientation: match GuidelineOrientation::try_from(guide.orientation) {
+                Ok(GuidelineOrientation::Vertical) => dm::GuidelineOrientation::Vertical,
+                _ => dm::GuidelineOrientation::Horizontal,
+            },
+        }
+    }
+}
+
+impl From<rv_data::Url> for dm::Url {
+    fn from(url: rv_data::Url) -> Self {
+        Self {
+            url: match url.storage {
+                Some(rv_data::url::Storage::AbsoluteString(url) | rv_data::url::Storage::RelativePath(url)) => url,
+                None => String::new(),
+            },
+        }
+    }
+}
+
+impl From<rv_data::Transition> for dm::Transition {
+    fn from(transition: rv_data::Transition) -> Self {
+        use rv_data::effect::effect_variable::{self, effect_direction::EffectDirection};
+
+        let effect = transition.effect.as_ref();
+        let direction = effect
+            .and_then(|effect| effect.variables.iter().find_map(|variable| match &variable.r#type {
+                Some(effect_variable::Type::Direction(direction)) => EffectDirection::try_from(direction.direction).ok(),
+                _ => None,
+            }))
+            .map_or(dm::Direction::Left, |direction| match direction {
+                EffectDirection::Right => dm::Direction::Right,
+                EffectDirection::Top => dm::Direction::Up,
+                EffectDirection::Bottom => dm::Direction::Down,
+                _ => dm::Direction::Left,
+            });
+        // Effects we don't model (e.g. third-party ones) read as a cut
+        let transition_type = match effect.map(|effect| effect.render_id.rsplit('.').next().unwrap_or_default()) {
+            Some("dissolve") => dm::TransitionType::Dissolve,
+            Some("push") => dm::TransitionType::Push { direction },
+            Some("wipe") => dm::TransitionType::Wipe { direction },
+            Some("fadethroughblack") => dm::TransitionType::FadeThroughBlack,
+            _ => dm::TransitionType::Cut,
+        };
+
+        Self { transition_type, duration: transition.duration }
+    }
+}
+
+impl From<rv_data::slide::Element> for dm::Element {
+    fn from(element: rv_data::slide::Element) -> Self {
+        if text_of(&element).is_some() {
+            Self::Text(decode_text_element(&element))
+        } else {
+            Self::Other(Box::new(element))
+        }
+    }
+}
+
+fn text_of(element: &rv_data::slide::Element) -> Option<&graphics::Text> {
+    element.element.as_ref().and_then(|element| element.text.as_ref())
+}
+
+/// Decodes the text, styling and layout of a slide text element.
+fn decode_text_element(element: &rv_data::slide::Element) -> dm::TextElement {
+    use rv_data::graphics::text::attributes::Fill;
+
+    let text = text_of(element);
+    let attributes = text.and_then(|text| text.attributes.as_ref());
+
+    dm::TextElement {
+        content: text.map(text_content).unwrap_or_default(),
+        font: attributes.and_then(|attributes| attributes.font.clone()).unwrap_or_default().into(),
+        color: match attributes.and_then(|attributes| attributes.fill.as_ref()) {
+            Some(Fill::TextSolidFill(color)) => color.clone().into(),
+            _ => dm::Color { red: 1.0, green: 1.0, blue: 1.0, alpha: 1.0 },
+        },
+        paragraph_style: attributes.and_then(|attributes| attributes.paragraph_style.clone()).map_or_else(dm::ParagraphStyle::default, std::convert::Into::into),
+        shadow: text.and_then(|text| text.shadow.clone()).map(std::convert::Into::into),
+        mask: None,
+        bounds: element.element.as_ref().and_then(|element| element.bounds.clone()).map(std::convert::Into::into),
+        custom_attributes: attributes.map(|attributes| attributes.custom_attributes.iter().filter_map(decode_custom_attribute).collect()).unwrap_or_default(),
+        text_scroller: element.text_scroller.clone().map(std::convert::Into::into),
+    }
+}
+
+fn text_content(text: &graphics::Text) -> String {
+    crate::propresenter::rtf::rtf_to_text(&String::from_utf8_lossy(&text.rtf_data)).unwrap_or_default()
+}
+
+/// Writes a text element's modelled fields over the protobuf it was decoded
+/// from. New content keeps the run formatting of the source's RTF.
+fn overlay_text_element(mut source: rv_data::slide::Element, text_element: dm::TextElement) -> rv_data::slide::Element {
+    use crate::propresenter::rtf::replace_rtf_text;
+    use rv_data::graphics::text::attributes::Fill;
+
+    let original = decode_text_element(&source);
+    if original == text_element {
+        return source;
+    }
+
+    if original.text_scroller != text_element.text_scroller {
+        source.text_scroller = text_element.text_scroller.map(std::convert::Into::into);
+    }
+    let Some(element) = source.element.as_mut() else {
+        return source;
+    };
+    if original.bounds != text_element.bounds {
+        element.bounds = text_element.bounds.map(std::convert::Into::into);
+    }
+    let Some(text) = element.text.as_mut() else {
+        return source;
+    };
+
+    if original.content != text_element.content {
+        text.rtf_data = replace_rtf_text(&text.rtf_data, &text_element.content);
+    }
+    if original.shadow != text_element.shadow {
+        text.shadow = text_element.shadow.map(std::convert::Into::into);
+    }
+
+    let attributes = text.attributes.get_or_insert_with(Default::default);
+    if original.font != text_element.font {
+        attributes.font = Some(text_element.font.into());
+    }
+    if original.color != text_element.color {
+        attributes.fill = Some(Fill::TextSolidFill(text_element.color.into()));
+    }
+    if original.paragraph_style != text_element.paragraph_style {
+        attributes.paragraph_style = Some(text_element.paragraph_style.into());
+    }
+    if original.custom_attributes != text_element.custom_attributes {
+        attributes.custom_attributes = text_element.custom_attributes.into_iter().map(std::convert::Into::into).collect();
+    }
+    source
+}
+
+impl From<rv_data::Font> for dm::Font {
+    fn from(font: rv_data::Font) -> Self {
+        Self {
+            name: font.name,
+            size: font.size,
+            bold: font.bold,
+            italic: font.italic,
+            family: font.family,
+            face: font.face,
+        }
+    }
+}
+
+impl From<rv_data::graphics::Shadow> for dm::Shadow {
+    #[allow(clippy::cast_possible_truncation)] // opacity is 0.0..=1.0
+    fn from(shadow: rv_data::graphics::Shadow) -> Self {
+        Self {
+            color: shadow.color.map_or(dm::Color { red: 0.0, green: 0.0, blue: 0.0, alpha: 1.0 }, std::convert::Into::into),
+            radius: shadow.radius,
+            offset: dm::Point { x: shadow.offset, y: 0.0 },
+            opacity: shadow.opacity as f32,
+            angle: shadow.angle,
+            style: dm::ShadowStyle::Drop,
+            enable: shadow.enable,
+        }
+    }
+}
+
+impl From<rv_data::graphics::text::attributes::Paragraph> for dm::ParagraphStyle {
+    fn from(style: rv_data::graphics::text::attributes::Paragraph) -> Self {
+        use rv_data::graphics::text::attributes::Alignment;
+
+        Self {
+            alignment: match Alignment::try_from(style.alignment) {
+                Ok(Alignment::Right) => dm::TextAlignment::Right,
+                Ok(Alignment::Center) => dm::TextAlignment::Center,
+                Ok(Alignment::Justified) => dm::TextAlignment::Justified,
+                _ => dm::TextAlignment::Left,
+            },
+            first_line_head_indent: style.first_line_head_indent,
+            head_indent: style.head_indent,
+            tail_indent: style.tail_indent,
+            line_height_multiple: style.line_height_multiple,
+            maximum_line_height: style.maximum_line_height,
+            minimum_line_height: style.minimum_line_height,
+            line_spacing: style.line_spacing,
+            paragraph_spacing: style.paragraph_spacing,
+            paragraph_spacing_before: style.paragraph_spacing_before,
+            tab_stops: style.tab_stops.into_iter().map(|tab_stop| dm::TabStop {
+                position: tab_stop.location,
+                alignment: match Alignment::try_from(tab_stop.alignment) {
+                    Ok(Alignment::Center) => dm::TabAlignment::Center,
+                    Ok(Alignment::Right) => dm::TabAlignment::Right,
+                    Ok(Alignment::Natural) => dm::TabAlignment::Decimal,
+                    _ => dm::TabAlignment::Left,
+                },
+            }).collect(),
+            default_tab_interval: style.default_tab_interval,
+            text_list: None,
+            text_lists: Vec::new(),
+        }
+    }
+}
+
+/// Decodes the custom text attributes the data model has variants for.
+fn decode_custom_attribute(attribute: &rv_data::graphics::text::attributes::CustomAttribute) -> Option<dm::CustomAttribute> {
+    use rv_data::graphics::text::attributes::custom_attribute::Attribute;
+
+    let attribute_type = match attribute.attribute.as_ref()? {
+        Attribute::OriginalFontSize(size) => dm::CustomAttributeType::OriginalFontSize(*size),
+        Attribute::FontScaleFactor(factor) => dm::CustomAttributeType::FontScaleFactor(*factor),
+        Attribute::Chord(chord) => dm::CustomAttributeType::Chord(chord.clone()),
+        _ => return None,
+    };
+    Some(dm::CustomAttribute {
+        range: attribute.range.clone().map_or(dm::Range { start: 0, end: 0 }, std::convert::Into::into),
+        attribute: attribute_type,
+    })
+}
+
+impl From<rv_data::slide::element::TextScroller> for dm::TextScroller {
+    fn from(scroller: rv_data::slide::element::TextScroller) -> Self {
+        use rv_data::slide::element::text_scroller::Direction;
+
+        Self {
+            should_scroll: scroller.should_scroll,
+            scroll_rate: scroller.scroll_rate,
+            should_repeat: scroller.should_repeat,
+            repeat_distance: scroller.repeat_distance,
+            scrolling_direction: match Direction::try_from(scroller.scrolling_direction) {
+                Ok(Direction::Right) => dm::ScrollDirection::Right,
+                Ok(Direction::Up) => dm::ScrollDirection::Up,
+                Ok(Direction::Down) => dm::ScrollDirection::Down,
+                _ => dm::ScrollDirection::Left,
+            },
+            starts_off_screen: scroller.starts_off_screen,
+            fade_left: scroller.fade_left,
+            fade_right: scroller.fade_right,
+        }
+    }
+}
+
+/// Converts a protobuf presentation into the data model, keeping the protobuf
+/// as the result's `source` so unmodified fields encode back byte-for-byte.
+pub fn convert_rv_data_to_presentation(presentation: rv_data::Presentation) -> dm::Presentation {
+    presentation.into()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+    use std::path::PathBuf;
+    use prost::Message;
+    use crate::propresenter::{deserialize::read_presentation_file, rtf, serialize::encode_presentation};
+
+    const EXAMPLES: [&str; 3] = [
+        "Titus 2v11-13 (NRSVue).pro",
+        "Tom Nametag.pro",
+        "[Hymn] Amazing Grace.pro",
+    ];
+
+    fn get_example_path(filename: &str) -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("data");
+        path.push("examples");
+        path.push("propresenter");
+        path.push(filename);
+        path
+    }
+
+    fn first_text_element(presentation: &mut dm::Presentation) -> &mut dm::TextElement {
+        presentation.cues.iter_mut()
+            .flat_map(|cue| cue.actions.iter_mut())
+            .find_map(|action| match action {
+                dm::Action::Slide { slide, .. } => slide.base.elements.iter_mut().find_map(|element| match element {
+                    dm::Element::Text(text) => Some(text),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .expect("a slide with a text element")
+    }
+
+    #[test]
+    fn test_round_trip_with_source_is_exact() {
+        for filename in EXAMPLES {
+            let original = read_presentation_file(get_example_path(filename)).unwrap();
+            let model = convert_rv_data_to_presentation(original.clone());
+            let encoded = convert_presentation_to_rv_data(model);
+
+            assert_eq!(encoded, original, "{filename} changed on round trip");
+            assert_eq!(encode_presentation(&encoded), encode_presentation(&original), "{filename} bytes changed");
+        }
+    }
+
+    /// The model with its `source` dropped, so only what it holds gets encoded.
+    fn without_source(mut model: dm::Presentation) -> dm::Presentation {
+        model.source = None;
+        for cue in &mut model.cues {
+            cue.source = None;
+        }
+        model
+    }
+
+    /// `presentation` with the fields the model doesn't carry cleared, so
+    /// encoding without `source` can be compared with the decoded file.
+    fn without_uncarried(presentation: &rv_data::Presentation) -> rv_data::Presentation {
+        // UUIDs are carried, but not their spelling
+        let mut data = encode_presentation(presentation);
+        for i in 0..data.len().saturating_sub(35) {
+            let uuid = &mut data[i..i + 36];
+            if uuid.iter().enumerate().all(|(j, b)| if matches!(j, 8 | 13 | 18 | 23) { *b == b'-' } else { b.is_ascii_hexdigit() }) {
+                uuid.make_ascii_uppercase();
+            }
+        }
+        let mut presentation = rv_data::Presentation::decode(data.as_slice()).unwrap();
+
+        // Only the app name and version are modelled
+        presentation.application_info = None;
+        presentation.background = None;
+        presentation.chord_chart = None;
+        presentation.selected_arrangement = None;
+        for group in presentation.cue_groups.iter_mut().filter_map(|group| group.group.as_mut()) {
+            group.color = None;
+            group.application_group_name.clear();
+        }
+        for cue in &mut presentation.cues {
+            cue.trigger_time = None;
+            for action in &mut cue.actions {
+                action.label = None;
+            }
+        }
+        presentation
+    }
+
+    #[test]
+    fn test_model_holds_every_field() {
+        for filename in EXAMPLES {
+            let original = read_presentation_file(get_example_path(filename)).unwrap();
+            let model = without_source(convert_rv_data_to_presentation(original.clone()));
+            let encoded = convert_presentation_to_rv_data(model);
+
+            assert_eq!(without_uncarried(&encoded), without_uncarried(&original), "{filename} changed without its source");
+        }
+    }
+
+    #[test]
+    fn test_timeline_cue_edits_are_kept() {
+        let original = read_presentation_file(get_example_path("[Hymn] Amazing Grace.pro")).unwrap();
+        let mut model = convert_rv_data_to_presentation(original);
+        let cue_uuid = model.cues[0].uuid;
+        let mut timeline = model.timeline.take().unwrap_or_else(dm::Timeline::new);
+        timeline.add_cue(dm::TimelineCue {
+            trigger_time: 12.5,
+            name: "First slide".to_string(),
+            uuid: cue_uuid,
+            action: None,
+        });
+        model.timeline = Some(timeline.clone());
+        assert!(model.source.is_some());
+
+        let decoded = convert_rv_data_to_presentation(convert_presentation_to_rv_data(model));
+        assert_eq!(decoded.timeline, Some(timeline));
+    }
+
+    #[test]
+    fn test_edited_text_keeps_source_styling() {
+        let original = read_presentation_file(get_example_path("[Hymn] Amazing Grace.pro")).unwrap();
+        let mut model = convert_rv_data_to_presentation(original);
+        let original_text = first_text_element(&mut model).clone();
+        first_text_element(&mut model).content = "How sweet the sound".to_string();
+        model.name = "Amazing Grace (edited)".to_string();
+
+        let encoded = convert_presentation_to_rv_data(model);
+        let mut decoded = convert_rv_data_to_presentation(encoded.clone());
+        assert_eq!(decoded.name, "Amazing Grace (edited)");
+        let text = first_text_element(&mut decoded);
+        assert_eq!(text.content, "How sweet the sound");
+        assert_eq!(text.font, original_text.font);
+        assert_eq!(text.bounds, original_text.bounds);
+
+        let source = read_presentation_file(get_example_path("[Hymn] Amazing Grace.pro")).unwrap();
+        assert_eq!(encoded.application_info, source.application_info);
+        assert_eq!(encoded.cues.len(), source.cues.len());
+        let rtf_font = |presentation: &rv_data::Presentation| {
+            presentation.cues.iter()
+                .flat_map(|cue| &cue.actions)
+                .filter_map(presentation_slide)
+                .flat_map(|slide| slide.base_slide.iter().flat_map(|base| &base.elements))
+                .find_map(text_of)
+                .and_then(|text| rtf::extract_rtf_options(&text.rtf_data))
+                .map(|options| (options.font_name, options.font_size))
+        };
+        assert_eq!(rtf_font(&encoded), rtf_font(&source));
+    }
+
+    #[test]
+    fn test_media_files_are_videos_or_images_by_extension() {
+        use rv_data::action::{media_type::MediaType, ActionTypeData};
+        use rv_data::media::TypeProperties;
+
+        let convert = |path: &str| {
+            let action = dm::Action::Media {
+                uuid: Uuid::new_v4(),
+                name: "Background".to_string(),
+                source: dm::MediaSource::File(PathBuf::from(path)),
+                fit: dm::MediaFit::Scale,
+                opacity: 1.0,
+                volume: 0.5,
+                delay_time: 0.0,
+                duration: 0.0,
+                enabled: true,
+            };
+            let Some(ActionTypeData::Media(media)) = convert_action_to_rv_data(action, None).action_type_data else {
+                panic!("not a media action");
+            };
+            (media.media_type, media.element.and_then(|element| element.type_properties))
+        };
+
+        let (media_type, properties) = convert("/Media/Sunrise Loop.MOV");
+        assert!(matches!(media_type, Some(MediaType::Video(_))));
+        let Some(TypeProperties::Video(video)) = properties else { panic!("not video properties") };
+        assert_eq!(video.audio.map(|audio| audio.volume), Some(0.5));
+        assert!(video.file.and_then(|file| file.local_url).is_some());
+
+        let (media_type, properties) = convert("/Media/Hymn Background.jpg");
+        assert!(matches!(media_type, Some(MediaType::Image(_))));
+        assert!(matches!(properties, Some(TypeProperties::Image(_))));
+    }
+}
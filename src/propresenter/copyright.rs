@@ -0,0 +1,268 @@
+//! CCLI copyright credits for song presentations.
+//!
+//! A CCLI license requires each song shown to credit its writers and
+//! copyright along with the song number and the church's license number.
+//! [`add_copyright`] fills in the presentation's CCLI block, which
+//! `ProPresenter` uses for its own copyright display and reporting, and can
+//! also put the credits on the slides: as a line under the last slide's
+//! text, or as a final slide styled like the template.
+
+use super::convert::convert_slide_to_rv_data;
+use super::data_model as dm;
+use super::export::get_group_color;
+use super::generated::rv_data;
+use super::template::{clone_slide_with_text, extract_template_slide, template_cue};
+
+/// Name of the group holding a copyright slide
+pub const COPYRIGHT_GROUP: &str = "Copyright";
+
+/// Where a song's copyright credits are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CopyrightFooter {
+    /// Only in the presentation's CCLI block
+    Off,
+    /// A line under the last slide's text
+    Line,
+    /// A final slide of its own
+    #[default]
+    Slide,
+}
+
+impl CopyrightFooter {
+    /// Parse a setting value: "off", "line" or "slide"
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "off" | "none" | "0" | "false" | "no" => Some(Self::Off),
+            "line" => Some(Self::Line),
+            "slide" | "on" | "1" | "true" | "yes" => Some(Self::Slide),
+            _ => None,
+        }
+    }
+}
+
+/// The credits shown for a song: title, writers, copyright notice and CCLI numbers
+///
+/// Empty fields are left out. `license` is the church's CCLI license number.
+pub fn credit_lines(ccli: &dm::CCLIInfo, license: Option<&str>) -> Vec<String> {
+    let title = Some(ccli.song_title.trim()).filter(|t| !t.is_empty()).map(String::from);
+    let author = Some(ccli.author.trim()).filter(|a| !a.is_empty()).map(String::from);
+    title.into_iter()
+        .chain(author)
+        .chain(copyright_notice(ccli))
+        .chain(ccli_numbers(ccli.song_number, license))
+        .collect()
+}
+
+/// The credits on one line, without the title
+///
+/// Example: "John Newton · Public Domain · CCLI Song # 22025 / License # 1234567"
+pub fn credit_line(ccli: &dm::CCLIInfo, license: Option<&str>) -> String {
+    let mut lines = credit_lines(ccli, license);
+    if !ccli.song_title.trim().is_empty() {
+        lines.remove(0);
+    }
+    lines.join(" · ")
+}
+
+/// Whether `line` is a [`credit_line`] for `ccli`, with any license number
+pub fn is_credit_line(line: &str, ccli: &dm::CCLIInfo) -> bool {
+    // The credits before the CCLI numbers, which depend on the license
+    let lead = credit_line(&dm::CCLIInfo { song_number: 0, ..ccli.clone() }, None);
+    let Some(rest) = line.trim().strip_prefix(lead.as_str()) else { return false };
+    let numbers = match rest.strip_prefix(" · ") {
+        _ if lead.is_empty() => rest,
+        _ if rest.is_empty() => return true,
+        Some(numbers) => numbers,
+        None => return false,
+    };
+    numbers.starts_with("CCLI ")
+}
+
+/// "© 2001 Worship Together", keeping "Public Domain" as it is
+fn copyright_notice(ccli: &dm::CCLIInfo) -> Option<String> {
+    let publisher = ccli.publisher.trim().trim_start_matches('©').trim();
+    if publisher.to_lowercase().contains("public domain") {
+        return Some(publisher.to_string());
+    }
+    let year = Some(ccli.copyright_year)
+        .filter(|year| *year > 0 && !publisher.contains(&year.to_string()));
+    let notice = match year {
+        Some(year) if publisher.is_empty() => year.to_string(),
+        Some(year) => format!("{year} {publisher}"),
+        None => publisher.to_string(),
+    };
+    (!notice.is_empty()).then(|| format!("© {notice}"))
+}
+
+/// "CCLI Song # 22025 / License # 1234567", or whichever number is known
+fn ccli_numbers(song_number: u32, license: Option<&str>) -> Option<String> {
+    let license = license.map(str::trim).filter(|l| !l.is_empty());
+    match (song_number, license) {
+        (0, None) => None,
+        (0, Some(license)) => Some(format!("CCLI License # {license}")),
+        (song, None) => Some(format!("CCLI Song # {song}")),
+        (song, Some(license)) => Some(format!("CCLI Song # {song} / License # {license}")),
+    }
+}
+
+/// Credit a song presentation built from `template`
+///
+/// Sets the CCLI block, then adds the credits where `footer` says. Nothing
+/// is added to the slides when there is nothing to credit beyond the title.
+/// Returns whether the slides were changed.
+pub fn add_copyright(
+    presentation: &mut rv_data::Presentation,
+    template: &rv_data::Presentation,
+    ccli: &dm::CCLIInfo,
+    license: Option<&str>,
+    footer: CopyrightFooter,
+) -> bool {
+    presentation.ccli = Some(ccli.clone().into());
+
+    let line = credit_line(ccli, license);
+    if line.is_empty() {
+        return false;
+    }
+    match footer {
+        CopyrightFooter::Off => false,
+        CopyrightFooter::Line => append_line(presentation, &line),
+        CopyrightFooter::Slide => append_slide(presentation, template, &credit_lines(ccli, license).join("\n")),
+    }
+}
+
+/// Add `line` under the text of the presentation's last slide
+fn append_line(presentation: &mut rv_data::Presentation, line: &str) -> bool {
+    use rv_data::action::{slide_type, ActionTypeData, SlideType};
+
+    let last_slide = presentation.cues.iter_mut().rev()
+        .flat_map(|cue| cue.actions.iter_mut())
+        .find_map(|action| match &mut action.action_type_data {
+            Some(ActionTypeData::Slide(SlideType { slide: Some(slide_type::Slide::Presentation(slide)) })) => Some(slide),
+            _ => None,
+        });
+    let Some(slide) = last_slide else { return false };
+
+    let mut model = dm::Slide::from(slide.clone());
+    let Some(text) = model.base.elements.iter_mut().find_map(|element| match element {
+        dm::Element::Text(text) => Some(text),
+        _ => None,
+    }) else {
+        return false;
+    };
+    text.content = format!("{}\n{line}", text.content);
+    *slide = convert_slide_to_rv_data(model);
+    true
+}
+
+/// Add a slide showing `text` in a group of its own, at the end of every arrangement
+fn append_slide(presentation: &mut rv_data::Presentation, template: &rv_data::Presentation, text: &str) -> bool {
+    let Some(template_slide) = extract_template_slide(template) else { return false };
+    let (cue_uuid, cue) = template_cue(template, clone_slide_with_text(&template_slide, text));
+    presentation.cues.push(cue);
+
+    let group_uuid = Some(rv_data::Uuid { string: uuid::Uuid::new_v4().to_string() });
+    presentation.cue_groups.push(rv_data::presentation::CueGroup {
+        group: Some(rv_data::Group {
+            uuid: group_uuid.clone(),
+            name: COPYRIGHT_GROUP.to_string(),
+            color: Some(get_group_color(COPYRIGHT_GROUP).into()),
+            hot_key: None,
+            application_group_identifier: None,
+            application_group_name: String::new(),
+        }),
+        cue_identifiers: vec![rv_data::Uuid { string: cue_uuid.to_string() }],
+    });
+    for arrangement in &mut presentation.arrangements {
+        arrangement.group_identifiers.extend(group_uuid.clone());
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+    use crate::propresenter::extract::extract_slide_text;
+    use crate::propresenter::template::{build_presentation_from_template_with_options, TemplateCache, TemplateType, TextMeasure};
+
+    fn slide_texts(presentation: &rv_data::Presentation) -> String {
+        let actions = presentation.cues.iter().flat_map(|cue| &cue.actions);
+        actions.filter_map(extract_slide_text).collect::<Vec<_>>().join("\n\n")
+    }
+
+    fn song_ccli() -> dm::CCLIInfo {
+        dm::CCLIInfo {
+            author: "Chris Tomlin, John Newton".to_string(),
+            artist_credits: String::new(),
+            song_title: "Amazing Grace (My Chains Are Gone)".to_string(),
+            publisher: "2006 worshiptogether.com songs".to_string(),
+            copyright_year: 2006,
+            song_number: 4_768_151,
+            display: true,
+            album: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_credit_lines() {
+        let ccli = song_ccli();
+        assert_eq!(credit_lines(&ccli, Some("1234567")), [
+            "Amazing Grace (My Chains Are Gone)",
+            "Chris Tomlin, John Newton",
+            "© 2006 worshiptogether.com songs",
+            "CCLI Song # 4768151 / License # 1234567",
+        ]);
+
+        let hymn = dm::CCLIInfo { publisher: "Public Domain".to_string(), copyright_year: 1779, song_number: 0, ..ccli.clone() };
+        assert_eq!(credit_line(&hymn, Some(" 1234567 ")), "Chris Tomlin, John Newton · Public Domain · CCLI License # 1234567");
+
+        assert!(is_credit_line(&credit_line(&ccli, Some("1234567")), &ccli));
+        assert!(is_credit_line(&credit_line(&ccli, None), &ccli));
+        assert!(!is_credit_line("Chris Tomlin, John Newton", &ccli));
+        assert!(!is_credit_line("My chains are gone", &ccli));
+        assert!(is_credit_line(&credit_line(&hymn, Some("1234567")), &hymn));
+        assert!(is_credit_line(&credit_line(&hymn, None), &hymn));
+
+        let untitled = dm::CCLIInfo { song_title: String::new(), author: String::new(), ..hymn };
+        assert_eq!(credit_line(&untitled, None), "Public Domain");
+        assert_eq!(CopyrightFooter::parse(" Line "), Some(CopyrightFooter::Line));
+        assert_eq!(CopyrightFooter::parse("sideways"), None);
+    }
+
+    #[test]
+    fn test_add_copyright_slide_and_line() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("data");
+        path.push("templates");
+        let mut cache = TemplateCache::new(vec![path]);
+        let template = cache.get(TemplateType::Song).unwrap().clone();
+        let content: Vec<String> = ["[Verse 1]", "Amazing grace", "", "[Chorus]", "My chains are gone"]
+            .iter().map(ToString::to_string).collect();
+        let build = || build_presentation_from_template_with_options("Test", &template, &content, &TextMeasure::Columns(40), 4).unwrap();
+        let ccli = song_ccli();
+
+        let mut presentation = build();
+        assert!(add_copyright(&mut presentation, &template, &ccli, Some("1234567"), CopyrightFooter::Slide));
+        assert_eq!(presentation.ccli.as_ref().map(|c| c.song_number), Some(4_768_151));
+        assert_eq!(presentation.cues.len(), 3);
+        let group = presentation.cue_groups.last().and_then(|g| g.group.as_ref()).unwrap();
+        assert_eq!(group.name, COPYRIGHT_GROUP);
+        assert_eq!(presentation.arrangements[0].group_identifiers.last(), group.uuid.as_ref());
+        let text = slide_texts(&presentation);
+        assert!(text.contains("CCLI Song # 4768151 / License # 1234567"), "{text}");
+
+        let mut presentation = build();
+        assert!(add_copyright(&mut presentation, &template, &ccli, Some("1234567"), CopyrightFooter::Line));
+        assert_eq!(presentation.cues.len(), 2);
+        let text = slide_texts(&presentation);
+        assert!(text.contains("My chains are gone\nChris Tomlin, John Newton · © 2006"), "{text}");
+
+        // Credits with nothing but a title stay in the CCLI block
+        let mut presentation = build();
+        let bare = dm::CCLIInfo { author: String::new(), publisher: String::new(), copyright_year: 0, song_number: 0, ..ccli };
+        assert!(!add_copyright(&mut presentation, &template, &bare, None, CopyrightFooter::Slide));
+        assert!(presentation.ccli.is_some());
+        assert_eq!(presentation.cues.len(), 2);
+    }
+}
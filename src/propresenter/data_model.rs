@@ -20,6 +20,8 @@ use std::path::PathBuf;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use crate::propresenter::generated::rv_data;
+
 /// Represents a ProPresenter presentation
 #[derive(Debug, Clone)]
 pub struct Presentation {
@@ -73,10 +75,14 @@ pub struct Presentation {
     
     /// Slideshow-specific settings
     pub slide_show: Option<SlideShow>,
+
+    /// The decoded file this was converted from. Encoding writes the modeled
+    /// fields back over it, so anything the model doesn't cover survives.
+    pub source: Option<Box<rv_data::Presentation>>,
 }
 
 /// CCLI (Christian Copyright Licensing International) information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CCLIInfo {
     pub author: String,
     pub artist_credits: String,
@@ -89,7 +95,7 @@ pub struct CCLIInfo {
 }
 
 /// Bible reference information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BibleReference {
     pub book_index: u32,
     pub book_name: String,
@@ -115,14 +121,14 @@ pub enum ContentDestination {
 }
 
 /// Layer identification for actions
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LayerIdentification {
     pub uuid: Uuid,
     pub name: String,
 }
 
 /// Action types that can be triggered in a timeline
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Action {
     Clear {
         target_layer: i32,
@@ -164,20 +170,23 @@ pub enum Action {
         duration: f64,
         enabled: bool,
     },
-    // Add other action types as needed
+    /// An action the model doesn't cover, kept as decoded
+    Other(Box<rv_data::Action>),
 }
 
 /// A cue point in the timeline
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TimelineCue {
     pub trigger_time: f64,
     pub name: String,
+    /// The cue triggered, or the action's identifier
     pub uuid: Uuid,
-    pub action: Action,
+    /// Action run at the trigger time; `None` triggers the cue `uuid`
+    pub action: Option<Action>,
 }
 
 /// Timeline for automated presentation playback
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Timeline {
     pub duration: f64,
     pub loop_enabled: bool,
@@ -187,7 +196,7 @@ pub struct Timeline {
 }
 
 /// An arrangement of slides
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Arrangement {
     pub uuid: Uuid,
     pub name: String,
@@ -195,7 +204,7 @@ pub struct Arrangement {
 }
 
 /// Represents a single slide in a presentation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Slide {
     /// Base slide properties
     pub base: BaseSlide,
@@ -211,10 +220,13 @@ pub struct Slide {
     
     /// Transition settings
     pub transition: Option<Transition>,
+
+    /// The decoded slide this was converted from, for unmodeled fields
+    pub source: Option<rv_data::PresentationSlide>,
 }
 
 /// Base slide properties
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BaseSlide {
     /// Unique identifier
     pub uuid: Uuid,
@@ -239,7 +251,7 @@ pub struct BaseSlide {
 }
 
 /// Layout guideline
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Guideline {
     pub position: f64,
     pub orientation: GuidelineOrientation,
@@ -268,7 +280,7 @@ impl Default for Color {
 }
 
 /// Represents an element that can appear on a slide
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Element {
     /// Text element with formatting
     Text(TextElement),
@@ -295,6 +307,9 @@ pub enum Element {
         duration: chrono::Duration,
         text_color: Color,
     },
+
+    /// An element the model doesn't cover, kept as decoded
+    Other(Box<rv_data::slide::Element>),
 }
 
 /// Text alignment options
@@ -307,7 +322,7 @@ pub enum TextAlignment {
 }
 
 /// Shape types
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ShapeType {
     Rectangle,
     Ellipse,
@@ -336,7 +351,7 @@ pub enum MediaFit {
 }
 
 /// Timer format options
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TimerFormat {
     ElapsedTime,
     RemainingTime,
@@ -445,20 +460,20 @@ pub struct ApplicationInfo {
 }
 
 /// Represents a URL
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Url {
     pub url: String,
 }
 
 /// Represents a transition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Transition {
     pub transition_type: TransitionType,
     pub duration: f64,
 }
 
 /// Transition types
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TransitionType {
     Cut,
     Dissolve,
@@ -468,7 +483,7 @@ pub enum TransitionType {
 }
 
 /// Direction for transitions
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Direction {
     Left,
     Right,
@@ -668,6 +683,7 @@ impl Default for Presentation {
             music_key: String::new(),
             music: None,
             slide_show: None,
+            source: None,
         }
     }
 }
@@ -688,6 +704,7 @@ impl Default for Slide {
             template_guidelines: Vec::new(),
             chord_chart: None,
             transition: None,
+            source: None,
         }
     }
 }
@@ -1030,7 +1047,7 @@ impl TimelineCue {
             trigger_time,
             name: name.into(),
             uuid: Uuid::new_v4(),
-            action,
+            action: Some(action),
         }
     }
 
@@ -1064,6 +1081,9 @@ pub struct Cue {
     pub completion_action_type: CompletionActionType,
     pub completion_action_uuid: Option<Uuid>,
     pub completion_time: f64,
+
+    /// The decoded cue this was converted from, for unmodeled fields
+    pub source: Option<rv_data::Cue>,
 }
 
 /// Completion target type for cues
@@ -1086,7 +1106,7 @@ pub enum CompletionActionType {
 }
 
 /// Hot key definition for cues
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct HotKey {
     pub key_code: u32,
     pub modifiers: u32,
@@ -1094,7 +1114,7 @@ pub struct HotKey {
 }
 
 /// Represents a cue group in a presentation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CueGroup {
     /// The group details
     pub group: Group,
@@ -1104,7 +1124,7 @@ pub struct CueGroup {
 }
 
 /// Represents a group for organizing cues
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Group {
     /// Unique identifier
     pub uuid: Uuid,
@@ -2,7 +2,7 @@
 //!
 //! Converts edited text content (with verse markers) into `ProPresenter` .pro files.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 use super::builder::PresentationBuilder;
@@ -13,6 +13,9 @@ use super::data_model::{
     TextElement,
 };
 use super::serialize::write_presentation_file;
+use super::template::join_chord_lines;
+use crate::lyrics::chordpro::{self, Transposition};
+use crate::lyrics::is_section_marker;
 
 /// Errors that can occur during export
 #[derive(Debug, thiserror::Error)]
@@ -28,6 +31,10 @@ pub enum ExportError {
     /// Failed to serialize the presentation to disk
     #[error("Serialize error: {0}")]
     Serialize(#[from] super::serialize::SerializeError),
+
+    /// Failed to read an existing presentation to update
+    #[error("Read error: {0}")]
+    Read(#[from] super::deserialize::ProPresenterError),
 }
 
 /// A parsed stanza from editor content
@@ -37,6 +44,149 @@ pub struct Stanza {
     pub label: Option<String>,
     /// Text content of the stanza
     pub lines: Vec<String>,
+    /// Content line index of each of `lines`
+    pub line_indices: Vec<usize>,
+    /// Notes, transition and background set by directive lines
+    pub directives: StanzaDirectives,
+}
+
+/// Slide settings for a stanza, from directive lines in the editor
+///
+/// - `{notes: Pause for prayer}` adds a line of slide notes
+/// - `{transition: push left 0.5}` sets the transition: `cut`, `dissolve`,
+///   `push` / `wipe` with a direction, or `fade` (through black), and an
+///   optional duration in seconds
+/// - `{background: #203040}` sets a background color, any other value is a
+///   media file played on the background layer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StanzaDirectives {
+    /// Slide notes
+    pub notes: Option<String>,
+    /// Transition into each of the stanza's slides
+    pub transition: Option<dm::Transition>,
+    /// What's behind the stanza's slides
+    pub background: Option<SlideBackground>,
+}
+
+/// A stanza's background
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlideBackground {
+    /// A solid slide background color
+    Color(Color),
+    /// An image or video on the background layer
+    Media(PathBuf),
+}
+
+/// Transition duration when a `{transition}` directive doesn't give one, in seconds
+const DEFAULT_TRANSITION_DURATION: f64 = 0.5;
+
+impl StanzaDirectives {
+    /// Whether directive `name` is a slide setting rather than a chord
+    /// directive like `{key: G}`
+    pub fn is_setting(name: &str) -> bool {
+        matches!(name, "notes" | "note" | "transition" | "background" | "bg")
+    }
+
+    /// Record directive `name` with `value`. Returns false for directives
+    /// that aren't slide settings (e.g. `{key: G}`) or values that don't parse.
+    pub fn apply(&mut self, name: &str, value: &str) -> bool {
+        match name {
+            "notes" | "note" if !value.is_empty() => {
+                let notes = self.notes.get_or_insert_with(String::new);
+                if !notes.is_empty() {
+                    notes.push('\n');
+                }
+                notes.push_str(value);
+                true
+            }
+            "transition" => parse_transition(value).map(|transition| self.transition = Some(transition)).is_some(),
+            "background" | "bg" if !value.is_empty() => {
+                self.background = Some(parse_color(value).map_or_else(
+                    || SlideBackground::Media(PathBuf::from(value)),
+                    SlideBackground::Color,
+                ));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether no slide settings were given
+    pub const fn is_empty(&self) -> bool {
+        self.notes.is_none() && self.transition.is_none() && self.background.is_none()
+    }
+
+    /// Set the slide's notes, transition and background color
+    pub fn apply_to_slide(&self, slide: &mut Slide) {
+        if let Some(notes) = &self.notes {
+            slide.notes = Some(notes.clone());
+        }
+        if let Some(transition) = &self.transition {
+            slide.transition = Some(transition.clone());
+        }
+        if let Some(SlideBackground::Color(color)) = &self.background {
+            slide.base.background_color = Some(*color);
+            slide.base.draws_background_color = true;
+        }
+    }
+
+    /// A media action putting a media background behind the stanza's slides
+    pub fn background_action(&self) -> Option<Action> {
+        let Some(SlideBackground::Media(path)) = &self.background else { return None };
+        Some(Action::Media {
+            uuid: Uuid::new_v4(),
+            name: path.file_stem().map_or_else(|| "Background".to_string(), |stem| stem.to_string_lossy().into_owned()),
+            source: dm::MediaSource::File(path.clone()),
+            fit: dm::MediaFit::Scale,
+            opacity: 1.0,
+            volume: 1.0,
+            delay_time: 0.0,
+            duration: 0.0,
+            enabled: true,
+        })
+    }
+}
+
+/// Parse a transition like `dissolve 1.5` or `push left`
+fn parse_transition(value: &str) -> Option<dm::Transition> {
+    let mut words = value.split_whitespace().map(str::to_lowercase);
+    let kind = words.next()?;
+    let mut direction = dm::Direction::Left;
+    let mut duration = None;
+    for word in words {
+        match word.trim_end_matches('s') {
+            "left" => direction = dm::Direction::Left,
+            "right" => direction = dm::Direction::Right,
+            "up" | "top" => direction = dm::Direction::Up,
+            "down" | "bottom" => direction = dm::Direction::Down,
+            number => duration = Some(number.parse::<f64>().ok().filter(|d| d.is_finite() && *d >= 0.0)?),
+        }
+    }
+    let transition_type = match kind.as_str() {
+        "cut" | "none" => dm::TransitionType::Cut,
+        "dissolve" => dm::TransitionType::Dissolve,
+        "push" => dm::TransitionType::Push { direction },
+        "wipe" => dm::TransitionType::Wipe { direction },
+        "fade" | "black" => dm::TransitionType::FadeThroughBlack,
+        _ => return None,
+    };
+    let default = if transition_type == dm::TransitionType::Cut { 0.0 } else { DEFAULT_TRANSITION_DURATION };
+    Some(dm::Transition { transition_type, duration: duration.unwrap_or(default) })
+}
+
+/// Parse a `#rrggbb` or `#rrggbbaa` color
+fn parse_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok()).map(|c| f64::from(c) / 255.0);
+    Some(Color {
+        red: channel(0)?,
+        green: channel(2)?,
+        blue: channel(4)?,
+        alpha: if hex.len() == 8 { channel(6)? } else { 1.0 },
+    })
 }
 
 /// Parse editor content into stanzas
@@ -44,44 +194,78 @@ pub struct Stanza {
 /// Content format:
 /// - Lines starting with `[Label]` define group labels
 /// - Blank lines separate stanzas
+/// - Directive lines set the stanza's [`StanzaDirectives`]; ones after a
+///   blank line apply to the next stanza
 /// - Non-blank lines are slide content
 pub fn parse_stanzas(content: &[String]) -> Vec<Stanza> {
     let mut stanzas = Vec::new();
     let mut current_label: Option<String> = None;
     let mut current_lines: Vec<String> = Vec::new();
+    let mut current_indices: Vec<usize> = Vec::new();
+    let mut current_directives = StanzaDirectives::default();
+    // Directives after the current stanza's last line. They belong to the
+    // stanza unless a label follows, which they belong to instead
+    let mut pending: Vec<(String, String)> = Vec::new();
 
-    for line in content {
+    for (idx, line) in content.iter().enumerate() {
         let trimmed = line.trim();
         
+        // Other ChordPro directives ({key: G}) only affect chords
+        if let Some((name, value)) = chordpro::parse_directive(trimmed) {
+            if current_lines.is_empty() {
+                current_directives.apply(&name, &value);
+            } else {
+                pending.push((name, value));
+            }
+            continue;
+        }
+
         // Check for label markers like [Verse 1], [Chorus], etc.
-        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+        if is_section_marker(trimmed) {
             // Save previous stanza if exists
             if !current_lines.is_empty() {
                 stanzas.push(Stanza {
                     label: current_label.take(),
                     lines: std::mem::take(&mut current_lines),
+                    line_indices: std::mem::take(&mut current_indices),
+                    directives: std::mem::take(&mut current_directives),
                 });
             }
             current_label = Some(trimmed[1..trimmed.len()-1].to_string());
         } else if trimmed.is_empty() {
             // Blank line: end current stanza
+            for (name, value) in std::mem::take(&mut pending) {
+                current_directives.apply(&name, &value);
+            }
             if !current_lines.is_empty() {
                 stanzas.push(Stanza {
                     label: current_label.take(),
                     lines: std::mem::take(&mut current_lines),
+                    line_indices: std::mem::take(&mut current_indices),
+                    directives: std::mem::take(&mut current_directives),
                 });
             }
         } else {
             // Regular content line
             current_lines.push(line.clone());
+            current_indices.push(idx);
+        }
+
+        for (name, value) in std::mem::take(&mut pending) {
+            current_directives.apply(&name, &value);
         }
     }
 
     // Don't forget the last stanza
+    for (name, value) in pending {
+        current_directives.apply(&name, &value);
+    }
     if !current_lines.is_empty() {
         stanzas.push(Stanza {
             label: current_label,
             lines: current_lines,
+            line_indices: current_indices,
+            directives: current_directives,
         });
     }
 
@@ -89,7 +273,19 @@ pub fn parse_stanzas(content: &[String]) -> Vec<Stanza> {
 }
 
 /// Create a slide from stanza content
-fn create_slide(content: &str) -> Slide {
+///
+/// `chords` are `(UTF-16 offset, name)` pairs attached as chord attributes.
+fn create_slide(content: &str, chords: &[(usize, String)]) -> Slide {
+    let custom_attributes = chords.iter()
+        .map(|(pos, name)| {
+            let start = u32::try_from(*pos).unwrap_or(u32::MAX);
+            dm::CustomAttribute {
+                range: dm::Range { start, end: start.saturating_add(1) },
+                attribute: dm::CustomAttributeType::Chord(name.clone()),
+            }
+        })
+        .collect();
+
     let text_element = TextElement {
         content: content.to_string(),
         font: Font {
@@ -124,7 +320,7 @@ fn create_slide(content: &str) -> Slide {
             origin: Point { x: 0.0, y: 0.0 },
             size: Size { width: 1920.0, height: 1080.0 },
         }),
-        custom_attributes: Vec::new(),
+        custom_attributes,
         text_scroller: None,
     };
 
@@ -142,11 +338,12 @@ fn create_slide(content: &str) -> Slide {
         template_guidelines: Vec::new(),
         chord_chart: None,
         transition: None,
+        source: None,
     }
 }
 
 /// Group colors for different stanza types
-fn get_group_color(label: &str) -> Color {
+pub(crate) fn get_group_color(label: &str) -> Color {
     let label_lower = label.to_lowercase();
     if label_lower.contains("verse") {
         Color { red: 0.2, green: 0.4, blue: 1.0, alpha: 1.0 }  // Blue
@@ -174,25 +371,36 @@ pub fn build_presentation_from_content(
 
     let mut cues = Vec::new();
     let mut cue_groups = Vec::new();
+    let transposition = Transposition::from_content(content);
 
     for stanza in &stanzas {
-        // Combine stanza lines into slide content
-        let slide_content = stanza.lines.join("\n");
-        let slide = create_slide(&slide_content);
+        // Combine stanza lines into slide content, moving chords into attributes
+        let lines: Vec<_> = stanza.lines.iter()
+            .map(|line| {
+                let mut parsed = chordpro::parse_editor_line(line);
+                transposition.apply(&mut parsed);
+                parsed
+            })
+            .collect();
+        let (slide_content, chords) = join_chord_lines(lines.iter().map(Some));
+        let mut slide = create_slide(&slide_content, &chords);
+        stanza.directives.apply_to_slide(&mut slide);
         
         let cue_uuid = Uuid::new_v4();
+        let mut actions = vec![Action::Slide {
+            uuid: Uuid::new_v4(),
+            name: stanza.label.clone().unwrap_or_else(|| "Slide".to_string()),
+            slide,
+            delay_time: 0.0,
+            duration: 0.0,
+            enabled: true,
+            layer_identification: None,
+        }];
+        actions.extend(stanza.directives.background_action());
         let cue = Cue {
             uuid: cue_uuid,
             name: stanza.label.clone().unwrap_or_else(|| "Slide".to_string()),
-            actions: vec![Action::Slide {
-                uuid: Uuid::new_v4(),
-                name: stanza.label.clone().unwrap_or_else(|| "Slide".to_string()),
-                slide,
-                delay_time: 0.0,
-                duration: 0.0,
-                enabled: true,
-                layer_identification: None,
-            }],
+            actions,
             enabled: true,
             hot_key: None,
             completion_target_type: CompletionTargetType::None,
@@ -200,6 +408,7 @@ pub fn build_presentation_from_content(
             completion_action_type: CompletionActionType::First,
             completion_action_uuid: None,
             completion_time: 0.0,
+            source: None,
         };
         cues.push(cue);
 
@@ -254,6 +463,8 @@ mod tests {
     #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
 
     use super::*;
+    use crate::propresenter::generated::rv_data;
+    use crate::propresenter::media::url_file_path;
 
     #[test]
     fn test_parse_stanzas_simple() {
@@ -273,6 +484,73 @@ mod tests {
         assert_eq!(stanzas[1].label, Some("Chorus".to_string()));
     }
 
+    #[test]
+    fn test_stanza_directives_reach_slides() {
+        let content: Vec<String> = [
+            "{key: G}",
+            "[Verse 1]",
+            "{notes: Pause for prayer}",
+            "{transition: push up 1.5s}",
+            "Amazing grace how sweet the sound",
+            "{note: Band out}",
+            "",
+            "{background: #336699}",
+            "[Chorus]",
+            "I once was lost but now am found",
+            "",
+            "{background: /Media/Sunrise Loop.mov}",
+            "{transition: sparkle}",
+            "Was blind but now I see",
+            "{notes: Key change}",
+            "[Verse 2]",
+            "'Twas grace that taught my heart to fear",
+        ].iter().map(ToString::to_string).collect();
+
+        let stanzas = parse_stanzas(&content);
+        assert_eq!(stanzas.len(), 4);
+        assert_eq!(stanzas[0].lines, ["Amazing grace how sweet the sound"]);
+        assert_eq!(stanzas[0].directives.notes.as_deref(), Some("Pause for prayer\nBand out"));
+        assert_eq!(
+            stanzas[0].directives.transition,
+            Some(dm::Transition { transition_type: dm::TransitionType::Push { direction: dm::Direction::Up }, duration: 1.5 })
+        );
+        let blue = Color { red: 0.2, green: 0.4, blue: 0.6, alpha: 1.0 };
+        assert_eq!(stanzas[1].directives.background, Some(SlideBackground::Color(blue)));
+        assert_eq!(stanzas[2].directives.background, Some(SlideBackground::Media(PathBuf::from("/Media/Sunrise Loop.mov"))));
+        assert!(stanzas[2].directives.transition.is_none());
+        // Written right above a label, with no blank line: the label's
+        assert!(stanzas[2].directives.notes.is_none());
+        assert_eq!(stanzas[3].directives.notes.as_deref(), Some("Key change"));
+
+        // Through the protobuf and back
+        let presentation = build_presentation_from_content("Amazing Grace", &content).unwrap();
+        let decoded = dm::Presentation::from(convert_presentation_to_rv_data(presentation));
+        let slide = |cue: &Cue| cue.actions.iter().find_map(|action| match action {
+            Action::Slide { slide, .. } => Some(slide.clone()),
+            _ => None,
+        }).unwrap();
+
+        let verse = slide(&decoded.cues[0]);
+        assert_eq!(verse.notes.as_deref(), Some("Pause for prayer\nBand out"));
+        assert_eq!(verse.transition, stanzas[0].directives.transition);
+        let chorus = slide(&decoded.cues[1]);
+        assert!(chorus.base.draws_background_color);
+        // Protobuf colors are single precision
+        let rv_color = |color: Color| rv_data::Color::from(color);
+        assert_eq!(chorus.base.background_color.map(rv_color), Some(rv_color(blue)));
+        assert!(chorus.notes.is_none() && chorus.transition.is_none());
+        assert_eq!(decoded.cues[1].actions.len(), 1);
+        // Media actions aren't modelled on the way back
+        let media_path = decoded.cues[2].actions.iter().find_map(|action| match action {
+            Action::Other(action) => match &action.action_type_data {
+                Some(rv_data::action::ActionTypeData::Media(media)) => media.element.as_ref()?.url.as_ref().and_then(url_file_path),
+                _ => None,
+            },
+            _ => None,
+        });
+        assert_eq!(media_path, Some(PathBuf::from("/Media/Sunrise Loop.mov")));
+    }
+
     #[test]
     fn test_parse_stanzas_no_labels() {
         let content = vec![
@@ -46,7 +46,7 @@ pub fn extract_text_from_pro(path: &Path) -> Result<Vec<String>, String> {
 }
 
 /// Extract text from a slide action
-fn extract_slide_text(action: &rv_data::Action) -> Option<String> {
+pub(crate) fn extract_slide_text(action: &rv_data::Action) -> Option<String> {
     use rv_data::action::slide_type::Slide;
 
     // Navigate through the oneof to find SlideType
@@ -0,0 +1,385 @@
+//! Font metrics for measuring slide text.
+//!
+//! Reads just enough of a `TrueType`/`OpenType` file (`head`, `hhea`,
+//! `hmtx`, `cmap`, `name`) to know how wide each character is and how tall
+//! a line is, so text can be wrapped the way `ProPresenter` will draw it.
+//! Font collections (`.ttc`) are supported; glyph shaping and kerning are not.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Horizontal and vertical metrics of one font face.
+#[derive(Debug, Clone)]
+pub struct FontMetrics {
+    units_per_em: f64,
+    line_height: f64,
+    /// Advance width per character, in font units
+    advances: HashMap<char, u16>,
+    /// Advance of the missing-glyph box
+    missing_advance: u16,
+}
+
+impl FontMetrics {
+    /// Parse face `index` of a font file (0 unless it is a collection).
+    pub fn parse(data: &[u8], index: usize) -> Option<Self> {
+        let face = Face::open(data, index)?;
+        let head = face.table(b"head")?;
+        let hhea = face.table(b"hhea")?;
+        let hmtx = face.table(b"hmtx")?;
+
+        let units_per_em = f64::from(read_u16(head, 18)?.max(1));
+        let ascender = f64::from(read_i16(hhea, 4)?);
+        let descender = f64::from(read_i16(hhea, 6)?);
+        let line_gap = f64::from(read_i16(hhea, 8)?);
+        let metric_count = usize::from(read_u16(hhea, 34)?);
+
+        // Glyphs past the last metric share its advance
+        let advance = |glyph: u16| {
+            let i = usize::from(glyph).min(metric_count.saturating_sub(1));
+            read_u16(hmtx, i * 4).unwrap_or(0)
+        };
+        let advances = face.table(b"cmap")
+            .map(parse_cmap)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(c, glyph)| (c, advance(glyph)))
+            .collect();
+
+        Some(Self {
+            units_per_em,
+            line_height: ascender - descender + line_gap,
+            advances,
+            missing_advance: advance(0),
+        })
+    }
+
+    /// Width of `text` set at `size` points.
+    pub fn text_width(&self, text: &str, size: f64) -> f64 {
+        let units: f64 = text.chars()
+            .map(|c| f64::from(self.advances.get(&c).copied().unwrap_or(self.missing_advance)))
+            .sum();
+        units * size / self.units_per_em
+    }
+
+    /// Distance between baselines at `size` points.
+    pub fn line_height(&self, size: f64) -> f64 {
+        self.line_height * size / self.units_per_em
+    }
+}
+
+/// Font faces found in a set of directories, looked up by name.
+#[derive(Debug, Default)]
+pub struct FontLibrary {
+    /// Font files by normalized file name (without extension)
+    by_file_name: HashMap<String, PathBuf>,
+    /// Faces by normalized family, full and `PostScript` name
+    by_name: HashMap<String, (PathBuf, usize)>,
+    loaded: HashMap<String, Option<Arc<FontMetrics>>>,
+}
+
+impl FontLibrary {
+    /// Index the font files in `dirs` (recursively).
+    ///
+    /// Reads the name table of every font found, which takes a while for
+    /// the system font folders, so call it off the UI thread. Earlier
+    /// directories win when two define the same name.
+    pub fn scan(dirs: &[PathBuf]) -> Self {
+        let mut library = Self::default();
+        let files = dirs.iter()
+            .flat_map(|dir| walkdir::WalkDir::new(dir).into_iter().filter_map(std::result::Result::ok))
+            .map(walkdir::DirEntry::into_path)
+            .filter(|path| is_font_file(path));
+
+        for path in files {
+            let Ok(data) = std::fs::read(&path) else { continue };
+            for index in 0..Face::count(&data) {
+                let Some(face) = Face::open(&data, index) else { continue };
+                for name in face.table(b"name").map(parse_names).unwrap_or_default() {
+                    library.by_name.entry(normalize_name(&name)).or_insert_with(|| (path.clone(), index));
+                }
+            }
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                library.by_file_name.entry(normalize_name(stem)).or_insert(path);
+            }
+        }
+        library
+    }
+
+    /// Metrics for the font called `name` (family, full or `PostScript` name).
+    pub fn get(&mut self, name: &str) -> Option<Arc<FontMetrics>> {
+        let key = normalize_name(name);
+        if let Some(metrics) = self.loaded.get(&key) {
+            return metrics.clone();
+        }
+        let metrics = self.find(&key).map(Arc::new);
+        if metrics.is_none() {
+            tracing::debug!("No font file found for '{name}'");
+        }
+        self.loaded.insert(key, metrics.clone());
+        metrics
+    }
+
+    fn find(&self, key: &str) -> Option<FontMetrics> {
+        // A file named after the font is the cheap, common case
+        let by_file_name = self.by_file_name.get(key)
+            .and_then(|path| FontMetrics::parse(&std::fs::read(path).ok()?, 0));
+        if by_file_name.is_some() {
+            return by_file_name;
+        }
+
+        let (path, index) = self.by_name.get(key)?;
+        FontMetrics::parse(&std::fs::read(path).ok()?, *index)
+    }
+}
+
+/// Lowercase with spaces, hyphens and underscores removed, so
+/// `Helvetica Neue`, `HelveticaNeue` and `helvetica-neue` all agree.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e.to_lowercase().as_str(), "ttf" | "otf" | "ttc"))
+}
+
+/// One face's table directory within a font file.
+struct Face<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Face<'a> {
+    /// Number of faces in the file.
+    fn count(data: &[u8]) -> usize {
+        if data.starts_with(b"ttcf") {
+            read_u32(data, 8).map_or(0, |n| n as usize)
+        } else {
+            1
+        }
+    }
+
+    fn open(data: &'a [u8], index: usize) -> Option<Self> {
+        let offset = if data.starts_with(b"ttcf") {
+            read_u32(data, 12 + index * 4)? as usize
+        } else if index == 0 {
+            0
+        } else {
+            return None;
+        };
+        Some(Self { data, offset })
+    }
+
+    fn table(&self, tag: &[u8]) -> Option<&'a [u8]> {
+        let count = usize::from(read_u16(self.data, self.offset + 4)?);
+        (0..count).find_map(|i| {
+            let record = self.offset + 12 + i * 16;
+            if self.data.get(record..record + 4)? != tag {
+                return None;
+            }
+            let start = read_u32(self.data, record + 8)? as usize;
+            let length = read_u32(self.data, record + 12)? as usize;
+            self.data.get(start..start + length)
+        })
+    }
+}
+
+/// Map characters to glyph IDs from the best Unicode subtable.
+fn parse_cmap(cmap: &[u8]) -> HashMap<char, u16> {
+    let count = read_u16(cmap, 2).map_or(0, usize::from);
+    let mut subtables: Vec<(u16, &[u8])> = (0..count)
+        .filter_map(|i| {
+            let record = 4 + i * 8;
+            let platform = read_u16(cmap, record)?;
+            let encoding = read_u16(cmap, record + 2)?;
+            let unicode = platform == 0 || (platform == 3 && matches!(encoding, 1 | 10));
+            let table = cmap.get(read_u32(cmap, record + 4)? as usize..)?;
+            unicode.then_some((read_u16(table, 0)?, table))
+        })
+        .collect();
+    // Full-Unicode format 12 beats the BMP-only format 4
+    subtables.sort_by_key(|(format, _)| std::cmp::Reverse(*format == 12));
+
+    subtables.into_iter()
+        .find_map(|(format, table)| match format {
+            4 => parse_cmap_format4(table),
+            12 => parse_cmap_format12(table),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+fn parse_cmap_format4(table: &[u8]) -> Option<HashMap<char, u16>> {
+    let seg_count = usize::from(read_u16(table, 6)? / 2);
+    let ends = 14;
+    let starts = ends + seg_count * 2 + 2;
+    let deltas = starts + seg_count * 2;
+    let range_offsets = deltas + seg_count * 2;
+
+    let mut map = HashMap::new();
+    for seg in 0..seg_count {
+        let end = read_u16(table, ends + seg * 2)?;
+        let start = read_u16(table, starts + seg * 2)?;
+        let delta = read_u16(table, deltas + seg * 2)?;
+        let range_offset = usize::from(read_u16(table, range_offsets + seg * 2)?);
+        for code in start..=end {
+            if code == 0xFFFF {
+                break;
+            }
+            let glyph = if range_offset == 0 {
+                code.wrapping_add(delta)
+            } else {
+                let at = range_offsets + seg * 2 + range_offset + usize::from(code - start) * 2;
+                match read_u16(table, at) {
+                    Some(0) | None => 0,
+                    Some(glyph) => glyph.wrapping_add(delta),
+                }
+            };
+            if let (Some(c), true) = (char::from_u32(u32::from(code)), glyph != 0) {
+                map.insert(c, glyph);
+            }
+        }
+    }
+    Some(map)
+}
+
+fn parse_cmap_format12(table: &[u8]) -> Option<HashMap<char, u16>> {
+    let groups = read_u32(table, 12)? as usize;
+    let mut map = HashMap::new();
+    for group in 0..groups {
+        let record = 16 + group * 12;
+        let start = read_u32(table, record)?;
+        let end = read_u32(table, record + 4)?.min(0x0010_FFFF);
+        let first_glyph = read_u32(table, record + 8)?;
+        for code in start..=end {
+            let glyph = u16::try_from(first_glyph.checked_add(code - start)?).ok()?;
+            if let Some(c) = char::from_u32(code) {
+                map.insert(c, glyph);
+            }
+        }
+    }
+    Some(map)
+}
+
+/// Family, full and `PostScript` names from the `name` table.
+fn parse_names(name: &[u8]) -> Vec<String> {
+    let count = read_u16(name, 2).map_or(0, usize::from);
+    let storage = read_u16(name, 4).map_or(0, usize::from);
+    (0..count)
+        .filter_map(|i| {
+            let record = 6 + i * 12;
+            let platform = read_u16(name, record)?;
+            let name_id = read_u16(name, record + 6)?;
+            if !matches!(name_id, 1 | 4 | 6) {
+                return None;
+            }
+            let length = usize::from(read_u16(name, record + 8)?);
+            let offset = storage + usize::from(read_u16(name, record + 10)?);
+            let bytes = name.get(offset..offset + length)?;
+            // Macintosh names are single-byte; Unicode and Windows are UTF-16BE
+            Some(if platform == 1 {
+                bytes.iter().map(|&b| char::from(b)).collect()
+            } else {
+                let units: Vec<u16> = bytes.chunks_exact(2).map(|b| u16::from_be_bytes([b[0], b[1]])).collect();
+                String::from_utf16_lossy(&units)
+            })
+        })
+        .collect()
+}
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    data.get(at..at + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_i16(data: &[u8], at: usize) -> Option<i16> {
+    data.get(at..at + 2).map(|b| i16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    data.get(at..at + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Build a minimal font file for tests: glyph 0 is the missing glyph,
+/// then one glyph per `(char, advance)` pair, on a 1000-unit em.
+#[cfg(test)]
+pub(crate) fn test_font(name: &str, glyphs: &[(char, u16)]) -> Vec<u8> {
+    fn table(parts: &[&[u8]]) -> Vec<u8> {
+        parts.concat()
+    }
+    let be16 = |v: u16| v.to_be_bytes().to_vec();
+    let glyph_count = u16::try_from(glyphs.len() + 1).unwrap_or(u16::MAX);
+
+    let mut head = vec![0u8; 54];
+    head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+    let mut hhea = vec![0u8; 36];
+    hhea[4..6].copy_from_slice(&800i16.to_be_bytes());
+    hhea[6..8].copy_from_slice(&(-200i16).to_be_bytes());
+    hhea[34..36].copy_from_slice(&glyph_count.to_be_bytes());
+    let mut hmtx = table(&[&be16(500), &be16(0)]);
+    for (_, advance) in glyphs {
+        hmtx.extend(table(&[&be16(*advance), &be16(0)]));
+    }
+
+    // Format 12 subtable: one group per glyph
+    let groups = u32::try_from(glyphs.len()).unwrap_or(0);
+    let mut subtable = table(&[&be16(12), &be16(0), &(16 + groups * 12).to_be_bytes(), &0u32.to_be_bytes(), &groups.to_be_bytes()]);
+    for (glyph, (c, _)) in (1u32..).zip(glyphs) {
+        let code = u32::from(*c);
+        subtable.extend(table(&[&code.to_be_bytes(), &code.to_be_bytes(), &glyph.to_be_bytes()]));
+    }
+    let cmap = table(&[&be16(0), &be16(1), &be16(3), &be16(10), &12u32.to_be_bytes(), &subtable]);
+
+    let name_utf16: Vec<u8> = name.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let name_len = u16::try_from(name_utf16.len()).unwrap_or(0);
+    let name_table = table(&[&be16(0), &be16(1), &be16(18), &be16(3), &be16(1), &be16(0x409), &be16(4), &be16(name_len), &be16(0), &name_utf16]);
+
+    let tables: [(&[u8; 4], Vec<u8>); 5] = [(b"cmap", cmap), (b"head", head), (b"hhea", hhea), (b"hmtx", hmtx), (b"name", name_table)];
+    let mut font = table(&[&0x0001_0000u32.to_be_bytes(), &be16(5), &[0; 6]]);
+    let mut offset = 12 + tables.len() * 16;
+    let mut body: Vec<u8> = Vec::new();
+    for (tag, data) in &tables {
+        let start = u32::try_from(offset).unwrap_or(0);
+        let length = u32::try_from(data.len()).unwrap_or(0);
+        font.extend(table(&[*tag, &[0; 4], &start.to_be_bytes(), &length.to_be_bytes()]));
+        body.extend(data);
+        offset += data.len();
+    }
+    font.extend(body);
+    font
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn test_parse_metrics() {
+        let metrics = FontMetrics::parse(&test_font("Test Sans", &[('a', 600), ('W', 1000), (' ', 250)]), 0).unwrap();
+        assert!((metrics.text_width("aW a", 10.0) - 24.5).abs() < 1e-9);
+        // Unmapped characters use the missing glyph's advance
+        assert!((metrics.text_width("é", 10.0) - 5.0).abs() < 1e-9);
+        assert!((metrics.line_height(50.0) - 50.0).abs() < 1e-9);
+        assert!(FontMetrics::parse(b"not a font", 0).is_none());
+    }
+
+    #[test]
+    fn test_library_finds_fonts_by_file_or_internal_name() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Hymn-Serif.ttf"), test_font("Hymn Serif", &[('a', 400)])).unwrap();
+        std::fs::write(dir.path().join("odd_file.otf"), test_font("Chapel Display", &[('a', 700)])).unwrap();
+
+        let mut library = FontLibrary::scan(&[dir.path().to_path_buf()]);
+        let serif = library.get("HymnSerif").expect("found by file name");
+        assert!((serif.text_width("a", 1000.0) - 400.0).abs() < 1e-9);
+        let display = library.get("Chapel Display").expect("found by name table");
+        assert!((display.text_width("a", 1000.0) - 700.0).abs() < 1e-9);
+        assert!(library.get("Helvetica").is_none());
+    }
+}
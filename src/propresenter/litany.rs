@@ -0,0 +1,261 @@
+//! Responsive reading (litany) formatting.
+//!
+//! Parses "L:" / "P:" / "Leader:" / "People:" style text into speaker-tagged
+//! lines, groups each leader line with its response, and builds slides where
+//! congregational parts are bold.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::generated::rv_data;
+use super::rtf::RtfLine;
+use super::template::{
+    SlidePreview, TextMeasure, assemble_presentation, clone_slide_with_lines,
+    extract_template_slide,
+};
+
+/// Speaker prefix at the start of a line, e.g. "P: And also with you."
+#[allow(clippy::expect_used)]
+static RE_SPEAKER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*(leader|people|all|congregation|one|many|l|p|a|c)\s*:\s*(.*)$")
+        .expect("valid regex: RE_SPEAKER")
+});
+
+/// Who reads a line of a litany
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speaker {
+    /// Worship leader ("L:", "Leader:", "One:")
+    Leader,
+    /// Congregation response ("P:", "People:", "C:", "Congregation:", "Many:")
+    People,
+    /// Everyone in unison ("A:", "All:")
+    All,
+}
+
+impl Speaker {
+    /// Parse a speaker prefix (without the colon).
+    pub fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix.to_lowercase().as_str() {
+            "l" | "leader" | "one" => Some(Self::Leader),
+            "p" | "people" | "c" | "congregation" | "many" => Some(Self::People),
+            "a" | "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    /// Whether this part is shown in bold (everything the congregation reads).
+    pub const fn is_bold(self) -> bool {
+        matches!(self, Self::People | Self::All)
+    }
+}
+
+/// A line of a litany with its speaker prefix removed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LitanyLine {
+    /// Speaker, inherited from the previous prefixed line for continuations
+    pub speaker: Option<Speaker>,
+    /// Line text without the prefix
+    pub text: String,
+}
+
+impl LitanyLine {
+    /// Whether this line is read by the congregation.
+    pub fn is_bold(&self) -> bool {
+        self.speaker.is_some_and(Speaker::is_bold)
+    }
+}
+
+/// Returns true if any line carries a speaker prefix.
+pub fn has_speaker_prefixes(content: &[String]) -> bool {
+    content.iter().any(|line| RE_SPEAKER.is_match(line))
+}
+
+/// Parse content into litany exchanges.
+///
+/// An exchange starts at a leader line (or at the first line after a blank)
+/// and includes the responses that follow, so the call and its response can
+/// be kept on the same slide. Unprefixed lines continue the previous speaker
+/// until a blank line.
+pub fn parse_litany(content: &[String]) -> Vec<Vec<LitanyLine>> {
+    let mut exchanges: Vec<Vec<LitanyLine>> = Vec::new();
+    let mut current: Vec<LitanyLine> = Vec::new();
+    let mut speaker: Option<Speaker> = None;
+
+    for line in content {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            if !current.is_empty() {
+                exchanges.push(std::mem::take(&mut current));
+            }
+            speaker = None;
+            continue;
+        }
+
+        let prefixed = RE_SPEAKER.captures(trimmed).and_then(|caps| {
+            let role = Speaker::from_prefix(caps.get(1)?.as_str())?;
+            Some((role, caps.get(2).map_or("", |m| m.as_str()).trim().to_string()))
+        });
+
+        let text = match prefixed {
+            Some((role, text)) => {
+                // A new leader line after a response begins the next exchange
+                if role == Speaker::Leader && current.iter().any(|l| l.speaker != Some(Speaker::Leader)) {
+                    exchanges.push(std::mem::take(&mut current));
+                }
+                speaker = Some(role);
+                text
+            }
+            None => trimmed.to_string(),
+        };
+
+        if !text.is_empty() {
+            current.push(LitanyLine { speaker, text });
+        }
+    }
+
+    if !current.is_empty() {
+        exchanges.push(current);
+    }
+    exchanges
+}
+
+/// Pack exchanges into slides without splitting an exchange across slides.
+///
+/// An exchange that alone exceeds `max_lines` gets a slide to itself.
+pub fn split_litany_for_slides(
+    exchanges: Vec<Vec<LitanyLine>>,
+    measure: &TextMeasure,
+    max_lines: usize,
+) -> Vec<Vec<LitanyLine>> {
+    let max = measure.slide_capacity(max_lines);
+
+    let mut slides: Vec<Vec<LitanyLine>> = Vec::new();
+    let mut current: Vec<LitanyLine> = Vec::new();
+    let mut current_lines = 0;
+
+    for exchange in exchanges {
+        let visual_lines: usize = exchange.iter().map(|l| measure.visual_lines(&l.text)).sum();
+
+        if current_lines > 0 && current_lines + visual_lines > max {
+            slides.push(std::mem::take(&mut current));
+            current_lines = 0;
+        }
+
+        current.extend(exchange);
+        current_lines += visual_lines;
+    }
+
+    if !current.is_empty() {
+        slides.push(current);
+    }
+    slides
+}
+
+/// The slides [`build_litany_presentation_from_template`] would produce
+pub fn preview_litany_slides(content: &[String], measure: &TextMeasure, max_lines: usize) -> Vec<SlidePreview> {
+    split_litany_for_slides(parse_litany(content), measure, max_lines)
+        .iter()
+        .map(|lines| {
+            let text = lines.iter().map(|l| l.text.as_str()).collect::<Vec<_>>().join("\n");
+            SlidePreview::new(text, None, measure, max_lines)
+        })
+        .collect()
+}
+
+/// Build a litany presentation from a template
+///
+/// Same signature as
+/// [`build_presentation_from_template_with_options`](super::template::build_presentation_from_template_with_options),
+/// so callers can pick a builder by slide type.
+pub fn build_litany_presentation_from_template(
+    name: &str,
+    template: &rv_data::Presentation,
+    content: &[String],
+    measure: &TextMeasure,
+    max_lines_per_slide: usize,
+) -> Option<rv_data::Presentation> {
+    let template_slide = extract_template_slide(template)?;
+
+    let slides = split_litany_for_slides(parse_litany(content), measure, max_lines_per_slide)
+        .iter()
+        .map(|lines| {
+            let rtf_lines: Vec<RtfLine<'_>> = lines
+                .iter()
+                .map(|l| RtfLine { text: &l.text, bold: l.is_bold() })
+                .collect();
+            clone_slide_with_lines(&template_slide, &rtf_lines)
+        })
+        .collect();
+
+    Some(assemble_presentation(name, template, slides))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_speaker_prefixes() {
+        let content = lines("L: The Lord be with you.\nP: And also with you.\nLeader: Lift up your hearts.\nPeople: We lift them up to the Lord.");
+        let exchanges = parse_litany(&content);
+        assert_eq!(exchanges.len(), 2);
+        assert_eq!(exchanges[0][0], LitanyLine { speaker: Some(Speaker::Leader), text: "The Lord be with you.".to_string() });
+        assert!(exchanges[0][1].is_bold());
+        assert_eq!(exchanges[1][1].text, "We lift them up to the Lord.");
+    }
+
+    #[test]
+    fn test_continuation_lines_inherit_speaker() {
+        let content = lines("L: Give thanks to the Lord,\nfor he is good.\nP: His steadfast love\nendures forever.\n\nClosing words");
+        let exchanges = parse_litany(&content);
+        assert_eq!(exchanges.len(), 2);
+        assert_eq!(exchanges[0][1].speaker, Some(Speaker::Leader));
+        assert!(exchanges[0][3].is_bold());
+        assert_eq!(exchanges[1][0].speaker, None);
+    }
+
+    #[test]
+    fn test_pairs_stay_together() {
+        let content = lines("L: One\nP: Two\nL: Three\nP: Four\nL: Five\nP: Six");
+        let slides = split_litany_for_slides(parse_litany(&content), &TextMeasure::Columns(40), 3);
+        assert_eq!(slides.len(), 3);
+        for slide in &slides {
+            assert_eq!(slide.len(), 2);
+            assert_eq!(slide[0].speaker, Some(Speaker::Leader));
+            assert_eq!(slide[1].speaker, Some(Speaker::People));
+        }
+    }
+
+    #[test]
+    fn test_build_with_info_template() {
+        use super::super::template::{TemplateCache, TemplateType};
+
+        let template_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data").join("templates");
+        let mut cache = TemplateCache::new(vec![template_dir]);
+        let template = cache.get(TemplateType::Info).unwrap().clone();
+
+        let content = lines("L: The Lord be with you.\nP: And also with you.");
+        let pres = build_litany_presentation_from_template("Call to Worship", &template, &content, &TextMeasure::Columns(40), 4).unwrap();
+        assert_eq!(pres.cues.len(), 1);
+
+        let slide = extract_template_slide(&pres).unwrap();
+        let rtf = slide.base_slide.unwrap().elements.into_iter()
+            .find_map(|e| e.element.and_then(|g| g.text))
+            .map(|t| String::from_utf8_lossy(&t.rtf_data).to_string())
+            .unwrap();
+        assert!(rtf.contains(r"{\b And also with you.}"), "{rtf}");
+    }
+
+    #[test]
+    fn test_has_speaker_prefixes() {
+        assert!(has_speaker_prefixes(&lines("Intro\nP: Amen.")));
+        assert!(!has_speaker_prefixes(&lines("Note: this is not a litany")));
+    }
+}
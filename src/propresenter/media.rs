@@ -0,0 +1,441 @@
+//! Media files referenced by presentations.
+//!
+//! Presentations point at backgrounds, videos and images through `URL`
+//! messages spread across cues, actions, slide elements and fills. This
+//! module finds them, adds the local files to a bundle's file list and
+//! rewrites each URL to a path inside the bundle, so the presentation opens
+//! on a machine that doesn't have the original files. Unpacking a bundle
+//! points the URLs back at local copies.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::propresenter::generated::rv_data::{self, url};
+
+/// Folder inside a bundle that holds copied media
+pub const MEDIA_DIR: &str = "Media";
+
+/// Contents of a file in a bundle
+///
+/// Media copied from this machine stays on disk until the bundle is written,
+/// then streams into the zip, so videos are never held in memory whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileData {
+    /// Contents in memory, like an encoded .pro file or an entry read from a zip
+    Bytes(Vec<u8>),
+    /// A local file, read when written
+    Local(PathBuf),
+}
+
+impl FileData {
+    /// The contents, if they're in memory
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(data) => Some(data),
+            Self::Local(_) => None,
+        }
+    }
+
+    /// Size in bytes (0 for a local file that can't be read)
+    pub fn size(&self) -> u64 {
+        match self {
+            Self::Bytes(data) => data.len() as u64,
+            Self::Local(path) => std::fs::metadata(path).map_or(0, |meta| meta.len()),
+        }
+    }
+
+    /// Copy the contents into `out`
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Self::Bytes(data) => out.write_all(data),
+            Self::Local(path) => io::copy(&mut File::open(path)?, out).map(|_| ()),
+        }
+    }
+
+    /// Whether the file at `path` holds these contents
+    ///
+    /// Sizes are compared first; contents are only read when they match.
+    pub fn matches_file(&self, path: &Path) -> io::Result<bool> {
+        if matches!(self, Self::Local(local) if local == path) {
+            return Ok(true);
+        }
+        if std::fs::metadata(path)?.len() != self.size() {
+            return Ok(false);
+        }
+        let existing = std::fs::read(path)?;
+        Ok(match self {
+            Self::Bytes(data) => *data == existing,
+            Self::Local(local) => std::fs::read(local)? == existing,
+        })
+    }
+
+    /// Whether both hold the same contents
+    fn same_contents(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Bytes(a), Self::Bytes(b)) => a == b,
+            (data, Self::Local(path)) | (Self::Local(path), data) => data.matches_file(path).unwrap_or(false),
+        }
+    }
+}
+
+/// Call `f` on every URL in `presentation`. Returns whether `f` changed any.
+///
+/// Covers the places presentations keep file URLs: the chord charts, media
+/// and external presentation actions (on cues and the timeline), and the
+/// media fills of slide elements and their text.
+pub fn for_each_url(presentation: &mut rv_data::Presentation, mut f: impl FnMut(&mut rv_data::Url) -> bool) -> bool {
+    presentation_urls(presentation).into_iter().fold(false, |changed, url| f(url) | changed)
+}
+
+fn presentation_urls(presentation: &mut rv_data::Presentation) -> Vec<&mut rv_data::Url> {
+    use rv_data::presentation::timeline::cue::TriggerInfo;
+
+    let mut urls: Vec<&mut rv_data::Url> = presentation.chord_chart.iter_mut().collect();
+    for action in presentation.cues.iter_mut().flat_map(|cue| &mut cue.actions) {
+        action_urls(action, &mut urls);
+    }
+    if let Some(timeline) = &mut presentation.timeline {
+        let cue_actions = timeline.cues.iter_mut().chain(&mut timeline.cues_v2).filter_map(|cue| match &mut cue.trigger_info {
+            Some(TriggerInfo::Action(action)) => Some(action),
+            _ => None,
+        });
+        for action in timeline.audio_action.iter_mut().chain(cue_actions) {
+            action_urls(action, &mut urls);
+        }
+    }
+    urls
+}
+
+fn action_urls<'a>(action: &'a mut rv_data::Action, urls: &mut Vec<&'a mut rv_data::Url>) {
+    use rv_data::action::{slide_type::Slide, ActionTypeData};
+
+    match &mut action.action_type_data {
+        Some(ActionTypeData::Media(media)) => urls.extend(media.element.as_mut().and_then(|media| media.url.as_mut())),
+        Some(ActionTypeData::ExternalPresentation(external)) => urls.extend(external.url.as_mut()),
+        Some(ActionTypeData::Slide(slide)) => match &mut slide.slide {
+            Some(Slide::Presentation(slide)) => {
+                urls.extend(slide.chord_chart.as_mut());
+                slide_urls(slide.base_slide.as_mut(), urls);
+            }
+            Some(Slide::Prop(slide)) => slide_urls(slide.base_slide.as_mut(), urls),
+            None => {}
+        },
+        _ => {}
+    }
+}
+
+fn slide_urls<'a>(slide: Option<&'a mut rv_data::Slide>, urls: &mut Vec<&'a mut rv_data::Url>) {
+    use rv_data::graphics::fill::FillType;
+    use rv_data::graphics::text::attributes::{custom_attribute::Attribute, Fill};
+
+    let elements = slide.into_iter().flat_map(|slide| &mut slide.elements).filter_map(|element| element.element.as_mut());
+    for element in elements {
+        if let Some(FillType::Media(media)) = element.fill.as_mut().and_then(|fill| fill.fill_type.as_mut()) {
+            urls.extend(media.url.as_mut());
+        }
+        let Some(attributes) = element.text.as_mut().and_then(|text| text.attributes.as_mut()) else { continue };
+        let custom_fills = attributes.custom_attributes.iter_mut().filter_map(|custom| match &mut custom.attribute {
+            Some(Attribute::MediaFill(fill)) => Some(fill),
+            _ => None,
+        });
+        let fill = match &mut attributes.fill {
+            Some(Fill::MediaFill(fill)) => Some(fill),
+            _ => None,
+        };
+        for fill in fill.into_iter().chain(custom_fills) {
+            urls.extend(fill.media.as_mut().and_then(|media| media.url.as_mut()));
+        }
+    }
+}
+
+/// The local file an absolute `file://` URL points at
+pub fn url_file_path(url: &rv_data::Url) -> Option<PathBuf> {
+    let Some(url::Storage::AbsoluteString(absolute)) = &url.storage else { return None };
+    let path = absolute.strip_prefix("file://")?;
+    Some(PathBuf::from(percent_decode(path)))
+}
+
+/// The bundle path a URL points at, if it was rewritten by [`bundle_media`]
+pub const fn url_bundle_path(url: &rv_data::Url) -> Option<&str> {
+    match &url.storage {
+        Some(url::Storage::RelativePath(path)) => Some(path.as_str()),
+        _ => None,
+    }
+}
+
+/// Point `url` at `path` inside the bundle
+pub fn set_bundle_path(url: &mut rv_data::Url, path: &str) {
+    url.storage = Some(url::Storage::RelativePath(path.to_string()));
+    url.relative_file_path = Some(url::RelativeFilePath::Local(url::LocalRelativePath {
+        root: url::local_relative_path::Root::CurrentResource as i32,
+        path: path.to_string(),
+    }));
+}
+
+/// Point `url` at local file `path`
+pub fn set_file_path(url: &mut rv_data::Url, path: &Path) {
+    url.storage = Some(url::Storage::AbsoluteString(file_url(path)));
+    url.relative_file_path = None;
+}
+
+/// A `file://` URL for `path`, escaping characters that mean something in URLs
+pub fn file_url(path: &Path) -> String {
+    let mut url = String::from("file://");
+    for c in path.to_string_lossy().chars() {
+        match c {
+            '%' => url.push_str("%25"),
+            ' ' => url.push_str("%20"),
+            '#' => url.push_str("%23"),
+            '&' => url.push_str("%26"),
+            '?' => url.push_str("%3F"),
+            c => url.push(c),
+        }
+    }
+    url
+}
+
+/// Decode `%XX` escapes, leaving malformed ones as they are
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Add the local media `presentation` references to `files` under
+/// [`MEDIA_DIR`], pointing its URLs at the bundled copies.
+///
+/// Files that aren't there (missing on this machine, or not files) keep
+/// their URL. A file already in `files` with the same contents is reused;
+/// a different file with the same name gets a numbered one. Returns the
+/// number of URLs rewritten.
+pub fn bundle_media(presentation: &mut rv_data::Presentation, files: &mut Vec<(String, FileData)>) -> usize {
+    let mut count = 0;
+    for_each_url(presentation, |url| {
+        let Some(path) = url_file_path(url) else { return false };
+        // Linked presentations aren't media
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("pro")) {
+            return false;
+        }
+        let Some(name) = path.file_name().and_then(|n| n.to_str()).filter(|_| path.is_file()) else {
+            return false;
+        };
+        let entry = media_entry(files, name, FileData::Local(path.clone()));
+        set_bundle_path(url, &entry);
+        count += 1;
+        true
+    });
+    count
+}
+
+/// Point the bundle-relative URLs in `presentation` at local files.
+///
+/// `resolve` maps a bundle path to where that file was unpacked; URLs it
+/// returns `None` for are left alone. Returns the number of URLs rewritten.
+pub fn unbundle_media(presentation: &mut rv_data::Presentation, resolve: impl Fn(&str) -> Option<PathBuf>) -> usize {
+    let mut count = 0;
+    for_each_url(presentation, |url| {
+        let Some(path) = url_bundle_path(url).and_then(&resolve) else { return false };
+        set_file_path(url, &path);
+        count += 1;
+        true
+    });
+    count
+}
+
+/// `name`, or `name (n).ext` from the second copy on
+pub fn numbered_name(name: &str, n: u32) -> String {
+    if n < 2 {
+        return name.to_string();
+    }
+    let file_name = Path::new(name);
+    let stem = file_name.file_stem().and_then(|s| s.to_str()).unwrap_or(name);
+    let extension = file_name.extension().and_then(|e| e.to_str()).map(|e| format!(".{e}")).unwrap_or_default();
+    format!("{stem} ({n}){extension}")
+}
+
+/// The bundle path for media file `name`, adding it to `files` unless an identical copy is there
+fn media_entry(files: &mut Vec<(String, FileData)>, name: &str, data: FileData) -> String {
+    let mut n = 1u32;
+    loop {
+        let candidate = format!("{MEDIA_DIR}/{}", numbered_name(name, n));
+        match files.iter().find(|(file, _)| *file == candidate) {
+            Some((_, existing)) if existing.same_contents(&data) => return candidate,
+            Some(_) => n += 1,
+            None => {
+                files.push((candidate.clone(), data));
+                return candidate;
+            }
+        }
+    }
+}
+
+/// Bundle paths of the media `presentation` points at
+pub fn bundled_media(presentation: &rv_data::Presentation) -> HashSet<String> {
+    let mut paths = HashSet::new();
+    let mut copy = presentation.clone();
+    for_each_url(&mut copy, |url| {
+        if let Some(path) = url_bundle_path(url) {
+            paths.insert(path.to_string());
+        }
+        false
+    });
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+
+    fn local_url(path: &Path) -> rv_data::Url {
+        rv_data::Url {
+            platform: url::Platform::Macos as i32,
+            storage: Some(url::Storage::AbsoluteString(file_url(path))),
+            relative_file_path: None,
+        }
+    }
+
+    /// A presentation with a media cue for each path and a chord chart for the first
+    fn presentation_with_media(paths: &[&Path]) -> rv_data::Presentation {
+        let cues = paths.iter().map(|path| rv_data::Cue {
+            actions: vec![rv_data::Action {
+                action_type_data: Some(rv_data::action::ActionTypeData::Media(rv_data::action::MediaType {
+                    element: Some(rv_data::Media { url: Some(local_url(path)), ..Default::default() }),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        rv_data::Presentation {
+            chord_chart: paths.first().map(|path| local_url(path)),
+            cues: cues.collect(),
+            ..Default::default()
+        }
+    }
+
+    fn media_urls(presentation: &rv_data::Presentation) -> Vec<rv_data::Url> {
+        let mut urls = Vec::new();
+        for_each_url(&mut presentation.clone(), |url| {
+            urls.push(url.clone());
+            false
+        });
+        urls
+    }
+
+    #[test]
+    fn test_finds_urls_in_slides_and_timeline() {
+        use rv_data::graphics::{self, text::attributes};
+
+        let url = |name: &str| local_url(Path::new(name));
+        let media = |name: &str| rv_data::Media { url: Some(url(name)), ..Default::default() };
+        let text_fill = graphics::text::MediaFill { media: Some(media("/text.mov")) };
+        let element = graphics::Element {
+            fill: Some(graphics::Fill {
+                fill_type: Some(graphics::fill::FillType::Media(media("/fill.jpg"))),
+                ..Default::default()
+            }),
+            text: Some(graphics::Text {
+                attributes: Some(graphics::text::Attributes {
+                    fill: Some(attributes::Fill::MediaFill(text_fill.clone())),
+                    custom_attributes: vec![attributes::CustomAttribute {
+                        attribute: Some(attributes::custom_attribute::Attribute::MediaFill(text_fill)),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let slide = rv_data::action::SlideType {
+            slide: Some(rv_data::action::slide_type::Slide::Presentation(rv_data::PresentationSlide {
+                base_slide: Some(rv_data::Slide {
+                    elements: vec![rv_data::slide::Element { element: Some(element), ..Default::default() }],
+                    ..Default::default()
+                }),
+                chord_chart: Some(url("/slide chart.pdf")),
+                ..Default::default()
+            })),
+        };
+        let audio = presentation_with_media(&[Path::new("/audio.mp3")]).cues.remove(0).actions.remove(0);
+        let mut presentation = rv_data::Presentation {
+            cues: vec![rv_data::Cue {
+                actions: vec![rv_data::Action {
+                    action_type_data: Some(rv_data::action::ActionTypeData::Slide(slide)),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            timeline: Some(rv_data::presentation::Timeline { audio_action: Some(audio), ..Default::default() }),
+            ..Default::default()
+        };
+
+        let paths: Vec<PathBuf> = media_urls(&presentation).iter().filter_map(url_file_path).collect();
+        let expected = ["/slide chart.pdf", "/fill.jpg", "/text.mov", "/text.mov", "/audio.mp3"];
+        assert_eq!(paths, expected.map(PathBuf::from));
+        assert!(for_each_url(&mut presentation, |url| {
+            set_bundle_path(url, "Media/x");
+            true
+        }));
+        assert_eq!(bundled_media(&presentation), HashSet::from(["Media/x".to_string()]));
+    }
+
+    #[test]
+    fn test_file_urls_round_trip() {
+        let path = Path::new("/Users/Shared/Worship Loops/Blue #2 (100%).mov");
+        assert_eq!(file_url(path), "file:///Users/Shared/Worship%20Loops/Blue%20%232%20(100%25).mov");
+        assert_eq!(url_file_path(&local_url(path)).as_deref(), Some(path));
+        assert_eq!(percent_decode("a%20b%2"), "a b%2");
+        assert_eq!(percent_decode("caf%C3%A9"), "café");
+    }
+
+    #[test]
+    fn test_bundle_media_copies_and_rewrites() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("other")).unwrap();
+        let background = dir.path().join("Sunrise Loop.mov");
+        let same_name = dir.path().join("other").join("Sunrise Loop.mov");
+        std::fs::write(&background, b"loop").unwrap();
+        std::fs::write(&same_name, b"different loop").unwrap();
+        let missing = dir.path().join("missing.jpg");
+
+        let mut presentation = presentation_with_media(&[&background, &same_name, &missing]);
+        let mut files = Vec::new();
+        assert_eq!(bundle_media(&mut presentation, &mut files), 3);
+
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["Media/Sunrise Loop.mov", "Media/Sunrise Loop (2).mov"]);
+        let urls = media_urls(&presentation);
+        assert_eq!(urls.iter().filter_map(url_bundle_path).count(), 3);
+        assert_eq!(presentation.chord_chart.as_ref().and_then(url_bundle_path), Some("Media/Sunrise Loop.mov"));
+        assert!(urls.iter().any(|url| url_file_path(url) == Some(missing.clone())));
+        assert_eq!(
+            bundled_media(&presentation),
+            HashSet::from(["Media/Sunrise Loop.mov".to_string(), "Media/Sunrise Loop (2).mov".to_string()])
+        );
+
+        // Bundling again finds nothing left to copy
+        assert_eq!(bundle_media(&mut presentation, &mut files), 0);
+        assert_eq!(files.len(), 2);
+    }
+}
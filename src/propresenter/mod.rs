@@ -1,14 +1,19 @@
 //! `ProPresenter` file format support.
 //!
 //! This module provides types and utilities for reading, writing, and
-//! manipulating `ProPresenter` presentation files (.pro) and playlist files (.proplaylist).
+//! manipulating `ProPresenter` presentation files (.pro), playlist files (.proplaylist)
+//! and bundles (.probundle).
 
 /// Presentation analysis tools.
 pub mod analyze;
 /// Builder pattern for creating presentations.
 pub mod builder;
+/// Bundle file support (.probundle).
+pub mod bundle;
 /// Conversion between data model and protobuf types.
 pub mod convert;
+/// CCLI copyright credits for song presentations.
+pub mod copyright;
 /// High-level data model types.
 pub mod data_model;
 /// File deserialization (reading .pro files).
@@ -17,8 +22,14 @@ pub mod deserialize;
 pub mod export;
 /// Extract plain text from presentations.
 pub mod extract;
+/// Font metrics for fitting slide text.
+pub mod fonts;
 /// Generated protobuf types.
 pub mod generated;
+/// Responsive reading (litany) formatting.
+pub mod litany;
+/// Media references and bundling them with presentations.
+pub mod media;
 /// Presentation comparison and parsing utilities.
 pub mod parser;
 /// Playlist file support (.proplaylist).
@@ -29,6 +40,8 @@ pub mod template;
 pub mod rtf;
 /// File serialization (writing .pro files).
 pub mod serialize;
+/// In-place updates of matched .pro files from editor content.
+pub mod update;
 /// UUID generation utilities.
 pub mod uuid;
 
@@ -1,16 +1,19 @@
 //! `ProPresenter` playlist file support.
 //!
-//! Writes protobuf-encoded playlist files (.proplaylist) to disk.
+//! Writes protobuf-encoded playlist files (.proplaylist) to disk, and reads
+//! existing ones back for editing or syncing with a changed plan.
 
+use std::collections::HashSet;
 use std::fs::File;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::Path;
 use prost::Message;
 use uuid::Uuid;
 use zip::write::FileOptions;
-use zip::ZipWriter;
+use zip::{ZipArchive, ZipWriter};
 
 use crate::propresenter::generated::rv_data::{self, playlist, playlist_document, playlist_item, url};
+use crate::propresenter::media::{self, FileData};
 use crate::types::SlideType;
 
 /// Errors that can occur when writing playlist files
@@ -24,6 +27,10 @@ pub enum PlaylistError {
     #[error("Encoding error: {0}")]
     Encode(String),
 
+    /// Failed to decode the playlist data, or the archive has none
+    #[error("Decoding error: {0}")]
+    Decode(String),
+
     /// A zip archive error occurred
     #[error("Zip error: {0}")]
     Zip(#[from] zip::result::ZipError),
@@ -45,6 +52,8 @@ pub struct PlaylistEntry {
     pub arrangement_uuid: Option<Uuid>,
     /// Optional embedded presentation data (if Some, embeds in zip instead of referencing external)
     pub embedded_data: Option<Vec<u8>>,
+    /// Planning Center item ID, stored as an item tag so a later sync can find the item
+    pub pco_item_id: Option<String>,
 }
 
 impl PlaylistEntry {
@@ -229,57 +238,65 @@ pub fn get_embedded_filename(name: &str, slide_type: SlideType) -> String {
     }
 }
 
+/// A playlist item for `entry`, pointing at `embedded_filename` if the entry is embedded
+fn playlist_item(entry: &PlaylistEntry, embedded_filename: &str) -> rv_data::PlaylistItem {
+    // For embedded files, create a path that ProPresenter can find:
+    // - absolute_string: file:/// URL (can be placeholder path)
+    // - relative_file_path: Libraries/Default/<filename>.pro with root=Show
+    let (file_url, relative_path) = if entry.embedded_data.is_some() {
+        // Create a plausible absolute path (ProPresenter will use relative)
+        let encoded_name = embedded_filename.replace(' ', "%20");
+        let abs_path = format!("file:///Libraries/Default/{encoded_name}");
+        // Relative path within ProPresenter's library structure
+        let rel = url::RelativeFilePath::Local(url::LocalRelativePath {
+            root: url::local_relative_path::Root::Show as i32,
+            path: format!("Libraries/Default/{embedded_filename}"),
+        });
+        (abs_path, Some(rel))
+    } else {
+        let file_url = path_to_file_url(&entry.presentation_path);
+        let relative_path = extract_relative_path(&entry.presentation_path);
+        (file_url, relative_path)
+    };
+    
+    rv_data::PlaylistItem {
+        uuid: Some(rv_data::Uuid { string: Uuid::new_v4().to_string() }),
+        name: entry.name.clone(),
+        tags: Vec::new(),
+        is_hidden: false,
+        item_type: Some(playlist_item::ItemType::Presentation(
+            playlist_item::Presentation {
+                document_path: Some(rv_data::Url {
+                    platform: rv_data::url::Platform::Macos as i32,
+                    storage: Some(rv_data::url::Storage::AbsoluteString(file_url)),
+                    relative_file_path: relative_path,
+                }),
+                arrangement: entry.arrangement_uuid.map(|u| rv_data::Uuid { 
+                    string: u.to_string() 
+                }),
+                content_destination: rv_data::action::ContentDestination::Global as i32,
+                user_music_key: None,
+                arrangement_name: String::new(),
+            },
+        )),
+    }
+}
+
 /// Build a `PlaylistDocument` from a list of entries
 ///
 /// `ProPresenter` expects a two-level structure:
 /// - Root Playlist (container) with `playlists` field containing child playlists
 /// - Child Playlist with `items` field containing the actual `PlaylistItems`
 pub fn build_playlist(name: &str, entries: &[PlaylistEntry]) -> rv_data::PlaylistDocument {
+    let mut tags = Vec::new();
     let items: Vec<rv_data::PlaylistItem> = entries
         .iter()
         .map(|entry| {
-            let embedded_filename = entry.embedded_filename();
-            
-            // For embedded files, create a path that ProPresenter can find:
-            // - absolute_string: file:/// URL (can be placeholder path)
-            // - relative_file_path: Libraries/Default/<filename>.pro with root=Show
-            let (file_url, relative_path) = if entry.embedded_data.is_some() {
-                // Create a plausible absolute path (ProPresenter will use relative)
-                let encoded_name = embedded_filename.replace(' ', "%20");
-                let abs_path = format!("file:///Libraries/Default/{encoded_name}");
-                // Relative path within ProPresenter's library structure
-                let rel = url::RelativeFilePath::Local(url::LocalRelativePath {
-                    root: url::local_relative_path::Root::Show as i32,
-                    path: format!("Libraries/Default/{embedded_filename}"),
-                });
-                (abs_path, Some(rel))
-            } else {
-                let file_url = path_to_file_url(&entry.presentation_path);
-                let relative_path = extract_relative_path(&entry.presentation_path);
-                (file_url, relative_path)
-            };
-            
-            rv_data::PlaylistItem {
-                uuid: Some(rv_data::Uuid { string: Uuid::new_v4().to_string() }),
-                name: entry.name.clone(),
-                tags: Vec::new(),
-                is_hidden: false,
-                item_type: Some(playlist_item::ItemType::Presentation(
-                    playlist_item::Presentation {
-                        document_path: Some(rv_data::Url {
-                            platform: rv_data::url::Platform::Macos as i32,
-                            storage: Some(rv_data::url::Storage::AbsoluteString(file_url)),
-                            relative_file_path: relative_path,
-                        }),
-                        arrangement: entry.arrangement_uuid.map(|u| rv_data::Uuid { 
-                            string: u.to_string() 
-                        }),
-                        content_destination: rv_data::action::ContentDestination::Global as i32,
-                        user_music_key: None,
-                        arrangement_name: String::new(),
-                    },
-                )),
+            let mut item = playlist_item(entry, &entry.embedded_filename());
+            if let Some(id) = &entry.pco_item_id {
+                item.tags.push(pco_tag(&mut tags, id));
             }
+            item
         })
         .collect();
 
@@ -340,19 +357,122 @@ pub fn build_playlist(name: &str, entries: &[PlaylistEntry]) -> rv_data::Playlis
         }),
         r#type: playlist_document::Type::Presentation as i32,
         root_node: Some(root_node),
-        tags: Vec::new(),
+        tags,
         live_video_playlist: None,
         downloads_playlist: None,
     }
 }
 
+/// Prefix of the tag names that carry Planning Center item IDs
+const PCO_TAG_PREFIX: &str = "PCO ";
+
+/// The UUID of the tag for Planning Center item `id`, adding the tag to `tags` if needed
+fn pco_tag(tags: &mut Vec<playlist::Tag>, id: &str) -> rv_data::Uuid {
+    let name = format!("{PCO_TAG_PREFIX}{id}");
+    if let Some(uuid) = tags.iter().find(|tag| tag.name == name).and_then(|tag| tag.uuid.clone()) {
+        return uuid;
+    }
+    let uuid = rv_data::Uuid { string: Uuid::new_v4().to_string() };
+    tags.push(playlist::Tag {
+        color: Some(rv_data::Color { red: 0.5, green: 0.5, blue: 0.5, alpha: 1.0 }),
+        name,
+        uuid: Some(uuid.clone()),
+    });
+    uuid
+}
+
 /// Write a playlist document to a .proplaylist file
 /// 
-/// If entries have `embedded_data`, those .pro files are bundled into the zip.
+/// If entries have `embedded_data`, those .pro files are bundled into the zip,
+/// along with the local media they reference (see [`media::bundle_media`]).
 pub fn write_playlist_file(
     playlist: &rv_data::PlaylistDocument,
     entries: &[PlaylistEntry],
     path: impl AsRef<Path>,
+) -> Result<(), PlaylistError> {
+    // Deduplicate filenames to avoid zip entry collisions.
+    let mut used_names = HashSet::new();
+    let mut files = Vec::new();
+    let mut media_files = Vec::new();
+    for entry in entries {
+        if let Some(data) = &entry.embedded_data {
+            let filename = unique_filename(&entry.embedded_filename(), |name| used_names.contains(name));
+            used_names.insert(filename.clone());
+            files.push((filename, FileData::Bytes(embed_media(data, &mut media_files))));
+        }
+    }
+    files.append(&mut media_files);
+
+    write_playlist_zip(playlist, &files, path)
+}
+
+/// Presentation `data` with its local media copied into `files`, or as is if it has none
+fn embed_media(data: &[u8], files: &mut Vec<(String, FileData)>) -> Vec<u8> {
+    let Ok(mut presentation) = rv_data::Presentation::decode(data) else { return data.to_vec() };
+    if media::bundle_media(&mut presentation, files) == 0 {
+        return data.to_vec();
+    }
+    presentation.encode_to_vec()
+}
+
+/// Whether two embedded files are the same apart from their UUIDs.
+///
+/// Presentations built from templates get fresh UUIDs on every build, so a
+/// rebuilt item only counts as changed if something else differs.
+fn same_content(a: &[u8], b: &[u8]) -> bool {
+    a == b || (a.len() == b.len() && normalize_uuids(a) == normalize_uuids(b))
+}
+
+/// `data` with each distinct UUID replaced by a stand-in numbered in order
+/// of first appearance. Stand-ins are as long as UUIDs, so protobuf length
+/// prefixes stay valid.
+fn normalize_uuids(data: &[u8]) -> Vec<u8> {
+    const UUID_LEN: usize = 36;
+    let is_uuid = |bytes: &[u8]| bytes.iter().enumerate().all(|(i, b)| {
+        if matches!(i, 8 | 13 | 18 | 23) { *b == b'-' } else { b.is_ascii_hexdigit() }
+    });
+
+    let mut seen: Vec<Vec<u8>> = Vec::new();
+    let mut out = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        let Some(uuid) = data.get(i..i + UUID_LEN).filter(|bytes| is_uuid(bytes)) else {
+            out.push(data[i]);
+            i += 1;
+            continue;
+        };
+        let uuid = uuid.to_ascii_uppercase();
+        let n = seen.iter().position(|s| *s == uuid).unwrap_or_else(|| {
+            seen.push(uuid);
+            seen.len() - 1
+        });
+        out.extend_from_slice(format!("00000000-0000-0000-0000-{n:012}").as_bytes());
+        i += UUID_LEN;
+    }
+    out
+}
+
+/// Pick `base`, or `base (2).pro`, `base (3).pro`... if `taken`
+fn unique_filename(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(base) {
+        return base.to_string();
+    }
+    let stem = base.trim_end_matches(".pro");
+    let mut n = 2u32;
+    loop {
+        let candidate = format!("{stem} ({n}).pro");
+        if !taken(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Write the playlist zip: embedded files and media first, then the `data` entry
+fn write_playlist_zip(
+    playlist: &rv_data::PlaylistDocument,
+    files: &[(String, FileData)],
+    path: impl AsRef<Path>,
 ) -> Result<(), PlaylistError> {
     let mut buf = Vec::new();
     playlist
@@ -367,28 +487,9 @@ pub fn write_playlist_file(
     let options = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
 
     // Write embedded .pro files first (at root level like the sample).
-    // Deduplicate filenames to avoid zip entry collisions.
-    let mut used_names = std::collections::HashSet::new();
-    for entry in entries {
-        if let Some(data) = &entry.embedded_data {
-            let base = entry.embedded_filename();
-            let filename = if used_names.contains(&base) {
-                let stem = base.trim_end_matches(".pro");
-                let mut n = 2u32;
-                loop {
-                    let candidate = format!("{stem} ({n}).pro");
-                    if !used_names.contains(&candidate) {
-                        break candidate;
-                    }
-                    n += 1;
-                }
-            } else {
-                base
-            };
-            used_names.insert(filename.clone());
-            zip.start_file(&filename, options)?;
-            zip.write_all(data)?;
-        }
+    for (filename, data) in files {
+        zip.start_file(filename.as_str(), options)?;
+        data.write_to(&mut zip)?;
     }
 
     // Write the playlist data last
@@ -399,6 +500,392 @@ pub fn write_playlist_file(
     Ok(())
 }
 
+/// An existing .proplaylist loaded for editing
+///
+/// Keeps every zip entry besides `data` (embedded .pro files and media) so
+/// writing the playlist back loses nothing.
+#[derive(Debug, Clone)]
+pub struct PlaylistFile {
+    /// The decoded playlist document
+    pub document: rv_data::PlaylistDocument,
+    /// Other zip entries as `(entry name, contents)`, in archive order
+    pub files: Vec<(String, FileData)>,
+}
+
+/// How a playlist's items differ from the items a plan calls for
+///
+/// Items are matched by name, ignoring case and surrounding whitespace.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlaylistDiff {
+    /// Plan items with no playlist item, as `(plan index, name)`
+    pub missing: Vec<(usize, String)>,
+    /// Playlist items not in the plan, as `(playlist index, name)`
+    pub extra: Vec<(usize, String)>,
+    /// Whether the items in both are in a different order
+    pub reordered: bool,
+}
+
+impl PlaylistDiff {
+    /// Whether the playlist already matches the plan.
+    pub const fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && !self.reordered
+    }
+}
+
+/// What [`PlaylistFile::sync`] changed, by item name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlaylistChangeset {
+    /// Items inserted for new plan items
+    pub added: Vec<String>,
+    /// Items removed because the plan no longer has them
+    pub removed: Vec<String>,
+    /// Items whose embedded presentation (or name) changed
+    pub updated: Vec<String>,
+    /// Whether the kept items were put in a new order
+    pub reordered: bool,
+}
+
+impl PlaylistChangeset {
+    /// Whether the sync changed nothing.
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty() && !self.reordered
+    }
+}
+
+impl std::fmt::Display for PlaylistChangeset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+        let mut parts = Vec::new();
+        for (label, names) in [("added", &self.added), ("removed", &self.removed), ("updated", &self.updated)] {
+            if !names.is_empty() {
+                parts.push(format!("{label} {}", names.join(", ")));
+            }
+        }
+        if self.reordered {
+            parts.push("reordered".to_string());
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+/// Read a .proplaylist zip
+pub fn read_playlist_file(path: impl AsRef<Path>) -> Result<PlaylistFile, PlaylistError> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut document = None;
+    let mut files = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        if entry.name() == "data" {
+            let decoded = rv_data::PlaylistDocument::decode(data.as_slice())
+                .map_err(|e| PlaylistError::Decode(e.to_string()))?;
+            document = Some(decoded);
+        } else {
+            files.push((entry.name().to_string(), FileData::Bytes(data)));
+        }
+    }
+
+    let document = document.ok_or_else(|| PlaylistError::Decode("no data entry in playlist".to_string()))?;
+    Ok(PlaylistFile { document, files })
+}
+
+/// The first playlist in the tree that holds items
+fn item_playlist(node: &rv_data::Playlist) -> Option<&rv_data::Playlist> {
+    match &node.children_type {
+        Some(playlist::ChildrenType::Items(_)) => Some(node),
+        Some(playlist::ChildrenType::Playlists(array)) => array.playlists.iter().find_map(item_playlist),
+        None => None,
+    }
+}
+
+/// Mutable [`item_playlist`]
+fn item_playlist_mut(node: &mut rv_data::Playlist) -> Option<&mut rv_data::Playlist> {
+    match node.children_type {
+        Some(playlist::ChildrenType::Items(_)) => Some(node),
+        Some(playlist::ChildrenType::Playlists(ref mut array)) => array.playlists.iter_mut().find_map(item_playlist_mut),
+        None => None,
+    }
+}
+
+/// The file name a playlist item's presentation is stored under, if it has one
+///
+/// Embedded files are found by this name, as `ProPresenter` does.
+pub fn item_file_name(item: &rv_data::PlaylistItem) -> Option<String> {
+    let Some(playlist_item::ItemType::Presentation(presentation)) = &item.item_type else {
+        return None;
+    };
+    let url = presentation.document_path.as_ref()?;
+    let path = match (&url.relative_file_path, &url.storage) {
+        (Some(url::RelativeFilePath::Local(local)), _) => local.path.clone(),
+        (_, Some(url::Storage::AbsoluteString(absolute))) => file_url_to_path(absolute),
+        _ => return None,
+    };
+    Path::new(&path).file_name().and_then(|n| n.to_str()).map(String::from)
+}
+
+/// Undo [`path_to_file_url`]
+fn file_url_to_path(file_url: &str) -> String {
+    file_url.strip_prefix("file://").unwrap_or(file_url)
+        .replace("%20", " ")
+        .replace("%23", "#")
+        .replace("%26", "&")
+}
+
+/// Name used to match playlist items to plan items
+fn match_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+impl PlaylistFile {
+    /// Name of the playlist holding the items.
+    pub fn name(&self) -> &str {
+        self.document.root_node.as_ref()
+            .and_then(item_playlist)
+            .map_or("", |playlist| playlist.name.as_str())
+    }
+
+    /// The playlist's items, in order.
+    pub fn items(&self) -> &[rv_data::PlaylistItem] {
+        match self.document.root_node.as_ref().and_then(item_playlist).and_then(|p| p.children_type.as_ref()) {
+            Some(playlist::ChildrenType::Items(items)) => &items.items,
+            _ => &[],
+        }
+    }
+
+    fn items_mut(&mut self) -> Option<&mut Vec<rv_data::PlaylistItem>> {
+        match self.document.root_node.as_mut().and_then(item_playlist_mut)?.children_type {
+            Some(playlist::ChildrenType::Items(ref mut items)) => Some(&mut items.items),
+            _ => None,
+        }
+    }
+
+    /// The embedded .pro data for an item, if the bundle has it.
+    pub fn embedded_data(&self, item: &rv_data::PlaylistItem) -> Option<&[u8]> {
+        let name = item_file_name(item)?;
+        self.files.iter().find(|(file, _)| *file == name).and_then(|(_, data)| data.as_bytes())
+    }
+
+    /// Bundle paths of the media an item's embedded presentation uses
+    fn item_media(&self, item: &rv_data::PlaylistItem) -> HashSet<String> {
+        self.embedded_data(item)
+            .and_then(|data| rv_data::Presentation::decode(data).ok())
+            .map(|presentation| media::bundled_media(&presentation))
+            .unwrap_or_default()
+    }
+
+    /// Drop the bundled media in `candidates` that no item uses any more
+    fn prune_media(&mut self, candidates: &HashSet<String>) {
+        let used: HashSet<String> = self.items().iter().flat_map(|item| self.item_media(item)).collect();
+        self.files.retain(|(file, _)| !candidates.contains(file) || used.contains(file));
+    }
+
+    /// Insert an entry at `index` (clamped to the end), embedding its data
+    /// and local media if it has any.
+    ///
+    /// An embedded file whose name is taken gets a numbered name, and the
+    /// new item points at that name.
+    pub fn insert_entry(&mut self, index: usize, entry: &PlaylistEntry) {
+        let filename = unique_filename(&entry.embedded_filename(), |name| self.files.iter().any(|(file, _)| file == name));
+        let item = playlist_item(entry, &filename);
+        if let Some(data) = &entry.embedded_data {
+            let data = embed_media(data, &mut self.files);
+            self.files.push((filename, FileData::Bytes(data)));
+        }
+
+        // A playlist with no item list gets the structure we build
+        if self.items_mut().is_none() {
+            self.document = build_playlist(self.name(), &[]);
+        }
+        if let Some(items) = self.items_mut() {
+            items.insert(index.min(items.len()), item);
+        }
+    }
+
+    /// Remove the item at `index`, dropping its embedded file and media once no item uses them.
+    pub fn remove_item(&mut self, index: usize) -> Option<rv_data::PlaylistItem> {
+        let media = self.items().get(index).map(|item| self.item_media(item))?;
+        let items = self.items_mut()?;
+        let item = items.remove(index);
+        self.prune_media(&media);
+        if let Some(name) = item_file_name(&item) {
+            let still_used = self.items().iter().any(|other| item_file_name(other).as_ref() == Some(&name));
+            if !still_used {
+                self.files.retain(|(file, _)| *file != name);
+            }
+        }
+        Some(item)
+    }
+
+    /// Move the item at `from` to position `to`. Returns false if either is out of range.
+    pub fn move_item(&mut self, from: usize, to: usize) -> bool {
+        let Some(items) = self.items_mut() else { return false };
+        if from >= items.len() || to >= items.len() {
+            return false;
+        }
+        let item = items.remove(from);
+        items.insert(to, item);
+        true
+    }
+
+    /// Compare the items to a plan's item names, in plan order.
+    ///
+    /// Repeated names match playlist items of that name in turn.
+    pub fn compare<S: AsRef<str>>(&self, plan: &[S]) -> PlaylistDiff {
+        let keys: Vec<String> = self.items().iter().map(|item| match_key(&item.name)).collect();
+        let mut used = vec![false; keys.len()];
+        let mut diff = PlaylistDiff::default();
+        let mut last_matched = None;
+
+        for (plan_index, name) in plan.iter().enumerate() {
+            let key = match_key(name.as_ref());
+            let found = keys.iter().zip(&used).position(|(k, used)| *k == key && !used);
+            match found {
+                Some(index) => {
+                    if let Some(slot) = used.get_mut(index) {
+                        *slot = true;
+                    }
+                    diff.reordered |= last_matched.is_some_and(|last| index < last);
+                    last_matched = Some(index);
+                }
+                None => diff.missing.push((plan_index, name.as_ref().to_string())),
+            }
+        }
+
+        diff.extra = self.items().iter().zip(&used).enumerate()
+            .filter(|(_, (_, used))| !**used)
+            .map(|(i, (item, _))| (i, item.name.clone()))
+            .collect();
+        diff
+    }
+
+    /// The Planning Center item ID stored in an item's tags, if any.
+    pub fn item_pco_id(&self, item: &rv_data::PlaylistItem) -> Option<String> {
+        item.tags.iter().find_map(|uuid| {
+            self.document.tags.iter()
+                .find(|tag| tag.uuid.as_ref() == Some(uuid))
+                .and_then(|tag| tag.name.strip_prefix(PCO_TAG_PREFIX))
+                .map(String::from)
+        })
+    }
+
+    /// Bring the items in line with `entries`, the plan's items in order.
+    ///
+    /// Items are matched by their stored Planning Center item ID, then by
+    /// name for items without one. Matched items keep their UUID and
+    /// settings, and their embedded file is only rewritten if its contents
+    /// (with media bundled) changed; UUIDs regenerated by a rebuild don't
+    /// count. Unmatched entries are inserted and unmatched items removed,
+    /// along with media nothing uses any more.
+    pub fn sync(&mut self, entries: &[PlaylistEntry]) -> PlaylistChangeset {
+        let mut changes = PlaylistChangeset::default();
+        if self.items_mut().is_none() {
+            self.document = build_playlist(self.name(), &[]);
+        }
+        let old_media: HashSet<String> = self.items().iter().flat_map(|item| self.item_media(item)).collect();
+        let old_items = self.items_mut().map(std::mem::take).unwrap_or_default();
+        let old_ids: Vec<Option<String>> = old_items.iter().map(|item| self.item_pco_id(item)).collect();
+        let old_files: Vec<Option<String>> = old_items.iter().map(item_file_name).collect();
+        let claimed = |id: &String| entries.iter().any(|entry| entry.pco_item_id.as_ref() == Some(id));
+
+        let mut used = vec![false; old_items.len()];
+        let mut last_matched = None;
+        let mut items = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let key = match_key(&entry.name);
+            let by_id = entry.pco_item_id.as_ref().and_then(|id| {
+                old_ids.iter().zip(&used).position(|(old, used)| !used && old.as_ref() == Some(id))
+            });
+            let found = by_id.or_else(|| {
+                old_items.iter().enumerate().position(|(i, item)| {
+                    !used[i] && match_key(&item.name) == key && !old_ids[i].as_ref().is_some_and(claimed)
+                })
+            });
+
+            let Some(index) = found else {
+                let filename = unique_filename(&entry.embedded_filename(), |name| self.files.iter().any(|(file, _)| file == name));
+                let mut item = playlist_item(entry, &filename);
+                if let Some(data) = &entry.embedded_data {
+                    let data = embed_media(data, &mut self.files);
+                    self.files.push((filename, FileData::Bytes(data)));
+                }
+                if let Some(id) = &entry.pco_item_id {
+                    item.tags.push(pco_tag(&mut self.document.tags, id));
+                }
+                changes.added.push(entry.name.clone());
+                items.push(item);
+                continue;
+            };
+
+            used[index] = true;
+            changes.reordered |= last_matched.is_some_and(|last| index < last);
+            last_matched = Some(index);
+            let mut item = old_items[index].clone();
+            let mut updated = item.name != entry.name;
+            item.name.clone_from(&entry.name);
+            if let Some(data) = &entry.embedded_data {
+                let data = &embed_media(data, &mut self.files);
+                let shared = old_files.iter().filter(|file| **file == old_files[index]).count() > 1;
+                let file = old_files[index].as_ref().filter(|_| !shared)
+                    .and_then(|name| self.files.iter_mut().find(|(file, _)| file == name));
+                if let Some((_, old_data)) = file {
+                    if !old_data.as_bytes().is_some_and(|old| same_content(old, data)) {
+                        *old_data = FileData::Bytes(data.clone());
+                        updated = true;
+                    }
+                } else {
+                    // External or shared: give the item its own embedded file
+                    let filename = unique_filename(&entry.embedded_filename(), |name| self.files.iter().any(|(file, _)| file == name));
+                    item.item_type = playlist_item(entry, &filename).item_type;
+                    self.files.push((filename, FileData::Bytes(data.clone())));
+                    updated = true;
+                }
+            }
+            if let Some(id) = &entry.pco_item_id {
+                let tag = pco_tag(&mut self.document.tags, id);
+                if !item.tags.contains(&tag) {
+                    item.tags.push(tag);
+                }
+            }
+            if updated {
+                changes.updated.push(entry.name.clone());
+            }
+            items.push(item);
+        }
+
+        changes.removed = old_items.iter().zip(&used)
+            .filter(|(_, used)| !**used)
+            .map(|(item, _)| item.name.clone())
+            .collect();
+        if let Some(slot) = self.items_mut() {
+            *slot = items;
+        }
+
+        // Drop embedded files and ID tags only removed items used
+        let kept_files: Vec<Option<String>> = self.items().iter().map(item_file_name).collect();
+        let orphaned: Vec<&String> = old_files.iter().zip(&used)
+            .filter_map(|(file, used)| file.as_ref().filter(|_| !used))
+            .filter(|file| !kept_files.contains(&Some((*file).clone())))
+            .collect();
+        self.files.retain(|(file, _)| !orphaned.contains(&file));
+        self.prune_media(&old_media);
+        let kept_tags: Vec<rv_data::Uuid> = self.items().iter().flat_map(|item| item.tags.iter().cloned()).collect();
+        self.document.tags.retain(|tag| {
+            !tag.name.starts_with(PCO_TAG_PREFIX) || tag.uuid.as_ref().is_some_and(|uuid| kept_tags.contains(uuid))
+        });
+
+        changes
+    }
+
+    /// Write the playlist back out, with its embedded files.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), PlaylistError> {
+        write_playlist_zip(&self.document, &self.files, path)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
@@ -444,6 +931,7 @@ mod tests {
                 presentation_path: "/path/to/amazing_grace.pro".to_string(),
                 arrangement_uuid: None,
                 embedded_data: None,
+                pco_item_id: None,
             },
             PlaylistEntry {
                 name: "How Great Thou Art".to_string(),
@@ -452,6 +940,7 @@ mod tests {
                 presentation_path: "/path/to/how_great.pro".to_string(),
                 arrangement_uuid: Some(Uuid::new_v4()),
                 embedded_data: None,
+                pco_item_id: None,
             },
         ];
 
@@ -619,6 +1108,7 @@ mod tests {
             presentation_path: String::new(),
             arrangement_uuid: None,
             embedded_data: None,
+            pco_item_id: None,
         };
         // Parens preserved because matched files skip sanitization
         assert_eq!(entry.embedded_filename(), "Morning By Morning (I Will Trust).pro");
@@ -634,6 +1124,7 @@ mod tests {
                 presentation_path: String::new(),
                 arrangement_uuid: None,
                 embedded_data: Some(vec![1]),
+                pco_item_id: None,
             },
             PlaylistEntry {
                 name: "Scripture (Hope)".to_string(),
@@ -642,6 +1133,7 @@ mod tests {
                 presentation_path: String::new(),
                 arrangement_uuid: None,
                 embedded_data: Some(vec![2]),
+                pco_item_id: None,
             },
         ];
 
@@ -660,6 +1152,190 @@ mod tests {
         assert!(names.contains(&"Untitled (2).pro".to_string()));
     }
 
+    fn embedded_entry(name: &str, data: u8) -> PlaylistEntry {
+        PlaylistEntry {
+            name: name.to_string(),
+            slide_type: SlideType::Lyrics,
+            from_matched_file: true,
+            presentation_path: String::new(),
+            arrangement_uuid: None,
+            embedded_data: Some(vec![data]),
+            pco_item_id: None,
+        }
+    }
+
+    fn item_names(playlist: &PlaylistFile) -> Vec<&str> {
+        playlist.items().iter().map(|item| item.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_read_edit_and_write_back() {
+        let entries = vec![
+            embedded_entry("Amazing Grace", 1),
+            embedded_entry("Be Thou My Vision", 2),
+            PlaylistEntry {
+                name: "Doxology".to_string(),
+                slide_type: SlideType::Lyrics,
+                from_matched_file: true,
+                presentation_path: "/Users/Shared/ProPresenter/Libraries/Default/Doxology.pro".to_string(),
+                arrangement_uuid: None,
+                embedded_data: None,
+                pco_item_id: None,
+            },
+        ];
+        let output_path = get_test_output_path("test_edit.proplaylist");
+        write_playlist_file(&build_playlist("Last Week", &entries), &entries, &output_path).unwrap();
+
+        let mut playlist = read_playlist_file(&output_path).unwrap();
+        assert_eq!(playlist.name(), "Last Week");
+        assert_eq!(item_names(&playlist), ["Amazing Grace", "Be Thou My Vision", "Doxology"]);
+        assert_eq!(playlist.embedded_data(&playlist.items()[1]), Some([2].as_slice()));
+        assert_eq!(item_file_name(&playlist.items()[2]).as_deref(), Some("Doxology.pro"));
+        assert_eq!(playlist.embedded_data(&playlist.items()[2]), None);
+
+        let diff = playlist.compare(&["be thou my vision", "Amazing Grace", "Holy, Holy, Holy"]);
+        assert_eq!(diff.missing, [(2, "Holy, Holy, Holy".to_string())]);
+        assert_eq!(diff.extra, [(2, "Doxology".to_string())]);
+        assert!(diff.reordered);
+
+        // Bring it in line with the plan
+        assert!(playlist.remove_item(2).is_some());
+        assert!(playlist.move_item(1, 0));
+        playlist.insert_entry(2, &embedded_entry("Holy, Holy, Holy", 3));
+        // A second file named like an existing one gets its own entry
+        playlist.insert_entry(9, &embedded_entry("Amazing Grace", 4));
+        assert_eq!(item_file_name(&playlist.items()[3]).as_deref(), Some("Amazing Grace (2).pro"));
+        assert!(playlist.remove_item(3).is_some());
+        assert!(!playlist.move_item(0, 3));
+
+        let edited_path = get_test_output_path("test_edit_saved.proplaylist");
+        playlist.write(&edited_path).unwrap();
+        let saved = read_playlist_file(&edited_path).unwrap();
+        assert_eq!(item_names(&saved), ["Be Thou My Vision", "Amazing Grace", "Holy, Holy, Holy"]);
+        assert!(saved.compare(&["Be Thou My Vision", "Amazing Grace", "Holy, Holy, Holy"]).is_empty());
+        let files: Vec<&str> = saved.files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(files, ["Amazing Grace.pro", "Be Thou My Vision.pro", "Holy, Holy, Holy.pro"]);
+        assert_eq!(saved.embedded_data(&saved.items()[2]), Some([3].as_slice()));
+    }
+
+    fn plan_entry(id: &str, name: &str, data: u8) -> PlaylistEntry {
+        PlaylistEntry { pco_item_id: Some(id.to_string()), ..embedded_entry(name, data) }
+    }
+
+    #[test]
+    fn test_sync_with_changed_plan() {
+        let entries = vec![
+            plan_entry("101", "Amazing Grace", 1),
+            plan_entry("102", "Sermon", 2),
+            plan_entry("103", "Doxology", 3),
+            embedded_entry("Announcements", 4),
+        ];
+        let output_path = get_test_output_path("test_sync.proplaylist");
+        write_playlist_file(&build_playlist("Sunday", &entries), &entries, &output_path).unwrap();
+        let mut playlist = read_playlist_file(&output_path).unwrap();
+        assert_eq!(playlist.item_pco_id(&playlist.items()[1]).as_deref(), Some("102"));
+        assert_eq!(playlist.item_pco_id(&playlist.items()[3]), None);
+        let sermon_uuid = playlist.items()[1].uuid.clone();
+
+        // Sermon renamed and moved up, Doxology dropped, a new song added,
+        // Announcements matched by name
+        let plan = vec![
+            plan_entry("102", "Sermon: John 3", 2),
+            plan_entry("101", "Amazing Grace", 5),
+            plan_entry("104", "Holy, Holy, Holy", 6),
+            embedded_entry("Announcements", 4),
+        ];
+        let changes = playlist.sync(&plan);
+        assert_eq!(changes.added, ["Holy, Holy, Holy"]);
+        assert_eq!(changes.removed, ["Doxology"]);
+        assert_eq!(changes.updated, ["Sermon: John 3", "Amazing Grace"]);
+        assert!(changes.reordered);
+        assert_eq!(
+            changes.to_string(),
+            "added Holy, Holy, Holy; removed Doxology; updated Sermon: John 3, Amazing Grace; reordered"
+        );
+
+        assert_eq!(item_names(&playlist), ["Sermon: John 3", "Amazing Grace", "Holy, Holy, Holy", "Announcements"]);
+        assert_eq!(playlist.items()[0].uuid, sermon_uuid);
+        assert_eq!(playlist.embedded_data(&playlist.items()[1]), Some([5].as_slice()));
+        assert_eq!(playlist.item_pco_id(&playlist.items()[2]).as_deref(), Some("104"));
+        let files: Vec<&str> = playlist.files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(files, ["Amazing Grace.pro", "Sermon.pro", "Announcements.pro", "Holy, Holy, Holy.pro"]);
+        assert!(!playlist.document.tags.iter().any(|tag| tag.name == "PCO 103"));
+
+        // Syncing the same plan again changes nothing
+        let synced_path = get_test_output_path("test_sync_saved.proplaylist");
+        playlist.write(&synced_path).unwrap();
+        let mut saved = read_playlist_file(&synced_path).unwrap();
+        assert!(saved.sync(&plan).is_empty());
+    }
+
+    #[test]
+    fn test_sync_ignores_rebuilt_uuids() {
+        use crate::propresenter::template::{build_presentation_from_template, TemplateCache, TemplateType};
+
+        let template_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data").join("templates");
+        let template = TemplateCache::new(vec![template_dir]).get(TemplateType::Song).unwrap().clone();
+        let build = |lyrics: &str| {
+            let content: Vec<String> = lyrics.lines().map(String::from).collect();
+            let data = build_presentation_from_template("Doxology", &template, &content).unwrap().encode_to_vec();
+            PlaylistEntry { embedded_data: Some(data), ..plan_entry("103", "Doxology", 0) }
+        };
+
+        let lyrics = "[Verse 1]\nPraise God from whom all blessings flow\n\n[Chorus]\nAmen";
+        let first = vec![build(lyrics)];
+        let output_path = get_test_output_path("test_sync_rebuilt.proplaylist");
+        write_playlist_file(&build_playlist("Sunday", &first), &first, &output_path).unwrap();
+        let mut playlist = read_playlist_file(&output_path).unwrap();
+
+        let rebuilt = vec![build(lyrics)];
+        assert_ne!(first[0].embedded_data, rebuilt[0].embedded_data);
+        assert!(playlist.sync(&rebuilt).is_empty());
+
+        let edited = vec![build(&lyrics.replace("Amen", "Amen, amen"))];
+        assert_eq!(playlist.sync(&edited).updated, ["Doxology"]);
+    }
+
+    #[test]
+    fn test_media_is_bundled_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let background = dir.path().join("Blue Loop.mov");
+        std::fs::write(&background, b"loop").unwrap();
+        let media_url = rv_data::Url {
+            platform: url::Platform::Macos as i32,
+            storage: Some(url::Storage::AbsoluteString(path_to_file_url(&background.to_string_lossy()))),
+            relative_file_path: None,
+        };
+        let background_cue = rv_data::Cue {
+            actions: vec![rv_data::Action {
+                action_type_data: Some(rv_data::action::ActionTypeData::Media(rv_data::action::MediaType {
+                    element: Some(rv_data::Media { url: Some(media_url), ..Default::default() }),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let presentation = rv_data::Presentation { cues: vec![background_cue], ..Default::default() };
+        let entries = vec![PlaylistEntry {
+            embedded_data: Some(presentation.encode_to_vec()),
+            ..plan_entry("201", "Welcome", 0)
+        }];
+
+        let output_path = get_test_output_path("test_media.proplaylist");
+        write_playlist_file(&build_playlist("Media", &entries), &entries, &output_path).unwrap();
+        let mut playlist = read_playlist_file(&output_path).unwrap();
+        let files: Vec<&str> = playlist.files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(files, ["Welcome.pro", "Media/Blue Loop.mov"]);
+        let embedded = rv_data::Presentation::decode(playlist.embedded_data(&playlist.items()[0]).unwrap()).unwrap();
+        assert_eq!(media::bundled_media(&embedded), HashSet::from(["Media/Blue Loop.mov".to_string()]));
+
+        // The bundled copy counts as unchanged, and goes once nothing uses it
+        assert!(playlist.sync(&entries).is_empty());
+        assert!(playlist.remove_item(0).is_some());
+        assert!(playlist.files.is_empty());
+    }
+
     #[test]
     fn test_write_playlist_file() {
         let entries = vec![
@@ -670,6 +1346,7 @@ mod tests {
                 presentation_path: "/Users/Shared/ProPresenter/Libraries/Default/Test.pro".to_string(),
                 arrangement_uuid: None,
                 embedded_data: None,
+                pco_item_id: None,
             },
         ];
 
@@ -1,13 +1,13 @@
 //! RTF conversion utilities for `ProPresenter`.
 //!
 //! Provides RTF parsing and generation for `ProPresenter` slide content.
+//! RTF is read by a tokenizer into styled runs ([`RtfDocument`]) and written
+//! back from them, so per-run formatting survives text replacement.
 
-// Allow unwrap for compile-time constant regex patterns in LazyLock blocks
-#![allow(dead_code, clippy::unwrap_used)]
+#![allow(dead_code)]
 
+use std::collections::HashMap;
 use std::fmt::Write;
-use std::sync::LazyLock;
-use regex::Regex;
 
 /// Superscript digit characters for detection
 const SUPERSCRIPT_CHARS: &[char] = &['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
@@ -17,6 +17,15 @@ fn is_superscript(c: char) -> bool {
     SUPERSCRIPT_CHARS.contains(&c)
 }
 
+/// Convert a digit to its superscript character
+const fn digit_to_superscript(c: char) -> char {
+    match c {
+        '0' => '⁰', '1' => '¹', '2' => '²', '3' => '³', '4' => '⁴',
+        '5' => '⁵', '6' => '⁶', '7' => '⁷', '8' => '⁸', '9' => '⁹',
+        _ => c,
+    }
+}
+
 /// Convert superscript character to regular digit
 const fn superscript_to_digit(c: char) -> char {
     match c {
@@ -50,6 +59,646 @@ impl Default for RtfOptions {
     }
 }
 
+/// Character formatting of a run of RTF text
+#[allow(clippy::struct_excessive_bools)] // Independent RTF toggles (\b, \i, \ul, \super)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunStyle {
+    /// Font name from the font table
+    pub font_name: String,
+    /// Font size in half-points, as `\fs` gives it
+    pub font_size_half_points: u32,
+    /// Text color RGB
+    pub color: (u8, u8, u8),
+    /// Bold (`\b`)
+    pub bold: bool,
+    /// Italic (`\i`)
+    pub italic: bool,
+    /// Underlined (`\ul`)
+    pub underline: bool,
+    /// Raised superscript (`\super`), as verse numbers are
+    pub superscript: bool,
+    /// Character spacing in quarter-points (`\expnd`)
+    pub kerning: i32,
+}
+
+impl Default for RunStyle {
+    fn default() -> Self {
+        Self::from(&RtfOptions::default())
+    }
+}
+
+impl From<&RtfOptions> for RunStyle {
+    fn from(options: &RtfOptions) -> Self {
+        Self {
+            font_name: options.font_name.clone(),
+            font_size_half_points: options.font_size * 2,
+            color: options.color,
+            bold: false,
+            italic: false,
+            underline: false,
+            superscript: false,
+            kerning: options.kerning,
+        }
+    }
+}
+
+impl From<&RunStyle> for RtfOptions {
+    fn from(style: &RunStyle) -> Self {
+        Self {
+            font_name: style.font_name.clone(),
+            font_size: style.font_size_half_points / 2,
+            color: style.color,
+            kerning: style.kerning,
+        }
+    }
+}
+
+/// A run of text in one style
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RtfRun {
+    /// Run text; `\n` is a paragraph break
+    pub text: String,
+    /// Formatting of the whole run
+    pub style: RunStyle,
+}
+
+/// RTF text as styled runs
+///
+/// Superscript runs hold plain digits; [`RtfDocument::text`] shows them as
+/// superscript characters, the way slide text is edited.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RtfDocument {
+    /// Paragraph formatting control words (`\pard...\qc...`), empty for the default
+    pub paragraph: String,
+    /// Formatting in effect where the text starts
+    pub style: RunStyle,
+    /// Styled text in order
+    pub runs: Vec<RtfRun>,
+}
+
+/// A paragraph of slide text with optional bold emphasis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtfLine<'a> {
+    /// Plain text of the paragraph (may contain superscript digits)
+    pub text: &'a str,
+    /// Render the paragraph bold
+    pub bold: bool,
+}
+
+/// Paragraph formatting used when a document has none of its own
+const DEFAULT_PARAGRAPH: &str = r"\pard\pardeftab1680\sl20\slleading480\pardirnatural\partightenfactor0";
+
+impl RtfDocument {
+    /// Parse an RTF document, or `None` if `rtf` isn't RTF.
+    pub fn parse(rtf: &str) -> Option<Self> {
+        rtf.starts_with("{\\rtf").then(|| Parser::default().run(&tokenize(rtf)))
+    }
+
+    /// Parse RTF bytes as stored in a text element.
+    pub fn from_bytes(rtf_data: &[u8]) -> Option<Self> {
+        Self::parse(&String::from_utf8_lossy(rtf_data))
+    }
+
+    /// Plain paragraphs in `style`, bold where a line asks for it.
+    pub fn from_lines(lines: &[RtfLine<'_>], style: &RunStyle) -> Self {
+        let document = Self { paragraph: String::new(), style: style.clone(), runs: Vec::new() };
+        document.with_lines(lines)
+    }
+
+    /// The text with paragraph breaks as `\n` and superscript digits as `¹²³`.
+    pub fn text(&self) -> String {
+        plain_text(&self.runs)
+    }
+
+    /// Runs split at paragraph breaks.
+    pub fn paragraphs(&self) -> Vec<Vec<RtfRun>> {
+        let mut paragraphs = vec![Vec::new()];
+        for run in &self.runs {
+            for (i, part) in run.text.split('\n').enumerate() {
+                if i > 0 {
+                    paragraphs.push(Vec::new());
+                }
+                if let (false, Some(paragraph)) = (part.is_empty(), paragraphs.last_mut()) {
+                    paragraph.push(RtfRun { text: part.to_string(), style: run.style.clone() });
+                }
+            }
+        }
+        paragraphs
+    }
+
+    /// The style most of the first paragraph is set in, ignoring superscripts.
+    pub fn main_style(&self) -> RunStyle {
+        self.paragraphs().iter()
+            .find_map(|paragraph| main_style(paragraph))
+            .unwrap_or_else(|| RunStyle { superscript: false, ..self.style.clone() })
+    }
+
+    /// Replace the text with `lines`, keeping this document's formatting.
+    ///
+    /// A line equal to the paragraph at its position keeps that paragraph's
+    /// runs; other lines take the main style of the paragraph at their
+    /// position (or of the last one), made bold if the line asks for it.
+    #[must_use]
+    pub fn with_lines(&self, lines: &[RtfLine<'_>]) -> Self {
+        let paragraphs = self.paragraphs();
+        let base = RunStyle { superscript: false, ..self.style.clone() };
+        let mut runs: Vec<RtfRun> = Vec::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                let style = runs.last().map_or(&base, |run| &run.style).clone();
+                push_run(&mut runs, "\n", style);
+            }
+            let old = paragraphs.get(i);
+            if let Some(old) = old.filter(|old| !line.bold && plain_text(old) == line.text) {
+                for run in old {
+                    push_run(&mut runs, &run.text, run.style.clone());
+                }
+                continue;
+            }
+            let mut style = old.or_else(|| paragraphs.last())
+                .and_then(|paragraph| main_style(paragraph))
+                .unwrap_or_else(|| base.clone());
+            style.bold |= line.bold;
+            push_text(&mut runs, line.text, &style);
+        }
+
+        Self { paragraph: self.paragraph.clone(), style: base, runs }
+    }
+
+    /// Write the document as `ProPresenter`-compatible RTF.
+    ///
+    /// The header sets the document's style; runs in any other style are
+    /// written as groups that change only what differs.
+    pub fn to_rtf(&self) -> String {
+        let base = RunStyle { superscript: false, ..self.style.clone() };
+        let mut fonts = vec![base.font_name.as_str()];
+        let mut colors = vec![base.color];
+        for run in &self.runs {
+            if !fonts.contains(&run.style.font_name.as_str()) {
+                fonts.push(&run.style.font_name);
+            }
+            if !colors.contains(&run.style.color) {
+                colors.push(run.style.color);
+            }
+        }
+
+        // RTF header matching ProPresenter's format
+        let mut rtf = String::from("{\\rtf1\\ansi\\ansicpg1252\\cocoartf2821\n");
+        rtf.push_str(r"\cocoatextscaling0\cocoaplatform0");
+
+        rtf.push_str(r"{\fonttbl");
+        for (i, name) in fonts.iter().enumerate() {
+            let _ = write!(rtf, r"\f{i}\fswiss\fcharset0 {name};");
+        }
+        rtf.push_str("}\n");
+
+        // Color table - index 0 is auto, index 1 repeats the main color and
+        // the run colors start at index 2
+        rtf.push_str(r"{\colortbl;");
+        for (r, g, b) in std::iter::once(base.color).chain(colors.iter().copied()) {
+            let _ = write!(rtf, r"\red{r}\green{g}\blue{b};");
+        }
+        rtf.push_str("}\n");
+
+        // Expanded color table for Cocoa, in percent times 1000
+        rtf.push_str(r"{\*\expandedcolortbl;;");
+        for &(r, g, b) in &colors {
+            let percent = |c: u8| u32::from(c) * 100_000 / 255;
+            let (r, g, b) = (percent(r), percent(g), percent(b));
+            let _ = write!(rtf, r"\cssrgb\c{r}\c{g}\c{b};");
+        }
+        rtf.push_str("}\n");
+
+        // Default tab width and paragraph formatting
+        rtf.push_str("\\deftab1680\n");
+        rtf.push_str(if self.paragraph.is_empty() { DEFAULT_PARAGRAPH } else { &self.paragraph });
+        rtf.push_str("\n\n");
+
+        // Font size, color reference (cf2 = color table index 2), and kerning
+        // expnd is in quarter-points, expndtw is in twentieths of a point
+        // 1 quarter-point = 5 twentieths of a point (20/4 = 5)
+        let kerning = base.kerning;
+        let kerning_tw = base.kerning * 5;
+        let _ = write!(rtf, r"\f0\fs{} \cf2 \kerning1\expnd{kerning}\expndtw{kerning_tw}", base.font_size_half_points);
+        for (on, word) in [(base.bold, r"\b"), (base.italic, r"\i"), (base.underline, r"\ul")] {
+            if on {
+                rtf.push_str(word);
+            }
+        }
+        rtf.push('\n');
+
+        for run in &self.runs {
+            if run.style == base {
+                push_escaped(&mut rtf, &run.text);
+            } else {
+                rtf.push('{');
+                push_style_words(&mut rtf, &run.style, &base, &fonts, &colors);
+                rtf.push(' ');
+                push_escaped(&mut rtf, &run.text);
+                rtf.push('}');
+            }
+        }
+
+        // Close RTF document
+        rtf.push('}');
+        rtf
+    }
+}
+
+/// Append `text` to `runs`, joining the last run if it has the same style
+fn push_run(runs: &mut Vec<RtfRun>, text: &str, style: RunStyle) {
+    match runs.last_mut() {
+        Some(run) if run.style == style => run.text.push_str(text),
+        _ => runs.push(RtfRun { text: text.to_string(), style }),
+    }
+}
+
+/// Append plain text in `style`, turning superscript digits into superscript runs
+fn push_text(runs: &mut Vec<RtfRun>, text: &str, style: &RunStyle) {
+    let superscript = RunStyle { superscript: true, ..style.clone() };
+    for c in text.chars() {
+        if is_superscript(c) {
+            push_run(runs, superscript_to_digit(c).encode_utf8(&mut [0; 4]), superscript.clone());
+        } else {
+            push_run(runs, c.encode_utf8(&mut [0; 4]), style.clone());
+        }
+    }
+}
+
+/// Join runs into plain text, showing superscript digits as `¹²³`
+fn plain_text(runs: &[RtfRun]) -> String {
+    runs.iter()
+        .flat_map(|run| run.text.chars().map(move |c| if run.style.superscript { digit_to_superscript(c) } else { c }))
+        .collect()
+}
+
+/// The style of a paragraph's longest non-superscript run (first on ties)
+fn main_style(paragraph: &[RtfRun]) -> Option<RunStyle> {
+    let run = paragraph.iter()
+        .rev()
+        .filter(|run| !run.style.superscript)
+        .max_by_key(|run| run.text.chars().count())
+        .or_else(|| paragraph.first())?;
+    Some(RunStyle { superscript: false, ..run.style.clone() })
+}
+
+/// Write the control words that turn `base` into `style`
+fn push_style_words(rtf: &mut String, style: &RunStyle, base: &RunStyle, fonts: &[&str], colors: &[(u8, u8, u8)]) {
+    if style.font_name != base.font_name {
+        let index = fonts.iter().position(|name| *name == style.font_name).unwrap_or(0);
+        let _ = write!(rtf, r"\f{index}");
+    }
+    if style.font_size_half_points != base.font_size_half_points {
+        let _ = write!(rtf, r"\fs{}", style.font_size_half_points);
+    }
+    if style.color != base.color {
+        let index = colors.iter().position(|color| *color == style.color).unwrap_or(0) + 2;
+        let _ = write!(rtf, r"\cf{index}");
+    }
+    for (on, was, word, off) in [
+        (style.bold, base.bold, r"\b", r"\b0"),
+        (style.italic, base.italic, r"\i", r"\i0"),
+        (style.underline, base.underline, r"\ul", r"\ulnone"),
+        (style.superscript, base.superscript, r"\super", r"\nosupersub"),
+    ] {
+        if on != was {
+            rtf.push_str(if on { word } else { off });
+        }
+    }
+    if style.kerning != base.kerning {
+        let _ = write!(rtf, r"\expnd{}\expndtw{}", style.kerning, style.kerning * 5);
+    }
+}
+
+/// Append plain text to an RTF document with proper RTF encoding
+fn push_escaped(rtf: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\n' => rtf.push_str(r"\par "),
+            '\t' => rtf.push_str(r"\tab "),
+            '\\' => rtf.push_str(r"\\"),
+            '{' => rtf.push_str(r"\{"),
+            '}' => rtf.push_str(r"\}"),
+            // Windows-1252 punctuation (curly quotes, dashes, ellipsis) as code-page escapes
+            _ if CP1252_HIGH.contains(&c) => {
+                let byte = CP1252_HIGH.iter().position(|&h| h == c).unwrap_or(0) + 0x80;
+                let _ = write!(rtf, r"\'{byte:02x}");
+            }
+            // Any other non-ASCII: \uN? where N is a signed UTF-16 unit and ? the fallback
+            _ if !c.is_ascii() => {
+                for unit in c.encode_utf16(&mut [0; 2]).iter() {
+                    let code = i32::from(*unit) - if *unit > 0x7FFF { 0x1_0000 } else { 0 };
+                    let _ = write!(rtf, r"\u{code}?");
+                }
+            }
+            _ => rtf.push(c),
+        }
+    }
+}
+
+/// Windows-1252 characters for bytes 0x80-0x9F (the rest of the upper half is Latin-1)
+const CP1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+/// Decode a `\'hh` byte as Windows-1252
+fn cp1252_char(byte: u8) -> char {
+    CP1252_HIGH.get(usize::from(byte).wrapping_sub(0x80)).copied().unwrap_or_else(|| char::from(byte))
+}
+
+/// A lexical piece of RTF
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    /// `{`
+    Open,
+    /// `}`
+    Close,
+    /// `\word` with its optional numeric parameter
+    Word(&'a str, Option<i32>),
+    /// `\` followed by a non-letter (`\\`, `\{`, `\~`, `\*`, or a newline)
+    Symbol(char),
+    /// `\'hh`
+    Hex(u8),
+    /// Literal text
+    Text(&'a str),
+}
+
+/// Split RTF into tokens. Raw line breaks are formatting, not text.
+fn tokenize(rtf: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = rtf;
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '{' => {
+                tokens.push(Token::Open);
+                1
+            }
+            '}' => {
+                tokens.push(Token::Close);
+                1
+            }
+            '\r' | '\n' => 1,
+            '\\' => {
+                let (token, len) = control(&rest[1..]);
+                tokens.extend(token);
+                1 + len
+            }
+            _ => {
+                let end = rest.find(['\\', '{', '}', '\r', '\n']).unwrap_or(rest.len());
+                tokens.push(Token::Text(&rest[..end]));
+                end
+            }
+        };
+        rest = &rest[len..];
+    }
+    tokens
+}
+
+/// Read the control word or symbol after a backslash, returning it and the bytes used
+fn control(rest: &str) -> (Option<Token<'_>>, usize) {
+    let word_len = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+    if word_len > 0 {
+        let word = &rest[..word_len];
+        let after = &rest[word_len..];
+        let sign = usize::from(after.starts_with('-'));
+        let digits = after[sign..].find(|c: char| !c.is_ascii_digit()).unwrap_or(after.len() - sign);
+        let param = (digits > 0).then(|| after[..sign + digits].parse().ok()).flatten();
+        let mut len = word_len + if digits > 0 { sign + digits } else { 0 };
+        // A space after a control word is its delimiter
+        if rest[len..].starts_with(' ') {
+            len += 1;
+        }
+        return (Some(Token::Word(word, param)), len);
+    }
+
+    match rest.chars().next() {
+        Some('\'') => rest.get(1..3)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .map_or((None, 1), |byte| (Some(Token::Hex(byte)), 3)),
+        Some('\r') if rest.starts_with("\r\n") => (Some(Token::Symbol('\n')), 2),
+        Some(c) => (Some(Token::Symbol(if c == '\r' { '\n' } else { c })), c.len_utf8()),
+        None => (None, 0),
+    }
+}
+
+/// Where text in the current group goes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Destination {
+    /// Document text
+    #[default]
+    Body,
+    /// Font names
+    FontTable,
+    /// Color entries
+    ColorTable,
+    /// Ignored (`\*` groups, stylesheets, pictures)
+    Skip,
+}
+
+/// Formatting state saved and restored by groups
+#[derive(Debug, Clone)]
+struct GroupState {
+    style: RunStyle,
+    destination: Destination,
+    /// Fallback characters after each `\u` (`\uc`)
+    unicode_skip: usize,
+}
+
+impl Default for GroupState {
+    fn default() -> Self {
+        Self { style: RunStyle::default(), destination: Destination::default(), unicode_skip: 1 }
+    }
+}
+
+/// Turns tokens into an [`RtfDocument`]
+#[derive(Debug, Default)]
+struct Parser {
+    document: RtfDocument,
+    state: GroupState,
+    stack: Vec<GroupState>,
+    fonts: HashMap<i32, String>,
+    /// Font table entry being read
+    font: (Option<i32>, String),
+    colors: Vec<Option<(u8, u8, u8)>>,
+    /// Color table entry being read
+    color: Option<(u8, u8, u8)>,
+    /// `\u` fallback characters still to skip
+    pending_skip: usize,
+    high_surrogate: Option<u16>,
+    /// Whether paragraph formatting is being collected (after `\pard`, before any text)
+    in_paragraph_format: bool,
+    started: bool,
+}
+
+impl Parser {
+    fn run(mut self, tokens: &[Token<'_>]) -> RtfDocument {
+        for token in tokens {
+            match *token {
+                Token::Open => self.stack.push(self.state.clone()),
+                Token::Close => {
+                    self.note_style();
+                    if let Some(state) = self.stack.pop() {
+                        self.state = state;
+                    }
+                }
+                Token::Word(word, param) => self.word(word, param),
+                Token::Symbol(c) => self.symbol(c),
+                Token::Hex(byte) => {
+                    if !self.skip_fallback() {
+                        self.push_char(cp1252_char(byte));
+                    }
+                }
+                Token::Text(text) => {
+                    for c in text.chars() {
+                        if !self.skip_fallback() {
+                            self.push_char(c);
+                        }
+                    }
+                }
+            }
+        }
+        self.note_style();
+        self.document
+    }
+
+    /// Until text starts, the document style is whatever the body last set
+    fn note_style(&mut self) {
+        if !self.started && self.state.destination == Destination::Body {
+            self.document.style = self.state.style.clone();
+        }
+    }
+
+    /// Consume one `\u` fallback character, if any are pending
+    const fn skip_fallback(&mut self) -> bool {
+        let skip = self.pending_skip > 0;
+        self.pending_skip = self.pending_skip.saturating_sub(1);
+        skip
+    }
+
+    fn word(&mut self, word: &str, param: Option<i32>) {
+        if self.skip_fallback() {
+            return;
+        }
+        let on = param != Some(0);
+        let style = &mut self.state.style;
+        match (self.state.destination, word) {
+            (_, "fonttbl") => self.state.destination = Destination::FontTable,
+            (_, "colortbl") => self.state.destination = Destination::ColorTable,
+            (_, "stylesheet" | "info" | "pict" | "header" | "footer" | "listtable" | "listoverridetable") => {
+                self.state.destination = Destination::Skip;
+            }
+            (Destination::FontTable, "f") => self.font = (param, String::new()),
+            (Destination::ColorTable, "red" | "green" | "blue") => {
+                let value = param.and_then(|p| u8::try_from(p).ok()).unwrap_or(0);
+                let (r, g, b) = self.color.get_or_insert((0, 0, 0));
+                *match word { "red" => r, "green" => g, _ => b } = value;
+            }
+            (Destination::Body, "par" | "line") => self.push_char('\n'),
+            (Destination::Body, "tab") => self.push_char('\t'),
+            (Destination::Body, "u") => {
+                if let Some(code) = param {
+                    self.push_unicode(code);
+                }
+                self.pending_skip = self.state.unicode_skip;
+            }
+            (Destination::Body, "uc") => self.state.unicode_skip = param.and_then(|n| usize::try_from(n).ok()).unwrap_or(1),
+            (Destination::Body, "pard") if !self.started => {
+                self.document.paragraph = r"\pard".to_string();
+                self.in_paragraph_format = true;
+            }
+            (Destination::Body, "plain") => *style = RunStyle::default(),
+            (Destination::Body, "f") => {
+                if let Some(name) = param.and_then(|n| self.fonts.get(&n)) {
+                    style.font_name.clone_from(name);
+                }
+            }
+            (Destination::Body, "fs") => {
+                if let Some(size) = param.and_then(|n| u32::try_from(n).ok()) {
+                    style.font_size_half_points = size;
+                }
+            }
+            (Destination::Body, "cf") => {
+                style.color = param.and_then(|n| usize::try_from(n).ok())
+                    .and_then(|n| self.colors.get(n).copied().flatten())
+                    .unwrap_or_else(|| RunStyle::default().color);
+            }
+            (Destination::Body, "b") => style.bold = on,
+            (Destination::Body, "i") => style.italic = on,
+            (Destination::Body, "ul") => style.underline = on,
+            (Destination::Body, "ulnone") => style.underline = false,
+            (Destination::Body, "super") => style.superscript = on,
+            (Destination::Body, "sub" | "nosupersub") => style.superscript = false,
+            (Destination::Body, "expnd") => style.kerning = param.unwrap_or(0),
+            // Character formatting that isn't modelled
+            (Destination::Body, "kerning" | "expndtw" | "cb" | "highlight" | "strike" | "up" | "dn") => {}
+            (Destination::Body, _) if self.in_paragraph_format => {
+                let _ = write!(self.document.paragraph, r"\{word}");
+                if let Some(param) = param {
+                    let _ = write!(self.document.paragraph, "{param}");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn symbol(&mut self, c: char) {
+        if c == '*' {
+            self.state.destination = Destination::Skip;
+            return;
+        }
+        if self.skip_fallback() {
+            return;
+        }
+        match c {
+            '\\' | '{' | '}' => self.push_char(c),
+            '~' => self.push_char('\u{a0}'),
+            '_' => self.push_char('\u{2011}'),
+            // Escaped line breaks are paragraph breaks in Cocoa RTF
+            '\n' => self.push_char('\n'),
+            _ => {}
+        }
+    }
+
+    /// Push a `\u` character; negative values are UTF-16 units above 0x7FFF
+    fn push_unicode(&mut self, code: i32) {
+        let Ok(unit) = u16::try_from(if code < 0 { code + 0x1_0000 } else { code }) else { return };
+        let units = match self.high_surrogate.take() {
+            Some(high) if (0xDC00..0xE000).contains(&unit) => vec![high, unit],
+            _ if (0xD800..0xDC00).contains(&unit) => {
+                self.high_surrogate = Some(unit);
+                return;
+            }
+            _ => vec![unit],
+        };
+        for c in char::decode_utf16(units).flatten() {
+            self.push_char(c);
+        }
+    }
+
+    fn push_char(&mut self, c: char) {
+        match self.state.destination {
+            Destination::Body => {
+                if !self.started {
+                    self.started = true;
+                    self.in_paragraph_format = false;
+                    self.document.style = self.state.style.clone();
+                }
+                push_run(&mut self.document.runs, c.encode_utf8(&mut [0; 4]), self.state.style.clone());
+            }
+            Destination::FontTable if c == ';' => {
+                let (number, name) = std::mem::take(&mut self.font);
+                let number = number.or_else(|| i32::try_from(self.fonts.len()).ok()).unwrap_or_default();
+                self.fonts.insert(number, name.trim().to_string());
+            }
+            Destination::FontTable => self.font.1.push(c),
+            Destination::ColorTable if c == ';' => self.colors.push(self.color.take()),
+            Destination::ColorTable | Destination::Skip => {}
+        }
+    }
+}
+
 /// Convert plain text to RTF format (simple version for backwards compatibility)
 /// 
 /// Handles:
@@ -68,102 +717,19 @@ pub fn text_to_rtf(text: &str) -> String {
 /// - Paragraph formatting
 /// - Superscript support
 pub fn text_to_rtf_styled(text: &str, options: &RtfOptions) -> String {
-    let (r, g, b) = options.color;
-    let font_size_halfpoints = options.font_size * 2;
-    
-    // Build RTF header matching ProPresenter's format
-    let mut rtf = String::new();
-    
-    // RTF header
-    rtf.push_str(r"{\rtf1\ansi\ansicpg1252\cocoartf2821");
-    rtf.push('\n');
-    
-    // Cocoa platform settings
-    rtf.push_str(r"\cocoatextscaling0\cocoaplatform0");
-    
-    // Font table
-    let font_name = &options.font_name;
-    let _ = write!(rtf, r"{{\fonttbl\f0\fswiss\fcharset0 {font_name};}}");
-
-    rtf.push('\n');
-    
-    // Color table - index 0 is auto, index 1 and 2 are our color
-    let _ = write!(rtf, r"{{\colortbl;\red{r}\green{g}\blue{b};\red{r}\green{g}\blue{b};}}");
-
-    rtf.push('\n');
-    
-    // Expanded color table for Cocoa
-    rtf.push_str(r"{\*\expandedcolortbl;;\cssrgb\c100000\c100000\c100000;}");
-    rtf.push('\n');
-    
-    // Default tab width
-    rtf.push_str(r"\deftab1680");
-    rtf.push('\n');
-    
-    // Paragraph formatting
-    rtf.push_str(r"\pard\pardeftab1680\sl20\slleading480\pardirnatural\partightenfactor0");
-    rtf.push('\n');
-    rtf.push('\n');
-    
-    // Font size, color reference (cf2 = color table index 2), and kerning
-    // expnd is in quarter-points, expndtw is in twentieths of a point
-    // 1 quarter-point = 5 twentieths of a point (20/4 = 5)
-    let kerning = options.kerning;
-    let kerning_tw = options.kerning * 5; // expndtw = expnd * 5 (quarter-points to twentieths)
-    let _ = write!(rtf, r"\f0\fs{font_size_halfpoints} \cf2 \kerning1\expnd{kerning}\expndtw{kerning_tw}");
-
-    rtf.push('\n');
-    
-    // Write the actual text content with proper RTF encoding
-    let mut in_super = false;
+    RtfDocument::from_lines(&text_lines(text), &options.into()).to_rtf()
+}
 
-    for c in text.chars() {
-        if is_superscript(c) {
-            // Start superscript if not already
-            if !in_super {
-                rtf.push_str(r"{\super ");
-                in_super = true;
-            }
-            rtf.push(superscript_to_digit(c));
-        } else {
-            // End superscript if we were in one
-            if in_super {
-                rtf.push('}');
-                in_super = false;
-            }
-            
-            match c {
-                '\n' => rtf.push_str(r"\par "),
-                '\\' => rtf.push_str(r"\\"),
-                '{' => rtf.push_str(r"\{"),
-                '}' => rtf.push_str(r"\}"),
-                // Handle common Unicode characters that need escaping for Windows-1252
-                '\u{2019}' => rtf.push_str(r"\'92"),  // Right single quote (')
-                '\u{2018}' => rtf.push_str(r"\'91"),  // Left single quote (')
-                '\u{201C}' => rtf.push_str(r"\'93"),  // Left double quote (")
-                '\u{201D}' => rtf.push_str(r"\'94"),  // Right double quote (")
-                '\u{2013}' => rtf.push_str(r"\'96"),  // En dash (–)
-                '\u{2014}' => rtf.push_str(r"\'97"),  // Em dash (—)
-                '\u{2026}' => rtf.push_str(r"\'85"),  // Ellipsis (…)
-                // Any other non-ASCII: use Unicode RTF escape
-                _ if c as u32 > 127 => {
-                    // \uN? where N is the Unicode code point, ? is a fallback char
-                    let code = c as i32;
-                    let _ = write!(rtf, r"\u{code}?");
-                }
-                _ => rtf.push(c),
-            }
-        }
-    }
-    
-    // Close any open superscript
-    if in_super {
-        rtf.push('}');
-    }
-    
-    // Close RTF document
-    rtf.push('}');
-    rtf
+/// Convert paragraphs to RTF, wrapping bold paragraphs in `{\b ...}` runs
+///
+/// Uses the same header and encoding as [`text_to_rtf_styled`].
+pub fn lines_to_rtf_styled(lines: &[RtfLine<'_>], options: &RtfOptions) -> String {
+    RtfDocument::from_lines(lines, &options.into()).to_rtf()
+}
+
+/// Split plain text into non-bold paragraphs
+fn text_lines(text: &str) -> Vec<RtfLine<'_>> {
+    text.split('\n').map(|text| RtfLine { text, bold: false }).collect()
 }
 
 /// Convert plain text to RTF bytes (for `ProPresenter`)
@@ -176,96 +742,41 @@ pub fn text_to_rtf_bytes_styled(text: &str, options: &RtfOptions) -> Vec<u8> {
     text_to_rtf_styled(text, options).into_bytes()
 }
 
+/// Convert paragraphs to RTF bytes with styling options
+pub fn lines_to_rtf_bytes_styled(lines: &[RtfLine<'_>], options: &RtfOptions) -> Vec<u8> {
+    lines_to_rtf_styled(lines, options).into_bytes()
+}
+
+/// Replace the text of existing RTF, keeping its run formatting
+///
+/// See [`RtfDocument::with_lines`]; data that isn't RTF gets the default style.
+pub fn replace_rtf_text(rtf_data: &[u8], text: &str) -> Vec<u8> {
+    replace_rtf_lines(rtf_data, &text_lines(text))
+}
+
+/// Replace the paragraphs of existing RTF, keeping its run formatting
+pub fn replace_rtf_lines(rtf_data: &[u8], lines: &[RtfLine<'_>]) -> Vec<u8> {
+    RtfDocument::from_bytes(rtf_data)
+        .unwrap_or_default()
+        .with_lines(lines)
+        .to_rtf()
+        .into_bytes()
+}
+
 /// Extract RTF options from existing RTF data
 ///
-/// Parses RTF to extract font name, size, and color settings.
-/// This can be used to match the style of an existing template.
-#[allow(clippy::unnecessary_wraps)] // Returns None for future invalid-input cases
+/// Reads the font name, size, color and kerning the first paragraph is
+/// mostly set in, or `None` if the data isn't RTF.
 pub fn extract_rtf_options(rtf_data: &[u8]) -> Option<RtfOptions> {
-    let rtf = String::from_utf8_lossy(rtf_data);
-    
-    let mut options = RtfOptions::default();
-    
-    // Extract font name from fonttbl
-    if let Some(font_match) = regex::Regex::new(r"\\f0\\fswiss\\fcharset0 ([^;]+);")
-        .ok()
-        .and_then(|re| re.captures(&rtf))
-    {
-        options.font_name = font_match.get(1).map(|m| m.as_str().to_string()).unwrap_or_default();
-    }
-    
-    // Extract font size (half-points)
-    if let Some(size_match) = regex::Regex::new(r"\\fs(\d+)")
-        .ok()
-        .and_then(|re| re.captures(&rtf))
-    {
-        if let Some(size_str) = size_match.get(1) {
-            if let Ok(half_points) = size_str.as_str().parse::<u32>() {
-                options.font_size = half_points / 2;
-            }
-        }
-    }
-    
-    // Extract color from colortbl (first non-auto color)
-    if let Some(color_match) = regex::Regex::new(r"\\red(\d+)\\green(\d+)\\blue(\d+)")
-        .ok()
-        .and_then(|re| re.captures(&rtf))
-    {
-        let r = color_match.get(1).and_then(|m| m.as_str().parse().ok()).unwrap_or(255);
-        let g = color_match.get(2).and_then(|m| m.as_str().parse().ok()).unwrap_or(255);
-        let b = color_match.get(3).and_then(|m| m.as_str().parse().ok()).unwrap_or(255);
-        options.color = (r, g, b);
-    }
-    
-    // Extract kerning
-    if let Some(kern_match) = regex::Regex::new(r"\\expnd(-?\d+)")
-        .ok()
-        .and_then(|re| re.captures(&rtf))
-    {
-        if let Some(kern_str) = kern_match.get(1) {
-            options.kerning = kern_str.as_str().parse().unwrap_or(5);
-        }
-    }
-    
-    Some(options)
+    RtfDocument::from_bytes(rtf_data).map(|document| RtfOptions::from(&document.main_style()))
 }
 
 /// Convert RTF data to plain text
 ///
-/// This is a simplified parser that handles common RTF patterns.
-/// For complex RTF documents, consider using a full RTF library.
+/// Blank lines are dropped and each line is trimmed; superscript digits
+/// come back as `¹²³`.
 pub fn rtf_to_text(rtf_data: &str) -> Option<String> {
-    // Header groups: fonttbl, colortbl, expandedcolortbl, stylesheet, etc.
-    static RE_HEADER_GROUPS: LazyLock<Regex> = LazyLock::new(|| Regex::new(
-        r"\{\\\*?\\(?:fonttbl|colortbl|expandedcolortbl|stylesheet|info|generator)[^{}]*(?:\{[^{}]*\}[^{}]*)*\}"
-    ).unwrap());
-    // Convert \par and \line to newlines
-    static RE_NEWLINE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\\(?:par|line)\s?").unwrap());
-    // Control words with optional numeric parameter and trailing space
-    static RE_CONTROL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\\[a-zA-Z]+[-]?\d*\s?").unwrap());
-    // Remaining braces
-    static RE_BRACES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[{}]").unwrap());
-
-    // Check if it looks like RTF
-    if !rtf_data.starts_with("{\\rtf") {
-        return None;
-    }
-
-    let mut text = rtf_data.to_string();
-
-    // Strip header groups (fonttbl, colortbl, etc.) that contain no user text
-    text = RE_HEADER_GROUPS.replace_all(&text, "").to_string();
-
-    // Convert paragraph breaks to newlines
-    text = RE_NEWLINE.replace_all(&text, "\n").to_string();
-
-    // Remove control words
-    text = RE_CONTROL.replace_all(&text, "").to_string();
-
-    // Remove braces
-    text = RE_BRACES.replace_all(&text, "").to_string();
-
-    // Clean up whitespace
+    let text = RtfDocument::parse(rtf_data)?.text();
     let text = text
         .lines()
         .map(str::trim)
@@ -299,6 +810,112 @@ mod tests {
         assert_eq!(rtf_to_text("plain text"), None);
     }
 
+    #[test]
+    fn test_bold_lines_rtf() {
+        let lines = [
+            RtfLine { text: "The Lord be with you.", bold: false },
+            RtfLine { text: "And also with you.", bold: true },
+        ];
+        let rtf = lines_to_rtf_styled(&lines, &RtfOptions::default());
+        assert!(rtf.contains(r"The Lord be with you.\par {\b And also with you.}"));
+        assert!(rtf_to_text(&rtf).unwrap().ends_with("The Lord be with you.\nAnd also with you."));
+    }
+
+    #[test]
+    fn test_generated_rtf_round_trips() {
+        let rtf = text_to_rtf_styled("Amazing grace\nHow sweet the sound", &RtfOptions::default());
+        assert_eq!(rtf_to_text(&rtf).unwrap(), "Amazing grace\nHow sweet the sound");
+    }
+
+    #[test]
+    fn test_escaped_line_breaks() {
+        let rtf = "{\\rtf1\\ansi Amazing grace\\\nHow sweet the sound}";
+        assert_eq!(rtf_to_text(rtf).unwrap(), "Amazing grace\nHow sweet the sound");
+    }
+
+    const STYLED: &str = r"{\rtf1\ansi\ansicpg1252{\fonttbl\f0\fswiss\fcharset0 Helvetica;\f1\fnil\fcharset0 Georgia;}
+{\colortbl;\red255\green255\blue255;\red255\green200\blue0;}
+{\*\expandedcolortbl;;\cssrgb\c100000\c80000\c0;}
+\pard\pardeftab1680\sl360\slmult1\qc\partightenfactor0
+
+\f0\fs120 \cf1 Holy, {\b holy}, {\i\f1 holy\cf2 !}\
+Lord {\ul God} Almighty}";
+
+    fn texts(document: &RtfDocument) -> Vec<&str> {
+        document.runs.iter().map(|run| run.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_reads_styled_runs() {
+        let document = RtfDocument::parse(STYLED).unwrap();
+        assert_eq!(texts(&document), ["Holy, ", "holy", ", ", "holy", "!", "\nLord ", "God", " Almighty"]);
+        assert_eq!(document.paragraph, r"\pard\pardeftab1680\sl360\slmult1\qc\partightenfactor0");
+
+        let style = |i: usize| &document.runs[i].style;
+        assert_eq!((style(0).font_name.as_str(), style(0).font_size_half_points, style(0).color), ("Helvetica", 120, (255, 255, 255)));
+        assert!(style(1).bold && !style(2).bold);
+        assert_eq!((style(3).font_name.as_str(), style(3).italic), ("Georgia", true));
+        assert_eq!((style(4).italic, style(4).color), (true, (255, 200, 0)));
+        assert!(style(6).underline && !style(7).underline);
+        assert_eq!(document.text(), "Holy, holy, holy!\nLord God Almighty");
+    }
+
+    #[test]
+    fn test_unicode_and_code_page_escapes() {
+        let rtf = r"{\rtf1\ansi \'91Tis so sweet\'92 \u8220?to trust\u8221? {\uc0\u8212 }caf\'e9 \u-10179?\u-8704?}";
+        assert_eq!(rtf_to_text(rtf).unwrap(), "\u{2018}Tis so sweet\u{2019} \u{201c}to trust\u{201d} \u{2014}caf\u{e9} \u{1f600}");
+
+        let document = RtfDocument::from_lines(&[RtfLine { text: "“Jesus”—café 😀 {x}", bold: false }], &RunStyle::default());
+        let rtf = document.to_rtf();
+        assert!(rtf.contains(r"\'93Jesus\'94\'97caf\u233? \u-10179?\u-8704? \{x\}"));
+        assert_eq!(RtfDocument::parse(&rtf).unwrap().runs, document.runs);
+    }
+
+    #[test]
+    fn test_superscript_verse_numbers() {
+        let rtf = r"{\rtf1\ansi\f0\fs160 \cf2 \kerning1\expnd20\expndtw100
+\super 11\nosupersub  For the grace of God has appeared, \super 12\nosupersub  training us}";
+        assert_eq!(rtf_to_text(rtf).unwrap(), "¹¹ For the grace of God has appeared, ¹² training us");
+        let options = extract_rtf_options(rtf.as_bytes()).unwrap();
+        assert_eq!((options.font_size, options.kerning), (80, 20));
+
+        let rtf = text_to_rtf("¹⁵The wilderness");
+        assert!(rtf.contains(r"{\super 15}The wilderness"));
+        assert_eq!(rtf_to_text(&rtf).unwrap(), "¹⁵The wilderness");
+    }
+
+    #[test]
+    fn test_written_runs_round_trip() {
+        let document = RtfDocument::parse(STYLED).unwrap();
+        let rtf = document.to_rtf();
+        assert!(rtf.contains(r"\qc"));
+        assert_eq!(RtfDocument::parse(&rtf).unwrap(), document);
+    }
+
+    #[test]
+    fn test_replace_keeps_run_formatting() {
+        let lines = [
+            RtfLine { text: "Holy, holy, holy, merciful and mighty", bold: false },
+            RtfLine { text: "Lord God Almighty", bold: false },
+            RtfLine { text: "Early in the morning", bold: true },
+        ];
+        let replaced = RtfDocument::from_bytes(&replace_rtf_lines(STYLED.as_bytes(), &lines)).unwrap();
+        assert_eq!(replaced.paragraph, r"\pard\pardeftab1680\sl360\slmult1\qc\partightenfactor0");
+        assert_eq!(replaced.text(), "Holy, holy, holy, merciful and mighty\nLord God Almighty\nEarly in the morning");
+
+        // A changed line is set in its old paragraph's main style, an
+        // unchanged one keeps its runs, and extra lines follow the last
+        let original = RtfDocument::parse(STYLED).unwrap().paragraphs();
+        let paragraphs = replaced.paragraphs();
+        assert_eq!(paragraphs[0].len(), 1);
+        assert_eq!(paragraphs[0][0].style, original[0][0].style);
+        assert_eq!(paragraphs[1], original[1]);
+        assert_eq!(paragraphs[2][0].style, RunStyle { bold: true, ..original[0][0].style.clone() });
+
+        let replaced = RtfDocument::from_bytes(&replace_rtf_text(STYLED.as_bytes(), "Holy, holy, holy!\nLord God Almighty")).unwrap();
+        assert_eq!(replaced.paragraphs(), original);
+    }
+
     #[test]
     fn test_multiline_rtf() {
         let rtf = r"{\rtf1\ansi Line 1\par Line 2\par}";
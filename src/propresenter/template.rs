@@ -4,10 +4,17 @@
 
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 use prost::Message;
 
+use super::convert::{convert_action_to_rv_data, convert_slide_to_rv_data};
+use super::data_model as dm;
+use super::export::{get_group_color, parse_stanzas, Stanza, StanzaDirectives};
+use super::fonts::{FontLibrary, FontMetrics};
 use super::generated::rv_data;
-use super::rtf::{text_to_rtf_bytes_styled, extract_rtf_options};
+use crate::lyrics::chordpro::{self, Transposition};
+use crate::lyrics::{is_section_marker, LyricLine};
+use super::rtf::{RtfLine, extract_rtf_options, replace_rtf_lines, replace_rtf_text};
 // Re-export constants for backwards compatibility
 pub use crate::constants::template::{
     DEFAULT_MAX_LINES_PER_SLIDE, DEFAULT_WRAP_COLUMN, MIN_SLIDE_WRAP,
@@ -22,6 +29,8 @@ pub enum TemplateType {
     Song,
     /// Informational/announcement slides
     Info,
+    /// Responsive readings with leader/people parts
+    Litany,
 }
 
 impl TemplateType {
@@ -32,13 +41,23 @@ impl TemplateType {
             Self::Scripture => "__template_scripture__.pro",
             Self::Song => "__template_song__.pro",
             Self::Info => "__template_info__.pro",
+            Self::Litany => "__template_litany__.pro",
         }
     }
     
     /// All template types
     #[must_use]
     pub const fn all() -> &'static [Self] {
-        &[Self::Scripture, Self::Song, Self::Info]
+        &[Self::Scripture, Self::Song, Self::Info, Self::Litany]
+    }
+    
+    /// Template to use when this one is not in the library
+    #[must_use]
+    pub const fn fallback(self) -> Option<Self> {
+        match self {
+            Self::Litany => Some(Self::Info),
+            Self::Scripture | Self::Song | Self::Info => None,
+        }
     }
 }
 
@@ -46,6 +65,8 @@ impl TemplateType {
 pub struct TemplateCache {
     templates: HashMap<TemplateType, rv_data::Presentation>,
     search_paths: Vec<PathBuf>,
+    fonts: FontLibrary,
+    fits: HashMap<TemplateType, Option<TextFit>>,
 }
 
 impl TemplateCache {
@@ -54,7 +75,36 @@ impl TemplateCache {
         Self {
             templates: HashMap::new(),
             search_paths,
+            fonts: FontLibrary::default(),
+            fits: HashMap::new(),
+        }
+    }
+    
+    /// Use `fonts` to find the fonts templates use
+    pub fn set_fonts(&mut self, fonts: FontLibrary) {
+        self.fonts = fonts;
+        self.fits.clear();
+    }
+    
+    /// How to measure slide text for a template type
+    ///
+    /// Uses the template's text box and font when the font file can be
+    /// found, otherwise falls back to counting columns at `wrap_column`.
+    pub fn text_measure(&mut self, template_type: TemplateType, wrap_column: usize) -> TextMeasure {
+        let template_type = if self.has_template(template_type) {
+            template_type
+        } else {
+            template_type.fallback().unwrap_or(template_type)
+        };
+        if !self.fits.contains_key(&template_type) {
+            let slide = self.templates.get(&template_type).and_then(extract_template_slide);
+            let fit = slide.and_then(|slide| TextFit::from_slide(&slide, &mut self.fonts));
+            self.fits.insert(template_type, fit);
         }
+        self.fits.get(&template_type)
+            .cloned()
+            .flatten()
+            .map_or(TextMeasure::Columns(wrap_column), TextMeasure::Fit)
     }
     
     /// Add a search path
@@ -100,6 +150,7 @@ impl TemplateCache {
     pub fn load_from_bytes(&mut self, template_type: TemplateType, data: &[u8]) -> bool {
         if let Ok(presentation) = rv_data::Presentation::decode(data) {
             self.templates.insert(template_type, presentation);
+            self.fits.remove(&template_type);
             true
         } else {
             false
@@ -122,11 +173,135 @@ pub fn extract_template_slide(presentation: &rv_data::Presentation) -> Option<rv
     None
 }
 
+/// A template's text box and font, for wrapping text as `ProPresenter` will
+#[derive(Debug, Clone)]
+pub struct TextFit {
+    font: Arc<FontMetrics>,
+    font_size: f64,
+    width: f64,
+    height: f64,
+}
+
+impl TextFit {
+    /// Read the first text element's bounds, margins and font from a template slide.
+    pub fn from_slide(slide: &rv_data::PresentationSlide, fonts: &mut FontLibrary) -> Option<Self> {
+        let element = slide.base_slide.iter()
+            .flat_map(|base| &base.elements)
+            .filter_map(|e| e.element.as_ref())
+            .find(|e| e.text.is_some())?;
+        let text = element.text.as_ref()?;
+        let size = element.bounds.as_ref()?.size.as_ref()?;
+        let margins = text.margins.clone().unwrap_or_default();
+        let options = extract_rtf_options(&text.rtf_data)?;
+
+        let fit = Self {
+            font: fonts.get(&options.font_name)?,
+            font_size: f64::from(options.font_size.max(1)),
+            width: size.width - margins.left - margins.right,
+            height: size.height - margins.top - margins.bottom,
+        };
+        (fit.width > 0.0 && fit.height > 0.0).then_some(fit)
+    }
+
+    /// Build a fit from known metrics, e.g. for tests and previews.
+    pub const fn new(font: Arc<FontMetrics>, font_size: f64, width: f64, height: f64) -> Self {
+        Self { font, font_size, width, height }
+    }
+
+    /// Lines of text that fit in the box.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn box_lines(&self) -> usize {
+        let line_height = self.font.line_height(self.font_size);
+        if line_height <= 0.0 {
+            return 1;
+        }
+        ((self.height / line_height).floor() as usize).max(1)
+    }
+
+    /// Lines `text` takes when word-wrapped to the box width.
+    pub fn wrapped_lines(&self, text: &str) -> usize {
+        text.lines().map(|line| self.wrap_paragraph(line)).sum::<usize>().max(1)
+    }
+
+    fn wrap_paragraph(&self, paragraph: &str) -> usize {
+        let space = self.font.text_width(" ", self.font_size);
+        let mut lines = 1;
+        let mut used: Option<f64> = None;
+        for word in paragraph.split_whitespace() {
+            let width = self.font.text_width(word, self.font_size);
+            if used.is_some_and(|u| u + space + width > self.width) {
+                lines += 1;
+                used = None;
+            }
+            let start = used.map_or(0.0, |u| u + space);
+            if start + width <= self.width {
+                used = Some(start + width);
+                continue;
+            }
+            // A word wider than the box breaks between characters
+            let mut run = 0.0;
+            for c in word.chars() {
+                let w = self.font.text_width(c.encode_utf8(&mut [0; 4]), self.font_size);
+                if run > 0.0 && run + w > self.width {
+                    lines += 1;
+                    run = 0.0;
+                }
+                run += w;
+            }
+            used = Some(run);
+        }
+        lines
+    }
+}
+
+/// How slide text is measured when splitting content into slides
+#[derive(Debug, Clone)]
+pub enum TextMeasure {
+    /// Unicode width divided by a wrap column
+    Columns(usize),
+    /// Word wrapping in the template's text box with the template's font
+    Fit(TextFit),
+}
+
+impl TextMeasure {
+    /// Visual lines `text` takes on a slide.
+    pub fn visual_lines(&self, text: &str) -> usize {
+        match self {
+            Self::Columns(wrap_column) => estimate_visual_lines(text, (*wrap_column).max(MIN_SLIDE_WRAP)),
+            Self::Fit(fit) => fit.wrapped_lines(text),
+        }
+    }
+
+    /// Most lines a slide may hold: `max_lines`, or fewer if the text box is smaller.
+    pub fn slide_capacity(&self, max_lines: usize) -> usize {
+        match self {
+            Self::Columns(_) => max_lines.max(1),
+            Self::Fit(fit) => max_lines.max(1).min(fit.box_lines()),
+        }
+    }
+}
+
 /// Clone a template slide and replace its text content
 /// 
-/// Preserves the template's styling (font, color, kerning) by extracting
-/// the RTF options from the original and applying them to the new text.
+/// Preserves the template's styling: each new line takes the run formatting
+/// (font, size, color, emphasis) of the template paragraph in its place.
 pub fn clone_slide_with_text(template_slide: &rv_data::PresentationSlide, new_text: &str) -> rv_data::PresentationSlide {
+    clone_slide_with_rtf(template_slide, |rtf_data| replace_rtf_text(rtf_data, new_text))
+}
+
+/// Clone a template slide and replace its text with styled paragraphs
+///
+/// Like [`clone_slide_with_text`], but paragraphs marked bold are emitted as
+/// bold RTF runs.
+pub fn clone_slide_with_lines(template_slide: &rv_data::PresentationSlide, lines: &[RtfLine<'_>]) -> rv_data::PresentationSlide {
+    clone_slide_with_rtf(template_slide, |rtf_data| replace_rtf_lines(rtf_data, lines))
+}
+
+/// Clone a template slide, regenerating each text element's RTF with `make_rtf`
+fn clone_slide_with_rtf(
+    template_slide: &rv_data::PresentationSlide,
+    make_rtf: impl Fn(&[u8]) -> Vec<u8>,
+) -> rv_data::PresentationSlide {
     let mut slide = template_slide.clone();
     
     // Navigate: base_slide -> elements -> element (graphics::Element) -> text
@@ -134,12 +309,8 @@ pub fn clone_slide_with_text(template_slide: &rv_data::PresentationSlide, new_te
         for slide_element in &mut base_slide.elements {
             if let Some(ref mut graphics_element) = slide_element.element {
                 if let Some(ref mut text) = graphics_element.text {
-                    // Extract RTF options from original template to preserve styling
-                    let rtf_options = extract_rtf_options(&text.rtf_data)
-                        .unwrap_or_default();
-                    
-                    // Generate new RTF with template's styling (font, color, etc)
-                    text.rtf_data = text_to_rtf_bytes_styled(new_text, &rtf_options);
+                    // Generate new RTF in the template's run styling
+                    text.rtf_data = make_rtf(&text.rtf_data);
                 }
             }
         }
@@ -159,58 +330,167 @@ pub fn split_content_for_slides(
     wrap_column: usize,
     max_lines: usize,
 ) -> Vec<String> {
-    let wrap_col = wrap_column.max(MIN_SLIDE_WRAP);
-    let max = max_lines.max(1);
+    let mut slides: Vec<String> = split_line_indices(content, &TextMeasure::Columns(wrap_column), max_lines)
+        .iter()
+        .map(|slide| join_slide_text(slide, content))
+        .filter(|text| !text.is_empty())
+        .collect();
     
-    let mut slides: Vec<String> = Vec::new();
-    let mut current_slide: Vec<String> = Vec::new();
+    // If nothing was generated, return empty slide
+    if slides.is_empty() {
+        slides.push(String::new());
+    }
+    
+    slides
+}
+
+/// Join a slide's content lines (`None` for a paragraph break) into trimmed text
+fn join_slide_text(slide: &[Option<usize>], content: &[String]) -> String {
+    slide.iter()
+        .map(|idx| idx.and_then(|i| content.get(i)).map_or("", String::as_str))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// A slide as the template path will cut it, for previewing in the editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlidePreview {
+    /// Displayed slide text (chords and markers removed)
+    pub text: String,
+    /// Content line the slide starts on, when known
+    pub start_line: Option<usize>,
+    /// Visual lines the text takes on the slide
+    pub visual_lines: usize,
+    /// Most lines the slide can hold
+    pub max_lines: usize,
+    /// Whether the slide holds more than `max_lines`
+    pub overflows: bool,
+}
+
+impl SlidePreview {
+    /// Measure `text` against the slide capacity for `max_lines`.
+    pub(crate) fn new(text: String, start_line: Option<usize>, measure: &TextMeasure, max_lines: usize) -> Self {
+        let visual_lines = measure.visual_lines(&text);
+        let max_lines = measure.slide_capacity(max_lines);
+        Self { overflows: visual_lines > max_lines, text, start_line, visual_lines, max_lines }
+    }
+}
+
+/// The slides [`build_presentation_from_template_with_options`] would produce
+///
+/// A single line too long to fit is still given its own slide, so those
+/// slides are flagged as overflowing rather than split.
+pub fn preview_slides(content: &[String], measure: &TextMeasure, max_lines: usize) -> Vec<SlidePreview> {
+    let texts: Vec<String> = display_lines(content).into_iter().map(|l| l.text).collect();
+    split_stanza_indices(content, &texts, measure, max_lines)
+        .iter()
+        .flat_map(|(_, slides)| slides)
+        .filter_map(|slide| {
+            let text = join_slide_text(slide, &texts);
+            let start_line = slide.iter().flatten().next().copied();
+            (!text.is_empty()).then(|| SlidePreview::new(text, start_line, measure, max_lines))
+        })
+        .collect()
+}
+
+/// A stanza and its slides, as content line indices (`None` for a paragraph break)
+type StanzaSlides = (Stanza, Vec<Vec<Option<usize>>>);
+
+/// Split content into stanzas, then each stanza into slides
+///
+/// Returns each stanza with its slides as indices into `content`; `texts`
+/// are the displayed lines the slides are measured by.
+fn split_stanza_indices(
+    content: &[String],
+    texts: &[String],
+    measure: &TextMeasure,
+    max_lines: usize,
+) -> Vec<StanzaSlides> {
+    parse_stanzas(content)
+        .into_iter()
+        .map(|stanza| {
+            let stanza_texts: Vec<String> = stanza.line_indices.iter()
+                .map(|&i| texts.get(i).cloned().unwrap_or_default())
+                .collect();
+            let slides = split_line_indices(&stanza_texts, measure, max_lines)
+                .into_iter()
+                .map(|slide| slide.into_iter()
+                    .map(|idx| idx.and_then(|i| stanza.line_indices.get(i).copied()))
+                    .collect())
+                .collect();
+            (stanza, slides)
+        })
+        .collect()
+}
+
+/// Parse editor lines into displayed text and chords, applying any transposition
+fn display_lines(content: &[String]) -> Vec<LyricLine> {
+    let transposition = Transposition::from_content(content);
+    content.iter()
+        .map(|line| {
+            let mut parsed = chordpro::parse_editor_line(line);
+            transposition.apply(&mut parsed);
+            parsed
+        })
+        .collect()
+}
+
+/// Group content line indices into slides
+///
+/// Each slide lists the indices of its lines in order; `None` is a paragraph
+/// break. Group markers (`[Verse 1]`) and `ChordPro` directives are skipped.
+fn split_line_indices(
+    content: &[String],
+    measure: &TextMeasure,
+    max_lines: usize,
+) -> Vec<Vec<Option<usize>>> {
+    let max = measure.slide_capacity(max_lines);
+    
+    let mut slides: Vec<Vec<Option<usize>>> = Vec::new();
+    let mut current_slide: Vec<Option<usize>> = Vec::new();
     let mut current_lines = 0;
     
-    for line in content {
+    for (idx, line) in content.iter().enumerate() {
         if line.trim().is_empty() {
             // Empty line = paragraph break
             // If we have content, add a blank line to current slide
             if !current_slide.is_empty() {
-                current_slide.push(String::new());
+                current_slide.push(None);
                 current_lines += 1;
             }
             continue;
         }
-        
-        // Estimate visual lines this content will take
-        let visual_lines = estimate_visual_lines(line, wrap_col);
+
+        // Group markers like [Verse 1] are editor labels, not slide text
+        if is_section_marker(line) || chordpro::is_directive(line) {
+            continue;
+        }
+
+        // Visual lines this content will take
+        let visual_lines = measure.visual_lines(line);
         
         // Would this overflow? Start a new slide
         if current_lines > 0 && current_lines + visual_lines > max {
-            // Finalize current slide
-            let slide_text = current_slide.join("\n").trim().to_string();
-            if !slide_text.is_empty() {
-                slides.push(slide_text);
-            }
-            current_slide.clear();
+            slides.push(std::mem::take(&mut current_slide));
             current_lines = 0;
         }
         
-        current_slide.push(line.clone());
+        current_slide.push(Some(idx));
         current_lines += visual_lines;
     }
     
     // Don't forget the last slide
-    let slide_text = current_slide.join("\n").trim().to_string();
-    if !slide_text.is_empty() {
-        slides.push(slide_text);
-    }
-    
-    // If nothing was generated, return empty slide
-    if slides.is_empty() {
-        slides.push(String::new());
+    if !current_slide.is_empty() {
+        slides.push(current_slide);
     }
     
     slides
 }
 
 /// Estimate how many visual lines a string will take when wrapped
-fn estimate_visual_lines(text: &str, wrap_column: usize) -> usize {
+pub(crate) fn estimate_visual_lines(text: &str, wrap_column: usize) -> usize {
     use unicode_width::UnicodeWidthStr;
     
     let mut lines = 0;
@@ -238,24 +518,157 @@ pub fn build_presentation_from_template(
         name,
         template,
         content,
-        DEFAULT_WRAP_COLUMN,
+        &TextMeasure::Columns(DEFAULT_WRAP_COLUMN),
         DEFAULT_MAX_LINES_PER_SLIDE,
     )
 }
 
-/// Build a presentation with custom wrap/split options
+/// Build a presentation with custom measuring/split options
+///
+/// Each stanza is split on its own. A `[Label]` starts a group named by it,
+/// which takes the unlabeled stanzas that follow; stanzas before the first
+/// label form one group styled like the template's.
 pub fn build_presentation_from_template_with_options(
     name: &str,
     template: &rv_data::Presentation,
     content: &[String],
-    wrap_column: usize,
+    measure: &TextMeasure,
     max_lines_per_slide: usize,
 ) -> Option<rv_data::Presentation> {
     let template_slide = extract_template_slide(template)?;
     
-    // Split content into slide-sized chunks
-    let slide_texts = split_content_for_slides(content, wrap_column, max_lines_per_slide);
+    // Inline chords come out of the audience text; split on what is displayed
+    let lines = display_lines(content);
+    let texts: Vec<String> = lines.iter().map(|l| l.text.clone()).collect();
+    
+    // Split each stanza into slide-sized chunks
+    let mut groups: Vec<SlideGroup> = Vec::new();
+    for (stanza, slides) in split_stanza_indices(content, &texts, measure, max_lines_per_slide) {
+        let cue_actions: Vec<_> = stanza.directives.background_action()
+            .map(|action| convert_action_to_rv_data(action, None))
+            .into_iter()
+            .collect();
+        let slides = slides.iter().filter_map(|slide| {
+            let (text, chords) = join_chord_lines(slide.iter().map(|idx| idx.and_then(|i| lines.get(i))));
+            (!text.is_empty()).then(|| {
+                let slide = apply_directives(clone_slide_with_chords(&template_slide, &text, &chords), &stanza.directives);
+                (slide, cue_actions.clone())
+            })
+        });
+        match groups.last_mut() {
+            Some((_, group_slides)) if stanza.label.is_none() => group_slides.extend(slides),
+            _ => groups.push((stanza.label, slides.collect())),
+        }
+    }
     
+    Some(assemble_grouped_presentation(name, template, groups))
+}
+
+/// Set a stanza's notes, transition and background color on a template slide
+///
+/// Goes through the data model, which writes only the changed fields over
+/// the template's slide.
+fn apply_directives(slide: rv_data::PresentationSlide, directives: &StanzaDirectives) -> rv_data::PresentationSlide {
+    if directives.is_empty() {
+        return slide;
+    }
+    let mut slide = dm::Slide::from(slide);
+    directives.apply_to_slide(&mut slide);
+    convert_slide_to_rv_data(slide)
+}
+
+/// Join a slide's lines (`None` for a paragraph break) and locate its chords
+///
+/// Chord positions are UTF-16 offsets into the joined, trimmed text, which
+/// is how `ProPresenter` addresses text attribute ranges.
+pub(crate) fn join_chord_lines<'a>(
+    slide: impl IntoIterator<Item = Option<&'a LyricLine>>,
+) -> (String, Vec<(usize, String)>) {
+    let mut text = String::new();
+    let mut chords = Vec::new();
+    
+    for (i, line) in slide.into_iter().enumerate() {
+        if i > 0 {
+            text.push('\n');
+        }
+        let Some(line) = line else { continue };
+        let line_start = text.encode_utf16().count();
+        for chord in &line.chords {
+            let offset: usize = line.text.chars().take(chord.position).map(char::len_utf16).sum();
+            chords.push((line_start + offset, chord.name.clone()));
+        }
+        text.push_str(&line.text);
+    }
+    
+    // Trim like plain slides, shifting chords to match
+    let leading = text[..text.len() - text.trim_start().len()].encode_utf16().count();
+    let trimmed = text.trim().to_string();
+    let last = trimmed.encode_utf16().count().saturating_sub(1);
+    let chords = chords.into_iter()
+        .map(|(pos, name)| (pos.saturating_sub(leading).min(last), name))
+        .collect();
+    (trimmed, chords)
+}
+
+/// Clone a template slide with new text and chord attributes
+///
+/// Each chord is attached to the character it is played on. The text
+/// element's own `ChordPro` display is left as the template set it, so the
+/// audience sees lyrics only while stage layouts that show chords pick them up.
+pub fn clone_slide_with_chords(
+    template_slide: &rv_data::PresentationSlide,
+    new_text: &str,
+    chords: &[(usize, String)],
+) -> rv_data::PresentationSlide {
+    use rv_data::graphics::text::attributes::{custom_attribute::Attribute, CustomAttribute};
+    
+    let mut slide = clone_slide_with_text(template_slide, new_text);
+    if chords.is_empty() {
+        return slide;
+    }
+    
+    let elements = slide.base_slide.iter_mut().flat_map(|base| &mut base.elements);
+    for text in elements.filter_map(|e| e.element.as_mut().and_then(|g| g.text.as_mut())) {
+        let attributes = text.attributes.get_or_insert_with(Default::default);
+        attributes.custom_attributes.retain(|a| !matches!(a.attribute, Some(Attribute::Chord(_))));
+        attributes.custom_attributes.extend(chords.iter().map(|(pos, name)| {
+            let start = i32::try_from(*pos).unwrap_or(i32::MAX);
+            CustomAttribute {
+                range: Some(rv_data::IntRange { start, end: start.saturating_add(1) }),
+                attribute: Some(Attribute::Chord(name.clone())),
+            }
+        }));
+    }
+    slide
+}
+
+/// Wrap prepared slides in cues and a single group styled like the template
+pub(crate) fn assemble_presentation(
+    name: &str,
+    template: &rv_data::Presentation,
+    slides: Vec<rv_data::PresentationSlide>,
+) -> rv_data::Presentation {
+    let slides = slides.into_iter().map(|slide| (slide, Vec::new())).collect();
+    assemble_grouped_presentation(name, template, vec![(None, slides)])
+}
+
+/// A slide with actions to add to its cue
+pub(crate) type CueSlide = (rv_data::PresentationSlide, Vec<rv_data::Action>);
+
+/// A group's label and its slides
+pub(crate) type SlideGroup = (Option<String>, Vec<CueSlide>);
+
+/// Wrap prepared slides in cues, one group per [`SlideGroup`]
+///
+/// Labeled groups take the label's name and color; unlabeled ones copy the
+/// template's first group. A label repeated with the same slides (a chorus
+/// sung again) reuses the earlier group. A "Default" arrangement plays the
+/// groups in order when any is labeled or the template had arrangements.
+pub(crate) fn assemble_grouped_presentation(
+    name: &str,
+    template: &rv_data::Presentation,
+    groups: Vec<SlideGroup>,
+) -> rv_data::Presentation {
     let mut presentation = template.clone();
     presentation.name = name.to_string();
     presentation.uuid = Some(rv_data::Uuid { string: uuid::Uuid::new_v4().to_string() });
@@ -265,69 +678,48 @@ pub fn build_presentation_from_template_with_options(
     presentation.cue_groups.clear();
     presentation.arrangements.clear();
     
-    // Create a cue for each slide chunk
-    let mut cue_uuids = Vec::new();
+    // Copy group settings from template - use None for optional fields to match working files
+    let template_group = template.cue_groups.first().and_then(|g| g.group.as_ref());
+    let mut labeled = false;
+    let mut order: Vec<rv_data::Uuid> = Vec::new();
+    let mut seen: Vec<(String, Vec<CueSlide>, rv_data::Uuid)> = Vec::new();
     
-    for text in &slide_texts {
-        if text.trim().is_empty() {
+    for (label, slides) in groups {
+        if slides.is_empty() {
             continue;
         }
+
+        // Sung again: play the earlier group once more
+        let content: Vec<_> = slides.iter().map(without_uuids).collect();
+        if let Some(label) = &label {
+            if let Some((_, _, uuid)) = seen.iter().find(|(name, earlier, _)| name == label && *earlier == content) {
+                order.push(uuid.clone());
+                continue;
+            }
+        }
         
-        let slide = clone_slide_with_text(&template_slide, text);
-        let cue_uuid = uuid::Uuid::new_v4();
-        let action_uuid = uuid::Uuid::new_v4();
-        
-        // Copy cue settings from template if available
-        let template_cue = template.cues.first();
-        
-        let cue = rv_data::Cue {
-            uuid: Some(rv_data::Uuid { string: cue_uuid.to_string() }),
-            name: String::new(),  // Empty like template
-            actions: vec![rv_data::Action {
-                uuid: Some(rv_data::Uuid { string: action_uuid.to_string() }),
-                name: String::new(),  // Empty like template
-                label: None,
-                delay_time: 0.0,
-                old_type: None,
-                is_enabled: true,
-                layer_identification: None,
-                duration: 0.0,
-                r#type: rv_data::action::ActionType::PresentationSlide as i32,
-                action_type_data: Some(rv_data::action::ActionTypeData::Slide(
-                    rv_data::action::SlideType {
-                        slide: Some(rv_data::action::slide_type::Slide::Presentation(slide)),
-                    }
-                )),
-            }],
-            completion_target_type: rv_data::cue::CompletionTargetType::None as i32,
-            completion_target_uuid: None,
-            // Use LAST like template/fresh presentations
-            completion_action_type: rv_data::cue::CompletionActionType::Last as i32,
-            completion_action_uuid: None,
-            trigger_time: None,  // None like template
-            // Empty hot_key like template (not None)
-            hot_key: template_cue.and_then(|c| c.hot_key.clone()).or_else(|| Some(rv_data::HotKey {
-                code: 0,
-                control_identifier: String::new(),
-            })),
-            pending_imports: Vec::new(),
-            is_enabled: true,
-            completion_time: 0.0,
-        };
+        // Create a cue for each slide chunk
+        let cue_uuids: Vec<uuid::Uuid> = slides.into_iter()
+            .map(|(slide, cue_actions)| {
+                let (cue_uuid, mut cue) = template_cue(template, slide);
+                cue.actions.extend(cue_actions.iter().map(|action| rv_data::Action {
+                    uuid: Some(rv_data::Uuid { string: uuid::Uuid::new_v4().to_string() }),
+                    ..action.clone()
+                }));
+                presentation.cues.push(cue);
+                cue_uuid
+            })
+            .collect();
         
-        cue_uuids.push(cue_uuid);
-        presentation.cues.push(cue);
-    }
-    
-    // Create a single group containing all cues
-    // Copy group settings from template - use None for optional fields to match working files
-    let template_group = template.cue_groups.first().and_then(|g| g.group.as_ref());
-    
-    if !cue_uuids.is_empty() {
-        let group_uuid = uuid::Uuid::new_v4();
-        let group = rv_data::presentation::CueGroup {
-            group: Some(rv_data::Group {
-                uuid: Some(rv_data::Uuid { string: group_uuid.to_string() }),
+        let group_uuid = Some(rv_data::Uuid { string: uuid::Uuid::new_v4().to_string() });
+        labeled |= label.is_some();
+        order.extend(group_uuid.clone());
+        if let (Some(label), Some(uuid)) = (&label, &group_uuid) {
+            seen.push((label.clone(), content, uuid.clone()));
+        }
+        let group = label.map_or_else(
+            || rv_data::Group {
+                uuid: group_uuid.clone(),
                 name: template_group.map(|g| g.name.clone()).unwrap_or_default(),
                 // Use None instead of explicit zeros - ProPresenter treats these differently
                 color: template_group.and_then(|g| g.color.clone()),
@@ -335,27 +727,95 @@ pub fn build_presentation_from_template_with_options(
                 // Use None instead of generating a UUID
                 application_group_identifier: template_group.and_then(|g| g.application_group_identifier.clone()),
                 application_group_name: template_group.map(|g| g.application_group_name.clone()).unwrap_or_default(),
-            }),
+            },
+            |label| rv_data::Group {
+                uuid: group_uuid.clone(),
+                color: Some(get_group_color(&label).into()),
+                name: label,
+                hot_key: None,
+                application_group_identifier: None,
+                application_group_name: String::new(),
+            },
+        );
+        presentation.cue_groups.push(rv_data::presentation::CueGroup {
+            group: Some(group),
             cue_identifiers: cue_uuids.iter()
                 .map(|u| rv_data::Uuid { string: u.to_string() })
                 .collect(),
+        });
+    }
+    
+    // Plain content only gets an arrangement if the template had one
+    if !presentation.cue_groups.is_empty() && (labeled || !template.arrangements.is_empty()) {
+        let arrangement = rv_data::presentation::Arrangement {
+            uuid: Some(rv_data::Uuid { string: uuid::Uuid::new_v4().to_string() }),
+            name: "Default".to_string(),
+            group_identifiers: order,
         };
-        presentation.cue_groups.push(group);
-        
-        // Only add arrangement if template had one
-        if !template.arrangements.is_empty() {
-            let arrangement = rv_data::presentation::Arrangement {
-                uuid: Some(rv_data::Uuid { string: uuid::Uuid::new_v4().to_string() }),
-                name: "Default".to_string(),
-                group_identifiers: vec![rv_data::Uuid { string: group_uuid.to_string() }],
-            };
-            presentation.arrangements.push(arrangement);
-            presentation.selected_arrangement = presentation.arrangements.first()
-                .and_then(|a| a.uuid.clone());
+        presentation.selected_arrangement.clone_from(&arrangement.uuid);
+        presentation.arrangements.push(arrangement);
+    }
+    
+    presentation
+}
+
+/// A slide and its cue actions with their generated UUIDs cleared, for
+/// comparing content.
+fn without_uuids((slide, actions): &(rv_data::PresentationSlide, Vec<rv_data::Action>)) -> (rv_data::PresentationSlide, Vec<rv_data::Action>) {
+    let mut slide = slide.clone();
+    if let Some(base) = slide.base_slide.as_mut() {
+        base.uuid = None;
+        for element in base.elements.iter_mut().filter_map(|element| element.element.as_mut()) {
+            element.uuid = None;
         }
     }
+    let actions = actions.iter().map(|action| rv_data::Action { uuid: None, ..action.clone() }).collect();
+    (slide, actions)
+}
+
+/// A cue showing `slide`, with settings copied from the template's first cue
+pub(crate) fn template_cue(template: &rv_data::Presentation, slide: rv_data::PresentationSlide) -> (uuid::Uuid, rv_data::Cue) {
+    let cue_uuid = uuid::Uuid::new_v4();
+    let action_uuid = uuid::Uuid::new_v4();
     
-    Some(presentation)
+    // Copy cue settings from template if available
+    let template_cue = template.cues.first();
+    
+    let cue = rv_data::Cue {
+        uuid: Some(rv_data::Uuid { string: cue_uuid.to_string() }),
+        name: String::new(),  // Empty like template
+        actions: vec![rv_data::Action {
+            uuid: Some(rv_data::Uuid { string: action_uuid.to_string() }),
+            name: String::new(),  // Empty like template
+            label: None,
+            delay_time: 0.0,
+            old_type: None,
+            is_enabled: true,
+            layer_identification: None,
+            duration: 0.0,
+            r#type: rv_data::action::ActionType::PresentationSlide as i32,
+            action_type_data: Some(rv_data::action::ActionTypeData::Slide(
+                rv_data::action::SlideType {
+                    slide: Some(rv_data::action::slide_type::Slide::Presentation(slide)),
+                }
+            )),
+        }],
+        completion_target_type: rv_data::cue::CompletionTargetType::None as i32,
+        completion_target_uuid: None,
+        // Use LAST like template/fresh presentations
+        completion_action_type: rv_data::cue::CompletionActionType::Last as i32,
+        completion_action_uuid: None,
+        trigger_time: None,  // None like template
+        // Empty hot_key like template (not None)
+        hot_key: template_cue.and_then(|c| c.hot_key.clone()).or_else(|| Some(rv_data::HotKey {
+            code: 0,
+            control_identifier: String::new(),
+        })),
+        pending_imports: Vec::new(),
+        is_enabled: true,
+        completion_time: 0.0,
+    };
+    (cue_uuid, cue)
 }
 
 #[cfg(test)]
@@ -406,7 +866,7 @@ mod tests {
             "Test Scripture",
             &template,
             &content,
-            DEFAULT_WRAP_COLUMN,
+            &TextMeasure::Columns(DEFAULT_WRAP_COLUMN),
             1,
         );
         assert!(presentation.is_some());
@@ -415,5 +875,178 @@ mod tests {
         assert_eq!(pres.name, "Test Scripture");
         assert_eq!(pres.cues.len(), 2);
     }
+
+    #[test]
+    fn test_preview_matches_export_and_flags_overflow() {
+        let content: Vec<String> = [
+            "[Verse 1]",
+            "[G]Amazing grace, how [C]sweet the sound",
+            "That saved a wretch like me",
+            "",
+            "I once was lost, but now am found, was blind but now I see, was blind but now I see",
+        ].iter().map(ToString::to_string).collect();
+
+        let slides = preview_slides(&content, &TextMeasure::Columns(20), 4);
+        assert_eq!(slides.len(), 2);
+        assert_eq!(slides[0].text, "Amazing grace, how sweet the sound\nThat saved a wretch like me");
+        assert_eq!(slides[0].start_line, Some(1));
+        assert_eq!((slides[0].visual_lines, slides[0].overflows), (4, false));
+        assert_eq!(slides[1].start_line, Some(4));
+        assert!(slides[1].overflows);
+
+        // Same cut as the exported presentation
+        let mut cache = TemplateCache::new(vec![get_template_path()]);
+        let template = cache.get(TemplateType::Song).unwrap().clone();
+        let pres = build_presentation_from_template_with_options("Test", &template, &content, &TextMeasure::Columns(20), 4).unwrap();
+        assert_eq!(pres.cues.len(), slides.len());
+    }
+
+    #[test]
+    fn test_stanzas_become_groups() {
+        let content: Vec<String> = [
+            "Welcome",
+            "",
+            "[Verse 1]",
+            "Amazing grace, how sweet the sound",
+            "That saved a wretch like me",
+            "",
+            "[Chorus]",
+            "My chains are gone",
+            "",
+            "I've been set free",
+            "",
+            "[Verse 2]",
+            "'Twas grace that taught my heart to fear",
+            "",
+            "[Chorus]",
+            "My chains are gone",
+            "",
+            "I've been set free",
+        ].iter().map(ToString::to_string).collect();
+
+        let mut cache = TemplateCache::new(vec![get_template_path()]);
+        let template = cache.get(TemplateType::Song).unwrap().clone();
+        let pres = build_presentation_from_template_with_options("Test", &template, &content, &TextMeasure::Columns(40), 4).unwrap();
+
+        // Short stanzas aren't packed onto one slide; the repeated chorus adds none
+        assert_eq!(pres.cues.len(), 5);
+        let groups: Vec<&rv_data::Group> = pres.cue_groups.iter().filter_map(|g| g.group.as_ref()).collect();
+        let template_group = template.cue_groups.first().and_then(|g| g.group.as_ref()).unwrap();
+        assert_eq!(groups.iter().map(|g| g.name.as_str()).collect::<Vec<_>>(), [template_group.name.as_str(), "Verse 1", "Chorus", "Verse 2"]);
+        assert_eq!(groups[0].color, template_group.color);
+        assert_eq!(groups[2].color, Some(get_group_color("Chorus").into()));
+        // An unlabeled stanza belongs to the group above it
+        assert_eq!(pres.cue_groups[2].cue_identifiers.len(), 2);
+
+        let arrangement = pres.arrangements.first().unwrap();
+        assert_eq!(arrangement.name, "Default");
+        assert_eq!(pres.selected_arrangement, arrangement.uuid);
+        let group_uuids: Vec<_> = groups.iter().filter_map(|g| g.uuid.clone()).collect();
+        assert_eq!(arrangement.group_identifiers, [0, 1, 2, 3, 2].map(|i| group_uuids[i].clone()));
+    }
+
+    #[test]
+    fn test_changed_repeat_gets_its_own_group() {
+        let content: Vec<String> = ["[Chorus]", "My chains are gone", "", "[Chorus]", "My chains are gone, gone"]
+            .iter().map(ToString::to_string).collect();
+
+        let mut cache = TemplateCache::new(vec![get_template_path()]);
+        let template = cache.get(TemplateType::Song).unwrap().clone();
+        let pres = build_presentation_from_template_with_options("Test", &template, &content, &TextMeasure::Columns(40), 4).unwrap();
+        assert_eq!(pres.cues.len(), 2);
+        assert_eq!(pres.cue_groups.len(), 2);
+    }
+
+    #[test]
+    fn test_stanza_directives_on_template_slides() {
+        let content: Vec<String> = [
+            "[Verse 1]",
+            "{notes: Keys only}",
+            "{transition: dissolve 2}",
+            "{background: /Loops/Calm.mov}",
+            "Amazing grace, how sweet the sound",
+            "",
+            "That saved a wretch like me",
+            "",
+            "{background: #000000}",
+            "[Chorus]",
+            "My chains are gone",
+        ].iter().map(ToString::to_string).collect();
+
+        let mut cache = TemplateCache::new(vec![get_template_path()]);
+        let template = cache.get(TemplateType::Song).unwrap().clone();
+        let template_slide = extract_template_slide(&template).unwrap();
+        let pres = build_presentation_from_template_with_options("Test", &template, &content, &TextMeasure::Columns(40), 1).unwrap();
+        assert_eq!(pres.cues.len(), 3);
+        let slide = |cue: &rv_data::Cue| match &cue.actions[0].action_type_data {
+            Some(rv_data::action::ActionTypeData::Slide(rv_data::action::SlideType {
+                slide: Some(rv_data::action::slide_type::Slide::Presentation(slide)),
+            })) => slide.clone(),
+            _ => panic!("first action isn't a slide"),
+        };
+
+        let verse = dm::Slide::from(slide(&pres.cues[0]));
+        assert_eq!(verse.notes.as_deref(), Some("Keys only"));
+        assert_eq!(verse.transition, Some(dm::Transition { transition_type: dm::TransitionType::Dissolve, duration: 2.0 }));
+        // The media background plays alongside the slide
+        let media_action = pres.cues[0].actions.get(1).unwrap();
+        assert_eq!(media_action.r#type, rv_data::action::ActionType::Media as i32);
+        assert_ne!(media_action.uuid, pres.cues[0].actions[0].uuid);
+
+        // Unmarked stanzas keep the template slide's settings
+        let plain = slide(&pres.cues[1]);
+        assert_eq!(pres.cues[1].actions.len(), 1);
+        assert_eq!(plain.notes, template_slide.notes);
+        assert_eq!(plain.transition, template_slide.transition);
+
+        let chorus = slide(&pres.cues[2]);
+        let base = chorus.base_slide.unwrap();
+        assert!(base.draws_background_color);
+        assert_eq!(base.background_color, Some(dm::Color { red: 0.0, green: 0.0, blue: 0.0, alpha: 1.0 }.into()));
+        // The rest of the template slide is kept
+        let template_base = template_slide.base_slide.unwrap();
+        assert_eq!(base.elements.len(), template_base.elements.len());
+        assert_eq!(base.size, template_base.size);
+    }
+
+    #[test]
+    fn test_text_fit_wraps_by_advance_width() {
+        use super::super::fonts::test_font;
+
+        // 50pt per character and 25pt spaces at size 100, in a 500x250 box
+        let font = FontMetrics::parse(&test_font("Test", &[(' ', 250)]), 0).unwrap();
+        let fit = TextFit::new(Arc::new(font), 100.0, 500.0, 250.0);
+        assert_eq!(fit.box_lines(), 2);
+        assert_eq!(fit.wrapped_lines("how sweet"), 1);
+        assert_eq!(fit.wrapped_lines("Amazing grace"), 2);
+        assert_eq!(fit.wrapped_lines("Hallelujahhallelujah!"), 3);
+        assert_eq!(fit.wrapped_lines("how sweet\nAmazing grace"), 3);
+
+        let measure = TextMeasure::Fit(fit);
+        assert_eq!(measure.slide_capacity(10), 2);
+        let content: Vec<String> = ["how sweet", "the sound", "that saved"].iter().map(ToString::to_string).collect();
+        assert_eq!(preview_slides(&content, &measure, 10).len(), 2);
+    }
+
+    #[test]
+    fn test_text_measure_uses_template_font() {
+        use super::super::fonts::test_font;
+
+        let mut cache = TemplateCache::new(vec![get_template_path()]);
+        assert!(matches!(cache.text_measure(TemplateType::Song, 40), TextMeasure::Columns(40)));
+
+        let slide = extract_template_slide(cache.get(TemplateType::Song).unwrap()).unwrap();
+        let rtf = slide.base_slide.iter()
+            .flat_map(|b| &b.elements)
+            .find_map(|e| e.element.as_ref().and_then(|g| g.text.as_ref()))
+            .map(|t| t.rtf_data.clone())
+            .unwrap();
+        let font_name = extract_rtf_options(&rtf).unwrap().font_name;
+
+        let fonts = tempfile::tempdir().unwrap();
+        std::fs::write(fonts.path().join(format!("{font_name}.ttf")), test_font(&font_name, &[])).unwrap();
+        cache.set_fonts(FontLibrary::scan(&[fonts.path().to_path_buf()]));
+        assert!(matches!(cache.text_measure(TemplateType::Song, 40), TextMeasure::Fit(_)));
+    }
 }
 
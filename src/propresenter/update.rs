@@ -0,0 +1,746 @@
+//! Update matched `ProPresenter` files in place from edited editor content.
+//!
+//! Opening a matched file lists its groups as `[Label]` stanzas, one stanza
+//! per slide. On export the edited stanzas are diffed against those groups:
+//! unchanged slides are left alone, changed ones get new RTF text in their
+//! existing style, and slides are added or removed within their group. Slides
+//! that stay keep their backgrounds, transitions, cue actions and UUIDs.
+//! Chords are listed inline (`[G]Amazing grace`) so they survive edits.
+//! Song credits aren't listed, and are left as they are.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+use uuid::Uuid;
+
+use super::copyright::{is_credit_line, COPYRIGHT_GROUP};
+use super::data_model as dm;
+use super::deserialize::read_presentation_file;
+use super::export::{get_group_color, parse_stanzas, ExportError};
+use super::extract::extract_slide_text;
+use super::generated::rv_data::{self, action::slide_type::Slide, action::ActionTypeData};
+use super::rtf::replace_rtf_text;
+use super::serialize::write_presentation_file;
+use super::template::join_chord_lines;
+use crate::lyrics::chordpro::{self, Transposition};
+
+/// Label for groups that have no name of their own
+const UNNAMED_GROUP: &str = "Group";
+
+/// A run of text slides listed under one label in the editor
+struct Section {
+    /// Group name, `None` for slides outside any group
+    label: Option<String>,
+    /// Index into the presentation's cue groups
+    group: Option<usize>,
+    /// Cue UUID and slide text, chords inline, for each text slide in group order
+    slides: Vec<(String, String)>,
+}
+
+/// An edited slide: its text and `(UTF-16 offset, name)` chords
+struct EditedSlide {
+    text: String,
+    chords: Vec<(usize, String)>,
+}
+
+impl EditedSlide {
+    /// The slide as the editor lists it, for comparing with the original.
+    fn listed(&self) -> String {
+        with_chords(&self.text, &self.chords)
+    }
+}
+
+/// One step of turning a group's original slides into its edited ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    /// Original slide kept as is
+    Keep(usize),
+    /// Original slide given the edited slide's text
+    Replace(usize, usize),
+    /// Edited slide with no original, added as a new cue
+    Insert(usize),
+    /// Original slide with no edited counterpart
+    Delete(usize),
+}
+
+/// Where a new cue goes relative to the existing ones
+enum Placement {
+    Before(String),
+    After(String),
+    End,
+}
+
+fn cue_id(cue: &rv_data::Cue) -> &str {
+    cue.uuid.as_ref().map_or("", |uuid| uuid.string.as_str())
+}
+
+/// The text elements of a cue's slide that hold text.
+fn text_elements(cue: &mut rv_data::Cue) -> impl Iterator<Item = &mut rv_data::graphics::Text> {
+    cue.actions.iter_mut()
+        .filter_map(|action| match action.action_type_data.as_mut() {
+            Some(ActionTypeData::Slide(rv_data::action::SlideType { slide: Some(Slide::Presentation(slide)) })) => Some(slide),
+            _ => None,
+        })
+        .take(1)
+        .flat_map(|slide| slide.base_slide.iter_mut().flat_map(|base| &mut base.elements))
+        .filter_map(|element| element.element.as_mut().and_then(|element| element.text.as_mut()))
+        .filter(|text| has_text(text))
+}
+
+fn has_text(text: &rv_data::graphics::Text) -> bool {
+    super::rtf::rtf_to_text(&String::from_utf8_lossy(&text.rtf_data)).is_some_and(|text| !text.trim().is_empty())
+}
+
+/// A cue's slide text with its chords written inline.
+fn cue_text(cue: &rv_data::Cue) -> Option<String> {
+    use rv_data::graphics::text::attributes::custom_attribute::Attribute;
+
+    let text = cue.actions.iter().find_map(extract_slide_text)?;
+    let chords: Vec<(usize, String)> = cue.actions.iter()
+        .find_map(|action| match action.action_type_data.as_ref() {
+            Some(ActionTypeData::Slide(rv_data::action::SlideType { slide: Some(Slide::Presentation(slide)) })) => Some(slide),
+            _ => None,
+        })
+        .into_iter()
+        .flat_map(|slide| slide.base_slide.iter().flat_map(|base| &base.elements))
+        .filter_map(|element| element.element.as_ref().and_then(|element| element.text.as_ref()))
+        .find(|text| has_text(text))
+        .and_then(|text| text.attributes.as_ref())
+        .map(|attributes| attributes.custom_attributes.iter()
+            .filter_map(|a| match (&a.attribute, &a.range) {
+                (Some(Attribute::Chord(name)), Some(range)) => Some((usize::try_from(range.start).ok()?, name.clone())),
+                _ => None,
+            })
+            .collect())
+        .unwrap_or_default();
+    Some(with_chords(&text, &chords))
+}
+
+/// Write `(UTF-16 offset, name)` chords into `text` as `[name]`.
+fn with_chords(text: &str, chords: &[(usize, String)]) -> String {
+    let mut chords: Vec<_> = chords.iter().collect();
+    chords.sort_by_key(|(pos, _)| *pos);
+    let mut chords = chords.into_iter().peekable();
+
+    let mut out = String::new();
+    let mut offset = 0;
+    for c in text.chars() {
+        while let Some((_, name)) = chords.next_if(|(pos, _)| *pos <= offset) {
+            let _ = write!(out, "[{name}]");
+        }
+        out.push(c);
+        offset += c.len_utf16();
+    }
+    for (_, name) in chords {
+        let _ = write!(out, "[{name}]");
+    }
+    out
+}
+
+fn group_label(group: &rv_data::presentation::CueGroup) -> String {
+    group.group.as_ref()
+        .map(|group| group.name.trim())
+        .filter(|name| !name.is_empty())
+        .unwrap_or(UNNAMED_GROUP)
+        .to_string()
+}
+
+/// The cue ending in the credit line [`super::copyright::add_copyright`]
+/// writes under the last slide, and that line.
+fn credited_cue(presentation: &rv_data::Presentation) -> Option<(String, String)> {
+    let ccli = dm::CCLIInfo::from(presentation.ccli.clone()?);
+    let copyright_cues: HashSet<&str> = presentation.cue_groups.iter()
+        .filter(|group| group_label(group) == COPYRIGHT_GROUP)
+        .flat_map(|group| &group.cue_identifiers)
+        .map(|uuid| uuid.string.as_str())
+        .collect();
+    let (cue, text) = presentation.cues.iter().rev()
+        .filter(|cue| !copyright_cues.contains(cue_id(cue)))
+        .find_map(|cue| cue_text(cue).map(|text| (cue, text)))?;
+    let line = text.lines().last().filter(|line| is_credit_line(line, &ccli))?;
+    Some((cue_id(cue).to_string(), line.to_string()))
+}
+
+/// Text slides of a presentation, grouped the way the editor lists them:
+/// ungrouped slides first, then each group in order.
+///
+/// Song credits aren't listed, since export adds them again: the Copyright
+/// group is left out, and so is a credit line under the last slide.
+fn sections(presentation: &rv_data::Presentation) -> Vec<Section> {
+    let credited = credited_cue(presentation);
+    let texts: HashMap<&str, String> = presentation.cues.iter()
+        .filter_map(|cue| cue_text(cue).map(|text| (cue_id(cue), text)))
+        .map(|(id, text)| match &credited {
+            Some((credited, line)) if credited == id => {
+                let kept = text.strip_suffix(line.as_str()).unwrap_or(&text).trim_end_matches('\n');
+                (id, kept.to_string())
+            }
+            _ => (id, text),
+        })
+        .collect();
+    let grouped: HashSet<&str> = presentation.cue_groups.iter()
+        .flat_map(|group| &group.cue_identifiers)
+        .map(|uuid| uuid.string.as_str())
+        .collect();
+
+    let mut sections = Vec::new();
+    let ungrouped: Vec<_> = presentation.cues.iter()
+        .map(cue_id)
+        .filter(|id| !grouped.contains(id))
+        .filter_map(|id| texts.get(id).map(|text| (id.to_string(), text.clone())))
+        .collect();
+    if !ungrouped.is_empty() {
+        sections.push(Section { label: None, group: None, slides: ungrouped });
+    }
+
+    for (index, group) in presentation.cue_groups.iter().enumerate() {
+        if group_label(group) == COPYRIGHT_GROUP {
+            continue;
+        }
+        let slides: Vec<_> = group.cue_identifiers.iter()
+            .filter_map(|uuid| texts.get(uuid.string.as_str()).map(|text| (uuid.string.clone(), text.clone())))
+            .collect();
+        if !slides.is_empty() {
+            sections.push(Section { label: Some(group_label(group)), group: Some(index), slides });
+        }
+    }
+    sections
+}
+
+/// Editor lines for a presentation: each group's label, then its slides
+/// separated by blank lines.
+pub fn editor_lines(presentation: &rv_data::Presentation) -> Vec<String> {
+    let mut lines = Vec::new();
+    for section in sections(presentation) {
+        for (index, (_, text)) in section.slides.iter().enumerate() {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            if let (0, Some(label)) = (index, &section.label) {
+                lines.push(format!("[{label}]"));
+            }
+            lines.extend(text.lines().map(str::to_string));
+        }
+    }
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Split editor content into labelled runs of slides. An unlabelled stanza
+/// belongs to the group above it.
+fn edited_sections(content: &[String]) -> Vec<(Option<String>, Vec<EditedSlide>)> {
+    let transposition = Transposition::from_content(content);
+    let mut sections: Vec<(Option<String>, Vec<EditedSlide>)> = Vec::new();
+
+    for stanza in parse_stanzas(content) {
+        let lines: Vec<_> = stanza.lines.iter()
+            .map(|line| {
+                let mut parsed = chordpro::parse_editor_line(line);
+                transposition.apply(&mut parsed);
+                parsed
+            })
+            .collect();
+        let (text, chords) = join_chord_lines(lines.iter().map(Some));
+
+        match sections.last_mut() {
+            Some((_, slides)) if stanza.label.is_none() => slides.push(EditedSlide { text, chords }),
+            _ => sections.push((stanza.label, vec![EditedSlide { text, chords }])),
+        }
+    }
+    sections
+}
+
+/// Line up `original` and `edited` slide texts, keeping the longest common
+/// run of unchanged slides and pairing up the changed ones in between.
+fn diff(original: &[&str], edited: &[&str]) -> Vec<Edit> {
+    let (n, m) = (original.len(), edited.len());
+    let mut common = vec![vec![0_usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            common[i][j] = if original[i] == edited[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let (mut gap_i, mut gap_j) = (0, 0);
+    while i < n || j < m {
+        if original.get(i).is_some_and(|text| edited.get(j) == Some(text)) {
+            push_gap(&mut edits, gap_i..i, gap_j..j);
+            edits.push(Edit::Keep(i));
+            i += 1;
+            j += 1;
+            (gap_i, gap_j) = (i, j);
+        } else if j < m && (i == n || common[i][j + 1] >= common[i + 1][j]) {
+            j += 1;
+        } else {
+            i += 1;
+        }
+    }
+    push_gap(&mut edits, gap_i..n, gap_j..m);
+    edits
+}
+
+/// Pair changed originals with edits, then delete or insert the remainder.
+fn push_gap(edits: &mut Vec<Edit>, original: std::ops::Range<usize>, edited: std::ops::Range<usize>) {
+    let paired = original.len().min(edited.len());
+    edits.extend(original.clone().zip(edited.clone()).map(|(i, j)| Edit::Replace(i, j)));
+    edits.extend(original.skip(paired).map(Edit::Delete));
+    edits.extend(edited.skip(paired).map(Edit::Insert));
+}
+
+/// Rewrite a cue's slide text in the style of its existing RTF.
+///
+/// The first non-blank text element gets the new text and chords; any other
+/// text elements are emptied, since the editor showed them joined as one.
+fn set_cue_text(cue: &mut rv_data::Cue, slide: &EditedSlide) {
+    use rv_data::graphics::text::attributes::{custom_attribute::Attribute, CustomAttribute};
+
+    let mut written = false;
+    for text in text_elements(cue) {
+        let new_text = if written { "" } else { slide.text.as_str() };
+        text.rtf_data = replace_rtf_text(&text.rtf_data, new_text);
+
+        let attributes = text.attributes.get_or_insert_with(Default::default);
+        attributes.custom_attributes.retain(|a| !matches!(a.attribute, Some(Attribute::Chord(_))));
+        if !written {
+            attributes.custom_attributes.extend(slide.chords.iter().map(|(pos, name)| {
+                let start = i32::try_from(*pos).unwrap_or(i32::MAX);
+                CustomAttribute {
+                    range: Some(rv_data::IntRange { start, end: start.saturating_add(1) }),
+                    attribute: Some(Attribute::Chord(name.clone())),
+                }
+            }));
+        }
+        written = true;
+    }
+}
+
+/// Copy a cue with fresh UUIDs for the cue, its actions, slide and elements.
+fn copy_cue(template: &rv_data::Cue, slide: &EditedSlide) -> rv_data::Cue {
+    let new_uuid = || Some(rv_data::Uuid { string: Uuid::new_v4().to_string().to_uppercase() });
+
+    let mut cue = template.clone();
+    cue.uuid = new_uuid();
+    for action in &mut cue.actions {
+        action.uuid = new_uuid();
+        if let Some(ActionTypeData::Slide(rv_data::action::SlideType { slide: Some(Slide::Presentation(slide)) })) = action.action_type_data.as_mut() {
+            if let Some(base) = slide.base_slide.as_mut() {
+                base.uuid = new_uuid();
+                for element in base.elements.iter_mut().filter_map(|element| element.element.as_mut()) {
+                    element.uuid = new_uuid();
+                }
+                base.element_build_order.clear();
+            }
+        }
+    }
+    set_cue_text(&mut cue, slide);
+    cue
+}
+
+/// Apply edited editor content to a presentation's groups and slides.
+///
+/// Groups are matched by label. Labels with no group become new groups at the
+/// end of every arrangement; groups missing from the content lose their text
+/// slides, and are dropped along with their arrangement entries once empty.
+#[allow(clippy::too_many_lines)] // One pass over groups, cues and arrangements
+pub fn update_presentation(presentation: &mut rv_data::Presentation, content: &[String]) -> Result<(), ExportError> {
+    let edited = edited_sections(content);
+    if edited.is_empty() {
+        return Err(ExportError::Build("No content to export".to_string()));
+    }
+
+    let original = sections(presentation);
+    let credited = credited_cue(presentation);
+    let cues: HashMap<String, rv_data::Cue> = presentation.cues.iter()
+        .map(|cue| (cue_id(cue).to_string(), cue.clone()))
+        .collect();
+    let fallback = original.iter().rev().find_map(|section| section.slides.last()).map(|(id, _)| id.clone());
+
+    let mut used = vec![false; original.len()];
+    let mut removed: HashSet<String> = HashSet::new();
+    let mut placements: Vec<(Placement, rv_data::Cue)> = Vec::new();
+    let mut group_ids: HashMap<usize, Vec<String>> = HashMap::new();
+    let mut new_groups: Vec<(String, Vec<String>)> = Vec::new();
+
+    for (label, slides) in &edited {
+        let matched = original.iter().enumerate()
+            .position(|(index, section)| !used[index] && section.label == *label);
+        let Some(index) = matched else {
+            // A new group, styled after the last text slide in the file
+            let template = fallback.as_ref().and_then(|id| cues.get(id))
+                .ok_or_else(|| ExportError::Build("No slide to copy styling from".to_string()))?;
+            let new_cues: Vec<_> = slides.iter().map(|slide| copy_cue(template, slide)).collect();
+            let ids = new_cues.iter().map(|cue| cue_id(cue).to_string()).collect();
+            if let Some(label) = label {
+                new_groups.push((label.clone(), ids));
+            }
+            placements.extend(new_cues.into_iter().map(|cue| (Placement::End, cue)));
+            continue;
+        };
+        used[index] = true;
+        let section = &original[index];
+
+        let original_texts: Vec<_> = section.slides.iter().map(|(_, text)| text.as_str()).collect();
+        let edited_listed: Vec<_> = slides.iter().map(EditedSlide::listed).collect();
+        let edited_texts: Vec<_> = edited_listed.iter().map(String::as_str).collect();
+        let mut anchor: Option<&str> = None;
+        let mut inserted: Vec<(Option<&str>, String)> = Vec::new();
+
+        for edit in diff(&original_texts, &edited_texts) {
+            match edit {
+                Edit::Keep(i) => anchor = Some(&section.slides[i].0),
+                Edit::Replace(i, j) => {
+                    let id = &section.slides[i].0;
+                    // Keep the credit line the editor didn't list
+                    let slide = match &credited {
+                        Some((credited, line)) if credited == id => &EditedSlide {
+                            text: format!("{}\n{line}", slides[j].text),
+                            chords: slides[j].chords.clone(),
+                        },
+                        _ => &slides[j],
+                    };
+                    if let Some(cue) = presentation.cues.iter_mut().find(|cue| cue_id(cue) == id) {
+                        set_cue_text(cue, slide);
+                    }
+                    anchor = Some(id);
+                }
+                Edit::Delete(i) => {
+                    removed.insert(section.slides[i].0.clone());
+                    anchor = Some(&section.slides[i].0);
+                }
+                Edit::Insert(j) => {
+                    let template_id = anchor.unwrap_or(&section.slides[0].0);
+                    let Some(template) = cues.get(template_id) else { continue };
+                    let cue = copy_cue(template, &slides[j]);
+                    inserted.push((anchor, cue_id(&cue).to_string()));
+                    let placement = anchor.map_or_else(
+                        || Placement::Before(section.slides[0].0.clone()),
+                        |id| Placement::After(id.to_string()),
+                    );
+                    placements.push((placement, cue));
+                }
+            }
+        }
+
+        if let Some(group) = section.group {
+            let mut ids: Vec<String> = inserted.iter()
+                .filter(|(anchor, _)| anchor.is_none())
+                .map(|(_, id)| id.clone())
+                .collect();
+            for uuid in &presentation.cue_groups[group].cue_identifiers {
+                if !removed.contains(&uuid.string) {
+                    ids.push(uuid.string.clone());
+                }
+                ids.extend(inserted.iter()
+                    .filter(|(anchor, _)| *anchor == Some(uuid.string.as_str()))
+                    .map(|(_, id)| id.clone()));
+            }
+            group_ids.insert(group, ids);
+        }
+    }
+
+    // Groups left out of the edited content lose their text slides
+    for (section, _) in original.iter().zip(&used).filter(|(_, used)| !**used) {
+        removed.extend(section.slides.iter().map(|(id, _)| id.clone()));
+        if let Some(group) = section.group {
+            let ids = presentation.cue_groups[group].cue_identifiers.iter()
+                .map(|uuid| uuid.string.clone())
+                .filter(|id| !removed.contains(id))
+                .collect();
+            group_ids.insert(group, ids);
+        }
+    }
+
+    // Rebuild the cue list, new cues next to the slides they follow
+    let mut ordered = Vec::with_capacity(presentation.cues.len() + placements.len());
+    for cue in std::mem::take(&mut presentation.cues) {
+        let id = cue_id(&cue).to_string();
+        for (_, new_cue) in placements.iter().filter(|(placement, _)| matches!(placement, Placement::Before(before) if *before == id)) {
+            ordered.push(new_cue.clone());
+        }
+        if !removed.contains(&id) {
+            ordered.push(cue);
+        }
+        for (_, new_cue) in placements.iter().filter(|(placement, _)| matches!(placement, Placement::After(after) if *after == id)) {
+            ordered.push(new_cue.clone());
+        }
+    }
+    ordered.extend(placements.into_iter()
+        .filter(|(placement, _)| matches!(placement, Placement::End))
+        .map(|(_, cue)| cue));
+    presentation.cues = ordered;
+
+    // Update group membership, dropping groups that ended up empty
+    let mut dropped: HashSet<String> = HashSet::new();
+    for (group, ids) in group_ids {
+        let cue_group = &mut presentation.cue_groups[group];
+        if ids.is_empty() {
+            dropped.extend(cue_group.group.as_ref().and_then(|g| g.uuid.as_ref()).map(|uuid| uuid.string.clone()));
+        }
+        cue_group.cue_identifiers = ids.into_iter().map(|string| rv_data::Uuid { string }).collect();
+    }
+    presentation.cue_groups.retain(|group| !group.cue_identifiers.is_empty());
+    for arrangement in &mut presentation.arrangements {
+        arrangement.group_identifiers.retain(|uuid| !dropped.contains(&uuid.string));
+    }
+
+    for (label, ids) in new_groups {
+        let group_uuid = rv_data::Uuid { string: Uuid::new_v4().to_string().to_uppercase() };
+        presentation.cue_groups.push(rv_data::presentation::CueGroup {
+            group: Some(rv_data::Group {
+                uuid: Some(group_uuid.clone()),
+                name: label.clone(),
+                color: Some(get_group_color(&label).into()),
+                hot_key: None,
+                application_group_identifier: None,
+                application_group_name: String::new(),
+            }),
+            cue_identifiers: ids.into_iter().map(|string| rv_data::Uuid { string }).collect(),
+        });
+        for arrangement in &mut presentation.arrangements {
+            arrangement.group_identifiers.push(group_uuid.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Apply edited content to the .pro file at `path` without writing it.
+/// Returns the updated presentation, or `None` if nothing changed.
+pub fn update_pro_file(path: &Path, content: &[String]) -> Result<Option<rv_data::Presentation>, ExportError> {
+    let original = read_presentation_file(path)?;
+    let mut presentation = original.clone();
+    update_presentation(&mut presentation, content)?;
+    Ok((presentation != original).then_some(presentation))
+}
+
+/// Write a presentation from [`update_pro_file`] back to `path`. The file as
+/// it was is first copied to `<name>.pro.bak`.
+pub fn save_pro_file(path: &Path, presentation: &rv_data::Presentation) -> Result<(), ExportError> {
+    std::fs::copy(path, backup_path(path))?;
+    write_presentation_file(presentation, path)?;
+    Ok(())
+}
+
+/// Where [`save_pro_file`] keeps the previous version of `path`.
+pub fn backup_path(path: &Path) -> std::path::PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+    use std::path::PathBuf;
+
+    fn amazing_grace() -> rv_data::Presentation {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("data");
+        path.push("examples");
+        path.push("propresenter");
+        path.push("[Hymn] Amazing Grace.pro");
+        read_presentation_file(path).unwrap()
+    }
+
+    fn group_texts(presentation: &rv_data::Presentation, name: &str) -> Vec<String> {
+        sections(presentation).into_iter()
+            .find(|section| section.label.as_deref() == Some(name))
+            .map(|section| section.slides.into_iter().map(|(_, text)| text).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_diff_keeps_common_slides() {
+        use Edit::{Delete, Insert, Keep, Replace};
+
+        assert_eq!(diff(&["a", "b", "c"], &["a", "b", "c"]), [Keep(0), Keep(1), Keep(2)]);
+        assert_eq!(diff(&["a", "b", "c"], &["a", "x", "c"]), [Keep(0), Replace(1, 1), Keep(2)]);
+        assert_eq!(diff(&["a", "c"], &["a", "b", "c"]), [Keep(0), Insert(1), Keep(1)]);
+        assert_eq!(diff(&["a", "b", "c"], &["a", "c"]), [Keep(0), Delete(1), Keep(2)]);
+        assert_eq!(diff(&["a"], &["x", "y"]), [Replace(0, 0), Insert(1)]);
+    }
+
+    #[test]
+    fn test_unchanged_content_leaves_file_untouched() {
+        let original = amazing_grace();
+        let lines = editor_lines(&original);
+        assert!(lines.contains(&"[Verse 1]".to_string()));
+
+        let mut presentation = original.clone();
+        update_presentation(&mut presentation, &lines).unwrap();
+        assert_eq!(presentation, original);
+    }
+
+    #[test]
+    fn test_edit_rewrites_only_changed_slide() {
+        let original = amazing_grace();
+        let verse_2 = group_texts(&original, "Verse 2");
+        let lines: Vec<String> = editor_lines(&original).into_iter()
+            .map(|line| if !line.is_empty() && verse_2[0].lines().any(|l| l == line) { line.to_uppercase() } else { line })
+            .collect();
+
+        let mut presentation = original.clone();
+        update_presentation(&mut presentation, &lines).unwrap();
+
+        assert_eq!(group_texts(&presentation, "Verse 2"), [verse_2[0].to_uppercase()]);
+        assert_eq!(group_texts(&presentation, "Verse 1"), group_texts(&original, "Verse 1"));
+        assert_eq!(presentation.cue_groups, original.cue_groups);
+        assert_eq!(presentation.cues.len(), original.cues.len());
+        let changed: Vec<_> = presentation.cues.iter().zip(&original.cues).filter(|(a, b)| a != b).collect();
+        assert_eq!(changed.len(), 1);
+        let (updated, source) = changed[0];
+        assert_eq!(updated.uuid, source.uuid);
+        assert_eq!(updated.actions[0].uuid, source.actions[0].uuid);
+    }
+
+    #[test]
+    fn test_add_and_remove_slides_within_groups() {
+        let original = amazing_grace();
+        let mut lines = editor_lines(&original);
+        // Add a second slide to Verse 1 and drop Verse 5 entirely
+        let verse_2 = lines.iter().position(|line| line == "[Verse 2]").unwrap();
+        lines.splice(verse_2..verse_2, ["A new slide".to_string(), String::new()]);
+        let verse_5 = lines.iter().position(|line| line == "[Verse 5]").unwrap();
+        let end = lines[verse_5..].iter().position(String::is_empty).map_or(lines.len(), |i| verse_5 + i + 1);
+        lines.drain(verse_5..end);
+
+        let mut presentation = original.clone();
+        update_presentation(&mut presentation, &lines).unwrap();
+
+        let verse_1 = group_texts(&presentation, "Verse 1");
+        assert_eq!(verse_1.len(), 2);
+        assert_eq!(verse_1[1], "A new slide");
+        assert!(group_texts(&presentation, "Verse 5").is_empty());
+        assert_eq!(presentation.cues.len(), original.cues.len());
+        assert!(presentation.arrangements[0].group_identifiers.len() < original.arrangements[0].group_identifiers.len());
+
+        // The new slide copies its neighbour's layout under new UUIDs
+        let new_id = &presentation.cue_groups.iter().find(|g| group_label(g) == "Verse 1").unwrap().cue_identifiers[1];
+        assert!(original.cues.iter().all(|cue| cue.uuid.as_ref() != Some(new_id)));
+    }
+
+    #[test]
+    fn test_chords_are_listed_and_kept_on_edit() {
+        use rv_data::graphics::text::attributes::{custom_attribute::Attribute, CustomAttribute};
+
+        let mut original = amazing_grace();
+        let verse_2 = original.cue_groups.iter().find(|g| group_label(g) == "Verse 2").unwrap().cue_identifiers[0].string.clone();
+        let cue = original.cues.iter_mut().find(|cue| cue_id(cue) == verse_2).unwrap();
+        let text = text_elements(cue).next().unwrap();
+        text.attributes.get_or_insert_with(Default::default).custom_attributes.push(CustomAttribute {
+            range: Some(rv_data::IntRange { start: 0, end: 1 }),
+            attribute: Some(Attribute::Chord("G".to_string())),
+        });
+
+        let lines = editor_lines(&original);
+        let first = lines.iter().position(|line| line == "[Verse 2]").unwrap() + 1;
+        assert!(lines[first].starts_with("[G]"), "{}", lines[first]);
+
+        // Unchanged chords leave the file alone; a text edit keeps them
+        let mut presentation = original.clone();
+        update_presentation(&mut presentation, &lines).unwrap();
+        assert_eq!(presentation, original);
+
+        let mut edited = lines.clone();
+        edited[first] = edited[first].to_uppercase();
+        update_presentation(&mut presentation, &edited).unwrap();
+        assert_ne!(presentation, original);
+        assert!(editor_lines(&presentation)[first].starts_with("[G]"));
+    }
+
+    #[test]
+    fn test_update_pro_file_keeps_a_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Amazing Grace.pro");
+        write_presentation_file(&amazing_grace(), &path).unwrap();
+        let before = std::fs::read(&path).unwrap();
+
+        let lines = editor_lines(&amazing_grace());
+        assert!(update_pro_file(&path, &lines).unwrap().is_none(), "unchanged files need no update");
+
+        let edited: Vec<String> = lines.into_iter().map(|line| line.replace("grace", "GRACE")).collect();
+        let updated = update_pro_file(&path, &edited).unwrap().unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), before, "updating doesn't write the file");
+        assert!(!backup_path(&path).exists());
+
+        save_pro_file(&path, &updated).unwrap();
+        assert_eq!(std::fs::read(backup_path(&path)).unwrap(), before);
+        assert_eq!(read_presentation_file(&path).unwrap(), updated);
+        assert_eq!(backup_path(&path).file_name().unwrap(), "Amazing Grace.pro.bak");
+    }
+
+    #[test]
+    fn test_new_label_becomes_group() {
+        let original = amazing_grace();
+        let mut lines = editor_lines(&original);
+        lines.extend(["[Doxology]".to_string(), "Praise God".to_string(), String::new()]);
+
+        let mut presentation = original.clone();
+        update_presentation(&mut presentation, &lines).unwrap();
+
+        assert_eq!(group_texts(&presentation, "Doxology"), ["Praise God"]);
+        assert_eq!(presentation.cue_groups.len(), original.cue_groups.len() + 1);
+        assert_eq!(presentation.arrangements[0].group_identifiers.len(), original.arrangements[0].group_identifiers.len() + 1);
+    }
+
+    #[test]
+    fn test_credits_are_not_listed_or_added_twice() {
+        use crate::propresenter::copyright::{add_copyright, CopyrightFooter};
+        use crate::propresenter::template::{build_presentation_from_template, TemplateCache, TemplateType};
+
+        let template_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data").join("templates");
+        let template = TemplateCache::new(vec![template_dir]).get(TemplateType::Song).unwrap().clone();
+        let ccli = dm::CCLIInfo {
+            author: "John Newton".to_string(),
+            song_title: "Amazing Grace".to_string(),
+            publisher: "Public Domain".to_string(),
+            song_number: 22_025,
+            artist_credits: String::new(),
+            copyright_year: 0,
+            display: true,
+            album: String::new(),
+        };
+        let content: Vec<String> = ["[Verse 1]", "Amazing grace", "", "[Verse 2]", "'Twas grace"]
+            .iter().map(ToString::to_string).collect();
+        let export = |lines: &[String], footer| {
+            let mut presentation = build_presentation_from_template("Amazing Grace", &template, lines).unwrap();
+            add_copyright(&mut presentation, &template, &ccli, Some("1234567"), footer);
+            presentation
+        };
+        let copyright_groups = |presentation: &rv_data::Presentation| {
+            presentation.cue_groups.iter().filter(|group| group_label(group) == COPYRIGHT_GROUP).count()
+        };
+
+        // Exporting what the editor loads gives the same slides and one copyright slide
+        let exported = export(&content, CopyrightFooter::Slide);
+        let lines = editor_lines(&exported);
+        assert_eq!(lines, editor_lines(&export(&content, CopyrightFooter::Off)));
+        assert_eq!(copyright_groups(&export(&lines, CopyrightFooter::Slide)), 1);
+
+        // Updating in place leaves the copyright slide alone
+        let mut updated = exported.clone();
+        update_presentation(&mut updated, &lines).unwrap();
+        assert_eq!(updated, exported);
+
+        // A credit line isn't listed, and stays under the slide when it's edited
+        let exported = export(&content, CopyrightFooter::Line);
+        let lines = editor_lines(&exported);
+        assert_eq!(lines, editor_lines(&export(&content, CopyrightFooter::Off)));
+        let edited: Vec<String> = lines.iter().map(|line| line.replace("'Twas grace", "'Twas grace that taught")).collect();
+        let mut updated = exported;
+        update_presentation(&mut updated, &edited).unwrap();
+        let last_text = updated.cues.iter().rev().find_map(cue_text);
+        assert_eq!(last_text.as_deref(), Some("'Twas grace that taught\nJohn Newton · Public Domain · CCLI Song # 22025 / License # 1234567"));
+        assert_eq!(editor_lines(&updated), edited);
+    }
+}
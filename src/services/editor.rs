@@ -14,6 +14,8 @@ pub enum EditorAction {
     Exit,
     /// The action was not handled by the editor.
     Unhandled,
+    /// Jump to the next (`true`) or previous (`false`) search match.
+    SearchNext(bool),
 }
 
 /// Trait for text editing operations.
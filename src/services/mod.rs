@@ -7,3 +7,4 @@ pub mod editor;
 pub mod playlist;
 pub mod scripture;
 pub mod search;
+pub mod vim;
@@ -69,6 +69,7 @@ impl PlaylistGenerator for ProPresenter7Playlist {
                 presentation_path: item.file_path.clone().unwrap_or_default(),
                 arrangement_uuid: None,
                 embedded_data: item.embedded_data.clone(),
+                pco_item_id: None,
             })
             .collect();
 
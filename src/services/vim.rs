@@ -0,0 +1,816 @@
+//! Vim-style modal editing on top of the [`Editor`] trait.
+//!
+//! Normal and visual mode keys are interpreted here; insert mode passes keys
+//! through (as [`EditorAction::Unhandled`]) so the regular editor handles
+//! typing. Supported: counts, `h j k l w b e 0 $ + - gg G { }`, the `d c y`
+//! operators with any motion, `dd cc yy D C Y x p P`, `i a I A o O`,
+//! `v`/`V` visual modes, and `n`/`N` handed back to the app. Undo and redo
+//! (`u`, `Ctrl+R`) belong to the app's history; see [`Vim::history_step`].
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::editor::{Editor, EditorAction};
+
+/// A position as `(line, column)`, with the column a byte offset.
+type Pos = (usize, usize);
+
+/// Largest count a command takes; longer counts are clamped to it.
+const MAX_COUNT: usize = 9999;
+
+/// Current modal state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VimMode {
+    /// Keys are commands
+    #[default]
+    Normal,
+    /// Keys type text
+    Insert,
+    /// Characterwise selection
+    Visual,
+    /// Linewise selection
+    VisualLine,
+}
+
+impl VimMode {
+    /// Status bar label.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Normal => "NORMAL",
+            Self::Insert => "INSERT",
+            Self::Visual => "VISUAL",
+            Self::VisualLine => "V-LINE",
+        }
+    }
+}
+
+/// Text held by yank and delete, pasted by `p`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Register {
+    /// Yanked text, lines joined by `\n`
+    pub text: String,
+    /// Whether the text is whole lines
+    pub linewise: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MotionKind {
+    /// Up to but not including the target
+    Exclusive,
+    /// Including the character at the target
+    Inclusive,
+    /// Whole lines from cursor to target
+    Linewise,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Motion {
+    target: Pos,
+    kind: MotionKind,
+}
+
+/// Character classes for word motions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Blank,
+    Punct,
+    Word,
+    EmptyLine,
+}
+
+/// Modal editing state: mode, pending count/operator and the register.
+#[derive(Debug, Clone, Default)]
+pub struct Vim {
+    mode: VimMode,
+    count: Option<usize>,
+    operator: Option<(char, usize)>,
+    pending_g: bool,
+    anchor: Pos,
+    register: Register,
+}
+
+impl Vim {
+    /// Start in normal mode with an empty register.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current mode.
+    pub const fn mode(&self) -> VimMode {
+        self.mode
+    }
+
+    /// Whether keys currently type text.
+    pub fn is_inserting(&self) -> bool {
+        self.mode == VimMode::Insert
+    }
+
+    /// Where the visual selection started, while in a visual mode.
+    pub fn visual_anchor(&self) -> Option<(usize, usize)> {
+        matches!(self.mode, VimMode::Visual | VimMode::VisualLine).then_some(self.anchor)
+    }
+
+    /// The register `p` pastes from.
+    pub const fn register(&self) -> &Register {
+        &self.register
+    }
+
+    /// Whether `key` is undo (`Some(false)`, `u`) or redo (`Some(true)`,
+    /// `Ctrl+R`) in normal mode with no command half-typed.
+    pub fn history_step(&self, key: KeyEvent) -> Option<bool> {
+        if self.mode != VimMode::Normal || self.count.is_some() || self.operator.is_some() || self.pending_g {
+            return None;
+        }
+        match key.code {
+            KeyCode::Char('u') if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::META) => {
+                Some(false)
+            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Handle one key. Returns [`EditorAction::Unhandled`] for keys the
+    /// regular editor should process (typing in insert mode, `Esc` in
+    /// normal mode, function keys, and modified keys like `Ctrl+Z`).
+    pub fn handle_key(&mut self, editor: &mut impl Editor, key: KeyEvent) -> EditorAction {
+        // Commands need a line to stand on, even in an emptied buffer
+        if editor.content().is_empty() {
+            editor.content_mut().push(String::new());
+        }
+        if self.mode == VimMode::Insert {
+            if key.code != KeyCode::Esc {
+                return EditorAction::Unhandled;
+            }
+            self.mode = VimMode::Normal;
+            let (line, col) = editor.cursor_position();
+            let col = editor.content().get(line).map_or(0, |text| prev_boundary(text, col));
+            editor.set_cursor(line, col);
+            clamp_normal(editor);
+            return EditorAction::NoChange;
+        }
+
+        let c = match key.code {
+            _ if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::META | KeyModifiers::ALT) => {
+                return EditorAction::Unhandled;
+            }
+            KeyCode::Char(c) => c,
+            KeyCode::Left | KeyCode::Backspace => 'h',
+            KeyCode::Right => 'l',
+            KeyCode::Up => 'k',
+            KeyCode::Down => 'j',
+            KeyCode::Home => '0',
+            KeyCode::End => '$',
+            KeyCode::Enter => '+',
+            KeyCode::Delete => 'x',
+            KeyCode::Esc if self.mode != VimMode::Normal || self.operator.is_some() || self.count.is_some() => {
+                self.reset();
+                self.mode = VimMode::Normal;
+                clamp_normal(editor);
+                return EditorAction::NoChange;
+            }
+            KeyCode::Esc | KeyCode::F(_) | KeyCode::PageUp | KeyCode::PageDown => return EditorAction::Unhandled,
+            // Nothing else types text outside insert mode
+            _ => return EditorAction::NoChange,
+        };
+        clamp_normal(editor);
+
+        let before = editor.content().to_vec();
+        let action = self.handle_char(editor, c);
+        if self.mode != VimMode::Insert {
+            clamp_normal(editor);
+        }
+        match action {
+            EditorAction::NoChange if editor.content() != before.as_slice() => EditorAction::Modified,
+            action => action,
+        }
+    }
+
+    const fn reset(&mut self) {
+        self.count = None;
+        self.operator = None;
+        self.pending_g = false;
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn handle_char(&mut self, editor: &mut impl Editor, c: char) -> EditorAction {
+        // Counts: "3w", "d2w", "2dd"
+        if c.is_ascii_digit() && (c != '0' || self.count.is_some()) && !self.pending_g {
+            let digit = c.to_digit(10).map_or(0, |d| d as usize);
+            self.count = Some(self.count.unwrap_or(0).saturating_mul(10).saturating_add(digit).min(MAX_COUNT));
+            return EditorAction::NoChange;
+        }
+        let typed_count = self.count.take();
+        let op_count = self.operator.map_or(1, |(_, n)| n);
+        let count = typed_count.unwrap_or(1).saturating_mul(op_count).min(MAX_COUNT);
+        let explicit_count = typed_count.or_else(|| (op_count > 1).then_some(op_count));
+
+        let motion_key = if self.pending_g {
+            self.pending_g = false;
+            if c != 'g' {
+                self.reset();
+                return EditorAction::NoChange;
+            }
+            Some('g')
+        } else if c == 'g' {
+            self.pending_g = true;
+            self.count = typed_count;
+            return EditorAction::NoChange;
+        } else {
+            matches!(c, 'h' | 'l' | 'j' | 'k' | '+' | '-' | 'w' | 'b' | 'e' | '0' | '$' | 'G' | '{' | '}').then_some(c)
+        };
+
+        let cursor = editor.cursor_position();
+        if let Some(key) = motion_key {
+            // "cw" changes to the end of the word, like "ce"
+            let key = if key == 'w' && self.operator.is_some_and(|(op, _)| op == 'c')
+                && class_at(editor.content(), cursor) != Class::Blank
+            {
+                'e'
+            } else {
+                key
+            };
+            let Some(motion) = motion(editor.content(), cursor, key, count, explicit_count) else {
+                self.reset();
+                return EditorAction::NoChange;
+            };
+            if let Some((op, _)) = self.operator.take() {
+                let motion = if key == 'w' { adjust_word_end(editor.content(), cursor, motion) } else { motion };
+                self.apply_operator(editor, op, cursor, motion);
+            } else {
+                editor.set_cursor(motion.target.0, motion.target.1);
+            }
+            return EditorAction::NoChange;
+        }
+
+        // Operators act on the selection in visual mode, else wait for a motion
+        if matches!(c, 'd' | 'c' | 'y' | 'x') && matches!(self.mode, VimMode::Visual | VimMode::VisualLine) {
+            let kind = if self.mode == VimMode::VisualLine { MotionKind::Linewise } else { MotionKind::Inclusive };
+            let anchor = self.anchor;
+            self.mode = VimMode::Normal;
+            let op = if c == 'x' { 'd' } else { c };
+            self.apply_operator(editor, op, anchor, Motion { target: cursor, kind });
+            return EditorAction::NoChange;
+        }
+        if matches!(c, 'd' | 'c' | 'y') {
+            match self.operator {
+                // "dd", "cc", "yy": count lines
+                Some((op, _)) if op == c => {
+                    self.operator = None;
+                    let last = cursor.0.saturating_add(count - 1).min(editor.line_count().saturating_sub(1));
+                    self.apply_operator(editor, c, cursor, Motion { target: (last, 0), kind: MotionKind::Linewise });
+                }
+                Some(_) => self.reset(),
+                None => self.operator = Some((c, typed_count.unwrap_or(1))),
+            }
+            return EditorAction::NoChange;
+        }
+        if self.operator.take().is_some() {
+            return EditorAction::NoChange;
+        }
+
+        let line_len = editor.content().get(cursor.0).map_or(0, String::len);
+        match c {
+            'x' => {
+                let end = repeat(cursor.1, count, |col| next_boundary(&editor.content()[cursor.0], col));
+                self.apply_operator(editor, 'd', cursor, Motion { target: (cursor.0, end), kind: MotionKind::Exclusive });
+            }
+            'D' | 'C' => {
+                let op = if c == 'D' { 'd' } else { 'c' };
+                self.apply_operator(editor, op, cursor, Motion { target: (cursor.0, line_len), kind: MotionKind::Exclusive });
+            }
+            'Y' => {
+                let last = cursor.0.saturating_add(count - 1).min(editor.line_count().saturating_sub(1));
+                self.apply_operator(editor, 'y', cursor, Motion { target: (last, 0), kind: MotionKind::Linewise });
+            }
+            'p' | 'P' => self.paste(editor, c == 'p', count),
+            'i' => self.mode = VimMode::Insert,
+            'a' => {
+                editor.set_cursor(cursor.0, next_boundary(&editor.content()[cursor.0], cursor.1));
+                self.mode = VimMode::Insert;
+            }
+            'I' => {
+                editor.set_cursor(cursor.0, first_non_blank(&editor.content()[cursor.0]));
+                self.mode = VimMode::Insert;
+            }
+            'A' => {
+                editor.set_cursor(cursor.0, line_len);
+                self.mode = VimMode::Insert;
+            }
+            'o' | 'O' => {
+                let line = if c == 'o' { cursor.0 + 1 } else { cursor.0 };
+                editor.content_mut().insert(line, String::new());
+                editor.set_cursor(line, 0);
+                self.mode = VimMode::Insert;
+            }
+            'v' | 'V' => {
+                let mode = if c == 'v' { VimMode::Visual } else { VimMode::VisualLine };
+                if self.mode == mode {
+                    self.mode = VimMode::Normal;
+                } else {
+                    if self.mode == VimMode::Normal {
+                        self.anchor = cursor;
+                    }
+                    self.mode = mode;
+                }
+            }
+            'n' => return EditorAction::SearchNext(true),
+            'N' => return EditorAction::SearchNext(false),
+            _ => {}
+        }
+        EditorAction::NoChange
+    }
+
+    /// Delete, change or yank from `from` over `motion`.
+    fn apply_operator(&mut self, editor: &mut impl Editor, op: char, from: Pos, motion: Motion) {
+        let content = editor.content();
+        let (mut start, mut end) = if from <= motion.target { (from, motion.target) } else { (motion.target, from) };
+        let mut kind = motion.kind;
+
+        // An exclusive motion ending at the start of a later line stops at
+        // the end of the line before, and covers whole lines if it began at
+        // the start of its own
+        if kind == MotionKind::Exclusive && end.1 == 0 && end.0 > start.0 {
+            end = (end.0 - 1, content[end.0 - 1].len());
+            if start.1 <= first_non_blank(&content[start.0]) {
+                kind = MotionKind::Linewise;
+            }
+        }
+
+        if kind == MotionKind::Linewise {
+            let lines = content[start.0..=end.0.min(content.len() - 1)].to_vec();
+            self.register = Register { text: lines.join("\n"), linewise: true };
+            match op {
+                'd' => {
+                    let lines = editor.content_mut();
+                    lines.drain(start.0..=end.0.min(lines.len() - 1));
+                    if lines.is_empty() {
+                        lines.push(String::new());
+                    }
+                    let line = start.0.min(lines.len() - 1);
+                    let col = first_non_blank(&lines[line]);
+                    editor.set_cursor(line, col);
+                }
+                'c' => {
+                    let lines = editor.content_mut();
+                    lines.splice(start.0..=end.0.min(lines.len() - 1), [String::new()]);
+                    editor.set_cursor(start.0, 0);
+                    self.mode = VimMode::Insert;
+                }
+                _ => editor.set_cursor(start.0, if from.0 == start.0 { from.1 } else { 0 }),
+            }
+            return;
+        }
+
+        if kind == MotionKind::Inclusive {
+            end.1 = next_boundary(&content[end.0], end.1);
+        }
+        start.1 = start.1.min(content[start.0].len());
+        end.1 = end.1.min(content[end.0].len());
+        self.register = Register { text: text_between(content, start, end), linewise: false };
+        match op {
+            'd' | 'c' => {
+                delete_between(editor.content_mut(), start, end);
+                editor.set_cursor(start.0, start.1);
+                if op == 'c' {
+                    self.mode = VimMode::Insert;
+                }
+            }
+            _ => editor.set_cursor(start.0, start.1),
+        }
+    }
+
+    /// Paste the register `count` times after (or before) the cursor.
+    fn paste(&self, editor: &mut impl Editor, after: bool, count: usize) {
+        if self.register.text.is_empty() && !self.register.linewise {
+            return;
+        }
+        let (line, col) = editor.cursor_position();
+        if self.register.linewise {
+            let at = if after { (line + 1).min(editor.line_count()) } else { line };
+            let pasted: Vec<String> = (0..count)
+                .flat_map(|_| self.register.text.split('\n').map(str::to_string))
+                .collect();
+            editor.content_mut().splice(at..at, pasted);
+            let col = first_non_blank(&editor.content()[at]);
+            editor.set_cursor(at, col);
+            return;
+        }
+
+        let text = self.register.text.repeat(count);
+        let current = &editor.content()[line];
+        let at = if after && !current.is_empty() { next_boundary(current, col) } else { col.min(current.len()) };
+        let tail = current[at..].to_string();
+        let mut pieces = text.split('\n');
+        let first = pieces.next().unwrap_or_default();
+        let rest: Vec<String> = pieces.map(str::to_string).collect();
+
+        let lines = editor.content_mut();
+        lines[line].truncate(at);
+        lines[line].push_str(first);
+        if let Some(last) = rest.last() {
+            // Multi-line text: cursor goes to the start of the paste
+            let new_lines = rest[..rest.len() - 1].iter().cloned().chain([format!("{last}{tail}")]);
+            let below = line + 1;
+            lines.splice(below..below, new_lines);
+            editor.set_cursor(line, at);
+        } else {
+            lines[line].push_str(&tail);
+            let end = at + first.len();
+            editor.set_cursor(line, prev_boundary(&editor.content()[line], end));
+        }
+    }
+}
+
+/// Where `key` moves from `cursor`, `count` times. `explicit` is the typed
+/// count, for motions like `G` that treat it as a line number.
+fn motion(content: &[String], cursor: Pos, key: char, count: usize, explicit: Option<usize>) -> Option<Motion> {
+    let last_line = content.len().checked_sub(1)?;
+    let line_text = |l: usize| content.get(l).map_or("", String::as_str);
+    let (line, col) = cursor;
+    let motion = match key {
+        'h' => Motion {
+            target: (line, repeat(col, count, |c| prev_boundary(line_text(line), c))),
+            kind: MotionKind::Exclusive,
+        },
+        'l' => Motion {
+            target: (line, repeat(col, count, |c| next_boundary(line_text(line), c))),
+            kind: MotionKind::Exclusive,
+        },
+        'j' | 'k' => {
+            let target = if key == 'j' { line.saturating_add(count).min(last_line) } else { line.saturating_sub(count) };
+            Motion { target: (target, col.min(line_text(target).len())), kind: MotionKind::Linewise }
+        }
+        '+' | '-' => {
+            let target = if key == '+' { line.saturating_add(count).min(last_line) } else { line.saturating_sub(count) };
+            Motion { target: (target, first_non_blank(line_text(target))), kind: MotionKind::Linewise }
+        }
+        '0' => Motion { target: (line, 0), kind: MotionKind::Exclusive },
+        '$' => {
+            let target = line.saturating_add(count - 1).min(last_line);
+            let text = line_text(target);
+            Motion { target: (target, prev_boundary(text, text.len())), kind: MotionKind::Inclusive }
+        }
+        'g' | 'G' => {
+            let default = if key == 'g' { 0 } else { last_line };
+            let target = explicit.map_or(default, |n| n.saturating_sub(1).min(last_line));
+            Motion { target: (target, first_non_blank(line_text(target))), kind: MotionKind::Linewise }
+        }
+        '}' | '{' => {
+            let blank = |l: usize| line_text(l).trim().is_empty();
+            let mut target = line;
+            for _ in 0..count {
+                let from = target;
+                // Skip blank lines, then the paragraph after them
+                if key == '}' {
+                    while target < last_line && blank(target) {
+                        target += 1;
+                    }
+                    while target < last_line && !blank(target) {
+                        target += 1;
+                    }
+                } else {
+                    while target > 0 && blank(target) {
+                        target -= 1;
+                    }
+                    while target > 0 && !blank(target) {
+                        target -= 1;
+                    }
+                }
+                if target == from {
+                    break;
+                }
+            }
+            let col = if key == '}' && target == last_line && !blank(target) { line_text(target).len() } else { 0 };
+            Motion { target: (target, col), kind: MotionKind::Exclusive }
+        }
+        'w' => Motion { target: repeat(cursor, count, |p| word_forward(content, p)), kind: MotionKind::Exclusive },
+        'b' => Motion { target: repeat(cursor, count, |p| word_backward(content, p)), kind: MotionKind::Exclusive },
+        'e' => Motion { target: repeat(cursor, count, |p| word_end(content, p)), kind: MotionKind::Inclusive },
+        _ => return None,
+    };
+    Some(motion)
+}
+
+/// `dw` on the last word of a line stops at the line end instead of taking
+/// the line break and the next line's indent.
+fn adjust_word_end(content: &[String], from: Pos, motion: Motion) -> Motion {
+    let (line, col) = motion.target;
+    if line > from.0 && col <= first_non_blank(&content[line]) && !content[line - 1].is_empty() {
+        Motion { target: (line - 1, content[line - 1].len()), ..motion }
+    } else {
+        motion
+    }
+}
+
+/// Apply `step` up to `count` times, stopping early once it stops moving
+/// (at the end of the line or buffer).
+fn repeat<T: Copy + PartialEq>(from: T, count: usize, step: impl Fn(T) -> T) -> T {
+    let mut at = from;
+    for _ in 0..count {
+        let next = step(at);
+        if next == at {
+            break;
+        }
+        at = next;
+    }
+    at
+}
+
+fn word_forward(content: &[String], pos: Pos) -> Pos {
+    let start = class_at(content, pos);
+    let mut p = pos;
+    if start != Class::Blank {
+        // Leave the current word (an empty line is a word of its own)
+        loop {
+            let Some(n) = next_pos(content, p) else { return p };
+            p = n;
+            if start == Class::EmptyLine || class_at(content, p) != start {
+                break;
+            }
+        }
+    }
+    while class_at(content, p) == Class::Blank {
+        let Some(n) = next_pos(content, p) else { break };
+        p = n;
+    }
+    p
+}
+
+fn word_end(content: &[String], pos: Pos) -> Pos {
+    let Some(mut p) = next_pos(content, pos) else { return pos };
+    while matches!(class_at(content, p), Class::Blank | Class::EmptyLine) {
+        let Some(n) = next_pos(content, p) else { return p };
+        p = n;
+    }
+    let class = class_at(content, p);
+    while let Some(n) = next_pos(content, p).filter(|&n| class_at(content, n) == class) {
+        p = n;
+    }
+    p
+}
+
+fn word_backward(content: &[String], pos: Pos) -> Pos {
+    let Some(mut p) = prev_pos(content, pos) else { return pos };
+    while class_at(content, p) == Class::Blank {
+        let Some(n) = prev_pos(content, p) else { return p };
+        p = n;
+    }
+    let class = class_at(content, p);
+    if class == Class::EmptyLine {
+        return p;
+    }
+    while let Some(n) = prev_pos(content, p).filter(|&n| class_at(content, n) == class) {
+        p = n;
+    }
+    p
+}
+
+/// Class of the character at `pos`; a line's end counts as blank.
+fn class_at(content: &[String], (line, col): Pos) -> Class {
+    let text = content.get(line).map_or("", String::as_str);
+    match text.get(col..).and_then(|rest| rest.chars().next()) {
+        None if text.is_empty() => Class::EmptyLine,
+        None => Class::Blank,
+        Some(c) if c.is_whitespace() => Class::Blank,
+        Some(c) if c.is_alphanumeric() || c == '_' => Class::Word,
+        Some(_) => Class::Punct,
+    }
+}
+
+/// Next character position, stepping through each line's end.
+fn next_pos(content: &[String], (line, col): Pos) -> Option<Pos> {
+    let text = content.get(line)?;
+    if col < text.len() {
+        Some((line, next_boundary(text, col)))
+    } else {
+        (line + 1 < content.len()).then_some((line + 1, 0))
+    }
+}
+
+fn prev_pos(content: &[String], (line, col): Pos) -> Option<Pos> {
+    if col > 0 {
+        Some((line, prev_boundary(content.get(line)?, col)))
+    } else {
+        line.checked_sub(1).map(|l| (l, content[l].len()))
+    }
+}
+
+fn next_boundary(text: &str, col: usize) -> usize {
+    text.get(col..)
+        .and_then(|rest| rest.chars().next())
+        .map_or(text.len(), |c| col + c.len_utf8())
+}
+
+fn prev_boundary(text: &str, col: usize) -> usize {
+    text.get(..col.min(text.len()))
+        .and_then(|before| before.chars().next_back())
+        .map_or(0, |c| col.min(text.len()) - c.len_utf8())
+}
+
+fn first_non_blank(text: &str) -> usize {
+    text.len() - text.trim_start().len()
+}
+
+fn text_between(content: &[String], start: Pos, end: Pos) -> String {
+    if start.0 == end.0 {
+        return content[start.0][start.1..end.1].to_string();
+    }
+    let mut text = content[start.0][start.1..].to_string();
+    for line in &content[start.0 + 1..end.0] {
+        text.push('\n');
+        text.push_str(line);
+    }
+    text.push('\n');
+    text.push_str(&content[end.0][..end.1]);
+    text
+}
+
+fn delete_between(content: &mut Vec<String>, start: Pos, end: Pos) {
+    let tail = content[end.0][end.1..].to_string();
+    content[start.0].truncate(start.1);
+    content[start.0].push_str(&tail);
+    content.drain(start.0 + 1..=end.0);
+}
+
+/// Normal mode keeps the cursor on a character, not past the line end.
+fn clamp_normal(editor: &mut impl Editor) {
+    let (line, col) = editor.cursor_position();
+    let line = line.min(editor.line_count().saturating_sub(1));
+    let text = editor.content().get(line).map_or("", String::as_str);
+    let last = prev_boundary(text, text.len());
+    let mut col = col.min(last);
+    while !text.is_char_boundary(col) {
+        col -= 1;
+    }
+    editor.set_cursor(line, col);
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+    use crate::app::EditorState;
+
+    fn editor(text: &str) -> EditorState {
+        EditorState { content: text.split('\n').map(str::to_string).collect(), ..EditorState::default() }
+    }
+
+    fn keys(vim: &mut Vim, editor: &mut EditorState, keys: &str) {
+        for c in keys.chars() {
+            let code = if c == '\u{1b}' { KeyCode::Esc } else { KeyCode::Char(c) };
+            // Insert mode hands typing back, like the app's editor
+            if vim.handle_key(editor, KeyEvent::from(code)) == EditorAction::Unhandled {
+                editor.insert_char(c);
+            }
+        }
+    }
+
+    fn text(editor: &EditorState) -> String {
+        editor.content.join("\n")
+    }
+
+    #[test]
+    fn test_word_motions_and_counts() {
+        let mut vim = Vim::new();
+        let mut ed = editor("Amazing grace, how sweet\nthe sound");
+        keys(&mut vim, &mut ed, "w");
+        assert_eq!(ed.cursor_position(), (0, 8));
+        keys(&mut vim, &mut ed, "w");
+        assert_eq!(ed.cursor_position(), (0, 13));
+        keys(&mut vim, &mut ed, "3w");
+        assert_eq!(ed.cursor_position(), (1, 0));
+        keys(&mut vim, &mut ed, "b");
+        assert_eq!(ed.cursor_position(), (0, 19));
+        keys(&mut vim, &mut ed, "e");
+        assert_eq!(ed.cursor_position(), (0, 23));
+        keys(&mut vim, &mut ed, "0$");
+        assert_eq!(ed.cursor_position(), (0, 23));
+        keys(&mut vim, &mut ed, "G");
+        assert_eq!(ed.cursor_position(), (1, 0));
+        keys(&mut vim, &mut ed, "gg");
+        assert_eq!(ed.cursor_position(), (0, 0));
+    }
+
+    #[test]
+    fn test_huge_counts_are_clamped() {
+        let huge = "99999999999999999999";
+        let mut vim = Vim::new();
+        let mut ed = editor("one two\nthree");
+        keys(&mut vim, &mut ed, &format!("{huge}j"));
+        assert_eq!(ed.cursor_position(), (1, 0));
+        keys(&mut vim, &mut ed, &format!("gg{huge}w"));
+        assert_eq!(ed.cursor_position(), (1, 4));
+        keys(&mut vim, &mut ed, &format!("gg{huge}x"));
+        assert_eq!(text(&ed), "\nthree");
+        keys(&mut vim, &mut ed, &format!("yy{huge}p"));
+        assert_eq!(ed.content.len(), 2 + MAX_COUNT);
+        keys(&mut vim, &mut ed, &format!("gg{huge}dd"));
+        assert_eq!(ed.content.len(), 2);
+    }
+
+    #[test]
+    fn test_paragraph_motions() {
+        let mut vim = Vim::new();
+        let mut ed = editor("[Verse 1]\none\ntwo\n\n[Chorus]\nthree\n");
+        keys(&mut vim, &mut ed, "}");
+        assert_eq!(ed.cursor_position(), (3, 0));
+        keys(&mut vim, &mut ed, "}");
+        assert_eq!(ed.cursor_position(), (6, 0));
+        keys(&mut vim, &mut ed, "2{");
+        assert_eq!(ed.cursor_position(), (0, 0));
+    }
+
+    #[test]
+    fn test_delete_change_yank_with_motions() {
+        let mut vim = Vim::new();
+        let mut ed = editor("thee we adore\nthee we praise");
+        keys(&mut vim, &mut ed, "cwyou\u{1b}");
+        assert_eq!(text(&ed), "you we adore\nthee we praise");
+        assert_eq!(vim.mode(), VimMode::Normal);
+        assert_eq!(ed.cursor_position(), (0, 2));
+
+        keys(&mut vim, &mut ed, "0d2w");
+        assert_eq!(text(&ed), "adore\nthee we praise");
+        keys(&mut vim, &mut ed, "dw");
+        assert_eq!(text(&ed), "\nthee we praise");
+
+        keys(&mut vim, &mut ed, "jwy$");
+        assert_eq!(vim.register(), &Register { text: "we praise".to_string(), linewise: false });
+        keys(&mut vim, &mut ed, "0D");
+        assert_eq!(text(&ed), "\n");
+        keys(&mut vim, &mut ed, "P");
+        assert_eq!(text(&ed), "\nthee we praise");
+    }
+
+    #[test]
+    fn test_line_operators_and_paste() {
+        let mut vim = Vim::new();
+        let mut ed = editor("one\ntwo\nthree\nfour");
+        keys(&mut vim, &mut ed, "2dd");
+        assert_eq!(text(&ed), "three\nfour");
+        assert!(vim.register().linewise);
+        keys(&mut vim, &mut ed, "p");
+        assert_eq!(text(&ed), "three\none\ntwo\nfour");
+        assert_eq!(ed.cursor_position(), (1, 0));
+
+        keys(&mut vim, &mut ed, "Gyyggp");
+        assert_eq!(text(&ed), "three\nfour\none\ntwo\nfour");
+        keys(&mut vim, &mut ed, "ccfive\u{1b}");
+        assert_eq!(text(&ed), "three\nfive\none\ntwo\nfour");
+        keys(&mut vim, &mut ed, "dG");
+        assert_eq!(text(&ed), "three");
+    }
+
+    #[test]
+    fn test_visual_mode_and_insert_passthrough() {
+        let mut vim = Vim::new();
+        let mut ed = editor("Holy, holy, holy");
+        keys(&mut vim, &mut ed, "wwve");
+        assert_eq!(vim.visual_anchor(), Some((0, 6)));
+        keys(&mut vim, &mut ed, "d");
+        assert_eq!(text(&ed), "Holy, , holy");
+        assert_eq!(vim.mode(), VimMode::Normal);
+
+        keys(&mut vim, &mut ed, "Vy");
+        assert_eq!(vim.register(), &Register { text: "Holy, , holy".to_string(), linewise: true });
+
+        keys(&mut vim, &mut ed, "hh3x");
+        assert_eq!(text(&ed), "Holy holy");
+
+        // Insert mode leaves typing to the editor; undo is the app's
+        keys(&mut vim, &mut ed, "A");
+        assert!(vim.is_inserting());
+        assert_eq!(vim.handle_key(&mut ed, KeyEvent::from(KeyCode::Char('!'))), EditorAction::Unhandled);
+        assert_eq!(vim.history_step(KeyEvent::from(KeyCode::Char('u'))), None);
+        keys(&mut vim, &mut ed, "\u{1b}");
+        assert_eq!(vim.history_step(KeyEvent::from(KeyCode::Char('u'))), Some(false));
+        assert_eq!(vim.handle_key(&mut ed, KeyEvent::from(KeyCode::Char('n'))), EditorAction::SearchNext(true));
+        assert_eq!(vim.handle_key(&mut ed, KeyEvent::from(KeyCode::Esc)), EditorAction::Unhandled);
+    }
+
+    #[test]
+    fn test_empty_buffer() {
+        for command in ["x", "a", "I", "p", "P", "dd", "D", "yyp", "cwhi\u{1b}", "Vd", "G$"] {
+            let mut vim = Vim::new();
+            let mut ed = EditorState { content: Vec::new(), ..EditorState::default() };
+            keys(&mut vim, &mut ed, command);
+            assert!(!ed.content.is_empty(), "{command}");
+        }
+    }
+
+    #[test]
+    fn test_special_keys_in_normal_mode() {
+        let mut vim = Vim::new();
+        let mut ed = editor("one\n  two");
+        assert_eq!(vim.handle_key(&mut ed, KeyEvent::from(KeyCode::Enter)), EditorAction::NoChange);
+        assert_eq!(ed.cursor_position(), (1, 2));
+        vim.handle_key(&mut ed, KeyEvent::from(KeyCode::Backspace));
+        assert_eq!(ed.cursor_position(), (1, 1));
+        assert_eq!(vim.handle_key(&mut ed, KeyEvent::from(KeyCode::Delete)), EditorAction::Modified);
+        assert_eq!(text(&ed), "one\n two");
+        assert_eq!(vim.handle_key(&mut ed, KeyEvent::from(KeyCode::Tab)), EditorAction::NoChange);
+        assert_eq!(vim.handle_key(&mut ed, KeyEvent::from(KeyCode::F(3))), EditorAction::Unhandled);
+        assert_eq!(text(&ed), "one\n two");
+    }
+}
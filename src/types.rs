@@ -17,6 +17,8 @@ pub enum SlideType {
     Scripture,
     /// Song lyrics with verse/chorus markers.
     Lyrics,
+    /// Responsive readings with leader/people parts.
+    Litany,
     /// Nametags and sermon titles.
     Title,
     /// Image-based slides (offertory, announcements).
@@ -27,7 +29,7 @@ impl SlideType {
     /// Returns all slide type variants in display order.
     #[must_use]
     pub const fn all() -> &'static [Self] {
-        &[Self::Scripture, Self::Lyrics, Self::Title, Self::Graphic, Self::Text]
+        &[Self::Scripture, Self::Lyrics, Self::Litany, Self::Title, Self::Graphic, Self::Text]
     }
 
     /// Returns the human-readable name of this slide type.
@@ -36,6 +38,7 @@ impl SlideType {
         match self {
             Self::Scripture => "Scripture",
             Self::Lyrics => "Lyrics",
+            Self::Litany => "Litany",
             Self::Title => "Title",
             Self::Graphic => "Graphic",
             Self::Text => "Text",
@@ -47,7 +50,8 @@ impl SlideType {
     pub const fn next(self) -> Self {
         match self {
             Self::Scripture => Self::Lyrics,
-            Self::Lyrics => Self::Title,
+            Self::Lyrics => Self::Litany,
+            Self::Litany => Self::Title,
             Self::Title => Self::Graphic,
             Self::Graphic => Self::Text,
             Self::Text => Self::Scripture,
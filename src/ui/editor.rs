@@ -9,8 +9,13 @@ use ratatui::{
 use unicode_width::UnicodeWidthStr;
 
 use crate::app::{App, VerseGroup, SlideType};
+use crate::lyrics::is_chord;
+use crate::lyrics::lint::{Diagnostic, Severity};
 use crate::bible::BibleVersion;
+use crate::find::Match;
 use crate::constants::editor::MIN_WRAP_COLUMN;
+use crate::constants::ui::{LINT_GUTTER_WIDTH, SLIDE_PREVIEW_WIDTH};
+use crate::propresenter::template::SlidePreview;
 
 /// A visual line with its source content line index and character offset
 #[derive(Debug, Clone)]
@@ -108,22 +113,31 @@ fn cursor_to_visual(
 }
 
 /// Render the text editor view with side pane, cursor, and selection highlighting.
-#[allow(clippy::cast_possible_truncation, clippy::similar_names)]
+#[allow(clippy::cast_possible_truncation, clippy::similar_names, clippy::too_many_lines)]
 pub fn draw_editor(f: &mut Frame, app: &mut App, area: Rect) {
-    // Split into main editor and side pane
+    // Split into main editor, side pane and (room permitting) slide preview
+    let show_preview = app.show_slide_preview && area.width >= 40 + 22 + SLIDE_PREVIEW_WIDTH;
     let main_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
             Constraint::Min(40),       // Main editor
             Constraint::Length(22),    // Side pane
+            Constraint::Length(if show_preview { SLIDE_PREVIEW_WIDTH } else { 0 }),
         ])
         .split(area);
     
     let editor_area = main_layout[0];
     let side_pane_area = main_layout[1];
+    if show_preview {
+        draw_slide_preview(f, app, main_layout[2]);
+    }
     
+    // Lint diagnostics get a gutter beside lyric text
+    let diagnostics = app.editor_diagnostics();
+    let gutter_width = if app.current_slide_type == SlideType::Lyrics { LINT_GUTTER_WIDTH } else { 0 };
+
     // Track viewport width for auto wrap column calculation
-    let new_width = editor_area.width as usize;
+    let new_width = editor_area.width.saturating_sub(gutter_width) as usize;
     if app.editor.last_viewport_width != Some(new_width) {
         app.editor.last_viewport_width = Some(new_width);
         app.update_wrap_column_from_viewport();
@@ -132,9 +146,10 @@ pub fn draw_editor(f: &mut Frame, app: &mut App, area: Rect) {
     // Draw the side pane based on slide type
     draw_side_pane(f, app, side_pane_area);
     
-    // Build editor title - include scripture reference if available
-    let title = match (&app.current_slide_type, &app.current_scripture_header) {
-        (SlideType::Scripture, Some(header)) => format!("Editor [{}] │ {}", app.current_slide_type.name(), header.display()),
+    // Build editor title - include scripture reference or hymn metadata if available
+    let title = match (&app.current_slide_type, &app.current_scripture_header, &app.current_hymn_metadata) {
+        (SlideType::Scripture, Some(header), _) => format!("Editor [{}] │ {}", app.current_slide_type.name(), header.display()),
+        (SlideType::Lyrics, _, Some(metadata)) => format!("Editor [{}] │ {}", app.current_slide_type.name(), metadata.summary()),
         _ => format!("Editor [{}]", app.current_slide_type.name()),
     };
     
@@ -146,8 +161,14 @@ pub fn draw_editor(f: &mut Frame, app: &mut App, area: Rect) {
     
     f.render_widget(editor_block.clone(), editor_area);
     
-    // Get the inner area for the editor content
-    let inner_area = editor_block.inner(editor_area);
+    // Get the inner area for the editor content, minus the lint gutter
+    let block_inner = editor_block.inner(editor_area);
+    let gutter_area = Rect { width: gutter_width.min(block_inner.width), ..block_inner };
+    let inner_area = Rect {
+        x: block_inner.x + gutter_area.width,
+        width: block_inner.width - gutter_area.width,
+        ..block_inner
+    };
     
     // Compute visual lines with soft-wrapping
     let visual_lines = compute_visual_lines(&app.editor.content, app.editor.wrap_column);
@@ -185,6 +206,8 @@ pub fn draw_editor(f: &mut Frame, app: &mut App, area: Rect) {
         None
     };
     
+    let search_matches = app.editor_search_matches();
+
     // Prepare content with styled lines
     let mut styled_content = Vec::new();
     
@@ -203,21 +226,25 @@ pub fn draw_editor(f: &mut Frame, app: &mut App, area: Rect) {
 
         let base_style = Style::default().fg(base_fg);
 
-        // Style this visual line segment
-        styled_content.push(Line::from(
-            style_visual_line(
-                vl,
-                selection_bounds,
-                base_style,
-                base_bg,
-                &app.verse_groups
-            )
-        ));
+        // Style this visual line segment, then mark search matches on top
+        let spans = style_visual_line(
+            vl,
+            selection_bounds,
+            base_style,
+            base_bg,
+            &app.verse_groups
+        );
+        let ranges = match_ranges(vl, &search_matches, &app.editor.content);
+        styled_content.push(Line::from(highlight_matches(spans, &ranges)));
     }
     
     // Render the editor content (no additional wrapping needed - we did it ourselves)
     let paragraph = Paragraph::new(styled_content);
     f.render_widget(paragraph, inner_area);
+
+    if gutter_area.width > 0 {
+        draw_lint_gutter(f, &visual_lines[start_visual..end_visual], &diagnostics, gutter_area);
+    }
     
     // Draw the wrap guide
     draw_wrap_guide(f, app, inner_area);
@@ -260,8 +287,11 @@ fn style_visual_line(
     let char_offset = vl.char_start;
 
     // Handle verse markers only if this is the start of the content line
-    if char_offset == 0 && line_content.starts_with('[') && line_content.contains(']') {
-        let marker_end = line_content.find(']').unwrap_or(line_content.len());
+    // (a leading inline chord like "[G]Amazing" is lyrics, not a marker)
+    let marker_end = line_content.find(']').unwrap_or(line_content.len());
+    if char_offset == 0 && line_content.starts_with('[') && marker_end < line_content.len()
+        && !is_chord(line_content[1..marker_end].trim())
+    {
         let marker_text = &line_content[1..marker_end];
         let rest_of_line = &line_content[marker_end+1..];
         
@@ -309,10 +339,173 @@ fn style_visual_line(
         }
     }
     
-    spans.push(Span::styled(line_content.clone(), base_style.bg(base_bg_color)));
+    push_chord_spans(&mut spans, line_content, base_style.bg(base_bg_color));
     spans
 }
 
+/// Push a line segment, dimming inline `[G]` chords so lyrics stand out
+fn push_chord_spans(spans: &mut Vec<Span<'static>>, text: &str, style: Style) {
+    let chord_style = style.fg(Color::Cyan).add_modifier(Modifier::DIM);
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find(']').map(|c| open + c) else { break };
+        if !is_chord(rest[open + 1..close].trim()) {
+            spans.push(Span::styled(rest[..=close].to_string(), style));
+            rest = &rest[close + 1..];
+            continue;
+        }
+        if open > 0 {
+            spans.push(Span::styled(rest[..open].to_string(), style));
+        }
+        spans.push(Span::styled(rest[open..=close].to_string(), chord_style));
+        rest = &rest[close + 1..];
+    }
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_string(), style));
+    }
+}
+
+/// Search matches on a visual line, as char ranges into its text
+fn match_ranges(vl: &VisualLine, matches: &[Match], content: &[String]) -> Vec<(usize, usize)> {
+    let Some(line) = content.get(vl.content_line) else { return Vec::new() };
+    matches.iter()
+        .filter(|m| m.line == vl.content_line)
+        .filter_map(|m| {
+            let start = line.get(..m.start)?.chars().count().saturating_sub(vl.char_start);
+            let end = line.get(..m.end)?.chars().count().saturating_sub(vl.char_start);
+            (start < end).then_some((start, end))
+        })
+        .collect()
+}
+
+/// Re-style the characters in `ranges` as search matches, keeping other styling
+fn highlight_matches(spans: Vec<Span<'static>>, ranges: &[(usize, usize)]) -> Vec<Span<'static>> {
+    if ranges.is_empty() {
+        return spans;
+    }
+    let match_style = Style::default().fg(Color::Black).bg(Color::Rgb(200, 160, 40));
+    let mut highlighted = Vec::new();
+    let mut pos = 0;
+    for span in spans {
+        let mut run = String::new();
+        let mut run_is_match = false;
+        for c in span.content.chars() {
+            let is_match = ranges.iter().any(|&(start, end)| pos >= start && pos < end);
+            if is_match != run_is_match && !run.is_empty() {
+                let style = if run_is_match { span.style.patch(match_style) } else { span.style };
+                highlighted.push(Span::styled(std::mem::take(&mut run), style));
+            }
+            run_is_match = is_match;
+            run.push(c);
+            pos += 1;
+        }
+        let style = if run_is_match { span.style.patch(match_style) } else { span.style };
+        highlighted.push(Span::styled(run, style));
+    }
+    highlighted
+}
+
+/// Mark lines with lint diagnostics: red for errors, yellow for warnings
+fn draw_lint_gutter(f: &mut Frame, visual_lines: &[VisualLine], diagnostics: &[Diagnostic], area: Rect) {
+    let marks: Vec<Line> = visual_lines.iter()
+        .map(|vl| {
+            let worst = diagnostics.iter()
+                .filter(|d| d.line == vl.content_line)
+                .map(|d| d.severity)
+                .max();
+            match worst {
+                Some(Severity::Error) if vl.char_start == 0 => Line::from(Span::styled("●", Style::default().fg(Color::Red))),
+                Some(Severity::Warning) if vl.char_start == 0 => Line::from(Span::styled("●", Style::default().fg(Color::Yellow))),
+                _ => Line::from(""),
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(marks), area);
+}
+
+/// Number and list the slides an export would produce, flagging overflows
+fn draw_slide_preview(f: &mut Frame, app: &mut App, area: Rect) {
+    let slides = app.slide_preview();
+    let block = Block::default()
+        .title(Span::styled(format!("Slides ({})", slides.len()), Style::default().fg(Color::Gray)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // The slide holding the cursor is the last one starting at or above it
+    let current = slides.iter().rposition(|s| s.start_line.is_some_and(|l| l <= app.editor.cursor_y));
+    let width = inner.width as usize;
+
+    let mut lines: Vec<Line> = Vec::new();
+    let mut current_span = (0, 0);
+    for (i, slide) in slides.iter().enumerate() {
+        if current == Some(i) {
+            current_span.0 = lines.len();
+        }
+        lines.push(slide_preview_header(i, slide, current == Some(i)));
+        let text_style = Style::default().fg(if slide.overflows { Color::LightRed } else { Color::White });
+        for text in slide.text.lines() {
+            lines.push(Line::from(Span::styled(truncate_to_width(text, width), text_style)));
+        }
+        if current == Some(i) {
+            current_span.1 = lines.len();
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Keep the current slide in view
+    let height = inner.height as usize;
+    let scroll = if current_span.1 > height {
+        current_span.0.min(current_span.1 - height)
+    } else {
+        0
+    };
+    let scroll = u16::try_from(scroll).unwrap_or(u16::MAX);
+    f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner);
+}
+
+/// "1 ─ 4/10" heading for a previewed slide
+fn slide_preview_header(index: usize, slide: &SlidePreview, is_current: bool) -> Line<'static> {
+    let (color, note) = if slide.overflows {
+        (Color::Red, " overflow")
+    } else if is_current {
+        (Color::Yellow, "")
+    } else {
+        (Color::DarkGray, "")
+    };
+    let mut style = Style::default().fg(color);
+    if is_current {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    Line::from(vec![
+        Span::styled(format!("{:>2} ", index + 1), style),
+        Span::styled(
+            format!("─ {}/{} lines{note}", slide.visual_lines, slide.max_lines),
+            style,
+        ),
+    ])
+}
+
+/// Cut `text` to `width` display columns, marking the cut with an ellipsis
+fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = UnicodeWidthStr::width(c.to_string().as_str());
+        if used + w + 1 > width {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push('…');
+    out
+}
+
 #[allow(clippy::cast_possible_truncation)]
 fn draw_wrap_guide(f: &mut Frame, app: &App, area: Rect) {
     let wrap_col = app.editor.wrap_column;
@@ -384,7 +577,7 @@ fn draw_version_pane(f: &mut Frame, app: &App, area: Rect) {
     // Add hint at bottom
     let mut all_lines = lines;
     all_lines.push(Line::from(""));
-    all_lines.push(Line::from(Span::styled("1-4: switch", Style::default().fg(Color::DarkGray))));
+    all_lines.push(Line::from(Span::styled(format!("1-{}: switch", versions.len()), Style::default().fg(Color::DarkGray))));
     
     let paragraph = Paragraph::new(all_lines).alignment(Alignment::Center);
     f.render_widget(paragraph, inner);
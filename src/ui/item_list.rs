@@ -34,6 +34,7 @@ pub fn draw_item_list(f: &mut Frame, app: &mut App, area: Rect) {
     let slide_type_indicators = [
         (SlideType::Scripture, "[ Scripture ]", Color::Cyan),
         (SlideType::Lyrics, "[ Lyrics ]", Color::Green),
+        (SlideType::Litany, "[ Litany ]", Color::LightGreen),
         (SlideType::Title, "[ Title ]", Color::Yellow),
         (SlideType::Graphic, "[ Graphic ]", Color::Magenta),
         (SlideType::Text, "[ Text ]", Color::Blue),
@@ -61,23 +62,27 @@ pub fn draw_item_list(f: &mut Frame, app: &mut App, area: Rect) {
             let is_completed = app.item_states.is_completed(&item_id);
             let is_ignored = app.item_states.is_ignored(&item_id);
 
-            // Check if item has custom editor content (mutually exclusive with file match)
+            // Check if item has editor content (a custom creation, or edits to its matched file)
             let has_editor_content = app.item_states.get_editor(&item_id)
                 .is_some_and(|state| state.content.iter().any(|line| !line.trim().is_empty()));
 
             let matched_file = app.item_states.get_matched_file(&item_id);
             
             // Determine status display: Created vs Matched vs neither
-            let status_display = if has_editor_content {
-                " -> [Created]".to_string()
-            } else if let Some(file_path) = matched_file {
-                let path = std::path::Path::new(file_path);
-                let filename = path.file_stem()
-                    .and_then(|stem| stem.to_str())
-                    .unwrap_or(file_path);
-                format!(" -> {filename}")
-            } else {
-                String::new()
+            let status_display = match (matched_file, has_editor_content) {
+                (Some(file_path), edited) => {
+                    let path = std::path::Path::new(file_path);
+                    let filename = path.file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .unwrap_or(file_path);
+                    if edited {
+                        format!(" -> {filename} [Edited]")
+                    } else {
+                        format!(" -> {filename}")
+                    }
+                }
+                (None, true) => " -> [Created]".to_string(),
+                (None, false) => String::new(),
             };
 
             // Determine base foreground and modifier
@@ -20,6 +20,7 @@ use ratatui::{
 };
 
 use crate::app::{App, AppMode};
+use crate::lyrics::structure::StructureProposal;
 
 /// Render the full application UI to the terminal frame.
 #[allow(clippy::cast_possible_truncation)]
@@ -46,6 +47,12 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         draw_loading_indicator(f);
     }
 
+    // Draw structure preview (blocking)
+    if let Some(proposal) = &app.pending_structure {
+        draw_structure_preview(f, proposal);
+        return;
+    }
+
     // Draw status/info modal (blocking)
     if let Some(status) = &app.status_message {
         draw_status_message(f, status);
@@ -93,17 +100,20 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     }
 }
 
-#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_possible_truncation, clippy::too_many_lines)]
 fn draw_command_bar(f: &mut Frame, app: &App, area: Rect) {
     let title = if app.is_global_command_mode { 
         "Command" 
     } else if app.file_search_active { 
         "Search Files" 
+    } else if app.mode == AppMode::Editor && app.editor.is_search_mode {
+        "Search"
     } else { 
         "Commands/Status" 
     };
     
-    let border_color = if app.file_search_active { Color::Cyan } else { Color::Yellow };
+    let searching = app.file_search_active || (app.mode == AppMode::Editor && app.editor.is_search_mode);
+    let border_color = if searching { Color::Cyan } else { Color::Yellow };
     
     let block = Block::default()
         .borders(Borders::ALL)
@@ -126,6 +136,11 @@ fn draw_command_bar(f: &mut Frame, app: &App, area: Rect) {
             .style(Style::default().fg(Color::Yellow));
         f.render_widget(command, inner_area);
         f.set_cursor(inner_area.left() + app.editor.command_buffer.len() as u16 + 2, inner_area.top());
+    } else if app.mode == AppMode::Editor && app.editor.is_search_mode {
+        let search = Paragraph::new(format!(" /{}", app.editor.search_buffer))
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(search, inner_area);
+        f.set_cursor(inner_area.left() + app.editor.search_buffer.len() as u16 + 2, inner_area.top());
     } else if app.file_search_active {
         // Show file search input
         let search = Paragraph::new(format!(" /{}", app.file_search_query))
@@ -154,6 +169,7 @@ fn draw_command_bar(f: &mut Frame, app: &App, area: Rect) {
                 ("t", "Type"),
                 ("Space", "Skip"),
                 ("g", "Generate"),
+                ("b", "Bundle"),
             ]),
             AppMode::Editor => {
                 let status = format!(
@@ -181,6 +197,30 @@ fn draw_command_bar(f: &mut Frame, app: &App, area: Rect) {
                 
                 let mut text = create_help_text(hints);
                 text.push(Span::styled(format!(" | {status}"), Style::default().fg(Color::Gray)));
+
+                if let Some(vim) = &app.vim {
+                    text.push(Span::styled(format!(" | -- {} --", vim.mode().label()), Style::default().fg(Color::Green)));
+                }
+
+                if let Some(pattern) = &app.editor.search_pattern {
+                    let count = app.editor_search_matches().len();
+                    text.push(Span::styled(format!(" | /{pattern}: {count} match(es)"), Style::default().fg(Color::Cyan)));
+                }
+
+                // Lint summary, with the message for the cursor line
+                let diagnostics = app.editor_diagnostics();
+                if !diagnostics.is_empty() {
+                    let errors = crate::lyrics::lint::error_count(&diagnostics);
+                    let warnings = diagnostics.len() - errors;
+                    let color = if errors > 0 { Color::Red } else { Color::Yellow };
+                    let summary = diagnostics.iter()
+                        .find(|d| d.line == app.editor.cursor_y)
+                        .map_or_else(
+                            || format!(" | {errors}E {warnings}W"),
+                            |current| format!(" | {errors}E {warnings}W: {}", current.message),
+                        );
+                    text.push(Span::styled(summary, Style::default().fg(color)));
+                }
                 
                 text
             }
@@ -549,7 +589,9 @@ fn build_help_content(app: &App) -> Vec<(&'static str, &'static str, bool)> {
                 ("Space", "Toggle ignore item", false),
                 ("e", "Edit item (load .pro or create)", false),
                 ("t", "Cycle slide type", false),
-                ("g", "Generate playlist", false),
+                ("g", "Generate or sync playlist", false),
+                ("b", "Export item as .probundle", false),
+                (":import <path>", "Import .probundle for item", false),
             ]);
         }
         AppMode::Editor => {
@@ -559,15 +601,25 @@ fn build_help_content(app: &App) -> Vec<(&'static str, &'static str, bool)> {
                 ("Shift+Arrows", "Select text", false),
                 ("Ctrl+A", "Select all", false),
                 ("Ctrl+C/X/V", "Copy/Cut/Paste", false),
+                ("Ctrl+Z/Ctrl+Y", "Undo/Redo", false),
+                ("/pattern", "Search (regex)", false),
+                ("F3/Shift+F3", "Next/previous match", false),
                 ("Alt+←/→", "Adjust wrap column", false),
                 ("", "", false),
                 ("── Scripture ──", "", true),
-                ("1-4", "Switch Bible version", false),
+                ("1-6", "Switch Bible version", false),
                 ("", "", false),
                 ("── Commands ──", "", true),
                 (":v1, :v2...", "Insert verse marker", false),
                 (":c, :c1...", "Insert chorus marker", false),
                 (":br", "Insert bridge marker", false),
+                (":structure", "Label verses/choruses", false),
+                (":lint / :fix", "List / fix style issues", false),
+                (":transpose A/-2", "Transpose chords to key/by steps", false),
+                (":s/a/b/g", "Replace in selection or all", false),
+                (":noh", "Clear search highlight", false),
+                (":preview", "Toggle slide preview", false),
+                (":vim", "Toggle vim-style modal keys", false),
                 (":wrap", "Apply word wrap", false),
                 (":export/:save", "Export as .pro file", false),
             ]);
@@ -587,6 +639,60 @@ fn build_help_content(app: &App) -> Vec<(&'static str, &'static str, bool)> {
     lines
 }
 
+// Draw the proposed section labels from `:structure`
+fn draw_structure_preview(f: &mut Frame, proposal: &StructureProposal) {
+    let size = f.size();
+
+    let width = 70.min(size.width.saturating_sub(4));
+    #[allow(clippy::cast_possible_truncation)]
+    let height = (proposal.sections.len() as u16 + 4).min(size.height.saturating_sub(4));
+
+    let area = Rect {
+        x: (size.width.saturating_sub(width)) / 2,
+        y: (size.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title(Span::styled(" Lyric Structure ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let inner_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .margin(1)
+        .split(area);
+
+    // One row per section: old header → new marker, then its first line
+    let rows: Vec<Line> = proposal.sections.iter()
+        .map(|section| {
+            let before = section.before.as_deref().unwrap_or("—");
+            let (after, after_style) = section.after.as_ref().map_or_else(
+                || ("(continued)".to_string(), Style::default().fg(Color::DarkGray)),
+                |label| (format!("[{label}]"), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            );
+            Line::from(vec![
+                Span::styled(format!("{before:>12} → "), Style::default().fg(Color::Gray)),
+                Span::styled(format!("{after:<14}"), after_style),
+                Span::styled(section.first_line.clone(), Style::default().fg(Color::White)),
+            ])
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(rows), inner_area[0]);
+
+    let hint = Paragraph::new("Apply? (y/n)")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+    f.render_widget(hint, inner_area[1]);
+}
+
 // Draw the Bible version picker modal
 fn draw_version_picker(f: &mut Frame, app: &App) {
     use crate::bible::BibleVersion;
@@ -89,6 +89,9 @@ struct CacheData {
     /// Item ID → editor state (content, cursor, etc.)
     #[serde(default)]
     editor_states: HashMap<String, crate::app::EditorState>,
+    /// Item ID → editor undo/redo history
+    #[serde(default)]
+    edit_histories: HashMap<String, crate::history::EditHistory>,
     /// Item ID → completion status
     #[serde(default)]
     item_completion: HashMap<String, bool>,
@@ -127,12 +130,16 @@ pub struct FileIndex {
     pub selection_frequency: HashMap<String, u32>,
     /// Item ID → editor state (persisted)
     pub editor_states: HashMap<String, crate::app::EditorState>,
+    /// Item ID → editor undo/redo history (persisted)
+    pub edit_histories: HashMap<String, crate::history::EditHistory>,
     /// Item ID → completion status (persisted)
     pub item_completion: HashMap<String, bool>,
     /// Item ID → ignored status (persisted)
     pub item_ignored: HashMap<String, bool>,
     /// Library path for cache persistence
     library_path: PathBuf,
+    /// Known hymnal codes (e.g. "GTG", "PH") for hymn-number matching
+    hymnal_codes: Vec<String>,
 }
 
 impl FileIndex {
@@ -193,9 +200,11 @@ impl FileIndex {
             item_file_selections: HashMap::new(),
             selection_frequency: HashMap::new(),
             editor_states: HashMap::new(),
+            edit_histories: HashMap::new(),
             item_completion: HashMap::new(),
             item_ignored: HashMap::new(),
             library_path: library_path.to_path_buf(),
+            hymnal_codes: Vec::new(),
         };
 
         // Save cache (ignore errors)
@@ -228,9 +237,11 @@ impl FileIndex {
             item_file_selections: cache.selections,
             selection_frequency: cache.frequency,
             editor_states: cache.editor_states,
+            edit_histories: cache.edit_histories,
             item_completion: cache.item_completion,
             item_ignored: cache.item_ignored,
             library_path: library_path.to_path_buf(),
+            hymnal_codes: Vec::new(),
         })
     }
 
@@ -243,6 +254,7 @@ impl FileIndex {
             selections: self.item_file_selections.clone(),
             frequency: self.selection_frequency.clone(),
             editor_states: self.editor_states.clone(),
+            edit_histories: self.edit_histories.clone(),
             item_completion: self.item_completion.clone(),
             item_ignored: self.item_ignored.clone(),
         };
@@ -274,9 +286,10 @@ impl FileIndex {
         self.item_file_selections.get(item_id)
     }
 
-    /// Save editor state for an item
-    pub fn save_editor_state(&mut self, item_id: &str, state: &crate::app::EditorState) {
+    /// Save editor state and its undo history for an item
+    pub fn save_editor_state(&mut self, item_id: &str, state: &crate::app::EditorState, history: &crate::history::EditHistory) {
         self.editor_states.insert(item_id.to_string(), state.clone());
+        self.edit_histories.insert(item_id.to_string(), history.clone());
         self.persist();
     }
 
@@ -285,6 +298,17 @@ impl FileIndex {
         self.editor_states.get(item_id)
     }
 
+    /// Get editor undo history for an item
+    pub fn get_edit_history(&self, item_id: &str) -> Option<&crate::history::EditHistory> {
+        self.edit_histories.get(item_id)
+    }
+
+    /// Forget editor state and undo history for an item
+    pub fn remove_editor_state(&mut self, item_id: &str) {
+        self.editor_states.remove(item_id);
+        self.edit_histories.remove(item_id);
+    }
+
     /// Save item completion status
     pub fn save_item_completion(&mut self, item_id: &str, completed: bool) {
         self.item_completion.insert(item_id.to_string(), completed);
@@ -338,6 +362,11 @@ impl FileIndex {
         self.persist();
     }
 
+    /// Set the hymnal codes recognized in queries and filenames ("GTG 510").
+    pub fn set_hymnal_codes(&mut self, codes: Vec<String>) {
+        self.hymnal_codes = codes;
+    }
+
     /// Find matching files for a search query
     pub fn find_matches(&self, query: impl AsRef<str>, max_results: usize) -> Vec<FileEntry> {
         let query_str = query.as_ref().trim();
@@ -351,7 +380,11 @@ impl FileIndex {
         let effective_lower = effective.to_lowercase();
         
         let matcher = SkimMatcherV2::default();
-        let hymn_number = extract_hymn_number(query_str);
+        let hymnal_ref = crate::hymnal::extract_hymnal_ref(query_str, &self.hymnal_codes);
+        let hymn_code = hymnal_ref.as_ref().map(|(code, _)| code.as_str());
+        let hymn_number = hymnal_ref.as_ref()
+            .map(|(_, num)| num.to_string())
+            .or_else(|| extract_hymn_number(query_str));
         let composite_parts = parse_composite_query(effective);
         let tokens = tokenize_query(&effective_lower);
 
@@ -360,7 +393,7 @@ impl FileIndex {
             .filter_map(|entry| {
                 let score = self.score_entry(
                     &matcher, entry, effective, &effective_lower,
-                    &query_lower, hymn_number.as_deref(), &composite_parts, &tokens,
+                    &query_lower, hymn_code, hymn_number.as_deref(), &composite_parts, &tokens,
                 )?;
                 Some((score, entry))
             })
@@ -387,6 +420,7 @@ impl FileIndex {
         term: &str,
         term_lower: &str,
         query_lower: &str,
+        hymn_code: Option<&str>,
         hymn_number: Option<&str>,
         composite_parts: &[&str],
         tokens: &[&str],
@@ -464,7 +498,13 @@ impl FileIndex {
 
         // Hymn number matching
         if let Some(num) = hymn_number {
-            if entry.file_name_lower.contains(&format!("#{num}")) ||
+            // Files citing a hymnal ("GTG 510 ...") must match its code and number
+            if let Some((entry_code, entry_num)) = crate::hymnal::extract_hymnal_ref(&entry.file_name, &self.hymnal_codes) {
+                if entry_num.to_string() == num && hymn_code.is_none_or(|code| code == entry_code) {
+                    score = score.max(9500);
+                    quality = 3;
+                }
+            } else if entry.file_name_lower.contains(&format!("#{num}")) ||
                entry.file_name_lower.contains(&format!(" {num} ")) ||
                entry.file_name_lower.contains(&format!("-{num}")) {
                 score = score.max(9000);